@@ -41,6 +41,10 @@ pub const DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS: u64 =
 pub const DEFAULT_ALLOW_AUCTION_BIDS: bool = true;
 /// Default value for allowing unrestricted transfers.
 pub const DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS: bool = true;
+/// Default value for allowing secp256r1 public keys in auction bids and delegations.
+pub const DEFAULT_ALLOW_SECP256R1_KEYS: bool = false;
+/// Default value for the maximum number of entries recorded in a single execution trace.
+pub const DEFAULT_MAX_TRACE_ENTRIES: u32 = 10_000;
 /// Default gas cost refund ratio.
 pub const DEFAULT_REFUND_HANDLING: RefundHandling = RefundHandling::Refund {
     refund_ratio: Ratio::new_raw(99, 100),
@@ -77,6 +81,11 @@ pub struct EngineConfig {
     /// set to `false` tokens can be transferred only from normal accounts to administrators
     /// and administrators to normal accounts but not normal accounts to normal accounts.
     pub(crate) allow_unrestricted_transfers: bool,
+    /// Auction entrypoints such as "add_bid" or "delegate" reject secp256r1 public keys if this
+    /// flag is set to `false`.
+    pub(crate) allow_secp256r1_keys: bool,
+    /// Maximum number of frames and host function calls recorded in a single execution trace.
+    max_trace_entries: u32,
     /// Refund handling config.
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
@@ -98,6 +107,8 @@ impl Default for EngineConfig {
             administrative_accounts: Default::default(),
             allow_auction_bids: DEFAULT_ALLOW_AUCTION_BIDS,
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
+            allow_secp256r1_keys: DEFAULT_ALLOW_SECP256R1_KEYS,
+            max_trace_entries: DEFAULT_MAX_TRACE_ENTRIES,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
         }
@@ -138,6 +149,8 @@ impl EngineConfig {
             administrative_accounts: Default::default(),
             allow_auction_bids: DEFAULT_ALLOW_AUCTION_BIDS,
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
+            allow_secp256r1_keys: DEFAULT_ALLOW_SECP256R1_KEYS,
+            max_trace_entries: DEFAULT_MAX_TRACE_ENTRIES,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
         }
@@ -198,6 +211,16 @@ impl EngineConfig {
         self.allow_unrestricted_transfers
     }
 
+    /// Returns true if secp256r1 public keys are allowed in auction bids and delegations.
+    pub fn allow_secp256r1_keys(&self) -> bool {
+        self.allow_secp256r1_keys
+    }
+
+    /// Returns the maximum number of entries recorded in a single execution trace.
+    pub fn max_trace_entries(&self) -> u32 {
+        self.max_trace_entries
+    }
+
     /// Checks if an account hash is an administrator.
     pub(crate) fn is_administrator(&self, account_hash: &AccountHash) -> bool {
         self.administrative_accounts.contains(account_hash)
@@ -232,6 +255,8 @@ pub struct EngineConfigBuilder {
     administrative_accounts: Option<BTreeSet<PublicKey>>,
     allow_auction_bids: Option<bool>,
     allow_unrestricted_transfers: Option<bool>,
+    allow_secp256r1_keys: Option<bool>,
+    max_trace_entries: Option<u32>,
     refund_handling: Option<RefundHandling>,
     fee_handling: Option<FeeHandling>,
 }
@@ -327,6 +352,18 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets the allow secp256r1 keys config option.
+    pub fn with_allow_secp256r1_keys(mut self, allow_secp256r1_keys: bool) -> Self {
+        self.allow_secp256r1_keys = Some(allow_secp256r1_keys);
+        self
+    }
+
+    /// Sets the max trace entries config option.
+    pub fn with_max_trace_entries(mut self, max_trace_entries: u32) -> Self {
+        self.max_trace_entries = Some(max_trace_entries);
+        self
+    }
+
     /// Sets the refund handling config option.
     pub fn with_refund_handling(mut self, refund_handling: RefundHandling) -> Self {
         match refund_handling {
@@ -375,6 +412,10 @@ impl EngineConfigBuilder {
         let allow_unrestricted_transfers = self
             .allow_unrestricted_transfers
             .unwrap_or(DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS);
+        let allow_secp256r1_keys = self
+            .allow_secp256r1_keys
+            .unwrap_or(DEFAULT_ALLOW_SECP256R1_KEYS);
+        let max_trace_entries = self.max_trace_entries.unwrap_or(DEFAULT_MAX_TRACE_ENTRIES);
         let refund_handling = self.refund_handling.unwrap_or(DEFAULT_REFUND_HANDLING);
         let fee_handling = self.fee_handling.unwrap_or(DEFAULT_FEE_HANDLING);
 
@@ -396,6 +437,8 @@ impl EngineConfigBuilder {
             administrative_accounts,
             allow_auction_bids,
             allow_unrestricted_transfers,
+            allow_secp256r1_keys,
+            max_trace_entries,
             refund_handling,
             fee_handling,
             strict_argument_checking,
@@ -1,4 +1,4 @@
-use datasize::DataSize;
+use num_rational::Ratio;
 use serde::{Deserialize, Serialize};
 
 use casper_types::bytesrepr::{self, FromBytes, ToBytes};
@@ -6,9 +6,10 @@ use casper_types::bytesrepr::{self, FromBytes, ToBytes};
 const FEE_HANDLING_PROPOSER_TAG: u8 = 0;
 const FEE_HANDLING_ACCUMULATE_TAG: u8 = 1;
 const FEE_HANDLING_BURN_TAG: u8 = 2;
+const FEE_HANDLING_SPLIT_TAG: u8 = 3;
 
 /// Defines how fees are handled in the system.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DataSize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum FeeHandling {
     /// Transaction fees are paid to the block proposer.
@@ -22,6 +23,57 @@ pub enum FeeHandling {
     Accumulate,
     /// Burn the fees.
     Burn,
+    /// Transaction fees are split between the block proposer, the accumulation purse and burning.
+    ///
+    /// The three ratios must add up to one.  Any dust amount resulting from multiplying the fee by
+    /// the ratios goes to the block proposer.
+    Split {
+        /// The part of the fee paid to the block proposer.
+        proposer_ratio: Ratio<u64>,
+        /// The part of the fee accumulated in a special purse and distributed during end of era
+        /// processing evenly among all administrator accounts.
+        accumulate_ratio: Ratio<u64>,
+        /// The part of the fee that is burned.
+        burn_ratio: Ratio<u64>,
+    },
+}
+
+impl FeeHandling {
+    /// Returns `true` if fees are (at least partly) accumulated in the accumulation purse.
+    pub fn is_accumulating(&self) -> bool {
+        match self {
+            FeeHandling::PayToProposer | FeeHandling::Burn => false,
+            FeeHandling::Accumulate => true,
+            FeeHandling::Split {
+                accumulate_ratio, ..
+            } => *accumulate_ratio.numer() != 0,
+        }
+    }
+
+    /// Returns `false` if this is a `Split` whose ratios do not add up to one.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            FeeHandling::PayToProposer | FeeHandling::Accumulate | FeeHandling::Burn => true,
+            FeeHandling::Split {
+                proposer_ratio,
+                accumulate_ratio,
+                burn_ratio,
+            } => {
+                if *proposer_ratio.denom() == 0
+                    || *accumulate_ratio.denom() == 0
+                    || *burn_ratio.denom() == 0
+                {
+                    return false;
+                }
+                // Sum up as `u128` to avoid overflowing with large denominators.
+                let to_u128 = |ratio: &Ratio<u64>| {
+                    Ratio::new(u128::from(*ratio.numer()), u128::from(*ratio.denom()))
+                };
+                to_u128(proposer_ratio) + to_u128(accumulate_ratio) + to_u128(burn_ratio)
+                    == Ratio::from_integer(1)
+            }
+        }
+    }
 }
 
 impl ToBytes for FeeHandling {
@@ -30,11 +82,34 @@ impl ToBytes for FeeHandling {
             FeeHandling::PayToProposer => Ok(vec![FEE_HANDLING_PROPOSER_TAG]),
             FeeHandling::Accumulate => Ok(vec![FEE_HANDLING_ACCUMULATE_TAG]),
             FeeHandling::Burn => Ok(vec![FEE_HANDLING_BURN_TAG]),
+            FeeHandling::Split {
+                proposer_ratio,
+                accumulate_ratio,
+                burn_ratio,
+            } => {
+                let mut buffer = bytesrepr::allocate_buffer(self)?;
+                buffer.push(FEE_HANDLING_SPLIT_TAG);
+                buffer.extend(proposer_ratio.to_bytes()?);
+                buffer.extend(accumulate_ratio.to_bytes()?);
+                buffer.extend(burn_ratio.to_bytes()?);
+                Ok(buffer)
+            }
         }
     }
 
     fn serialized_length(&self) -> usize {
-        1
+        1 + match self {
+            FeeHandling::PayToProposer | FeeHandling::Accumulate | FeeHandling::Burn => 0,
+            FeeHandling::Split {
+                proposer_ratio,
+                accumulate_ratio,
+                burn_ratio,
+            } => {
+                proposer_ratio.serialized_length()
+                    + accumulate_ratio.serialized_length()
+                    + burn_ratio.serialized_length()
+            }
+        }
     }
 }
 
@@ -45,6 +120,19 @@ impl FromBytes for FeeHandling {
             FEE_HANDLING_PROPOSER_TAG => Ok((FeeHandling::PayToProposer, rem)),
             FEE_HANDLING_ACCUMULATE_TAG => Ok((FeeHandling::Accumulate, rem)),
             FEE_HANDLING_BURN_TAG => Ok((FeeHandling::Burn, rem)),
+            FEE_HANDLING_SPLIT_TAG => {
+                let (proposer_ratio, rem) = FromBytes::from_bytes(rem)?;
+                let (accumulate_ratio, rem) = FromBytes::from_bytes(rem)?;
+                let (burn_ratio, rem) = FromBytes::from_bytes(rem)?;
+                Ok((
+                    FeeHandling::Split {
+                        proposer_ratio,
+                        accumulate_ratio,
+                        burn_ratio,
+                    },
+                    rem,
+                ))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -71,4 +159,34 @@ mod tests {
         let fee_config = FeeHandling::Burn;
         bytesrepr::test_serialization_roundtrip(&fee_config);
     }
+
+    #[test]
+    fn bytesrepr_roundtrip_for_split() {
+        let fee_config = FeeHandling::Split {
+            proposer_ratio: Ratio::new(3, 10),
+            accumulate_ratio: Ratio::new(1, 5),
+            burn_ratio: Ratio::new(1, 2),
+        };
+        bytesrepr::test_serialization_roundtrip(&fee_config);
+    }
+
+    #[test]
+    fn should_validate_split_ratios() {
+        let split = |proposer, accumulate, burn| FeeHandling::Split {
+            proposer_ratio: Ratio::new_raw(proposer, 10),
+            accumulate_ratio: Ratio::new_raw(accumulate, 10),
+            burn_ratio: Ratio::new_raw(burn, 10),
+        };
+        assert!(split(3, 2, 5).is_valid());
+        assert!(split(0, 0, 10).is_valid());
+        assert!(!split(3, 2, 4).is_valid());
+        assert!(!split(3, 3, 5).is_valid());
+
+        let zero_denominator = FeeHandling::Split {
+            proposer_ratio: Ratio::new_raw(1, 1),
+            accumulate_ratio: Ratio::new_raw(0, 0),
+            burn_ratio: Ratio::new_raw(0, 1),
+        };
+        assert!(!zero_denominator.is_valid());
+    }
 }
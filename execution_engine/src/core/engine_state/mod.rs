@@ -88,7 +88,7 @@ use crate::{
             upgrade::{ProtocolUpgradeError, SystemUpgrader},
         },
         execution::{self, DirectSystemContractCall, Executor},
-        runtime::RuntimeStack,
+        runtime::{ExecutionTrace, ExecutionTracer, GasProfiler, GasReport, RuntimeStack},
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
@@ -148,6 +148,26 @@ impl EngineState<LmdbGlobalState> {
         Ok(())
     }
 
+    /// Returns a page of the keys at the given state root hash whose serialized form starts with
+    /// `prefix`, ordered after `start_after` if given.
+    ///
+    /// Returns `None` if the state root hash is not found.
+    pub fn get_keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Digest,
+        prefix: &[u8],
+        start_after: Option<&Key>,
+        limit: usize,
+    ) -> Result<Option<Vec<Key>>, Error> {
+        let reader = match self.state.checkout(state_hash)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let keys = reader.keys_with_prefix_paginated(correlation_id, prefix, start_after, limit)?;
+        Ok(Some(keys))
+    }
+
     /// Provide a local cached-only version of engine-state.
     pub fn get_scratch_engine_state(&self) -> EngineState<ScratchGlobalState> {
         EngineState {
@@ -558,26 +578,8 @@ where
         let mut results = ExecutionResults::with_capacity(deploys.len());
 
         for deploy_item in deploys {
-            let result = match deploy_item.session {
-                ExecutableDeployItem::Transfer { .. } => self.transfer(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-                _ => self.deploy(
-                    correlation_id,
-                    &executor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                    exec_request.proposer.clone(),
-                ),
-            };
+            let result =
+                self.execute_deploy_item(correlation_id, &executor, &exec_request, deploy_item);
             match result {
                 Ok(result) => results.push_back(result),
                 Err(error) => {
@@ -589,6 +591,92 @@ where
         Ok(results)
     }
 
+    /// Executes the deploys in an execute request like [`run_execute`](Self::run_execute),
+    /// additionally recording an [`ExecutionTrace`] of each.
+    ///
+    /// Tracing is costly, so this must only be used for speculative execution, and never for
+    /// deploys executed as part of a block.
+    pub fn run_execute_with_traces(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, ExecutionTrace)>, Error> {
+        let tracer = ExecutionTracer::new(self.config().max_trace_entries());
+        let executor = Executor::new_with_tracer(self.config().clone(), tracer.clone());
+
+        exec_request
+            .take_deploys()
+            .into_iter()
+            .map(|deploy_item| {
+                let result = self.execute_deploy_item(
+                    correlation_id,
+                    &executor,
+                    &exec_request,
+                    deploy_item,
+                )?;
+                Ok((result, tracer.take_trace()))
+            })
+            .collect()
+    }
+
+    /// Executes the deploys in an execute request like [`run_execute`](Self::run_execute),
+    /// additionally recording a [`GasReport`] of each, attributing the gas charged to the entry
+    /// points in which it was charged.
+    ///
+    /// Profiling is costly, so this is intended for testing and tooling, and must never be used for
+    /// deploys executed as part of a block.
+    pub fn run_execute_with_gas_reports(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<Vec<(ExecutionResult, GasReport)>, Error> {
+        let gas_profiler = GasProfiler::default();
+        let executor = Executor::new_with_gas_profiler(self.config().clone(), gas_profiler.clone());
+
+        exec_request
+            .take_deploys()
+            .into_iter()
+            .map(|deploy_item| {
+                let result = self.execute_deploy_item(
+                    correlation_id,
+                    &executor,
+                    &exec_request,
+                    deploy_item,
+                )?;
+                Ok((result, gas_profiler.take_report()))
+            })
+            .collect()
+    }
+
+    fn execute_deploy_item(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        exec_request: &ExecuteRequest,
+        deploy_item: DeployItem,
+    ) -> Result<ExecutionResult, Error> {
+        match deploy_item.session {
+            ExecutableDeployItem::Transfer { .. } => self.transfer(
+                correlation_id,
+                executor,
+                exec_request.protocol_version,
+                exec_request.parent_state_hash,
+                BlockTime::new(exec_request.block_time),
+                deploy_item,
+                exec_request.proposer.clone(),
+            ),
+            _ => self.deploy(
+                correlation_id,
+                executor,
+                exec_request.protocol_version,
+                exec_request.parent_state_hash,
+                BlockTime::new(exec_request.block_time),
+                deploy_item,
+                exec_request.proposer.clone(),
+            ),
+        }
+    }
+
     fn get_authorized_account(
         &self,
         correlation_id: CorrelationId,
@@ -1732,9 +1820,9 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
         match self.config.fee_handling() {
-            FeeHandling::PayToProposer => {
+            FeeHandling::PayToProposer | FeeHandling::Split { .. } => {
                 // the proposer of the block this deploy is in receives the gas from this deploy
-                // execution
+                // execution (or its share of it, if the fee is split)
                 let proposer_account: Account = match tracking_copy
                     .borrow_mut()
                     .get_account(correlation_id, AccountHash::from(&proposer))
@@ -23,7 +23,7 @@ use crate::{
     storage::global_state::StateProvider,
 };
 
-use super::{engine_config::FeeHandling, EngineConfig};
+use super::EngineConfig;
 
 /// Represents a successfully executed upgrade.
 #[derive(Debug, Clone)]
@@ -347,8 +347,8 @@ where
 
     /// Creates an accumulation purse in the handle payment system contract if its not present.
     ///
-    /// This can happen on older networks that did not have support for [`FeeHandling::Accumulate`]
-    /// at the genesis. In such cases we have to check the state of handle payment contract and
+    /// This can happen on older networks that did not have support for accumulating fees at the
+    /// genesis. In such cases we have to check the state of handle payment contract and
     /// create an accumulation purse.
     pub(crate) fn create_accumulation_purse_if_required(
         &self,
@@ -356,9 +356,8 @@ where
         handle_payment_hash: &ContractHash,
         engine_config: &EngineConfig,
     ) -> Result<(), ProtocolUpgradeError> {
-        match engine_config.fee_handling() {
-            FeeHandling::PayToProposer | FeeHandling::Burn => return Ok(()),
-            FeeHandling::Accumulate => {}
+        if !engine_config.fee_handling().is_accumulating() {
+            return Ok(());
         }
 
         let mut address_generator = {
@@ -3,7 +3,7 @@ use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
 use casper_types::{
     account::{Account, AccountHash},
     bytesrepr::FromBytes,
-    contracts::NamedKeys,
+    contracts::{NamedKeys, DEFAULT_ENTRY_POINT_NAME},
     system::{auction, handle_payment, mint, AUCTION, HANDLE_PAYMENT, MINT},
     BlockTime, CLTyped, ContextAccessRights, DeployHash, EntryPointType, Gas, Key, Phase,
     ProtocolVersion, RuntimeArgs, StoredValue, U512,
@@ -16,7 +16,7 @@ use crate::{
             ExecError,
         },
         execution::{address_generator::AddressGenerator, Error},
-        runtime::{Runtime, RuntimeStack},
+        runtime::{ExecutionTracer, GasProfiler, Runtime, RuntimeStack},
         runtime_context::RuntimeContext,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
@@ -35,12 +35,43 @@ fn try_get_amount(runtime_args: &RuntimeArgs) -> Result<U512, ExecError> {
 /// Executor object deals with execution of WASM modules.
 pub struct Executor {
     config: EngineConfig,
+    tracer: Option<ExecutionTracer>,
+    gas_profiler: Option<GasProfiler>,
 }
 
 impl Executor {
     /// Creates new executor object.
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            tracer: None,
+            gas_profiler: None,
+        }
+    }
+
+    /// Creates new executor object which records an execution trace via `tracer`.
+    ///
+    /// Tracing adds overhead to every host function call, so it must only be enabled for
+    /// speculative execution.
+    pub fn new_with_tracer(config: EngineConfig, tracer: ExecutionTracer) -> Self {
+        Executor {
+            config,
+            tracer: Some(tracer),
+            gas_profiler: None,
+        }
+    }
+
+    /// Creates new executor object which records the gas charged per entry point via
+    /// `gas_profiler`.
+    ///
+    /// Profiling adds overhead to every gas charge, so it must never be enabled when executing
+    /// deploys as part of a block.
+    pub fn new_with_gas_profiler(config: EngineConfig, gas_profiler: GasProfiler) -> Self {
+        Executor {
+            config,
+            tracer: None,
+            gas_profiler: Some(gas_profiler),
+        }
     }
 
     /// Executes a WASM module.
@@ -100,7 +131,25 @@ impl Executor {
             spending_limit,
         );
 
-        let mut runtime = Runtime::new(self.config.clone(), context);
+        let mut runtime = Runtime::new(self.config.clone(), context, self.tracer.clone());
+
+        let (contract_hash, entry_point_name) = match &execution_kind {
+            ExecutionKind::Module(_) => (None, DEFAULT_ENTRY_POINT_NAME),
+            ExecutionKind::Contract {
+                contract_hash,
+                entry_point_name,
+            } => (Some(*contract_hash), entry_point_name.as_str()),
+        };
+        if let Some(tracer) = &self.tracer {
+            tracer.enter_frame(
+                stack.call_stack_elements(),
+                entry_point_name,
+                runtime.context().gas_counter(),
+            );
+        }
+        if let Some(gas_profiler) = &self.gas_profiler {
+            gas_profiler.enter_frame(phase, contract_hash, entry_point_name);
+        }
 
         let result = match execution_kind {
             ExecutionKind::Module(module_bytes) => {
@@ -117,6 +166,16 @@ impl Executor {
             }
         };
 
+        if let Some(tracer) = &self.tracer {
+            tracer.exit_frame(
+                runtime.context().gas_counter(),
+                result.as_ref().err().map(ToString::to_string),
+            );
+        }
+        if let Some(gas_profiler) = &self.gas_profiler {
+            gas_profiler.exit_frame();
+        }
+
         match result {
             Ok(_) => ExecutionResult::Success {
                 execution_journal: runtime.context().execution_journal(),
@@ -190,7 +249,7 @@ impl Executor {
 
         // Standard payment is executed in the calling account's context; the stack already
         // captures that.
-        let mut runtime = Runtime::new(self.config.clone(), runtime_context);
+        let mut runtime = Runtime::new(self.config.clone(), runtime_context, self.tracer.clone());
 
         match runtime.call_host_standard_payment(stack) {
             Ok(()) => ExecutionResult::Success {
@@ -306,7 +365,7 @@ impl Executor {
             remaining_spending_limit,
         );
 
-        let mut runtime = Runtime::new(self.config.clone(), runtime_context);
+        let mut runtime = Runtime::new(self.config.clone(), runtime_context, self.tracer.clone());
 
         // DO NOT alter this logic to call a system contract directly (such as via mint_internal,
         // etc). Doing so would bypass necessary context based security checks in some use cases. It
@@ -370,7 +429,7 @@ impl Executor {
         let gas_counter = Gas::default();
         let transfers = Vec::default();
 
-        RuntimeContext::new(
+        let mut runtime_context = RuntimeContext::new(
             tracking_copy,
             entry_point_type,
             named_keys,
@@ -390,7 +449,9 @@ impl Executor {
             self.config.clone(),
             transfers,
             remaining_spending_limit,
-        )
+        );
+        runtime_context.set_gas_profiler(self.gas_profiler.clone());
+        runtime_context
     }
 }
 
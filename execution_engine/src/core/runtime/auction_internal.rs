@@ -144,6 +144,10 @@ where
     fn allow_auction_bids(&self) -> bool {
         self.config.allow_auction_bids()
     }
+
+    fn allow_secp256r1_keys(&self) -> bool {
+        self.config.allow_secp256r1_keys()
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
@@ -204,7 +208,7 @@ where
         })
         .map_err(|_| Error::CLValue)?;
 
-        let gas_counter = self.gas_counter();
+        let gas_checkpoint = self.gas_checkpoint();
 
         self.context
             .access_rights_extend(&[source, target.into_add()]);
@@ -217,7 +221,7 @@ where
             .call_contract(mint_contract_hash, mint::METHOD_TRANSFER, args_values)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Transfer))?;
 
-        self.set_gas_counter(gas_counter);
+        self.reset_gas(gas_checkpoint);
         cl_value.into_t().map_err(|_| Error::CLValue)
     }
 
@@ -237,7 +241,7 @@ where
         })
         .map_err(|_| Error::CLValue)?;
 
-        let gas_counter = self.gas_counter();
+        let gas_checkpoint = self.gas_checkpoint();
 
         let mint_contract_hash = self.get_mint_contract().map_err(|exec_error| {
             <Option<Error>>::from(exec_error).unwrap_or(Error::MissingValue)
@@ -250,7 +254,7 @@ where
                 args_values,
             )
             .map_err(|error| <Option<Error>>::from(error).unwrap_or(Error::MintError))?;
-        self.set_gas_counter(gas_counter);
+        self.reset_gas(gas_checkpoint);
         cl_value
             .into_t::<Result<(), mint::Error>>()
             .map_err(|_| Error::CLValue)?
@@ -305,6 +309,21 @@ where
         // exception here.
         Ok(Runtime::context(self).account().main_purse())
     }
+
+    fn read_account_main_purse(
+        &mut self,
+        account_hash: &AccountHash,
+    ) -> Result<Option<URef>, Error> {
+        let maybe_value = self
+            .context
+            .read_gs_direct(&Key::Account(*account_hash))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::Account(account)) => Ok(Some(account.main_purse())),
+            Some(_) => Err(Error::Storage),
+            None => Ok(None),
+        }
+    }
 }
 
 impl<'a, R> Auction for Runtime<'a, R>
@@ -13,7 +13,7 @@ use casper_types::{
     StoredValue, URef, U512, UREF_SERIALIZED_LENGTH,
 };
 
-use super::{args::Args, Error, Runtime};
+use super::{args::Args, gas_profile::GasCategory, Error, Runtime};
 use crate::{
     core::resolvers::v1_function_index::FunctionIndex,
     shared::host_function_costs::{Cost, HostFunction, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY},
@@ -32,6 +32,51 @@ where
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
 
+        let tracer = match self.tracer.clone() {
+            Some(tracer) => tracer,
+            None => return self.invoke_host_function(func, args),
+        };
+        let arg_values = args
+            .as_ref()
+            .iter()
+            .filter_map(|value| match value {
+                RuntimeValue::I32(value) => Some(i64::from(*value)),
+                RuntimeValue::I64(value) => Some(*value),
+                // Floating point instructions are rejected during preprocessing.
+                RuntimeValue::F32(_) | RuntimeValue::F64(_) => None,
+            })
+            .collect();
+        let gas_before = self.gas_counter();
+        let result = self.invoke_host_function(func, args);
+        let gas_consumed = self
+            .gas_counter()
+            .checked_sub(gas_before)
+            .unwrap_or_default();
+        let error = match &result {
+            Ok(_) => None,
+            // Calling `ret` is signalled via an error, but is not a failure.
+            Err(Trap::Host(host_error))
+                if matches!(host_error.downcast_ref::<Error>(), Some(Error::Ret(_))) =>
+            {
+                None
+            }
+            Err(trap) => Some(trap.to_string()),
+        };
+        tracer.host_function_call(func.name(), arg_values, gas_consumed, error);
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         let host_function_costs = self.config.wasm_config().take_host_function_costs();
 
         match func {
@@ -189,7 +234,8 @@ where
                 let (gas_arg,): (u32,) = Args::parse(args)?;
                 // Gas is special cased internal host function and for accounting purposes it isn't
                 // represented in protocol data.
-                self.gas(Gas::new(gas_arg.into()))?;
+                self.context
+                    .charge_gas_profiled(Gas::new(gas_arg.into()), GasCategory::Opcode)?;
                 Ok(None)
             }
 
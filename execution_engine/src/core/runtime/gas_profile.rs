@@ -0,0 +1,280 @@
+//! Gas profiling, recorded on request to help contract authors tune their gas usage.
+//!
+//! Profiling is opt-in and is intended for tests and tooling; it must never be enabled when
+//! executing deploys as part of a block.
+
+use std::{cell::RefCell, mem, rc::Rc};
+
+use casper_types::{ContractHash, Gas, Phase};
+
+/// The kind of cost for which gas was charged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum GasCategory {
+    /// Wasm opcodes, charged per the chainspec's opcode costs.
+    Opcode,
+    /// Host function calls, charged per the chainspec's host function costs.
+    HostFunction,
+    /// Bytes written to global state, charged per the chainspec's storage costs.
+    Storage,
+    /// Calls to system contract entry points, charged per the chainspec's system costs.
+    SystemContractCall,
+}
+
+/// The gas charged within a single entry point, broken down by the kind of cost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GasBreakdown {
+    /// Gas charged for executing Wasm opcodes.
+    pub opcodes: Gas,
+    /// Gas charged for calling host functions.
+    pub host_functions: Gas,
+    /// Gas charged for writing to global state.
+    pub storage: Gas,
+    /// Gas charged for calling system contract entry points.
+    pub system_contract_calls: Gas,
+}
+
+impl GasBreakdown {
+    /// Returns the total gas charged across all kinds of cost.
+    pub fn total(&self) -> Gas {
+        self.opcodes + self.host_functions + self.storage + self.system_contract_calls
+    }
+
+    fn add(&mut self, category: GasCategory, amount: Gas) {
+        let total = match category {
+            GasCategory::Opcode => &mut self.opcodes,
+            GasCategory::HostFunction => &mut self.host_functions,
+            GasCategory::Storage => &mut self.storage,
+            GasCategory::SystemContractCall => &mut self.system_contract_calls,
+        };
+        *total += amount;
+    }
+}
+
+/// Identifies the entry point to which gas was attributed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasReportKey {
+    /// The phase of execution in which the entry point was executed.
+    pub phase: Phase,
+    /// The hash of the stored contract, or `None` for session code passed as module bytes.
+    pub contract_hash: Option<ContractHash>,
+    /// The name of the entry point.
+    pub entry_point: String,
+}
+
+/// A report of the gas charged during the execution of a single deploy, attributed to the entry
+/// points in which it was charged.
+///
+/// Gas is attributed to the innermost entry point being executed when it was charged, so the
+/// gas charged by an entry point does not include that of any contracts it calls.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasReport {
+    /// The entries in the order in which gas was first charged to them.  Deploys only execute a
+    /// handful of distinct entry points, so a `Vec` is searched rather than keeping a map.
+    entries: Vec<(GasReportKey, GasBreakdown)>,
+}
+
+impl GasReport {
+    /// Returns the gas charged within the given entry point, if it was executed.
+    pub fn get(
+        &self,
+        phase: Phase,
+        contract_hash: Option<ContractHash>,
+        entry_point: &str,
+    ) -> Option<&GasBreakdown> {
+        self.entries
+            .iter()
+            .find(|(key, _)| {
+                key.phase == phase
+                    && key.contract_hash == contract_hash
+                    && key.entry_point == entry_point
+            })
+            .map(|(_, breakdown)| breakdown)
+    }
+
+    /// Returns an iterator over the entry points executed and the gas charged within each, in the
+    /// order in which gas was first charged to them.
+    pub fn iter(&self) -> impl Iterator<Item = (&GasReportKey, &GasBreakdown)> {
+        self.entries.iter().map(|(key, breakdown)| (key, breakdown))
+    }
+
+    /// Returns the gas charged across all entry points.
+    pub fn total(&self) -> GasBreakdown {
+        let mut total = GasBreakdown::default();
+        for (_, breakdown) in &self.entries {
+            total.add(GasCategory::Opcode, breakdown.opcodes);
+            total.add(GasCategory::HostFunction, breakdown.host_functions);
+            total.add(GasCategory::Storage, breakdown.storage);
+            total.add(
+                GasCategory::SystemContractCall,
+                breakdown.system_contract_calls,
+            );
+        }
+        total
+    }
+
+    fn add(&mut self, key: &GasReportKey, category: GasCategory, amount: Gas) {
+        let index = match self
+            .entries
+            .iter()
+            .position(|(entry_key, _)| entry_key == key)
+        {
+            Some(index) => index,
+            None => {
+                self.entries.push((key.clone(), GasBreakdown::default()));
+                self.entries.len() - 1
+            }
+        };
+        self.entries[index].1.add(category, amount);
+    }
+}
+
+/// A snapshot of the report recorded by a [`GasProfiler`], to which it can be restored.
+pub(crate) struct GasProfileCheckpoint(GasReport);
+
+#[derive(Default)]
+struct ProfilerState {
+    report: GasReport,
+    open_frames: Vec<GasReportKey>,
+}
+
+/// Records a [`GasReport`].
+///
+/// The profiler is backed by an `Rc<RefCell<_>>`, meaning that clones will all share state,
+/// allowing it to be passed down to nested runtime contexts.
+#[derive(Clone, Default)]
+pub struct GasProfiler {
+    state: Rc<RefCell<ProfilerState>>,
+}
+
+impl GasProfiler {
+    /// Records entering the given entry point.  Gas charged until the matching call to
+    /// [`exit_frame`](Self::exit_frame) is attributed to it.
+    pub(crate) fn enter_frame(
+        &self,
+        phase: Phase,
+        contract_hash: Option<ContractHash>,
+        entry_point: &str,
+    ) {
+        self.state.borrow_mut().open_frames.push(GasReportKey {
+            phase,
+            contract_hash,
+            entry_point: entry_point.to_string(),
+        });
+    }
+
+    /// Returns the number of frames entered but not yet exited.
+    pub(crate) fn open_frame_count(&self) -> usize {
+        self.state.borrow().open_frames.len()
+    }
+
+    /// Records exiting the most recently entered entry point.
+    pub(crate) fn exit_frame(&self) {
+        self.state.borrow_mut().open_frames.pop();
+    }
+
+    /// Records gas charged within the most recently entered entry point.
+    ///
+    /// Gas charged while no entry point has been entered, such as during standard payment, is not
+    /// recorded.
+    pub(crate) fn charge(&self, category: GasCategory, amount: Gas) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        if let Some(key) = state.open_frames.last() {
+            state.report.add(key, category, amount);
+        }
+    }
+
+    /// Returns a checkpoint of the report recorded so far.
+    pub(crate) fn checkpoint(&self) -> GasProfileCheckpoint {
+        GasProfileCheckpoint(self.state.borrow().report.clone())
+    }
+
+    /// Restores the report to the given checkpoint, discarding the gas recorded since.
+    ///
+    /// This is used where gas charged for internal calls to system contracts is waived by
+    /// resetting the gas counter, so that the report only holds the gas actually charged.
+    pub(crate) fn restore(&self, checkpoint: GasProfileCheckpoint) {
+        self.state.borrow_mut().report = checkpoint.0;
+    }
+
+    /// Takes the report recorded so far, leaving an empty one in its place.
+    pub fn take_report(&self) -> GasReport {
+        let mut state = self.state.borrow_mut();
+        state.open_frames.clear();
+        mem::take(&mut state.report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::U512;
+
+    use super::*;
+
+    #[test]
+    fn should_attribute_gas_to_innermost_entry_point() {
+        let contract_hash = ContractHash::new([1; 32]);
+        let gas = |amount: u64| Gas::new(U512::from(amount));
+
+        let profiler = GasProfiler::default();
+        profiler.charge(GasCategory::Opcode, gas(1));
+        profiler.enter_frame(Phase::Session, None, "call");
+        profiler.charge(GasCategory::Opcode, gas(10));
+        profiler.charge(GasCategory::HostFunction, gas(20));
+        profiler
+            .clone()
+            .enter_frame(Phase::Session, Some(contract_hash), "transfer");
+        profiler.charge(GasCategory::Storage, gas(30));
+        profiler.charge(GasCategory::SystemContractCall, gas(40));
+        profiler.exit_frame();
+        profiler.charge(GasCategory::Opcode, gas(5));
+        profiler.exit_frame();
+
+        let report = profiler.take_report();
+        assert_eq!(report.iter().count(), 2);
+
+        let session = report
+            .get(Phase::Session, None, "call")
+            .expect("should have session entry");
+        assert_eq!(session.opcodes, gas(15));
+        assert_eq!(session.host_functions, gas(20));
+        assert_eq!(session.total(), gas(35));
+
+        let contract = report
+            .get(Phase::Session, Some(contract_hash), "transfer")
+            .expect("should have contract entry");
+        assert_eq!(contract.storage, gas(30));
+        assert_eq!(contract.system_contract_calls, gas(40));
+        assert_eq!(contract.total(), gas(70));
+
+        assert_eq!(report.total().total(), gas(105));
+        assert_eq!(profiler.take_report(), GasReport::default());
+    }
+
+    #[test]
+    fn should_discard_gas_recorded_after_checkpoint() {
+        let contract_hash = ContractHash::new([1; 32]);
+        let gas = |amount: u64| Gas::new(U512::from(amount));
+
+        let profiler = GasProfiler::default();
+        profiler.enter_frame(Phase::Session, None, "call");
+        profiler.charge(GasCategory::HostFunction, gas(10));
+        let checkpoint = profiler.checkpoint();
+        profiler.charge(GasCategory::HostFunction, gas(20));
+        profiler.enter_frame(Phase::Session, Some(contract_hash), "transfer");
+        profiler.charge(GasCategory::SystemContractCall, gas(30));
+        profiler.exit_frame();
+        profiler.restore(checkpoint);
+        profiler.charge(GasCategory::Opcode, gas(5));
+        profiler.exit_frame();
+
+        let report = profiler.take_report();
+        assert_eq!(report.iter().count(), 1);
+        let session = report
+            .get(Phase::Session, None, "call")
+            .expect("should have session entry");
+        assert_eq!(session.host_functions, gas(10));
+        assert_eq!(session.opcodes, gas(5));
+        assert_eq!(report.total().total(), gas(15));
+    }
+}
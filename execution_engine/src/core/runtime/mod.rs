@@ -2,11 +2,13 @@
 mod args;
 mod auction_internal;
 mod externals;
+pub mod gas_profile;
 mod handle_payment_internal;
 mod host_function_flag;
 mod mint_internal;
 pub mod stack;
 mod standard_payment_internal;
+pub mod trace;
 mod utils;
 
 use std::{
@@ -50,7 +52,10 @@ use crate::{
     core::{
         engine_state::EngineConfig,
         execution::{self, Error},
-        runtime::host_function_flag::HostFunctionFlag,
+        runtime::{
+            gas_profile::{GasCategory, GasProfileCheckpoint},
+            host_function_flag::HostFunctionFlag,
+        },
         runtime_context::{self, RuntimeContext},
         tracking_copy::TrackingCopyExt,
     },
@@ -64,7 +69,9 @@ use crate::{
         standard_payment::StandardPayment,
     },
 };
+pub use gas_profile::{GasBreakdown, GasProfiler, GasReport, GasReportKey};
 pub use stack::{RuntimeStack, RuntimeStackFrame, RuntimeStackOverflow};
+pub use trace::{ExecutionTrace, ExecutionTracer};
 
 enum CallContractIdentifier {
     Contract {
@@ -76,6 +83,12 @@ enum CallContractIdentifier {
     },
 }
 
+/// The gas charged at some point of execution, see [`Runtime::gas_checkpoint`].
+struct GasCheckpoint {
+    gas_counter: Gas,
+    gas_profile: Option<GasProfileCheckpoint>,
+}
+
 /// Represents the runtime properties of a WASM execution.
 pub struct Runtime<'a, R> {
     config: EngineConfig,
@@ -85,6 +98,7 @@ pub struct Runtime<'a, R> {
     context: RuntimeContext<'a, R>,
     stack: Option<RuntimeStack>,
     host_function_flag: HostFunctionFlag,
+    tracer: Option<ExecutionTracer>,
 }
 
 impl<'a, R> Runtime<'a, R>
@@ -93,7 +107,11 @@ where
     R::Error: Into<Error>,
 {
     /// Creates a new runtime instance.
-    pub(crate) fn new(config: EngineConfig, context: RuntimeContext<'a, R>) -> Self {
+    pub(crate) fn new(
+        config: EngineConfig,
+        context: RuntimeContext<'a, R>,
+        tracer: Option<ExecutionTracer>,
+    ) -> Self {
         Runtime {
             config,
             memory: None,
@@ -102,10 +120,12 @@ where
             context,
             stack: None,
             host_function_flag: HostFunctionFlag::default(),
+            tracer,
         }
     }
 
-    /// Creates a new runtime instance by cloning the config, and host function flag from `self`.
+    /// Creates a new runtime instance by cloning the config, host function flag and tracer from
+    /// `self`.
     fn new_invocation_runtime(
         &self,
         context: RuntimeContext<'a, R>,
@@ -122,6 +142,7 @@ where
             context,
             stack: Some(stack),
             host_function_flag: self.host_function_flag.clone(),
+            tracer: self.tracer.clone(),
         }
     }
 
@@ -140,6 +161,7 @@ where
             context,
             stack: Some(stack),
             host_function_flag: self.host_function_flag.clone(),
+            tracer: self.tracer.clone(),
         }
     }
 
@@ -162,6 +184,10 @@ where
         &self.context
     }
 
+    /// Charges gas without recording it in the gas report.
+    ///
+    /// This is only used to carry over the gas charged in the isolated runtime of a system
+    /// contract, where it has already been recorded if gas is being profiled.
     fn gas(&mut self, amount: Gas) -> Result<(), Error> {
         self.context.charge_gas(amount)
     }
@@ -171,9 +197,24 @@ where
         self.context.gas_counter()
     }
 
-    /// Sets new gas counter value.
-    fn set_gas_counter(&mut self, new_gas_counter: Gas) {
-        self.context.set_gas_counter(new_gas_counter);
+    /// Returns a checkpoint of the gas charged so far, to which the gas counter can be reset via
+    /// [`reset_gas`](Self::reset_gas).
+    fn gas_checkpoint(&self) -> GasCheckpoint {
+        GasCheckpoint {
+            gas_counter: self.gas_counter(),
+            gas_profile: self.context.gas_profiler().map(GasProfiler::checkpoint),
+        }
+    }
+
+    /// Resets the gas counter, and the gas report if gas is being profiled, to the given
+    /// checkpoint, waiving the gas charged since.
+    fn reset_gas(&mut self, checkpoint: GasCheckpoint) {
+        self.context.set_gas_counter(checkpoint.gas_counter);
+        if let (Some(gas_profiler), Some(gas_profile)) =
+            (self.context.gas_profiler(), checkpoint.gas_profile)
+        {
+            gas_profiler.restore(gas_profile);
+        }
     }
 
     /// Charge for a system contract call.
@@ -706,7 +747,7 @@ where
 
         // Charge just for the amount that particular entry point cost - using gas cost from the
         // isolated runtime might have a recursive costs whenever system contract calls other system
-        // contract.
+        // contract.  The gas profiler, if any, recorded these charges in the isolated runtime.
         self.gas(match mint_runtime.gas_counter().checked_sub(gas_counter) {
             None => gas_counter,
             Some(new_gas) => new_gas,
@@ -807,6 +848,7 @@ where
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
 
+        // The gas profiler, if any, recorded these charges in the isolated runtime.
         self.gas(match runtime.gas_counter().checked_sub(gas_counter) {
             None => gas_counter,
             Some(new_gas) => new_gas,
@@ -827,11 +869,11 @@ where
         // NOTE: This method (unlike other call_host_* methods) already runs on its own runtime
         // context.
         self.stack = Some(stack);
-        let gas_counter = self.gas_counter();
+        let gas_checkpoint = self.gas_checkpoint();
         let amount: U512 =
             Self::get_named_argument(self.context.args(), standard_payment::ARG_AMOUNT)?;
         let result = self.pay(amount).map_err(Self::reverter);
-        self.set_gas_counter(gas_counter);
+        self.reset_gas(gas_checkpoint);
         result
     }
 
@@ -1028,10 +1070,60 @@ where
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_SET_REWARD_POLICY => (|| {
+                runtime.charge_system_contract_call(auction_costs.set_reward_policy)?;
+
+                let public_key = Self::get_named_argument(runtime_args, auction::ARG_PUBLIC_KEY)?;
+                let reward_policy =
+                    Self::get_named_argument(runtime_args, auction::ARG_REWARD_POLICY)?;
+
+                runtime
+                    .set_reward_policy(public_key, reward_policy)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_SET_DELEGATOR_REWARD_POLICY => (|| {
+                runtime.charge_system_contract_call(auction_costs.set_delegator_reward_policy)?;
+
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let reward_policy =
+                    Self::get_named_argument(runtime_args, auction::ARG_REWARD_POLICY)?;
+
+                runtime
+                    .set_delegator_reward_policy(delegator, validator, reward_policy)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_SET_DELEGATION_LIMITS => (|| {
+                runtime.charge_system_contract_call(auction_costs.set_delegation_limits)?;
+
+                let public_key = Self::get_named_argument(runtime_args, auction::ARG_PUBLIC_KEY)?;
+                let minimum_delegation_amount =
+                    Self::get_named_argument(runtime_args, auction::ARG_MINIMUM_DELEGATION_AMOUNT)?;
+                let maximum_delegated_stake =
+                    Self::get_named_argument(runtime_args, auction::ARG_MAXIMUM_DELEGATED_STAKE)?;
+
+                runtime
+                    .set_delegation_limits(
+                        public_key,
+                        minimum_delegation_amount,
+                        maximum_delegated_stake,
+                    )
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
 
-        // Charge for the gas spent during execution in an isolated runtime.
+        // Charge for the gas spent during execution in an isolated runtime.  The gas profiler, if
+        // any, recorded these charges there.
         self.gas(match runtime.gas_counter().checked_sub(gas_counter) {
             None => gas_counter,
             Some(new_gas) => new_gas,
@@ -1185,6 +1277,40 @@ where
         identifier: CallContractIdentifier,
         entry_point_name: &str,
         args: RuntimeArgs,
+    ) -> Result<CLValue, Error> {
+        let tracer = self.tracer.clone();
+        let gas_profiler = self.context.gas_profiler().cloned();
+        if tracer.is_none() && gas_profiler.is_none() {
+            return self.execute_contract_untraced(identifier, entry_point_name, args);
+        }
+        let traced_frames_before = tracer.as_ref().map(ExecutionTracer::open_frame_count);
+        let profiled_frames_before = gas_profiler.as_ref().map(GasProfiler::open_frame_count);
+        let result = self.execute_contract_untraced(identifier, entry_point_name, args);
+        // Frames are only entered once the call stack has been extended, so don't exit a frame if
+        // the call failed before that.
+        if let (Some(tracer), Some(open_frames_before)) = (tracer, traced_frames_before) {
+            if tracer.open_frame_count() > open_frames_before {
+                tracer.exit_frame(
+                    self.gas_counter(),
+                    result.as_ref().err().map(ToString::to_string),
+                );
+            }
+        }
+        if let (Some(gas_profiler), Some(open_frames_before)) =
+            (gas_profiler, profiled_frames_before)
+        {
+            if gas_profiler.open_frame_count() > open_frames_before {
+                gas_profiler.exit_frame();
+            }
+        }
+        result
+    }
+
+    fn execute_contract_untraced(
+        &mut self,
+        identifier: CallContractIdentifier,
+        entry_point_name: &str,
+        args: RuntimeArgs,
     ) -> Result<CLValue, Error> {
         let (contract, contract_hash, contract_package) = match identifier {
             CallContractIdentifier::Contract { contract_hash } => {
@@ -1324,6 +1450,21 @@ where
             };
             stack.push(call_stack_element)?;
 
+            if let Some(tracer) = &self.tracer {
+                tracer.enter_frame(
+                    stack.call_stack_elements(),
+                    entry_point.name(),
+                    self.gas_counter(),
+                );
+            }
+            if let Some(gas_profiler) = self.context.gas_profiler() {
+                gas_profiler.enter_frame(
+                    self.context.phase(),
+                    Some(contract_hash),
+                    entry_point.name(),
+                );
+            }
+
             stack
         };
 
@@ -2153,13 +2294,13 @@ where
         &mut self,
         mint_contract_hash: ContractHash,
     ) -> Result<U512, Error> {
-        let gas_counter = self.gas_counter();
+        let gas_checkpoint = self.gas_checkpoint();
         let call_result = self.call_contract(
             mint_contract_hash,
             mint::METHOD_READ_BASE_ROUND_REWARD,
             RuntimeArgs::default(),
         );
-        self.set_gas_counter(gas_counter);
+        self.reset_gas(gas_checkpoint);
 
         let reward = call_result?.into_t()?;
         Ok(reward)
@@ -2168,14 +2309,14 @@ where
     /// Calls the `mint` method on the mint contract at the given mint
     /// contract key
     fn mint_mint(&mut self, mint_contract_hash: ContractHash, amount: U512) -> Result<URef, Error> {
-        let gas_counter = self.gas_counter();
+        let gas_checkpoint = self.gas_checkpoint();
         let runtime_args = {
             let mut runtime_args = RuntimeArgs::new();
             runtime_args.insert(mint::ARG_AMOUNT, amount)?;
             runtime_args
         };
         let call_result = self.call_contract(mint_contract_hash, mint::METHOD_MINT, runtime_args);
-        self.set_gas_counter(gas_counter);
+        self.reset_gas(gas_checkpoint);
 
         let result: Result<URef, mint::Error> = call_result?.into_t()?;
         Ok(result.map_err(system::Error::from)?)
@@ -2188,7 +2329,7 @@ where
         mint_contract_hash: ContractHash,
         amount: U512,
     ) -> Result<(), Error> {
-        let gas_counter = self.gas_counter();
+        let gas_checkpoint = self.gas_checkpoint();
         let runtime_args = {
             let mut runtime_args = RuntimeArgs::new();
             runtime_args.insert(mint::ARG_AMOUNT, amount)?;
@@ -2199,7 +2340,7 @@ where
             mint::METHOD_REDUCE_TOTAL_SUPPLY,
             runtime_args,
         );
-        self.set_gas_counter(gas_counter);
+        self.reset_gas(gas_checkpoint);
 
         let result: Result<(), mint::Error> = call_result?.into_t()?;
         Ok(result.map_err(system::Error::from)?)
@@ -2242,10 +2383,10 @@ where
             runtime_args
         };
 
-        let gas_counter = self.gas_counter();
+        let gas_checkpoint = self.gas_checkpoint();
         let call_result =
             self.call_contract(mint_contract_hash, mint::METHOD_TRANSFER, args_values);
-        self.set_gas_counter(gas_counter);
+        self.reset_gas(gas_checkpoint);
 
         Ok(call_result?.into_t()?)
     }
@@ -2819,7 +2960,8 @@ where
         T: AsRef<[Cost]> + Copy,
     {
         let cost = host_function.calculate_gas_cost(weights);
-        self.gas(cost)?;
+        self.context
+            .charge_gas_profiled(cost, GasCategory::HostFunction)?;
         Ok(())
     }
 
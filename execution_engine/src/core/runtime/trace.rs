@@ -0,0 +1,357 @@
+//! Execution traces, recorded on request for debugging purposes.
+//!
+//! Tracing is opt-in and is only ever enabled for speculative execution; it must never be enabled
+//! when executing deploys as part of a block.
+
+use std::{cell::RefCell, mem, rc::Rc};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{system::CallStackElement, Gas, U512};
+
+use crate::core::engine_state::engine_config::DEFAULT_MAX_TRACE_ENTRIES;
+
+/// A call to a host function made during execution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HostFunctionCall {
+    /// The name under which the host function is imported, e.g. `casper_read_value`.
+    pub name: String,
+    /// The arguments passed to the host function, mostly pointers into and sizes of Wasm memory.
+    pub args: Vec<i64>,
+    /// The gas consumed by the call, including any contract calls it made.
+    pub gas_consumed: U512,
+    /// The error returned by the host function, if any.
+    pub error: Option<String>,
+}
+
+/// A call stack frame entered during execution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TraceFrame {
+    /// The height of the call stack in this frame, starting at 1 for the outermost frame.
+    pub depth: u32,
+    /// The element pushed onto the call stack for this frame.
+    pub call_stack_element: CallStackElement,
+    /// The name of the entry point called.
+    pub entry_point: String,
+    /// The host function calls made directly from this frame, in the order they were made.
+    pub host_function_calls: Vec<HostFunctionCall>,
+    /// The gas consumed in this frame, including any frames entered from it.
+    pub gas_consumed: U512,
+    /// The error with which this frame exited, if any.
+    pub error: Option<String>,
+}
+
+/// The point at which execution failed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TraceRevert {
+    /// The call stack of the innermost frame which failed.
+    pub call_stack: Vec<CallStackElement>,
+    /// The entry point of the innermost frame which failed.
+    pub entry_point: String,
+    /// The host function which failed, if the failure was raised by a host function.
+    pub host_function: Option<String>,
+    /// The error with which the frame failed.
+    pub error: String,
+}
+
+/// A trace of the execution of a single deploy.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionTrace {
+    /// The frames entered during execution, in the order they were entered.  The `depth` of each
+    /// frame allows the call tree to be reconstructed.
+    pub frames: Vec<TraceFrame>,
+    /// The point at which execution failed, if it did.
+    pub revert: Option<TraceRevert>,
+    /// The number of frames and host function calls left out of the trace once the maximum number
+    /// of entries was reached.
+    pub omitted_entries: u64,
+}
+
+/// A frame which has been entered but not yet exited.
+struct OpenFrame {
+    /// The index of the frame in the trace, or `None` if it was omitted.
+    index: Option<usize>,
+    call_stack: Vec<CallStackElement>,
+    entry_point: String,
+    gas_at_entry: Gas,
+    /// The most recent host function call from this frame, if it returned an error.
+    failed_host_function: Option<&'static str>,
+}
+
+struct TracerState {
+    trace: ExecutionTrace,
+    open_frames: Vec<OpenFrame>,
+    max_entries: usize,
+    entries: usize,
+}
+
+impl TracerState {
+    /// Returns whether another entry can be recorded, counting it as omitted if not.
+    fn try_add_entry(&mut self) -> bool {
+        if self.entries >= self.max_entries {
+            self.trace.omitted_entries += 1;
+            return false;
+        }
+        self.entries += 1;
+        true
+    }
+}
+
+/// Records an [`ExecutionTrace`].
+///
+/// The tracer is backed by an `Rc<RefCell<_>>`, meaning that clones will all share state, allowing
+/// it to be passed down to nested runtimes.
+#[derive(Clone)]
+pub struct ExecutionTracer {
+    state: Rc<RefCell<TracerState>>,
+}
+
+impl ExecutionTracer {
+    /// Creates a tracer which records at most `max_entries` frames and host function calls per
+    /// trace.  Further entries are only counted in [`ExecutionTrace::omitted_entries`].
+    pub fn new(max_entries: u32) -> Self {
+        ExecutionTracer {
+            state: Rc::new(RefCell::new(TracerState {
+                trace: ExecutionTrace::default(),
+                open_frames: vec![],
+                max_entries: max_entries as usize,
+                entries: 0,
+            })),
+        }
+    }
+
+    /// Records entering a new call stack frame.
+    ///
+    /// `call_stack` must include the element of the frame being entered as its last element.
+    pub(crate) fn enter_frame(
+        &self,
+        call_stack: &[CallStackElement],
+        entry_point: &str,
+        gas_counter: Gas,
+    ) {
+        let call_stack_element = match call_stack.last() {
+            Some(call_stack_element) => call_stack_element.clone(),
+            None => return,
+        };
+        let mut state = self.state.borrow_mut();
+        let index = if state.try_add_entry() {
+            state.trace.frames.push(TraceFrame {
+                depth: call_stack.len() as u32,
+                call_stack_element,
+                entry_point: entry_point.to_string(),
+                host_function_calls: vec![],
+                gas_consumed: U512::zero(),
+                error: None,
+            });
+            Some(state.trace.frames.len() - 1)
+        } else {
+            None
+        };
+        state.open_frames.push(OpenFrame {
+            index,
+            call_stack: call_stack.to_vec(),
+            entry_point: entry_point.to_string(),
+            gas_at_entry: gas_counter,
+            failed_host_function: None,
+        });
+    }
+
+    /// Returns the number of frames entered but not yet exited.
+    pub(crate) fn open_frame_count(&self) -> usize {
+        self.state.borrow().open_frames.len()
+    }
+
+    /// Records exiting the most recently entered call stack frame.
+    ///
+    /// The first frame to exit with an error is recorded as the point at which execution failed.
+    pub(crate) fn exit_frame(&self, gas_counter: Gas, error: Option<String>) {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        let OpenFrame {
+            index,
+            call_stack,
+            entry_point,
+            gas_at_entry,
+            failed_host_function,
+        } = match state.open_frames.pop() {
+            Some(open_frame) => open_frame,
+            None => return,
+        };
+        if let Some(index) = index {
+            let frame = &mut state.trace.frames[index];
+            frame.gas_consumed = gas_counter.value().saturating_sub(gas_at_entry.value());
+            frame.error = error.clone();
+        }
+
+        if let Some(error) = error {
+            if state.trace.revert.is_none() {
+                state.trace.revert = Some(TraceRevert {
+                    call_stack,
+                    entry_point,
+                    host_function: failed_host_function.map(str::to_string),
+                    error,
+                });
+            }
+        }
+    }
+
+    /// Records a host function call made from the most recently entered call stack frame.
+    pub(crate) fn host_function_call(
+        &self,
+        name: &'static str,
+        args: Vec<i64>,
+        gas_consumed: Gas,
+        error: Option<String>,
+    ) {
+        let mut state = self.state.borrow_mut();
+        let index = match state.open_frames.last_mut() {
+            Some(open_frame) => {
+                open_frame.failed_host_function = error.as_ref().map(|_| name);
+                open_frame.index
+            }
+            None => return,
+        };
+        let index = match index {
+            Some(index) if state.try_add_entry() => index,
+            Some(_) => return,
+            None => {
+                state.trace.omitted_entries += 1;
+                return;
+            }
+        };
+        state.trace.frames[index]
+            .host_function_calls
+            .push(HostFunctionCall {
+                name: name.to_string(),
+                args,
+                gas_consumed: gas_consumed.value(),
+                error,
+            });
+    }
+
+    /// Takes the trace recorded so far, leaving an empty one in its place.
+    pub fn take_trace(&self) -> ExecutionTrace {
+        let mut state = self.state.borrow_mut();
+        state.open_frames.clear();
+        state.entries = 0;
+        mem::take(&mut state.trace)
+    }
+}
+
+impl Default for ExecutionTracer {
+    fn default() -> Self {
+        ExecutionTracer::new(DEFAULT_MAX_TRACE_ENTRIES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, ContractHash, ContractPackageHash};
+
+    use super::*;
+
+    #[test]
+    fn should_record_nested_frames_and_revert_point() {
+        let session = CallStackElement::session(AccountHash::new([1; 32]));
+        let contract = CallStackElement::stored_contract(
+            ContractPackageHash::new([2; 32]),
+            ContractHash::new([3; 32]),
+        );
+
+        let tracer = ExecutionTracer::default();
+        tracer.enter_frame(&[session.clone()], "call", Gas::new(U512::from(10)));
+        tracer.host_function_call(
+            "casper_read_value",
+            vec![1024, 36, 2048],
+            Gas::new(U512::from(5)),
+            None,
+        );
+        tracer.clone().enter_frame(
+            &[session.clone(), contract.clone()],
+            "transfer",
+            Gas::new(U512::from(15)),
+        );
+        tracer.host_function_call(
+            "casper_revert",
+            vec![1],
+            Gas::new(U512::from(1)),
+            Some("User error: 1".to_string()),
+        );
+        tracer.exit_frame(Gas::new(U512::from(25)), Some("User error: 1".to_string()));
+        tracer.exit_frame(Gas::new(U512::from(30)), Some("User error: 1".to_string()));
+
+        let trace = tracer.take_trace();
+        assert_eq!(trace.frames.len(), 2);
+
+        let outer = &trace.frames[0];
+        assert_eq!(outer.depth, 1);
+        assert_eq!(outer.call_stack_element, session);
+        assert_eq!(outer.host_function_calls.len(), 1);
+        assert_eq!(outer.host_function_calls[0].name, "casper_read_value");
+        assert_eq!(outer.host_function_calls[0].args, vec![1024, 36, 2048]);
+        assert_eq!(outer.gas_consumed, U512::from(20));
+
+        let inner = &trace.frames[1];
+        assert_eq!(inner.depth, 2);
+        assert_eq!(inner.call_stack_element, contract);
+        assert_eq!(inner.entry_point, "transfer");
+        assert_eq!(inner.gas_consumed, U512::from(10));
+
+        let revert = trace.revert.expect("should have revert point");
+        assert_eq!(revert.call_stack, vec![session, contract]);
+        assert_eq!(revert.entry_point, "transfer");
+        assert_eq!(revert.host_function.as_deref(), Some("casper_revert"));
+        assert_eq!(trace.omitted_entries, 0);
+
+        assert_eq!(tracer.take_trace(), ExecutionTrace::default());
+    }
+
+    #[test]
+    fn should_omit_entries_beyond_limit() {
+        let session = CallStackElement::session(AccountHash::new([1; 32]));
+        let contract = CallStackElement::stored_contract(
+            ContractPackageHash::new([2; 32]),
+            ContractHash::new([3; 32]),
+        );
+
+        let tracer = ExecutionTracer::new(3);
+        tracer.enter_frame(&[session.clone()], "call", Gas::new(U512::from(10)));
+        for _ in 0..3 {
+            tracer.host_function_call("casper_gas", vec![1], Gas::new(U512::one()), None);
+        }
+        tracer.enter_frame(
+            &[session.clone(), contract.clone()],
+            "transfer",
+            Gas::new(U512::from(15)),
+        );
+        tracer.host_function_call(
+            "casper_revert",
+            vec![1],
+            Gas::new(U512::one()),
+            Some("User error: 1".to_string()),
+        );
+        tracer.exit_frame(Gas::new(U512::from(20)), Some("User error: 1".to_string()));
+        tracer.exit_frame(Gas::new(U512::from(25)), Some("User error: 1".to_string()));
+
+        let trace = tracer.take_trace();
+        assert_eq!(trace.frames.len(), 1);
+        assert_eq!(trace.frames[0].host_function_calls.len(), 2);
+        assert_eq!(trace.frames[0].gas_consumed, U512::from(15));
+        assert_eq!(trace.omitted_entries, 3);
+
+        // The revert point is still recorded even though its frame was omitted.
+        let revert = trace.revert.expect("should have revert point");
+        assert_eq!(revert.call_stack, vec![session.clone(), contract]);
+        assert_eq!(revert.entry_point, "transfer");
+        assert_eq!(revert.host_function.as_deref(), Some("casper_revert"));
+
+        // The limit applies per trace.
+        tracer.enter_frame(&[session], "call", Gas::default());
+        assert_eq!(tracer.take_trace().frames.len(), 1);
+    }
+}
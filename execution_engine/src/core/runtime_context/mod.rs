@@ -27,6 +27,7 @@ use crate::{
     core::{
         engine_state::{execution_effect::ExecutionEffect, EngineConfig, SystemContractRegistry},
         execution::{AddressGenerator, Error},
+        runtime::gas_profile::{GasCategory, GasProfiler},
         runtime_context::dictionary::DictionaryValue,
         tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
     },
@@ -104,6 +105,7 @@ pub struct RuntimeContext<'a, R> {
     entry_point_type: EntryPointType,
     transfers: Vec<TransferAddr>,
     remaining_spending_limit: U512,
+    gas_profiler: Option<GasProfiler>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -156,6 +158,7 @@ where
             engine_config,
             transfers,
             remaining_spending_limit,
+            gas_profiler: None,
         }
     }
 
@@ -184,6 +187,7 @@ where
         let engine_config = self.engine_config.clone();
         let transfers = self.transfers.clone();
         let remaining_spending_limit = self.remaining_spending_limit();
+        let gas_profiler = self.gas_profiler.clone();
 
         RuntimeContext {
             tracking_copy,
@@ -205,9 +209,21 @@ where
             engine_config,
             transfers,
             remaining_spending_limit,
+            gas_profiler,
         }
     }
 
+    /// Sets the profiler with which gas charged in this context, and in contexts created from it,
+    /// is recorded.
+    pub(crate) fn set_gas_profiler(&mut self, gas_profiler: Option<GasProfiler>) {
+        self.gas_profiler = gas_profiler;
+    }
+
+    /// Returns the gas profiler, if gas is being profiled.
+    pub(crate) fn gas_profiler(&self) -> Option<&GasProfiler> {
+        self.gas_profiler.as_ref()
+    }
+
     /// Returns all authorization keys for this deploy.
     pub fn authorization_keys(&self) -> &BTreeSet<AccountHash> {
         &self.authorization_keys
@@ -864,6 +880,24 @@ where
         }
     }
 
+    /// Charges gas like [`charge_gas`](Self::charge_gas), additionally recording the gas actually
+    /// charged against `category` if gas is being profiled.
+    pub(crate) fn charge_gas_profiled(
+        &mut self,
+        amount: Gas,
+        category: GasCategory,
+    ) -> Result<(), Error> {
+        let gas_profiler = match self.gas_profiler.clone() {
+            Some(gas_profiler) => gas_profiler,
+            None => return self.charge_gas(amount),
+        };
+        let prev = self.gas_counter();
+        let result = self.charge_gas(amount);
+        let charged = self.gas_counter().checked_sub(prev).unwrap_or_default();
+        gas_profiler.charge(category, charged);
+        result
+    }
+
     /// Checks if we are calling a system contract.
     pub(crate) fn is_system_contract(&self, contract_hash: &ContractHash) -> Result<bool, Error> {
         Ok(self
@@ -885,7 +919,7 @@ where
 
         let gas_cost = storage_costs.calculate_gas_cost(bytes_count);
 
-        self.charge_gas(gas_cost)
+        self.charge_gas_profiled(gas_cost, GasCategory::Storage)
     }
 
     /// Charges gas for using a host system contract's entrypoint.
@@ -900,7 +934,7 @@ where
             return Ok(());
         }
         let amount: Gas = call_cost.into();
-        self.charge_gas(amount)
+        self.charge_gas_profiled(amount, GasCategory::SystemContractCall)
     }
 
     /// Writes data to global state with a measurement.
@@ -1,13 +1,14 @@
-use std::{cell::Cell, iter, rc::Rc};
+use std::{cell::Cell, collections::BTreeMap, iter, rc::Rc};
 
 use assert_matches::assert_matches;
 use proptest::prelude::*;
 
-use casper_hashing::Digest;
+use casper_hashing::{verify_global_state_proof, Digest, GlobalStateProofVerificationError};
 use casper_types::{
     account::{
         Account, AccountHash, ActionThresholds, AssociatedKeys, Weight, ACCOUNT_HASH_LENGTH,
     },
+    bytesrepr::{self, ToBytes},
     contracts::NamedKeys,
     gens::*,
     AccessRights, CLValue, Contract, EntryPoints, HashAddr, Key, KeyTag, ProtocolVersion,
@@ -580,6 +581,69 @@ fn query_for_circular_references_should_fail() {
     }
 }
 
+proptest! {
+    #[test]
+    fn verify_global_state_proof_should_accept_read_with_proof_output(
+        pairs in proptest::collection::vec((key_arb(), stored_value_arb()), 1..8),
+        other_value in stored_value_arb(),
+        tamper_index in any::<proptest::sample::Index>(),
+    ) {
+        let correlation_id = CorrelationId::new();
+
+        let pairs: Vec<(Key, StoredValue)> = pairs
+            .into_iter()
+            .map(|(key, value)| (key.normalize(), value))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect();
+        let (gs, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+        let view = gs.checkout(root_hash).unwrap().unwrap();
+
+        for (index, (key, value)) in pairs.iter().enumerate() {
+            let proof = view
+                .read_with_proof(correlation_id, key)
+                .unwrap()
+                .expect("should have proof");
+            let proof_bytes = vec![proof.clone()].to_bytes().unwrap();
+
+            prop_assert_eq!(
+                verify_global_state_proof(&root_hash, key, &[], value, &proof_bytes),
+                Ok(())
+            );
+            prop_assert_eq!(
+                verify_global_state_proof(&Digest::hash([]), key, &[], value, &proof_bytes),
+                Err(GlobalStateProofVerificationError::InvalidProofHash)
+            );
+            if other_value != *value {
+                prop_assert_eq!(
+                    verify_global_state_proof(&root_hash, key, &[], &other_value, &proof_bytes),
+                    Err(GlobalStateProofVerificationError::UnexpectedValue)
+                );
+            }
+            let (other_key, _) = &pairs[(index + 1) % pairs.len()];
+            if other_key != key {
+                prop_assert_eq!(
+                    verify_global_state_proof(&root_hash, other_key, &[], value, &proof_bytes),
+                    Err(GlobalStateProofVerificationError::UnexpectedKey)
+                );
+            }
+
+            let mut tampered_proof_bytes = proof_bytes.clone();
+            tampered_proof_bytes[tamper_index.index(proof_bytes.len())] ^= u8::MAX;
+            // Some encodings are not canonical, e.g. padding bytes of a few `Key` variants are
+            // ignored; tampering with those leaves the decoded proof, and hence its validity, intact.
+            let tampered_proofs: Result<Vec<TrieMerkleProof<Key, StoredValue>>, _> =
+                bytesrepr::deserialize(tampered_proof_bytes.clone());
+            if tampered_proofs.ok().as_deref() != Some(&[proof.clone()]) {
+                prop_assert!(
+                    verify_global_state_proof(&root_hash, key, &[], value, &tampered_proof_bytes)
+                        .is_err()
+                );
+            }
+        }
+    }
+}
+
 #[test]
 fn validate_query_proof_should_work() {
     // create account
@@ -829,6 +893,140 @@ fn validate_query_proof_should_work() {
     );
 }
 
+#[test]
+fn verify_global_state_proof_should_follow_query_path() {
+    // create account
+    let account_hash = AccountHash::new([3; 32]);
+    let fake_purse = URef::new([4; 32], AccessRights::READ_ADD_WRITE);
+    let account_value = StoredValue::Account(Account::create(
+        account_hash,
+        NamedKeys::default(),
+        fake_purse,
+    ));
+    let account_key = Key::Account(account_hash);
+
+    // create contract that refers to that account
+    let account_name = "account".to_string();
+    let named_keys = {
+        let mut tmp = NamedKeys::new();
+        tmp.insert(account_name.clone(), account_key);
+        tmp
+    };
+    let contract_value = StoredValue::Contract(Contract::new(
+        [2; 32].into(),
+        [3; 32].into(),
+        named_keys,
+        EntryPoints::default(),
+        ProtocolVersion::V1_0_0,
+    ));
+    let contract_key = Key::Hash([5; 32]);
+
+    // create account that refers to that contract and to a uref
+    let uref_key = Key::URef(URef::new([8; 32], AccessRights::READ_ADD_WRITE));
+    let account_hash = AccountHash::new([7; 32]);
+    let fake_purse = URef::new([6; 32], AccessRights::READ_ADD_WRITE);
+    let contract_name = "contract".to_string();
+    let uref_name = "uref".to_string();
+    let named_keys = {
+        let mut tmp = NamedKeys::new();
+        tmp.insert(contract_name.clone(), contract_key);
+        tmp.insert(uref_name.clone(), uref_key);
+        tmp
+    };
+    let associated_keys = AssociatedKeys::new(account_hash, Weight::new(1));
+    let main_account_value = StoredValue::Account(Account::new(
+        account_hash,
+        named_keys,
+        fake_purse,
+        associated_keys,
+        ActionThresholds::default(),
+    ));
+    let main_account_key = Key::Account(account_hash);
+
+    let cl_value = CLValue::from_t(U512::zero()).expect("should convert");
+    let uref_value = StoredValue::CLValue(cl_value);
+
+    // persist them
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (account_key, account_value.to_owned()),
+            (contract_key, contract_value),
+            (main_account_key, main_account_value),
+            (uref_key, uref_value),
+        ],
+    )
+    .unwrap();
+
+    let view = global_state
+        .checkout(root_hash)
+        .expect("should checkout")
+        .expect("should have view");
+
+    let tracking_copy = TrackingCopy::new(view);
+
+    let path = &[contract_name, account_name];
+
+    let result = tracking_copy
+        .query(
+            correlation_id,
+            &EngineConfig::default(),
+            main_account_key,
+            path,
+        )
+        .expect("should query");
+
+    let proofs = if let TrackingCopyQueryResult::Success { proofs, .. } = result {
+        proofs
+    } else {
+        panic!("query was not successful: {:?}", result)
+    };
+    let proof_bytes = proofs.to_bytes().unwrap();
+
+    // Happy path
+    verify_global_state_proof(
+        &root_hash,
+        &main_account_key,
+        path,
+        &account_value,
+        &proof_bytes,
+    )
+    .expect("should verify");
+
+    // The proof must follow the named keys of the given path, not just any named keys.
+    assert_eq!(
+        verify_global_state_proof(
+            &root_hash,
+            &main_account_key,
+            &[uref_name, path[1].clone()],
+            &account_value,
+            &proof_bytes,
+        ),
+        Err(GlobalStateProofVerificationError::UnexpectedKey)
+    );
+    assert_eq!(
+        verify_global_state_proof(
+            &root_hash,
+            &main_account_key,
+            &[path[1].clone(), path[0].clone()],
+            &account_value,
+            &proof_bytes,
+        ),
+        Err(GlobalStateProofVerificationError::PathCold)
+    );
+    assert_eq!(
+        verify_global_state_proof(
+            &root_hash,
+            &main_account_key,
+            &path[..1],
+            &account_value,
+            &proof_bytes,
+        ),
+        Err(GlobalStateProofVerificationError::PathLengthMismatch)
+    );
+}
+
 #[test]
 fn get_keys_should_return_keys_in_the_account_keyspace() {
     // account 1
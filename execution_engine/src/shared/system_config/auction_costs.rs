@@ -32,6 +32,12 @@ pub const DEFAULT_WITHDRAW_VALIDATOR_REWARD_COST: u32 = 10_000;
 pub const DEFAULT_READ_ERA_ID_COST: u32 = 10_000;
 /// Default cost of the `activate_bid` auction entry point.
 pub const DEFAULT_ACTIVATE_BID_COST: u32 = 10_000;
+/// Default cost of the `set_reward_policy` auction entry point.
+pub const DEFAULT_SET_REWARD_POLICY_COST: u32 = 10_000;
+/// Default cost of the `set_delegator_reward_policy` auction entry point.
+pub const DEFAULT_SET_DELEGATOR_REWARD_POLICY_COST: u32 = 10_000;
+/// Default cost of the `set_delegation_limits` auction entry point.
+pub const DEFAULT_SET_DELEGATION_LIMITS_COST: u32 = 10_000;
 
 /// Description of the costs of calling auction entrypoints.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -65,6 +71,12 @@ pub struct AuctionCosts {
     pub activate_bid: u32,
     /// Cost of calling the `redelegate` entry point.
     pub redelegate: u32,
+    /// Cost of calling the `set_reward_policy` entry point.
+    pub set_reward_policy: u32,
+    /// Cost of calling the `set_delegator_reward_policy` entry point.
+    pub set_delegator_reward_policy: u32,
+    /// Cost of calling the `set_delegation_limits` entry point.
+    pub set_delegation_limits: u32,
 }
 
 impl Default for AuctionCosts {
@@ -84,6 +96,9 @@ impl Default for AuctionCosts {
             read_era_id: DEFAULT_READ_ERA_ID_COST,
             activate_bid: DEFAULT_ACTIVATE_BID_COST,
             redelegate: DEFAULT_REDELEGATE_COST,
+            set_reward_policy: DEFAULT_SET_REWARD_POLICY_COST,
+            set_delegator_reward_policy: DEFAULT_SET_DELEGATOR_REWARD_POLICY_COST,
+            set_delegation_limits: DEFAULT_SET_DELEGATION_LIMITS_COST,
         }
     }
 }
@@ -107,6 +122,9 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            set_reward_policy,
+            set_delegator_reward_policy,
+            set_delegation_limits,
         } = self;
 
         ret.append(&mut get_era_validators.to_bytes()?);
@@ -123,6 +141,9 @@ impl ToBytes for AuctionCosts {
         ret.append(&mut read_era_id.to_bytes()?);
         ret.append(&mut activate_bid.to_bytes()?);
         ret.append(&mut redelegate.to_bytes()?);
+        ret.append(&mut set_reward_policy.to_bytes()?);
+        ret.append(&mut set_delegator_reward_policy.to_bytes()?);
+        ret.append(&mut set_delegation_limits.to_bytes()?);
 
         Ok(ret)
     }
@@ -143,6 +164,9 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            set_reward_policy,
+            set_delegator_reward_policy,
+            set_delegation_limits,
         } = self;
 
         get_era_validators.serialized_length()
@@ -159,6 +183,9 @@ impl ToBytes for AuctionCosts {
             + read_era_id.serialized_length()
             + activate_bid.serialized_length()
             + redelegate.serialized_length()
+            + set_reward_policy.serialized_length()
+            + set_delegator_reward_policy.serialized_length()
+            + set_delegation_limits.serialized_length()
     }
 }
 
@@ -178,6 +205,9 @@ impl FromBytes for AuctionCosts {
         let (read_era_id, rem) = FromBytes::from_bytes(rem)?;
         let (activate_bid, rem) = FromBytes::from_bytes(rem)?;
         let (redelegate, rem) = FromBytes::from_bytes(rem)?;
+        let (set_reward_policy, rem) = FromBytes::from_bytes(rem)?;
+        let (set_delegator_reward_policy, rem) = FromBytes::from_bytes(rem)?;
+        let (set_delegation_limits, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             Self {
                 get_era_validators,
@@ -194,6 +224,9 @@ impl FromBytes for AuctionCosts {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                set_reward_policy,
+                set_delegator_reward_policy,
+                set_delegation_limits,
             },
             rem,
         ))
@@ -217,6 +250,9 @@ impl Distribution<AuctionCosts> for Standard {
             read_era_id: rng.gen(),
             activate_bid: rng.gen(),
             redelegate: rng.gen(),
+            set_reward_policy: rng.gen(),
+            set_delegator_reward_policy: rng.gen(),
+            set_delegation_limits: rng.gen(),
         }
     }
 }
@@ -244,6 +280,9 @@ pub mod gens {
             read_era_id in num::u32::ANY,
             activate_bid in num::u32::ANY,
             redelegate in num::u32::ANY,
+            set_reward_policy in num::u32::ANY,
+            set_delegator_reward_policy in num::u32::ANY,
+            set_delegation_limits in num::u32::ANY,
         ) -> AuctionCosts {
             AuctionCosts {
                 get_era_validators,
@@ -260,6 +299,9 @@ pub mod gens {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                set_reward_policy,
+                set_delegator_reward_policy,
+                set_delegation_limits,
             }
         }
     }
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, ops::Deref, sync::Arc};
 
 use casper_hashing::Digest;
-use casper_types::{Key, StoredValue};
+use casper_types::{bytesrepr::ToBytes, Key, StoredValue};
 
 use crate::{
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
@@ -19,8 +19,8 @@ use crate::{
         trie_store::{
             lmdb::{LmdbTrieStore, ScratchTrieStore},
             operations::{
-                delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult,
+                delete, keys_with_prefix, keys_with_prefix_after, missing_children, put_trie, read,
+                read_with_proof, DeleteResult, ReadResult,
             },
         },
     },
@@ -134,6 +134,39 @@ fn compute_empty_root_hash() -> Result<(Digest, Trie<Key, StoredValue>), error::
     Ok((root_hash, root))
 }
 
+impl LmdbGlobalStateView {
+    /// Returns at most `limit` keys whose serialized form starts with `prefix`, in ascending order
+    /// of their serialized bytes.
+    ///
+    /// If `start_after` is given, iteration seeks directly past it and only keys ordered strictly
+    /// after it are returned, allowing the full set of matching keys to be paged through without
+    /// collecting it in memory.
+    pub fn keys_with_prefix_paginated(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&Key>,
+        limit: usize,
+    ) -> Result<Vec<Key>, error::Error> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let start_after_bytes = start_after.map(Key::to_bytes).transpose()?;
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = keys_with_prefix_after::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+            start_after_bytes.as_deref(),
+        );
+        let ret = keys_iter.take(limit).collect::<Result<Vec<_>, _>>()?;
+        txn.commit()?;
+        Ok(ret)
+    }
+}
+
 impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
     type Error = error::Error;
 
@@ -333,7 +366,7 @@ mod tests {
     use tempfile::tempdir;
 
     use casper_hashing::Digest;
-    use casper_types::{account::AccountHash, CLValue};
+    use casper_types::{account::AccountHash, CLValue, KeyTag};
 
     use super::*;
     use crate::storage::{
@@ -505,4 +538,48 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn keys_with_prefix_paginated_returns_pages_in_order() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .map(|TestPair { key, value }| (*key, Transform::Write(value.to_owned())))
+            .collect();
+        let root_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+
+        let prefix = [KeyTag::Account as u8];
+        let expected_keys: Vec<Key> = create_test_pairs_updated()
+            .iter()
+            .map(|test_pair| test_pair.key)
+            .collect();
+
+        let first_page = checkout
+            .keys_with_prefix_paginated(correlation_id, &prefix, None, 2)
+            .unwrap();
+        assert_eq!(first_page, expected_keys[..2]);
+
+        let second_page = checkout
+            .keys_with_prefix_paginated(correlation_id, &prefix, first_page.last(), 2)
+            .unwrap();
+        assert_eq!(second_page, expected_keys[2..]);
+
+        let last_page = checkout
+            .keys_with_prefix_paginated(correlation_id, &prefix, second_page.last(), 2)
+            .unwrap();
+        assert!(last_page.is_empty());
+
+        assert!(checkout
+            .keys_with_prefix_paginated(correlation_id, &prefix, None, 0)
+            .unwrap()
+            .is_empty());
+
+        let other_prefix = [KeyTag::Hash as u8];
+        assert!(checkout
+            .keys_with_prefix_paginated(correlation_id, &other_prefix, None, 2)
+            .unwrap()
+            .is_empty());
+    }
 }
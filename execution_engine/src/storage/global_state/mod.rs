@@ -9,6 +9,9 @@ pub mod lmdb;
 /// Lmdb implementation of global state with cache.
 pub mod scratch;
 
+/// Pruning of tries unreachable from retained state roots.
+pub mod trie_pruner;
+
 use std::{collections::HashMap, hash::BuildHasher};
 
 use tracing::error;
@@ -0,0 +1,367 @@
+//! Incremental pruning of trie nodes which are unreachable from a set of retained state roots.
+//!
+//! A pruning pass first marks every trie node reachable from the retained state roots, then
+//! sweeps the trie store in key order, deleting every node which was not marked.  Both phases are
+//! performed in bounded steps so that a pass can be spread over many calls to
+//! [`TriePruner::step`].
+//!
+//! Marks are kept in a dedicated database in the global state's LMDB environment rather than in
+//! memory, so the size of the retained state does not bound a pass.
+//!
+//! Since trie nodes are content-addressed and shared between state roots, every state root written
+//! while a pass is in progress must be registered via [`TriePruner::retain_root`] before the next
+//! step, otherwise nodes it shares with pruned roots may be deleted.
+
+use std::convert::TryFrom;
+
+use lmdb::{Cursor, Database, DatabaseFlags, Transaction, WriteFlags};
+use tracing::warn;
+
+use casper_hashing::Digest;
+use casper_types::{Key, StoredValue};
+
+use crate::storage::{
+    error,
+    global_state::lmdb::LmdbGlobalState,
+    store::Store,
+    transaction_source::TransactionSource,
+    trie::{self, LazyTrieLeaf, Trie},
+};
+
+/// The name of the database holding the trie nodes marked as reachable in the current pass.
+const MARKS_DB_NAME: &str = "TRIE_PRUNER_MARKS";
+
+/// The outcome of a single pruning step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PruneStepResult {
+    /// The number of trie nodes newly marked as reachable.
+    pub marked: usize,
+    /// The number of trie nodes examined by the sweep.
+    pub swept: usize,
+    /// The number of unreachable trie nodes deleted.
+    pub deleted: usize,
+    /// Whether this step completed a pruning pass.
+    pub pass_completed: bool,
+}
+
+/// Prunes trie nodes which are unreachable from a set of retained state roots.
+#[derive(Debug)]
+pub struct TriePruner {
+    /// The maximum number of trie nodes marked or swept per step.
+    batch_size: usize,
+    /// Whether a pass is in progress.
+    in_progress: bool,
+    /// The database of trie nodes known to be reachable from the retained state roots.
+    marks_db: Database,
+    /// Whether marks left over from a previous pass must be cleared before the next step.
+    clear_marks: bool,
+    /// The number of trie nodes marked as reachable in the current pass.
+    marked_count: usize,
+    /// Trie nodes reachable from the retained state roots which have yet to be marked.
+    to_mark: Vec<Digest>,
+    /// The last trie node examined by the sweep, if it has started.
+    sweep_cursor: Option<Digest>,
+}
+
+impl TriePruner {
+    /// Creates a new, idle pruner which marks or sweeps at most `batch_size` trie nodes per step,
+    /// keeping its marks in the LMDB environment of `global_state`.
+    pub fn new(global_state: &LmdbGlobalState, batch_size: usize) -> Result<Self, error::Error> {
+        let marks_db = global_state
+            .environment
+            .env()
+            .create_db(Some(MARKS_DB_NAME), DatabaseFlags::empty())?;
+        Ok(TriePruner {
+            batch_size: batch_size.max(1),
+            in_progress: false,
+            marks_db,
+            clear_marks: true,
+            marked_count: 0,
+            to_mark: Vec::new(),
+            sweep_cursor: None,
+        })
+    }
+
+    /// Returns `true` if no pass is in progress.
+    pub fn is_idle(&self) -> bool {
+        !self.in_progress
+    }
+
+    /// Returns the number of trie nodes marked as reachable so far in the current pass.
+    pub fn reachable_count(&self) -> usize {
+        self.marked_count
+    }
+
+    /// Starts a new pass which retains every trie node reachable from `retained_roots` or from the
+    /// empty root of `global_state`.
+    ///
+    /// Any pass already in progress is abandoned.  Its marks are cleared by the next step.
+    pub fn start_pass(
+        &mut self,
+        global_state: &LmdbGlobalState,
+        retained_roots: impl IntoIterator<Item = Digest>,
+    ) {
+        self.in_progress = true;
+        self.clear_marks = true;
+        self.marked_count = 0;
+        self.to_mark = retained_roots.into_iter().collect();
+        self.to_mark.push(global_state.empty_state_root_hash());
+        self.sweep_cursor = None;
+    }
+
+    /// Retains every trie node reachable from `root` for the remainder of the current pass.
+    ///
+    /// Does nothing if no pass is in progress.
+    pub fn retain_root(&mut self, root: Digest) {
+        if self.in_progress {
+            self.to_mark.push(root);
+        }
+    }
+
+    /// Performs one bounded step of the current pass.
+    ///
+    /// Sweeping only starts once every retained root has been fully marked.  If `allow_deletion`
+    /// is `false`, marking still progresses but the sweep is postponed; this must be the case
+    /// while tries are being written bottom-up (e.g. while syncing global state), as such tries
+    /// are not reachable from any root until their root node has been written.
+    pub fn step(
+        &mut self,
+        global_state: &LmdbGlobalState,
+        allow_deletion: bool,
+    ) -> Result<PruneStepResult, error::Error> {
+        let mut result = PruneStepResult::default();
+        if !self.in_progress {
+            return Ok(result);
+        }
+
+        if self.clear_marks {
+            let mut txn = global_state.environment.create_read_write_txn()?;
+            txn.clear_db(self.marks_db)?;
+            txn.commit()?;
+            self.clear_marks = false;
+        }
+
+        result.marked = self.mark(global_state)?;
+        if !self.to_mark.is_empty() || !allow_deletion {
+            return Ok(result);
+        }
+
+        let (swept, deleted, reached_end) = self.sweep(global_state)?;
+        result.swept = swept;
+        result.deleted = deleted;
+        if reached_end {
+            self.in_progress = false;
+            self.clear_marks = true;
+            self.sweep_cursor = None;
+            result.pass_completed = true;
+        }
+        Ok(result)
+    }
+
+    /// Marks up to `batch_size` trie nodes as reachable, returning the number marked.
+    fn mark(&mut self, global_state: &LmdbGlobalState) -> Result<usize, error::Error> {
+        let mut marked = 0;
+        if self.to_mark.is_empty() {
+            return Ok(marked);
+        }
+
+        let mut txn = global_state.environment.create_read_write_txn()?;
+        while marked < self.batch_size {
+            let trie_key = match self.to_mark.pop() {
+                Some(trie_key) => trie_key,
+                None => break,
+            };
+            if is_marked(&txn, self.marks_db, &trie_key)? {
+                continue;
+            }
+            // Missing nodes, e.g. those of a partially synced trie, have nothing to retain.
+            let trie_bytes = match Store::<Digest, Trie<Key, StoredValue>>::get_raw(
+                &*global_state.trie_store,
+                &txn,
+                &trie_key,
+            )? {
+                Some(trie_bytes) => trie_bytes,
+                None => continue,
+            };
+            let lazy_trie: LazyTrieLeaf<Key, StoredValue> =
+                trie::lazy_trie_deserialize(trie_bytes)?;
+            for child in trie::lazy_trie_iter_children(&lazy_trie) {
+                if !is_marked(&txn, self.marks_db, &child)? {
+                    self.to_mark.push(child);
+                }
+            }
+            txn.put(self.marks_db, &trie_key, &[], WriteFlags::empty())?;
+            marked += 1;
+        }
+        self.marked_count += marked;
+        txn.commit()?;
+
+        Ok(marked)
+    }
+
+    /// Examines up to `batch_size` trie nodes following the sweep cursor, deleting those not marked
+    /// as reachable.
+    ///
+    /// Returns the number of nodes examined and deleted, and whether the end of the store was
+    /// reached.
+    fn sweep(
+        &mut self,
+        global_state: &LmdbGlobalState,
+    ) -> Result<(usize, usize, bool), error::Error> {
+        let db = global_state.trie_store.get_db();
+        let mut txn = global_state.environment.create_read_write_txn()?;
+
+        let mut swept = 0;
+        let mut reached_end = true;
+        let mut unreachable = Vec::new();
+        {
+            let mut cursor = txn.open_ro_cursor(db)?;
+            let iter = match &self.sweep_cursor {
+                Some(sweep_cursor) => cursor.iter_from(sweep_cursor),
+                None => cursor.iter_start(),
+            };
+            for entry in iter {
+                let (key_bytes, _) = entry?;
+                let trie_key = match Digest::try_from(key_bytes) {
+                    Ok(trie_key) => trie_key,
+                    Err(_) => {
+                        warn!(?key_bytes, "unexpected key in trie store; skipping");
+                        continue;
+                    }
+                };
+                // `iter_from` starts at the cursor itself if it hasn't been deleted.
+                if Some(trie_key) == self.sweep_cursor {
+                    continue;
+                }
+                if swept == self.batch_size {
+                    reached_end = false;
+                    break;
+                }
+                swept += 1;
+                self.sweep_cursor = Some(trie_key);
+                if !is_marked(&txn, self.marks_db, &trie_key)? {
+                    unreachable.push(trie_key);
+                }
+            }
+        }
+
+        for trie_key in &unreachable {
+            txn.del(db, trie_key, None)?;
+        }
+        txn.commit()?;
+
+        Ok((swept, unreachable.len(), reached_end))
+    }
+}
+
+/// Returns `true` if `trie_key` is marked as reachable in `marks_db`.
+fn is_marked(
+    txn: &impl Transaction,
+    marks_db: Database,
+    trie_key: &Digest,
+) -> Result<bool, error::Error> {
+    match txn.get(marks_db, trie_key) {
+        Ok(_) => Ok(true),
+        Err(lmdb::Error::NotFound) => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use lmdb::DatabaseFlags;
+    use tempfile::tempdir;
+
+    use casper_types::{CLValue, Key, StoredValue};
+
+    use super::*;
+    use crate::{
+        shared::newtypes::CorrelationId,
+        storage::{
+            global_state::{StateProvider, StateReader},
+            transaction_source::lmdb::LmdbEnvironment,
+            trie_store::lmdb::LmdbTrieStore,
+            DEFAULT_TEST_MAX_DB_SIZE, DEFAULT_TEST_MAX_READERS,
+        },
+    };
+
+    fn put_value(
+        global_state: &LmdbGlobalState,
+        pre_state_hash: Digest,
+        key: Key,
+        value: u64,
+    ) -> Digest {
+        let mut stored_values = HashMap::new();
+        stored_values.insert(key, StoredValue::CLValue(CLValue::from_t(value).unwrap()));
+        global_state
+            .put_stored_values(CorrelationId::new(), pre_state_hash, stored_values)
+            .unwrap()
+    }
+
+    fn run_pass(pruner: &mut TriePruner, global_state: &LmdbGlobalState) -> usize {
+        let mut deleted = 0;
+        loop {
+            let result = pruner.step(global_state, true).unwrap();
+            deleted += result.deleted;
+            if result.pass_completed {
+                return deleted;
+            }
+        }
+    }
+
+    #[test]
+    fn should_prune_tries_unreachable_from_retained_roots() {
+        let tempdir = tempdir().unwrap();
+        let environment = LmdbEnvironment::new(
+            tempdir.path(),
+            DEFAULT_TEST_MAX_DB_SIZE,
+            DEFAULT_TEST_MAX_READERS,
+            true,
+        )
+        .unwrap();
+        let trie_store = LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap();
+        let global_state =
+            LmdbGlobalState::empty(Arc::new(environment), Arc::new(trie_store)).unwrap();
+
+        let key_1 = Key::Hash([1; 32]);
+        let key_2 = Key::Hash([2; 32]);
+        let root_1 = put_value(&global_state, global_state.empty_root(), key_1, 1);
+        let root_2 = put_value(&global_state, root_1, key_2, 2);
+        let root_3 = put_value(&global_state, root_2, key_1, 3);
+
+        let mut pruner = TriePruner::new(&global_state, 1).unwrap();
+        assert!(pruner.is_idle());
+        pruner.start_pass(&global_state, vec![root_3]);
+        // A root written during the pass must be retained as well.
+        let root_4 = put_value(&global_state, root_3, key_2, 4);
+        pruner.retain_root(root_4);
+
+        // Nothing is deleted while deletion is disallowed.
+        for _ in 0..100 {
+            assert_eq!(pruner.step(&global_state, false).unwrap().deleted, 0);
+        }
+        assert!(run_pass(&mut pruner, &global_state) > 0);
+        assert!(pruner.is_idle());
+
+        for root in [root_1, root_2] {
+            assert!(global_state.checkout(root).unwrap().is_none());
+        }
+        for (root, key) in [(root_3, key_1), (root_4, key_2)] {
+            let reader = global_state
+                .checkout(root)
+                .unwrap()
+                .expect("should retain root");
+            assert!(reader.read(CorrelationId::new(), &key).unwrap().is_some());
+        }
+        assert!(global_state
+            .checkout(global_state.empty_root())
+            .unwrap()
+            .is_some());
+
+        // A subsequent pass retaining the same roots deletes nothing further.
+        pruner.start_pass(&global_state, vec![root_3, root_4]);
+        assert_eq!(run_pass(&mut pruner, &global_state), 0);
+    }
+}
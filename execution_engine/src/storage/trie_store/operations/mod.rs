@@ -1035,12 +1035,28 @@ struct VisitedTrieNode<K, V> {
 
 pub struct KeysIterator<'a, 'b, K, V, T, S: TrieStore<K, V>> {
     initial_descend: VecDeque<u8>,
+    /// The serialized key after which iteration starts, if any.  Subtries holding only keys
+    /// ordered before or at it are skipped without being read.
+    start_after: Option<Vec<u8>>,
     visited: Vec<VisitedTrieNode<K, V>>,
     store: &'a S,
     txn: &'b T,
     state: KeysIteratorState<K, V, S>,
 }
 
+impl<'a, 'b, K, V, T, S: TrieStore<K, V>> KeysIterator<'a, 'b, K, V, T, S> {
+    /// Returns `true` if all keys in the subtrie at `path` are ordered before `start_after`.
+    fn is_before_start(&self, path: &[u8]) -> bool {
+        match &self.start_after {
+            Some(start_after) => {
+                let len = cmp::min(path.len(), start_after.len());
+                path[..len] < start_after[..len]
+            }
+            None => false,
+        }
+    }
+}
+
 impl<'a, 'b, K, V, T, S> Iterator for KeysIterator<'a, 'b, K, V, T, S>
 where
     K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
@@ -1080,9 +1096,15 @@ where
                         }
                     };
                     debug_assert!(key_bytes.starts_with(&path));
-                    // only return the leaf if it matches the initial descend path
+                    // only return the leaf if it matches the initial descend path and is ordered
+                    // after `start_after`
                     path.extend(&self.initial_descend);
-                    if key_bytes.starts_with(&path) {
+                    if key_bytes.starts_with(&path)
+                        && self
+                            .start_after
+                            .as_ref()
+                            .map_or(true, |start_after| key_bytes > *start_after)
+                    {
                         return Some(Ok(key));
                     }
                 }
@@ -1098,6 +1120,16 @@ where
                         .unwrap_or_default();
                     while index < RADIX {
                         if let Some(ref pointer) = pointer_block[index] {
+                            // once done descending, skip subtries ordered before `start_after`
+                            if self.initial_descend.is_empty() {
+                                path.push(index as u8);
+                                let skip = self.is_before_start(&path);
+                                path.pop();
+                                if skip {
+                                    index += 1;
+                                    continue;
+                                }
+                            }
                             maybe_next_trie = match self.store.get(self.txn, pointer.hash()) {
                                 Ok(trie) => trie,
                                 Err(e) => {
@@ -1135,7 +1167,10 @@ where
                     // matches the descend path
                     // if we are not, the check_prefix will be empty, so we will enter the if
                     // anyway
-                    if affix.starts_with(&check_prefix) {
+                    path.extend(affix.iter());
+                    if affix.starts_with(&check_prefix)
+                        && !(self.initial_descend.is_empty() && self.is_before_start(&path))
+                    {
                         maybe_next_trie = match self.store.get(self.txn, pointer.hash()) {
                             Ok(trie) => trie,
                             Err(e) => {
@@ -1144,7 +1179,6 @@ where
                             }
                         };
                         debug_assert!({ matches!(&maybe_next_trie, Some(Trie::Node { .. })) });
-                        path.extend(affix);
                     }
                 }
             }
@@ -1165,11 +1199,36 @@ where
 ///
 /// The root should be the apex of the trie.
 pub fn keys_with_prefix<'a, 'b, K, V, T, S>(
+    correlation_id: CorrelationId,
+    txn: &'b T,
+    store: &'a S,
+    root: &Digest,
+    prefix: &[u8],
+) -> KeysIterator<'a, 'b, K, V, T, S>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+{
+    keys_with_prefix_after(correlation_id, txn, store, root, prefix, None)
+}
+
+/// Returns the iterator over the keys in the subtrie matching `prefix` whose serialized form is
+/// ordered strictly after `start_after`, if given, in ascending order of their serialized form.
+///
+/// Subtries holding only keys ordered before `start_after` are skipped, so iteration seeks
+/// directly to the first matching key.
+///
+/// The root should be the apex of the trie.
+pub fn keys_with_prefix_after<'a, 'b, K, V, T, S>(
     _correlation_id: CorrelationId,
     txn: &'b T,
     store: &'a S,
     root: &Digest,
     prefix: &[u8],
+    start_after: Option<&[u8]>,
 ) -> KeysIterator<'a, 'b, K, V, T, S>
 where
     K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
@@ -1193,6 +1252,7 @@ where
 
     KeysIterator {
         initial_descend: prefix.iter().cloned().collect(),
+        start_after: start_after.map(<[u8]>::to_vec),
         visited,
         store,
         txn,
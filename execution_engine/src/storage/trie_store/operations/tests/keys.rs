@@ -313,4 +313,46 @@ mod keys_with_prefix_iterator {
         test_prefix(&[0, 0, 0, 0, 0, 0]); // 2 leaves
         test_prefix(&[0, 0, 0, 0, 0, 0, 1]); // 1 leaf
     }
+
+    fn test_prefix_after(prefix: &[u8], start_after: &[u8]) {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = create_6_leaf_trie().expect("should create a trie");
+        let context = InMemoryTestContext::new(&tries).expect("should create a new context");
+        let txn = context
+            .environment
+            .create_read_txn()
+            .expect("should create a read txn");
+        let expected = expected_keys(prefix)
+            .into_iter()
+            .filter(|key| key.0[..] > *start_after)
+            .collect::<Vec<_>>();
+        // keys are expected in ascending order without sorting
+        let actual = operations::keys_with_prefix_after::<TestKey, TestValue, _, _>(
+            correlation_id,
+            &txn,
+            &context.store,
+            &root_hash,
+            prefix,
+            Some(start_after),
+        )
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_prefixes_after() {
+        let all_keys = expected_keys(&[]);
+        for key in &all_keys {
+            test_prefix_after(&[], &key.0);
+            test_prefix_after(&[0], &key.0);
+            test_prefix_after(&[0, 0], &key.0);
+            test_prefix_after(&[0, 0, 0, 0], &key.0);
+        }
+        test_prefix_after(&[], &[]); // 6 leaves
+        test_prefix_after(&[], &[0, 0, 0]); // bound between subtries
+        test_prefix_after(&[], &[0, 0, 0, 0, 0, 0, 0, 0]); // bound longer than the keys
+        test_prefix_after(&[], &[1]); // 0 leaves
+        test_prefix_after(&[0, 1], &[0, 0, 2]); // 1 leaf, bound outside the prefix
+    }
 }
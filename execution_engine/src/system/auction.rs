@@ -9,7 +9,7 @@ use num_traits::{CheckedMul, CheckedSub};
 use casper_types::{
     account::AccountHash,
     system::auction::{
-        Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageAllocation,
+        Bid, DelegationRate, EraInfo, EraValidators, Error, RewardPolicy, SeigniorageAllocation,
         SeigniorageRecipients, ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
     },
     ApiError, EraId, PublicKey, U512,
@@ -70,6 +70,8 @@ pub trait Auction:
             return Err(Error::AuctionBidsDisabled.into());
         }
 
+        detail::check_public_key_types(self, [&public_key])?;
+
         let provided_account_hash = AccountHash::from_public_key(&public_key, |x| self.blake2b(x));
 
         if amount.is_zero() {
@@ -221,6 +223,9 @@ pub trait Auction:
             // allow new bids to come in.
             return Err(Error::AuctionBidsDisabled.into());
         }
+
+        detail::check_public_key_types(self, [&delegator_public_key, &validator_public_key])?;
+
         let provided_account_hash =
             AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
 
@@ -252,6 +257,8 @@ pub trait Auction:
             return Err(Error::DelegationAmountTooSmall.into());
         }
 
+        bid.check_delegation_limits(amount)?;
+
         detail::handle_delegation(
             self,
             bid,
@@ -334,6 +341,8 @@ pub trait Auction:
         new_validator: PublicKey,
         minimum_delegation_amount: u64,
     ) -> Result<U512, Error> {
+        detail::check_public_key_types(self, [&delegator_public_key, &new_validator])?;
+
         let provided_account_hash =
             AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
 
@@ -345,6 +354,12 @@ pub trait Auction:
             return Err(Error::DelegationAmountTooSmall);
         }
 
+        // The delegation limits of the new validator are checked again once the redelegation is
+        // processed, as they might have changed in the meantime.
+        if let Some(new_validator_bid) = self.read_bid(&new_validator.to_account_hash())? {
+            new_validator_bid.check_delegation_limits(amount)?;
+        }
+
         let validator_account_hash = AccountHash::from(&validator_public_key);
 
         let mut bid = match self.read_bid(&validator_account_hash)? {
@@ -552,6 +567,9 @@ pub trait Auction:
 
     /// Mint and distribute seigniorage rewards to validators and their delegators,
     /// according to `reward_factors` returned by the consensus component.
+    ///
+    /// Rewards are added to the stakes, unless the reward policy of the bid or delegator is
+    /// [`RewardPolicy::Liquid`], in which case they are paid into the main purse of the account.
     fn distribute(&mut self, reward_factors: BTreeMap<PublicKey, u64>) -> Result<(), Error> {
         if self.get_caller() != PublicKey::System.to_account_hash() {
             return Err(Error::InvalidCaller);
@@ -624,23 +642,23 @@ pub trait Auction:
             )?;
             let total_delegator_payout: U512 = delegator_payouts
                 .iter()
-                .map(|(_delegator_hash, amount, _bonding_purse)| *amount)
+                .map(|(_delegator_hash, amount, _payout_purse)| *amount)
                 .sum();
 
             let validators_part: Ratio<U512> = total_reward - Ratio::from(total_delegator_payout);
             let validator_reward = validators_part.to_integer();
-            let validator_bonding_purse = detail::reinvest_validator_reward(
+            let validator_payout_purse = detail::reinvest_validator_reward(
                 self,
                 seigniorage_allocations,
                 public_key.clone(),
                 validator_reward,
             )?;
 
-            self.mint_into_existing_purse(validator_reward, validator_bonding_purse)
+            self.mint_into_existing_purse(validator_reward, validator_payout_purse)
                 .map_err(Error::from)?;
 
-            for (_delegator_account_hash, delegator_payout, bonding_purse) in delegator_payouts {
-                self.mint_into_existing_purse(delegator_payout, bonding_purse)
+            for (_delegator_account_hash, delegator_payout, payout_purse) in delegator_payouts {
+                self.mint_into_existing_purse(delegator_payout, payout_purse)
                     .map_err(Error::from)?;
             }
         }
@@ -676,4 +694,89 @@ pub trait Auction:
 
         Ok(())
     }
+
+    /// Sets the policy applied to the rewards of a given validator.
+    fn set_reward_policy(
+        &mut self,
+        public_key: PublicKey,
+        reward_policy: RewardPolicy,
+    ) -> Result<(), Error> {
+        let provided_account_hash = AccountHash::from_public_key(&public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let mut bid = match self.read_bid(&provided_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        bid.with_reward_policy(reward_policy);
+
+        self.write_bid(provided_account_hash, bid)?;
+
+        Ok(())
+    }
+
+    /// Sets the policy applied to the rewards of a given delegator of a given validator.
+    fn set_delegator_reward_policy(
+        &mut self,
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        reward_policy: RewardPolicy,
+    ) -> Result<(), Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+        let mut bid = match self.read_bid(&validator_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        match bid.delegators_mut().get_mut(&delegator_public_key) {
+            Some(delegator) => {
+                delegator.with_reward_policy(reward_policy);
+            }
+            None => return Err(Error::DelegatorNotFound),
+        }
+
+        self.write_bid(validator_account_hash, bid)?;
+
+        Ok(())
+    }
+
+    /// Sets the minimum amount which can be delegated to a given validator at once and the maximum
+    /// total amount which can be delegated to it. `None` removes the respective limit.
+    ///
+    /// The limits only apply to new delegations and redelegations, existing delegations are left
+    /// unchanged.
+    fn set_delegation_limits(
+        &mut self,
+        public_key: PublicKey,
+        minimum_delegation_amount: Option<U512>,
+        maximum_delegated_stake: Option<U512>,
+    ) -> Result<(), Error> {
+        let provided_account_hash = AccountHash::from_public_key(&public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let mut bid = match self.read_bid(&provided_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        bid.with_delegation_limits(minimum_delegation_amount, maximum_delegated_stake)?;
+
+        self.write_bid(provided_account_hash, bid)?;
+
+        Ok(())
+    }
 }
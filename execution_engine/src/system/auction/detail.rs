@@ -6,7 +6,7 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        Bids, Delegator, Error, SeigniorageAllocation, SeigniorageRecipientsSnapshot,
+        Bids, Delegator, Error, RewardPolicy, SeigniorageAllocation, SeigniorageRecipientsSnapshot,
         UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY, ERA_END_TIMESTAMP_MILLIS_KEY,
         ERA_ID_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
     },
@@ -15,7 +15,8 @@ use casper_types::{
 use tracing::error;
 
 use super::{
-    Auction, Bid, EraValidators, MintProvider, RuntimeProvider, StorageProvider, ValidatorWeights,
+    AccountProvider, Auction, Bid, EraValidators, MintProvider, RuntimeProvider, StorageProvider,
+    ValidatorWeights,
 };
 
 fn read_from<P, T>(provider: &mut P, name: &str) -> Result<T, Error>
@@ -39,6 +40,24 @@ where
     provider.write(uref, value)
 }
 
+/// Returns an error if any of `public_keys` is of a type which is not enabled on this network.
+pub fn check_public_key_types<'a, P>(
+    provider: &P,
+    public_keys: impl IntoIterator<Item = &'a PublicKey>,
+) -> Result<(), Error>
+where
+    P: RuntimeProvider + ?Sized,
+{
+    if !provider.allow_secp256r1_keys()
+        && public_keys
+            .into_iter()
+            .any(|public_key| matches!(public_key, PublicKey::Secp256r1(_)))
+    {
+        return Err(Error::PublicKeyTypeNotAllowed);
+    }
+    Ok(())
+}
+
 pub fn get_bids<P>(provider: &mut P) -> Result<Bids, Error>
 where
     P: StorageProvider + RuntimeProvider + ?Sized,
@@ -220,7 +239,10 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(
                                     if is_under_max_delegator_cap(
                                         max_delegators_per_validator,
                                         new_validator_bid.delegators().len(),
-                                    ) {
+                                    ) && new_validator_bid
+                                        .check_delegation_limits(*unbonding_purse.amount())
+                                        .is_ok()
+                                    {
                                         handle_delegation(
                                             provider,
                                             new_validator_bid,
@@ -234,7 +256,7 @@ pub(crate) fn process_unbond_requests<P: Auction + ?Sized>(
                                         // Move funds from bid purse to unbonding purse
                                         provider.unbond(unbonding_purse).map_err(|err| {
                                             error!(
-                                            "Error unbonding purse {err:?} (delegator cap reached for new validator)"
+                                            "Error unbonding purse {err:?} (delegator cap or delegation limits reached for new validator)"
                                         );
                                             ApiError::from(Error::TransferToUnbondingPurse)
                                         })?
@@ -308,7 +330,28 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
     Ok(())
 }
 
-/// Reinvests delegator reward by increasing its stake.
+/// Returns the main purse of the account of `public_key` if rewards under `reward_policy` are paid
+/// into it, or `None` if they are added to the stake.
+///
+/// Liquid rewards of a missing account are added to the stake as well, so they are never lost.
+fn liquid_reward_purse<P>(
+    provider: &mut P,
+    reward_policy: RewardPolicy,
+    public_key: &PublicKey,
+) -> Result<Option<URef>, Error>
+where
+    P: AccountProvider,
+{
+    match reward_policy {
+        RewardPolicy::Compound => Ok(None),
+        RewardPolicy::Liquid => provider.read_account_main_purse(&public_key.to_account_hash()),
+    }
+}
+
+/// Pays out delegator rewards according to their reward policies, either by increasing their
+/// stakes or into the main purses of their accounts.
+///
+/// Returns the account hash, reward and purse to mint the reward into for every delegator.
 pub fn reinvest_delegator_rewards<P>(
     provider: &mut P,
     seigniorage_allocations: &mut Vec<SeigniorageAllocation>,
@@ -316,7 +359,7 @@ pub fn reinvest_delegator_rewards<P>(
     rewards: impl Iterator<Item = (PublicKey, Ratio<U512>)>,
 ) -> Result<Vec<(AccountHash, U512, URef)>, Error>
 where
-    P: StorageProvider,
+    P: StorageProvider + AccountProvider,
 {
     let mut delegator_payouts = Vec::new();
 
@@ -337,12 +380,19 @@ where
 
         let delegator_reward_trunc = delegator_reward.to_integer();
 
-        delegator.increase_stake(delegator_reward_trunc)?;
+        let payout_purse =
+            match liquid_reward_purse(provider, delegator.reward_policy(), &delegator_key)? {
+                Some(main_purse) => main_purse,
+                None => {
+                    delegator.increase_stake(delegator_reward_trunc)?;
+                    *delegator.bonding_purse()
+                }
+            };
 
         delegator_payouts.push((
             delegator_key.to_account_hash(),
             delegator_reward_trunc,
-            *delegator.bonding_purse(),
+            payout_purse,
         ));
 
         let allocation = SeigniorageAllocation::delegator(
@@ -359,7 +409,10 @@ where
     Ok(delegator_payouts)
 }
 
-/// Reinvests validator reward by increasing its stake and returns its bonding purse.
+/// Pays out validator reward according to its reward policy, either by increasing its stake or
+/// into the main purse of its account.
+///
+/// Returns the purse to mint the reward into.
 pub fn reinvest_validator_reward<P>(
     provider: &mut P,
     seigniorage_allocations: &mut Vec<SeigniorageAllocation>,
@@ -367,7 +420,7 @@ pub fn reinvest_validator_reward<P>(
     amount: U512,
 ) -> Result<URef, Error>
 where
-    P: StorageProvider,
+    P: StorageProvider + AccountProvider,
 {
     let validator_account_hash = AccountHash::from(&validator_public_key);
 
@@ -378,17 +431,22 @@ where
         }
     };
 
-    bid.increase_stake(amount)?;
+    let payout_purse =
+        match liquid_reward_purse(provider, bid.reward_policy(), &validator_public_key)? {
+            Some(main_purse) => main_purse,
+            None => {
+                bid.increase_stake(amount)?;
+                *bid.bonding_purse()
+            }
+        };
 
     let allocation = SeigniorageAllocation::validator(validator_public_key, amount);
 
     seigniorage_allocations.push(allocation);
 
-    let bonding_purse = *bid.bonding_purse();
-
     provider.write_bid(validator_account_hash, bid)?;
 
-    Ok(bonding_purse)
+    Ok(payout_purse)
 }
 
 pub(crate) fn handle_delegation<P>(
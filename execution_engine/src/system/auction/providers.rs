@@ -32,6 +32,9 @@ pub trait RuntimeProvider {
 
     /// Check if auction bids are allowed.
     fn allow_auction_bids(&self) -> bool;
+
+    /// Check if secp256r1 public keys are allowed in bids and delegations.
+    fn allow_secp256r1_keys(&self) -> bool;
 }
 
 /// Provides functionality of a contract storage.
@@ -105,4 +108,11 @@ pub trait MintProvider {
 pub trait AccountProvider {
     /// Get currently executing account's purse.
     fn get_main_purse(&self) -> Result<URef, Error>;
+
+    /// Reads the main purse of the account under `account_hash`. Returns `None` if there is no
+    /// such account.
+    fn read_account_main_purse(
+        &mut self,
+        account_hash: &AccountHash,
+    ) -> Result<Option<URef>, Error>;
 }
@@ -197,10 +197,54 @@ pub(crate) fn finalize_payment<P: MintProvider + RuntimeProvider + StorageProvid
             provider.write_balance(payment_purse, U512::zero())?;
             provider.reduce_total_supply(fee)?;
         }
+        FeeHandling::Split {
+            accumulate_ratio,
+            burn_ratio,
+            ..
+        } => {
+            // Target purse is the proposer's main purse.  Any dust amount is paid to the proposer.
+            let accumulated = apply_ratio(fee, accumulate_ratio)?;
+            let burned = apply_ratio(fee, burn_ratio)?;
+            let paid = fee
+                .checked_sub(accumulated)
+                .and_then(|remainder| remainder.checked_sub(burned))
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            if !accumulated.is_zero() {
+                let accumulation_purse = get_accumulation_purse(provider)?;
+                if let Err(error) =
+                    provider.transfer_purse_to_purse(payment_purse, accumulation_purse, accumulated)
+                {
+                    error!(%error, %accumulated, "unable to transfer fee to accumulation purse");
+                    return Err(Error::FailedTransferToRewardsPurse);
+                }
+            }
+            if !paid.is_zero() {
+                if let Err(error) = provider.transfer_purse_to_purse(payment_purse, target, paid) {
+                    error!(%error, %paid, %target, "unable to transfer fee");
+                    return Err(Error::FailedTransferToRewardsPurse);
+                }
+            }
+            if !burned.is_zero() {
+                // Only the burned part is left in the payment purse.
+                provider.write_balance(payment_purse, U512::zero())?;
+                provider.reduce_total_supply(burned)?;
+            }
+        }
     }
     Ok(())
 }
 
+/// Returns `amount * ratio`, rounded down.
+fn apply_ratio(amount: U512, ratio: Ratio<u64>) -> Result<U512, Error> {
+    let (numer, denom) = ratio.into();
+    let ratio = Ratio::new_raw(U512::from(numer), U512::from(denom));
+    Ok(Ratio::from(amount)
+        .checked_mul(&ratio)
+        .ok_or(Error::ArithmeticOverflow)?
+        .to_integer())
+}
+
 pub(crate) fn refund_to_account<M: MintProvider>(
     mint_provider: &mut M,
     payment_purse: URef,
@@ -235,9 +279,8 @@ where
     }
 
     // Distribute accumulation purse balance into all administrators
-    match provider.fee_handling() {
-        FeeHandling::PayToProposer | FeeHandling::Burn => return Ok(()),
-        FeeHandling::Accumulate => {}
+    if !provider.fee_handling().is_accumulating() {
+        return Ok(());
     }
 
     let administrative_accounts = provider.administrative_accounts().clone();
@@ -22,7 +22,7 @@ use casper_execution_engine::{
             engine_config::RefundHandling,
             era_validators::GetEraValidatorsRequest,
             execute_request::ExecuteRequest,
-            execution_result::ExecutionResult,
+            execution_result::{ExecutionResult, ExecutionResults},
             run_genesis_request::RunGenesisRequest,
             step::{EvictItem, StepRequest, StepSuccess},
             BalanceResult, EngineConfig, EngineConfigBuilder, EngineState, Error, GenesisSuccess,
@@ -31,6 +31,7 @@ use casper_execution_engine::{
             DEFAULT_MAX_QUERY_DEPTH,
         },
         execution,
+        runtime::GasReport,
     },
     shared::{
         additive_map::AdditiveMap,
@@ -101,6 +102,9 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_results: Vec<Vec<Rc<ExecutionResult>>>,
+    /// Gas reports of the deploys in each exec call made via
+    /// [`exec_with_gas_reports`](Self::exec_with_gas_reports).
+    gas_reports: Vec<Vec<GasReport>>,
     upgrade_results: Vec<Result<UpgradeSuccess, engine_state::Error>>,
     prune_results: Vec<Result<PruneResult, engine_state::Error>>,
     genesis_hash: Option<Digest>,
@@ -141,6 +145,7 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_results: self.exec_results.clone(),
+            gas_reports: self.gas_reports.clone(),
             upgrade_results: self.upgrade_results.clone(),
             prune_results: self.prune_results.clone(),
             genesis_hash: self.genesis_hash,
@@ -168,6 +173,7 @@ impl InMemoryWasmTestBuilder {
 
         WasmTestBuilder {
             exec_results: Vec::new(),
+            gas_reports: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             engine_state: Rc::new(engine_state),
@@ -193,6 +199,7 @@ impl InMemoryWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            gas_reports: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: maybe_post_state_hash,
@@ -331,6 +338,7 @@ impl LmdbWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            gas_reports: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: None,
@@ -436,6 +444,7 @@ impl LmdbWasmTestBuilder {
         let mut builder = WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_results: Vec::new(),
+            gas_reports: Vec::new(),
             upgrade_results: Vec::new(),
             prune_results: Vec::new(),
             genesis_hash: None,
@@ -745,22 +754,42 @@ where
             .engine_state
             .run_execute(CorrelationId::new(), exec_request);
         assert!(maybe_exec_results.is_ok());
-        // Parse deploy results
-        let execution_results = maybe_exec_results.as_ref().unwrap();
+        self.cache_exec_results(maybe_exec_results.unwrap());
+        self
+    }
+
+    /// Runs an [`ExecuteRequest`] like [`exec`](Self::exec), additionally recording a
+    /// [`GasReport`] of each deploy, attributing the gas charged to the contract entry points in
+    /// which it was charged.
+    ///
+    /// The reports can be retrieved via [`get_last_gas_reports`](Self::get_last_gas_reports) or
+    /// [`last_gas_report`](Self::last_gas_report).
+    pub fn exec_with_gas_reports(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
+        let exec_request = {
+            let hash = self.post_state_hash.expect("expected post_state_hash");
+            exec_request.parent_state_hash = hash;
+            exec_request
+        };
+
+        let maybe_exec_results = self
+            .engine_state
+            .run_execute_with_gas_reports(CorrelationId::new(), exec_request);
+        assert!(maybe_exec_results.is_ok());
+        let (execution_results, gas_reports) = maybe_exec_results.unwrap().into_iter().unzip();
+        self.cache_exec_results(execution_results);
+        self.gas_reports.push(gas_reports);
+        self
+    }
+
+    fn cache_exec_results(&mut self, execution_results: ExecutionResults) {
         // Cache transformations
         self.transforms.extend(
             execution_results
                 .iter()
                 .map(|res| res.execution_journal().clone()),
         );
-        self.exec_results.push(
-            maybe_exec_results
-                .unwrap()
-                .into_iter()
-                .map(Rc::new)
-                .collect(),
-        );
-        self
+        self.exec_results
+            .push(execution_results.into_iter().map(Rc::new).collect());
     }
 
     /// Commit effects of previous exec call on the latest post-state hash.
@@ -1009,6 +1038,21 @@ where
         Some(exec_results.iter().map(Rc::clone).collect())
     }
 
+    /// Returns the gas reports of the last call to
+    /// [`exec_with_gas_reports`](Self::exec_with_gas_reports).
+    pub fn get_last_gas_reports(&self) -> Option<&Vec<GasReport>> {
+        self.gas_reports.last()
+    }
+
+    /// Returns the gas report of the first deploy in the last call to
+    /// [`exec_with_gas_reports`](Self::exec_with_gas_reports).
+    pub fn last_gas_report(&self) -> &GasReport {
+        self.get_last_gas_reports()
+            .expect("Expected to be called after exec_with_gas_reports()")
+            .get(0)
+            .expect("should have gas report")
+    }
+
     /// Returns a count of exec results.
     pub fn get_exec_results_count(&self) -> usize {
         self.exec_results.len()
@@ -1402,9 +1446,10 @@ where
             .expect("should have standard payment hash")
     }
 
-    /// Resets the `exec_results`, `upgrade_results` and `transform` fields.
+    /// Resets the `exec_results`, `gas_reports`, `upgrade_results` and `transform` fields.
     pub fn clear_results(&mut self) -> &mut Self {
         self.exec_results = Vec::new();
+        self.gas_reports = Vec::new();
         self.upgrade_results = Vec::new();
         self.transforms = Vec::new();
         self
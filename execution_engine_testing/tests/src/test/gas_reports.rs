@@ -0,0 +1,101 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_PUBLIC_KEY, MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::runtime::GasReport;
+use casper_types::{
+    contracts::DEFAULT_ENTRY_POINT_NAME,
+    runtime_args,
+    system::auction::{self, DelegationRate},
+    Gas, Phase, RuntimeArgs, U512,
+};
+
+const CONTRACT_TRANSFER_TO_NAMED_PURSE: &str = "transfer_to_named_purse.wasm";
+const ARG_PURSE_NAME: &str = "purse_name";
+const NAMED_PURSE_NAME: &str = "purse_1";
+const ARG_AMOUNT: &str = "amount";
+const BOND_AMOUNT: u64 = 42;
+const BID_DELEGATION_RATE: DelegationRate = auction::DELEGATION_RATE_DENOMINATOR;
+
+/// Returns the sum of the gas charged within each entry point of the report.
+fn sum_of_entries(report: &GasReport) -> Gas {
+    report.iter().fold(Gas::default(), |sum, (_, breakdown)| {
+        sum + breakdown.total()
+    })
+}
+
+#[ignore]
+#[test]
+fn gas_report_of_session_code_should_sum_to_deploy_cost() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_NAMED_PURSE,
+        runtime_args! {
+            ARG_PURSE_NAME => NAMED_PURSE_NAME,
+            ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+        },
+    )
+    .build();
+    builder
+        .exec_with_gas_reports(exec_request)
+        .expect_success()
+        .commit();
+
+    let report = builder.last_gas_report();
+    let session = report
+        .get(Phase::Session, None, DEFAULT_ENTRY_POINT_NAME)
+        .expect("should have session entry");
+    assert!(session.opcodes > Gas::default());
+    assert!(session.host_functions > Gas::default());
+
+    // Standard payment is not charged, and so not reported.
+    assert!(report.iter().all(|(key, _)| key.phase == Phase::Session));
+
+    let cost = builder.last_exec_gas_cost();
+    assert_eq!(sum_of_entries(report), cost);
+    assert_eq!(report.total().total(), cost);
+}
+
+#[ignore]
+#[test]
+fn gas_report_of_system_contract_call_should_sum_to_deploy_cost() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let add_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        auction_hash,
+        auction::METHOD_ADD_BID,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => DEFAULT_ACCOUNT_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => U512::from(BOND_AMOUNT),
+            auction::ARG_DELEGATION_RATE => BID_DELEGATION_RATE,
+        },
+    )
+    .build();
+    builder
+        .exec_with_gas_reports(add_bid_request)
+        .expect_success()
+        .commit();
+
+    let report = builder.last_gas_report();
+    let add_bid = report
+        .get(Phase::Session, Some(auction_hash), auction::METHOD_ADD_BID)
+        .expect("should have add_bid entry");
+    let add_bid_cost = builder
+        .get_engine_state()
+        .config()
+        .system_config()
+        .auction_costs()
+        .add_bid;
+    assert_eq!(add_bid.system_contract_calls, Gas::from(add_bid_cost));
+
+    // The auction's internal calls to the mint are not charged, and so not reported.
+    let cost = builder.last_exec_gas_cost();
+    assert_eq!(sum_of_entries(report), cost);
+    assert_eq!(report.total().total(), cost);
+}
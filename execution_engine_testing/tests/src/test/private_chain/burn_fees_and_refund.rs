@@ -165,5 +165,139 @@ fn test_burning_fees(
                 "total supply should be burned exactly by the amount of calculated fees"
             );
         }
+        FeeHandling::Split { .. } => unreachable!("split fees are covered by test_splitting_fees"),
     }
 }
+
+/// Pays 30% of the fees to the proposer, accumulates 20% and burns 50%.
+const SPLIT_FEE_HANDLING: FeeHandling = FeeHandling::Split {
+    proposer_ratio: Ratio::new_raw(3, 10),
+    accumulate_ratio: Ratio::new_raw(1, 5),
+    burn_ratio: Ratio::new_raw(1, 2),
+};
+
+#[ignore]
+#[test]
+fn should_split_the_fees_without_refund() {
+    let zero_refund_handling = RefundHandling::Refund {
+        refund_ratio: Ratio::zero(),
+    };
+    let expected_fee_amount = *DEFAULT_PAYMENT;
+    test_splitting_fees(zero_refund_handling, expected_fee_amount, U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_split_the_fees_with_half_of_refund() {
+    let half_refund_handling = RefundHandling::Refund {
+        refund_ratio: Ratio::new(1, 2),
+    };
+    let expected_fee_amount =
+        (U512::from(DEFAULT_NOP_COST) / U512::from(2u64)) + (*DEFAULT_PAYMENT / U512::from(2u64));
+    test_splitting_fees(half_refund_handling, expected_fee_amount, U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_split_the_fees_and_burn_full_refund() {
+    let full_refund_handling = RefundHandling::Burn {
+        refund_ratio: Ratio::one(),
+    };
+    let expected_fee_amount = U512::from(DEFAULT_NOP_COST);
+    let expected_refund_amount = *DEFAULT_PAYMENT - U512::from(DEFAULT_NOP_COST);
+    test_splitting_fees(
+        full_refund_handling,
+        expected_fee_amount,
+        expected_refund_amount,
+    );
+}
+
+/// Returns the parts of the fee that are paid to the proposer, accumulated and burned, according
+/// to `SPLIT_FEE_HANDLING`.
+fn split_fee(fee: U512) -> (U512, U512, U512) {
+    let accumulated = fee / U512::from(5u64);
+    let burned = fee / U512::from(2u64);
+    (fee - accumulated - burned, accumulated, burned)
+}
+
+fn test_splitting_fees(
+    refund_handling: RefundHandling,
+    expected_fee_amount: U512,
+    expected_burned_refund_amount: U512,
+) {
+    let mut builder = private_chain::custom_setup_genesis_only(
+        PRIVATE_CHAIN_ALLOW_AUCTION_BIDS,
+        PRIVATE_CHAIN_ALLOW_UNRESTRICTED_TRANSFERS,
+        refund_handling,
+        SPLIT_FEE_HANDLING,
+    );
+    let handle_payment = builder.get_handle_payment_contract_hash();
+    let handle_payment_1 = builder
+        .get_contract(handle_payment)
+        .expect("should have handle payment contract");
+    let accumulation_purse = handle_payment_1
+        .named_keys()
+        .get(ACCUMULATION_PURSE_KEY)
+        .expect("should have accumulation purse")
+        .into_uref()
+        .expect("should be uref");
+    assert_eq!(builder.get_purse_balance(accumulation_purse), U512::zero());
+
+    let exec_request_1 = ExecuteRequestBuilder::module_bytes(
+        *DEFAULT_ADMIN_ACCOUNT_ADDR,
+        wasm_utils::do_minimum_bytes(),
+        RuntimeArgs::default(),
+    )
+    .build();
+    let proposer_purse = builder
+        .get_account(exec_request_1.proposer.to_account_hash())
+        .expect("should have proposer account")
+        .main_purse();
+    assert_eq!(builder.get_purse_balance(proposer_purse), U512::zero());
+
+    let total_supply_before = builder.total_supply(None);
+    builder.exec(exec_request_1).expect_success().commit();
+    let total_supply_after = builder.total_supply(None);
+
+    let (paid, accumulated, burned) = split_fee(expected_fee_amount);
+    assert_eq!(
+        builder.get_purse_balance(proposer_purse),
+        paid,
+        "proposer should receive its share of the fee, including dust"
+    );
+    assert_eq!(
+        builder.get_purse_balance(accumulation_purse),
+        accumulated,
+        "accumulation purse should receive its share of the fee"
+    );
+    assert_eq!(
+        total_supply_before - total_supply_after,
+        burned + expected_burned_refund_amount,
+        "total supply should be reduced by the burned share of the fee and the burned refund"
+    );
+
+    let exec_request_2 = {
+        let transfer_args = runtime_args! {
+            mint::ARG_TARGET => *ACCOUNT_1_ADDR,
+            mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+            mint::ARG_ID => <Option<u64>>::None,
+        };
+        ExecuteRequestBuilder::transfer(*DEFAULT_ADMIN_ACCOUNT_ADDR, transfer_args).build()
+    };
+    let proposer_balance_before = builder.get_purse_balance(proposer_purse);
+    let accumulated_before = builder.get_purse_balance(accumulation_purse);
+    let total_supply_before = builder.total_supply(None);
+    builder.exec(exec_request_2).expect_success().commit();
+    let total_supply_after = builder.total_supply(None);
+
+    let (paid, accumulated, burned) = split_fee(U512::from(DEFAULT_WASMLESS_TRANSFER_COST));
+    assert_eq!(
+        builder.get_purse_balance(proposer_purse) - proposer_balance_before,
+        paid
+    );
+    assert_eq!(
+        builder.get_purse_balance(accumulation_purse) - accumulated_before,
+        accumulated
+    );
+    assert_eq!(total_supply_before - total_supply_after, burned);
+}
@@ -20,7 +20,7 @@ use casper_execution_engine::{
             genesis::{ExecConfigBuilder, GenesisAccount, GenesisValidator},
             run_genesis_request::RunGenesisRequest,
             step::EvictItem,
-            EngineConfigBuilder, Error, RewardItem,
+            EngineConfigBuilder, Error, ExecuteRequest, RewardItem,
         },
         execution,
     },
@@ -35,7 +35,8 @@ use casper_types::{
         self,
         auction::{
             self, Bids, DelegationRate, EraValidators, Error as AuctionError, UnbondingPurses,
-            ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_NEW_VALIDATOR,
+            ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
+            ARG_MAXIMUM_DELEGATED_STAKE, ARG_MINIMUM_DELEGATION_AMOUNT, ARG_NEW_VALIDATOR,
             ARG_PUBLIC_KEY, ARG_VALIDATOR, ERA_ID_KEY, INITIAL_ERA_ID,
         },
     },
@@ -4368,3 +4369,266 @@ fn should_increase_existing_delegation_when_limit_exceeded() {
         .expect_success()
         .commit();
 }
+
+fn set_delegation_limits_request(
+    builder: &InMemoryWasmTestBuilder,
+    minimum_delegation_amount: Option<U512>,
+    maximum_delegated_stake: Option<U512>,
+) -> ExecuteRequest {
+    ExecuteRequestBuilder::contract_call_by_hash(
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_SET_DELEGATION_LIMITS,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_MINIMUM_DELEGATION_AMOUNT => minimum_delegation_amount,
+            ARG_MAXIMUM_DELEGATED_STAKE => maximum_delegated_stake,
+        },
+    )
+    .build()
+}
+
+#[ignore]
+#[test]
+fn should_enforce_validator_delegation_limits() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_to_validator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let transfer_to_delegator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(BID_ACCOUNT_1_BALANCE)
+        },
+    )
+    .build();
+
+    let transfer_to_delegator_2 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *BID_ACCOUNT_2_ADDR,
+            ARG_AMOUNT => U512::from(BID_ACCOUNT_2_BALANCE)
+        },
+    )
+    .build();
+
+    let post_genesis_request = vec![
+        transfer_to_validator_1,
+        transfer_to_delegator_1,
+        transfer_to_delegator_2,
+    ];
+
+    for request in post_genesis_request {
+        builder.exec(request).expect_success().commit();
+    }
+
+    let add_bid_request_1 = ExecuteRequestBuilder::standard(
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+            ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+        },
+    )
+    .build();
+
+    builder.exec(add_bid_request_1).expect_success().commit();
+
+    let minimum_delegation_amount = U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT + 1);
+    let maximum_delegated_stake = minimum_delegation_amount * 2;
+
+    // A minimum above the maximum is rejected.
+    let invalid_limits_request = set_delegation_limits_request(
+        &builder,
+        Some(maximum_delegated_stake + 1),
+        Some(maximum_delegated_stake),
+    );
+    builder.exec(invalid_limits_request).expect_failure();
+
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::InvalidDelegationLimits as u8));
+
+    let set_limits_request = set_delegation_limits_request(
+        &builder,
+        Some(minimum_delegation_amount),
+        Some(maximum_delegated_stake),
+    );
+    builder.exec(set_limits_request).expect_success().commit();
+
+    let bids: Bids = builder.get_bids();
+    let bid = &bids[&NON_FOUNDER_VALIDATOR_1_PK];
+    assert_eq!(
+        bid.minimum_delegation_amount(),
+        Some(minimum_delegation_amount)
+    );
+    assert_eq!(bid.maximum_delegated_stake(), Some(maximum_delegated_stake));
+
+    // The global minimum is met, but the validator's own minimum is not.
+    let delegation_request_1 = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT),
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegation_request_1).expect_failure();
+
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::DelegationAmountBelowValidatorMinimum as u8));
+
+    let delegation_request_2 = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => minimum_delegation_amount,
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegation_request_2).expect_success().commit();
+
+    // Together with the existing delegation this would exceed the validator's cap.
+    let delegation_request_3 = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_2_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => minimum_delegation_amount + 1,
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_2_PK.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegation_request_3).expect_failure();
+
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ExceededValidatorDelegationCap as u8));
+
+    let delegation_request_4 = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_2_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => minimum_delegation_amount,
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_2_PK.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegation_request_4).expect_success().commit();
+
+    // Removing the limits allows further delegations.
+    let remove_limits_request = set_delegation_limits_request(&builder, None, None);
+    builder
+        .exec(remove_limits_request)
+        .expect_success()
+        .commit();
+
+    let delegation_request_5 = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_2_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT),
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => BID_ACCOUNT_2_PK.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegation_request_5).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_reject_secp256r1_keys_in_bids_and_delegations_unless_allowed() {
+    let secp256r1_public_key = {
+        let secret_key = SecretKey::secp256r1_from_bytes([7; SecretKey::SECP256R1_LENGTH]).unwrap();
+        PublicKey::from(&secret_key)
+    };
+    let secp256r1_addr = AccountHash::from(&secp256r1_public_key);
+
+    let fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => secp256r1_addr,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+    let add_bid_request = || {
+        ExecuteRequestBuilder::standard(
+            secp256r1_addr,
+            CONTRACT_ADD_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => secp256r1_public_key.clone(),
+                ARG_AMOUNT => U512::from(ADD_BID_AMOUNT_1),
+                ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+            },
+        )
+        .build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder.exec(fund_request.clone()).expect_success().commit();
+
+    builder.exec(add_bid_request()).expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::PublicKeyTypeNotAllowed as u8));
+
+    let delegate_request = ExecuteRequestBuilder::standard(
+        secp256r1_addr,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATE_AMOUNT_1),
+            ARG_VALIDATOR => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ARG_DELEGATOR => secp256r1_public_key.clone(),
+        },
+    )
+    .build();
+    builder.exec(delegate_request).expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::PublicKeyTypeNotAllowed as u8));
+
+    let engine_config = EngineConfigBuilder::new()
+        .with_allow_secp256r1_keys(true)
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder.exec(fund_request).expect_success().commit();
+    builder.exec(add_bid_request()).expect_success().commit();
+
+    let bids: Bids = builder.get_bids();
+    assert!(bids.contains_key(&secp256r1_public_key));
+}
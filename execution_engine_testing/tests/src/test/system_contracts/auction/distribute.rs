@@ -11,20 +11,23 @@ use casper_engine_test_support::{
     PRODUCTION_ROUND_SEIGNIORAGE_RATE, PRODUCTION_RUN_GENESIS_REQUEST, SYSTEM_ADDR,
     TIMESTAMP_MILLIS_INCREMENT,
 };
-use casper_execution_engine::core::engine_state::{
-    engine_config::DEFAULT_MINIMUM_DELEGATION_AMOUNT, step::RewardItem,
+use casper_execution_engine::core::{
+    engine_state::{
+        self, engine_config::DEFAULT_MINIMUM_DELEGATION_AMOUNT, step::RewardItem, ExecuteRequest,
+    },
+    execution,
 };
 use casper_types::{
     self,
     account::AccountHash,
     runtime_args,
     system::auction::{
-        self, Bid, Bids, DelegationRate, Delegator, SeigniorageAllocation,
+        self, Bid, Bids, DelegationRate, Delegator, EraInfo, RewardPolicy, SeigniorageAllocation,
         SeigniorageRecipientsSnapshot, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
         ARG_PUBLIC_KEY, ARG_REWARD_FACTORS, ARG_VALIDATOR, BLOCK_REWARD,
         DELEGATION_RATE_DENOMINATOR, METHOD_DISTRIBUTE, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
     },
-    EraId, Key, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
+    ApiError, EraId, Key, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
 };
 
 const ARG_ENTRY_POINT: &str = "entry_point";
@@ -132,6 +135,60 @@ fn undelegate(
     builder.exec(undelegate_request).expect_success().commit();
 }
 
+fn set_reward_policy_request(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    validator: PublicKey,
+    reward_policy: RewardPolicy,
+) -> ExecuteRequest {
+    let auction = builder.get_auction_contract_hash();
+    ExecuteRequestBuilder::contract_call_by_hash(
+        sender,
+        auction,
+        auction::METHOD_SET_REWARD_POLICY,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => validator,
+            auction::ARG_REWARD_POLICY => reward_policy,
+        },
+    )
+    .build()
+}
+
+fn set_delegator_reward_policy_request(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    delegator: PublicKey,
+    validator: PublicKey,
+    reward_policy: RewardPolicy,
+) -> ExecuteRequest {
+    let auction = builder.get_auction_contract_hash();
+    ExecuteRequestBuilder::contract_call_by_hash(
+        sender,
+        auction,
+        auction::METHOD_SET_DELEGATOR_REWARD_POLICY,
+        runtime_args! {
+            auction::ARG_DELEGATOR => delegator,
+            auction::ARG_VALIDATOR => validator,
+            auction::ARG_REWARD_POLICY => reward_policy,
+        },
+    )
+    .build()
+}
+
+fn get_main_purse_balance(builder: &mut InMemoryWasmTestBuilder, account: AccountHash) -> U512 {
+    let main_purse = builder.get_expected_account(account).main_purse();
+    builder.get_purse_balance(main_purse)
+}
+
+fn get_era_info(builder: &mut InMemoryWasmTestBuilder) -> EraInfo {
+    builder
+        .query(None, Key::EraSummary, &[])
+        .expect("should have value")
+        .as_era_info()
+        .cloned()
+        .expect("should be era info")
+}
+
 fn get_delegator_staked_amount(
     builder: &mut InMemoryWasmTestBuilder,
     validator: PublicKey,
@@ -4648,3 +4705,218 @@ fn delegator_full_unbond_during_first_reward_era() {
     let delegator = get_delegator_bid(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone());
     assert!(delegator.is_none());
 }
+
+#[ignore]
+#[test]
+fn should_pay_out_liquid_rewards_into_main_purses() {
+    const VALIDATOR_1_STAKE: u64 = 1_000_000;
+    const DELEGATOR_1_STAKE: u64 = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+    const DELEGATOR_2_STAKE: u64 = DEFAULT_MINIMUM_DELEGATION_AMOUNT;
+    const VALIDATOR_1_DELEGATION_RATE: DelegationRate = 10;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    // advance past the initial auction delay due to special condition of post-genesis behavior.
+    builder.advance_eras_by_default_auction_delay(vec![], vec![]);
+
+    let mut post_genesis_requests = vec![];
+    for target in [*VALIDATOR_1_ADDR, *DELEGATOR_1_ADDR, *DELEGATOR_2_ADDR] {
+        let fund_request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => target,
+                ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+            },
+        )
+        .build();
+        post_genesis_requests.push(fund_request);
+    }
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+            ARG_DELEGATION_RATE => VALIDATOR_1_DELEGATION_RATE,
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+        },
+    )
+    .build();
+    post_genesis_requests.push(validator_1_add_bid_request);
+
+    for (sender, delegator, stake) in [
+        (*DELEGATOR_1_ADDR, DELEGATOR_1.clone(), DELEGATOR_1_STAKE),
+        (*DELEGATOR_2_ADDR, DELEGATOR_2.clone(), DELEGATOR_2_STAKE),
+    ] {
+        let delegate_request = ExecuteRequestBuilder::standard(
+            sender,
+            CONTRACT_DELEGATE,
+            runtime_args! {
+                ARG_AMOUNT => U512::from(stake),
+                ARG_VALIDATOR => VALIDATOR_1.clone(),
+                ARG_DELEGATOR => delegator,
+            },
+        )
+        .build();
+        post_genesis_requests.push(delegate_request);
+    }
+
+    post_genesis_requests.push(set_reward_policy_request(
+        &mut builder,
+        *VALIDATOR_1_ADDR,
+        VALIDATOR_1.clone(),
+        RewardPolicy::Liquid,
+    ));
+    post_genesis_requests.push(set_delegator_reward_policy_request(
+        &mut builder,
+        *DELEGATOR_1_ADDR,
+        DELEGATOR_1.clone(),
+        VALIDATOR_1.clone(),
+        RewardPolicy::Liquid,
+    ));
+
+    for request in post_genesis_requests {
+        builder.exec(request).expect_success().commit();
+    }
+
+    let validator_1_bid = get_validator_bid(&mut builder, VALIDATOR_1.clone()).unwrap();
+    assert_eq!(validator_1_bid.reward_policy(), RewardPolicy::Liquid);
+    let delegator_1_bid =
+        get_delegator_bid(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone()).unwrap();
+    assert_eq!(delegator_1_bid.reward_policy(), RewardPolicy::Liquid);
+    let delegator_2_bid =
+        get_delegator_bid(&mut builder, VALIDATOR_1.clone(), DELEGATOR_2.clone()).unwrap();
+    assert_eq!(delegator_2_bid.reward_policy(), RewardPolicy::Compound);
+
+    // step until the validator and its delegators are scheduled to receive rewards.
+    builder.advance_eras_by(4, vec![], vec![]);
+
+    let validator_1_balance_before = get_main_purse_balance(&mut builder, *VALIDATOR_1_ADDR);
+    let delegator_1_balance_before = get_main_purse_balance(&mut builder, *DELEGATOR_1_ADDR);
+    let delegator_2_balance_before = get_main_purse_balance(&mut builder, *DELEGATOR_2_ADDR);
+
+    builder.advance_era(
+        vec![RewardItem::new(VALIDATOR_1.clone(), BLOCK_REWARD)],
+        vec![],
+    );
+
+    let era_info = get_era_info(&mut builder);
+    let validator_1_payout = match era_info.select(VALIDATOR_1.clone()).next() {
+        Some(SeigniorageAllocation::Validator { amount, .. }) => *amount,
+        allocation => panic!("unexpected allocation {:?}", allocation),
+    };
+    let delegator_1_payout = match era_info.select(DELEGATOR_1.clone()).next() {
+        Some(SeigniorageAllocation::Delegator { amount, .. }) => *amount,
+        allocation => panic!("unexpected allocation {:?}", allocation),
+    };
+    let delegator_2_payout = match era_info.select(DELEGATOR_2.clone()).next() {
+        Some(SeigniorageAllocation::Delegator { amount, .. }) => *amount,
+        allocation => panic!("unexpected allocation {:?}", allocation),
+    };
+    assert!(!validator_1_payout.is_zero());
+    assert!(!delegator_1_payout.is_zero());
+    assert!(!delegator_2_payout.is_zero());
+
+    // Liquid rewards are paid into the main purses and leave the stakes unchanged.
+    let validator_1_bid = get_validator_bid(&mut builder, VALIDATOR_1.clone()).unwrap();
+    assert_eq!(
+        *validator_1_bid.staked_amount(),
+        U512::from(VALIDATOR_1_STAKE)
+    );
+    assert_eq!(
+        get_main_purse_balance(&mut builder, *VALIDATOR_1_ADDR),
+        validator_1_balance_before + validator_1_payout
+    );
+    assert_eq!(
+        get_delegator_staked_amount(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone()),
+        U512::from(DELEGATOR_1_STAKE)
+    );
+    assert_eq!(
+        get_main_purse_balance(&mut builder, *DELEGATOR_1_ADDR),
+        delegator_1_balance_before + delegator_1_payout
+    );
+
+    // Compounded rewards are added to the stake.
+    assert_eq!(
+        get_delegator_staked_amount(&mut builder, VALIDATOR_1.clone(), DELEGATOR_2.clone()),
+        U512::from(DELEGATOR_2_STAKE) + delegator_2_payout
+    );
+    assert_eq!(
+        get_main_purse_balance(&mut builder, *DELEGATOR_2_ADDR),
+        delegator_2_balance_before
+    );
+}
+
+#[ignore]
+#[test]
+fn should_only_set_reward_policy_of_own_bids() {
+    const VALIDATOR_1_STAKE: u64 = 1_000_000;
+    const VALIDATOR_1_DELEGATION_RATE: DelegationRate = 0;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let mut post_genesis_requests = vec![];
+    for target in [*VALIDATOR_1_ADDR, *DELEGATOR_1_ADDR] {
+        let fund_request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => target,
+                ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+            },
+        )
+        .build();
+        post_genesis_requests.push(fund_request);
+    }
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+            ARG_DELEGATION_RATE => VALIDATOR_1_DELEGATION_RATE,
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+        },
+    )
+    .build();
+    post_genesis_requests.push(validator_1_add_bid_request);
+
+    for request in post_genesis_requests {
+        builder.exec(request).expect_success().commit();
+    }
+
+    let request = set_reward_policy_request(
+        &mut builder,
+        *DELEGATOR_1_ADDR,
+        VALIDATOR_1.clone(),
+        RewardPolicy::Liquid,
+    );
+    builder.exec(request).expect_failure();
+    let error = builder.get_error().expect("should have error");
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == auction::Error::InvalidContext as u8
+    ));
+
+    let request = set_delegator_reward_policy_request(
+        &mut builder,
+        *DELEGATOR_1_ADDR,
+        DELEGATOR_1.clone(),
+        VALIDATOR_1.clone(),
+        RewardPolicy::Liquid,
+    );
+    builder.exec(request).expect_failure();
+    let error = builder.get_error().expect("should have error");
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == auction::Error::DelegatorNotFound as u8
+    ));
+
+    let validator_1_bid = get_validator_bid(&mut builder, VALIDATOR_1.clone()).unwrap();
+    assert_eq!(validator_1_bid.reward_policy(), RewardPolicy::Compound);
+}
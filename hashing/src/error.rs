@@ -105,3 +105,30 @@ pub enum MerkleConstructionError {
         count: String,
     },
 }
+
+/// Error verifying a Merkle proof of a global state entry.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GlobalStateProofVerificationError {
+    /// The proof could not be parsed.
+    #[error("Failed to parse global state proof: {0}")]
+    Bytesrepr(bytesrepr::Error),
+    /// The proof holds no entries.
+    #[error("Global state proof is empty")]
+    EmptyProof,
+    /// The proof does not hold one entry per element of the path, plus one for the first key.
+    #[error("Global state proof length does not match the path length")]
+    PathLengthMismatch,
+    /// An entry of the proof is not for the expected key.
+    #[error("Global state proof has an unexpected key")]
+    UnexpectedKey,
+    /// An entry of the proof does not hash to the expected state root hash.
+    #[error("Global state proof does not match the state root hash")]
+    InvalidProofHash,
+    /// The preceding entry's value has no named key for the next element of the path.
+    #[error("Global state proof path is not reachable via named keys")]
+    PathCold,
+    /// The value at the end of the proof is not the expected value.
+    #[error("Global state proof has an unexpected value")]
+    UnexpectedValue,
+}
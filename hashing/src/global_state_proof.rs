@@ -0,0 +1,303 @@
+//! Verification of Merkle proofs of global state entries.
+//!
+//! The node's `state_get_item` and `query_global_state` JSON-RPC methods return a `merkle_proof`
+//! field holding a hex-encoded, bytesrepr-encoded list of trie Merkle proofs, one per step of the
+//! queried path.  The functions here allow clients to check such proofs against a trusted state
+//! root hash without depending on the execution engine.
+
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    Key, StoredValue,
+};
+
+use crate::{error::GlobalStateProofVerificationError, Digest};
+
+/// The number of children of a trie node.
+const RADIX: usize = 256;
+
+const TRIE_LEAF_TAG: u8 = 0;
+const TRIE_NODE_TAG: u8 = 1;
+const TRIE_EXTENSION_TAG: u8 = 2;
+
+const LEAF_POINTER_TAG: u8 = 0;
+const NODE_POINTER_TAG: u8 = 1;
+
+const PROOF_STEP_NODE_TAG: u8 = 0;
+const PROOF_STEP_EXTENSION_TAG: u8 = 1;
+
+const OPTION_NONE_TAG: u8 = 0;
+const OPTION_SOME_TAG: u8 = 1;
+
+/// A pointer to a child of a trie node.
+#[derive(Clone, Copy, Debug)]
+struct Pointer {
+    tag: u8,
+    hash: Digest,
+}
+
+impl Pointer {
+    fn write_bytes(&self, writer: &mut Vec<u8>) {
+        writer.push(self.tag);
+        writer.extend_from_slice(self.hash.as_ref());
+    }
+}
+
+impl FromBytes for Pointer {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        if tag != LEAF_POINTER_TAG && tag != NODE_POINTER_TAG {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (hash, remainder) = Digest::from_bytes(remainder)?;
+        Ok((Pointer { tag, hash }, remainder))
+    }
+}
+
+/// A step from a trie entry towards the root of the trie.
+#[derive(Debug)]
+enum ProofStep {
+    Node {
+        hole_index: u8,
+        indexed_pointers_with_hole: Vec<(u8, Pointer)>,
+    },
+    Extension {
+        affix: Bytes,
+    },
+}
+
+impl FromBytes for ProofStep {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            PROOF_STEP_NODE_TAG => {
+                let (hole_index, remainder) = u8::from_bytes(remainder)?;
+                let (indexed_pointers_with_hole, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    ProofStep::Node {
+                        hole_index,
+                        indexed_pointers_with_hole,
+                    },
+                    remainder,
+                ))
+            }
+            PROOF_STEP_EXTENSION_TAG => {
+                let (affix, remainder) = Bytes::from_bytes(remainder)?;
+                Ok((ProofStep::Extension { affix }, remainder))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// A proof that a key-value pair is held in the global state trie.
+#[derive(Debug)]
+struct GlobalStateProof {
+    key: Key,
+    value: StoredValue,
+    steps: Vec<ProofStep>,
+}
+
+impl GlobalStateProof {
+    /// Recomputes the state root hash implied by this proof.
+    ///
+    /// This mirrors the hashing of leaves, nodes and extensions in the global state trie.
+    fn compute_state_root_hash(&self) -> Result<Digest, bytesrepr::Error> {
+        let mut hash = {
+            let mut leaf_bytes = vec![TRIE_LEAF_TAG];
+            self.key.write_bytes(&mut leaf_bytes)?;
+            self.value.write_bytes(&mut leaf_bytes)?;
+            Digest::hash_into_chunks_if_necessary(&leaf_bytes)
+        };
+
+        for (step_index, step) in self.steps.iter().enumerate() {
+            let pointer = Pointer {
+                tag: if step_index == 0 {
+                    LEAF_POINTER_TAG
+                } else {
+                    NODE_POINTER_TAG
+                },
+                hash,
+            };
+            let mut step_bytes = vec![];
+            match step {
+                ProofStep::Node {
+                    hole_index,
+                    indexed_pointers_with_hole,
+                } => {
+                    let mut pointer_block = [None; RADIX];
+                    for (index, sibling) in indexed_pointers_with_hole {
+                        pointer_block[*index as usize] = Some(*sibling);
+                    }
+                    pointer_block[*hole_index as usize] = Some(pointer);
+
+                    step_bytes.push(TRIE_NODE_TAG);
+                    for maybe_pointer in pointer_block.iter() {
+                        match maybe_pointer {
+                            Some(pointer) => {
+                                step_bytes.push(OPTION_SOME_TAG);
+                                pointer.write_bytes(&mut step_bytes);
+                            }
+                            None => step_bytes.push(OPTION_NONE_TAG),
+                        }
+                    }
+                }
+                ProofStep::Extension { affix } => {
+                    step_bytes.push(TRIE_EXTENSION_TAG);
+                    affix.write_bytes(&mut step_bytes)?;
+                    pointer.write_bytes(&mut step_bytes);
+                }
+            }
+            hash = Digest::hash(&step_bytes);
+        }
+
+        Ok(hash)
+    }
+}
+
+impl FromBytes for GlobalStateProof {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, remainder) = Key::from_bytes(bytes)?;
+        let (value, remainder) = StoredValue::from_bytes(remainder)?;
+        let (steps, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((GlobalStateProof { key, value, steps }, remainder))
+    }
+}
+
+/// Verifies that `value` is held at the end of `path` starting from `key` in the global state with
+/// the given root hash.
+///
+/// `proof_bytes` is the hex-decoded `merkle_proof` returned by the `state_get_item` and
+/// `query_global_state` JSON-RPC methods, and `path` is the path of named keys passed to the
+/// query.  The proof holds one entry for `key` followed by one entry per element of `path`.
+pub fn verify_global_state_proof(
+    state_root_hash: &Digest,
+    key: &Key,
+    path: &[String],
+    value: &StoredValue,
+    proof_bytes: &[u8],
+) -> Result<(), GlobalStateProofVerificationError> {
+    let proofs: Vec<GlobalStateProof> = bytesrepr::deserialize_from_slice(proof_bytes)
+        .map_err(GlobalStateProofVerificationError::Bytesrepr)?;
+
+    let mut proofs_iter = proofs.iter();
+    let first_proof = proofs_iter
+        .next()
+        .ok_or(GlobalStateProofVerificationError::EmptyProof)?;
+    if proofs_iter.len() != path.len() {
+        return Err(GlobalStateProofVerificationError::PathLengthMismatch);
+    }
+    if first_proof.key != key.normalize() {
+        return Err(GlobalStateProofVerificationError::UnexpectedKey);
+    }
+    verify_state_root_hash(state_root_hash, first_proof)?;
+
+    let mut proof_value = &first_proof.value;
+    for (proof, name) in proofs_iter.zip(path) {
+        let named_keys = match proof_value {
+            StoredValue::Account(account) => account.named_keys(),
+            StoredValue::Contract(contract) => contract.named_keys(),
+            _ => return Err(GlobalStateProofVerificationError::PathCold),
+        };
+        let named_key = named_keys
+            .get(name)
+            .ok_or(GlobalStateProofVerificationError::PathCold)?;
+        if named_key.normalize() != proof.key {
+            return Err(GlobalStateProofVerificationError::UnexpectedKey);
+        }
+        verify_state_root_hash(state_root_hash, proof)?;
+        proof_value = &proof.value;
+    }
+
+    if proof_value != value {
+        return Err(GlobalStateProofVerificationError::UnexpectedValue);
+    }
+
+    Ok(())
+}
+
+fn verify_state_root_hash(
+    state_root_hash: &Digest,
+    proof: &GlobalStateProof,
+) -> Result<(), GlobalStateProofVerificationError> {
+    let computed_hash = proof
+        .compute_state_root_hash()
+        .map_err(GlobalStateProofVerificationError::Bytesrepr)?;
+    if computed_hash != *state_root_hash {
+        return Err(GlobalStateProofVerificationError::InvalidProofHash);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{CLValue, Key, StoredValue};
+
+    use super::*;
+
+    #[test]
+    fn should_reject_malformed_proof() {
+        let key = Key::Hash([1; 32]);
+        let value = StoredValue::CLValue(CLValue::from_t(1_u64).unwrap());
+
+        let empty_proofs = 0_u32.to_bytes().unwrap();
+        assert!(matches!(
+            verify_global_state_proof(&Digest::hash([]), &key, &[], &value, &empty_proofs),
+            Err(GlobalStateProofVerificationError::EmptyProof)
+        ));
+
+        assert!(matches!(
+            verify_global_state_proof(&Digest::hash([]), &key, &[], &value, &[1, 2, 3]),
+            Err(GlobalStateProofVerificationError::Bytesrepr(_))
+        ));
+    }
+
+    #[test]
+    fn should_verify_proof_of_single_leaf() {
+        let key = Key::Hash([1; 32]);
+        let value = StoredValue::CLValue(CLValue::from_t(1_u64).unwrap());
+
+        // A trie holding a single leaf has the leaf's hash as its root.
+        let mut leaf_bytes = vec![TRIE_LEAF_TAG];
+        leaf_bytes.append(&mut key.to_bytes().unwrap());
+        leaf_bytes.append(&mut value.to_bytes().unwrap());
+        let state_root_hash = Digest::hash_into_chunks_if_necessary(&leaf_bytes);
+
+        let mut proof_bytes = 1_u32.to_bytes().unwrap();
+        proof_bytes.append(&mut key.to_bytes().unwrap());
+        proof_bytes.append(&mut value.to_bytes().unwrap());
+        proof_bytes.append(&mut 0_u32.to_bytes().unwrap());
+
+        verify_global_state_proof(&state_root_hash, &key, &[], &value, &proof_bytes)
+            .expect("should verify");
+
+        let other_value = StoredValue::CLValue(CLValue::from_t(2_u64).unwrap());
+        assert!(matches!(
+            verify_global_state_proof(&state_root_hash, &key, &[], &other_value, &proof_bytes),
+            Err(GlobalStateProofVerificationError::UnexpectedValue)
+        ));
+        assert!(matches!(
+            verify_global_state_proof(
+                &state_root_hash,
+                &Key::Hash([2; 32]),
+                &[],
+                &value,
+                &proof_bytes
+            ),
+            Err(GlobalStateProofVerificationError::UnexpectedKey)
+        ));
+        assert!(matches!(
+            verify_global_state_proof(&Digest::hash([]), &key, &[], &value, &proof_bytes),
+            Err(GlobalStateProofVerificationError::InvalidProofHash)
+        ));
+        assert!(matches!(
+            verify_global_state_proof(
+                &state_root_hash,
+                &key,
+                &["name".to_string()],
+                &value,
+                &proof_bytes
+            ),
+            Err(GlobalStateProofVerificationError::PathLengthMismatch)
+        ));
+    }
+}
@@ -9,6 +9,7 @@
 
 mod chunk_with_proof;
 mod error;
+mod global_state_proof;
 mod indexed_merkle_proof;
 
 use std::{
@@ -37,8 +38,10 @@ use casper_types::{
 };
 pub use chunk_with_proof::ChunkWithProof;
 pub use error::{
-    ChunkWithProofVerificationError, Error, MerkleConstructionError, MerkleVerificationError,
+    ChunkWithProofVerificationError, Error, GlobalStateProofVerificationError,
+    MerkleConstructionError, MerkleVerificationError,
 };
+pub use global_state_proof::verify_global_state_proof;
 pub use indexed_merkle_proof::IndexedMerkleProof;
 
 /// The output of the hash function.
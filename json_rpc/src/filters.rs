@@ -7,8 +7,10 @@
 mod tests;
 
 use bytes::Bytes;
+use futures::future;
 use http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
-use serde_json::{json, Map, Value};
+use serde::Serialize;
+use serde_json::{json, Value};
 use tracing::{debug, trace, warn};
 use warp::{
     body,
@@ -66,62 +68,181 @@ pub fn base_filter<P: AsRef<str>>(path: P, max_body_bytes: u32) -> BoxedFilter<(
         .boxed()
 }
 
-/// Handles parsing a JSON-RPC request from the given HTTP body, executing it using the appropriate
-/// handler, and providing a JSON-RPC response (which could be a success or failure).
+/// The JSON-RPC response(s) to be sent to the client.
+///
+/// A single request yields a single response, whereas a batch of requests yields an array of
+/// responses.
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+enum ResponseBody {
+    Single(Response),
+    Batch(Vec<Response>),
+}
+
+/// Handles parsing a JSON-RPC request or batch of requests from the given HTTP body, executing
+/// them using the appropriate handlers, and providing the JSON-RPC response(s) (which could be
+/// successes or failures).
 ///
 /// Returns an `Err(Rejection)` only if the request is a Notification as per the JSON-RPC
-/// specification, i.e. the request doesn't contain an "id" field.  In this case, no JSON-RPC
-/// response is sent to the client.
+/// specification, i.e. the request doesn't contain an "id" field, or if the request is a batch
+/// consisting entirely of Notifications.  In this case, no JSON-RPC response is sent to the client.
 ///
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
+///
+/// If the body is a batch of more than `max_batch_size` requests, none of the requests are
+/// executed, and a single failure response is returned.
 async fn handle_body(
     body: Bytes,
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
-) -> Result<Response, Rejection> {
-    let response = match serde_json::from_slice::<Map<String, Value>>(&body) {
-        Ok(unvalidated_request) => match Request::new(unvalidated_request, allow_unknown_fields) {
-            Ok(request) => handlers.handle_request(request).await,
-            Err(ErrorOrRejection::Error { id, error }) => {
-                debug!(?error, "got an invalid request");
-                Response::new_failure(id, error)
-            }
-            Err(ErrorOrRejection::Rejection(rejection)) => {
-                debug!(?rejection, "rejecting an invalid request");
-                return Err(rejection);
-            }
-        },
+    max_batch_size: u32,
+) -> Result<ResponseBody, Rejection> {
+    let response_body = match serde_json::from_slice::<Value>(&body) {
+        Ok(Value::Array(unvalidated_requests)) => {
+            return handle_batch(
+                unvalidated_requests,
+                handlers,
+                allow_unknown_fields,
+                max_batch_size,
+            )
+            .await;
+        }
+        Ok(unvalidated_request) => ResponseBody::Single(
+            handle_request(unvalidated_request, &handlers, allow_unknown_fields).await?,
+        ),
         Err(error) => {
             debug!(%error, "got bad json");
             let error = Error::new(ReservedErrorCode::ParseError, error.to_string());
-            Response::new_failure(Value::Null, error)
+            ResponseBody::Single(Response::new_failure(Value::Null, error))
+        }
+    };
+    Ok(response_body)
+}
+
+/// Handles a batch of JSON-RPC requests, executing them concurrently.
+///
+/// The responses are returned in the same order as the requests.  As per the JSON-RPC
+/// specification, no response is included for Notifications, and an empty batch is treated as a
+/// single invalid request.
+async fn handle_batch(
+    unvalidated_requests: Vec<Value>,
+    handlers: RequestHandlers,
+    allow_unknown_fields: bool,
+    max_batch_size: u32,
+) -> Result<ResponseBody, Rejection> {
+    if unvalidated_requests.is_empty() {
+        debug!("got an empty batch");
+        let error = Error::new(ReservedErrorCode::InvalidRequest, "Empty batch");
+        return Ok(ResponseBody::Single(Response::new_failure(
+            Value::Null,
+            error,
+        )));
+    }
+
+    if unvalidated_requests.len() > max_batch_size as usize {
+        debug!(
+            batch_size = unvalidated_requests.len(),
+            max_batch_size, "got a batch which is too large"
+        );
+        let error = Error::new(
+            ReservedErrorCode::InvalidRequest,
+            format!(
+                "Batch of {} requests exceeds the maximum allowed of {}",
+                unvalidated_requests.len(),
+                max_batch_size
+            ),
+        );
+        return Ok(ResponseBody::Single(Response::new_failure(
+            Value::Null,
+            error,
+        )));
+    }
+
+    let results = future::join_all(unvalidated_requests.into_iter().map(|unvalidated_request| {
+        handle_request(unvalidated_request, &handlers, allow_unknown_fields)
+    }))
+    .await;
+
+    let mut maybe_rejection = None;
+    let responses: Vec<Response> = results
+        .into_iter()
+        .filter_map(|result| match result {
+            Ok(response) => Some(response),
+            Err(rejection) => {
+                maybe_rejection = Some(rejection);
+                None
+            }
+        })
+        .collect();
+
+    match maybe_rejection {
+        Some(rejection) if responses.is_empty() => Err(rejection),
+        _ => Ok(ResponseBody::Batch(responses)),
+    }
+}
+
+/// Handles a single JSON-RPC request, either standalone or as an element of a batch.
+///
+/// Returns an `Err(Rejection)` only if the request is a Notification.
+async fn handle_request(
+    unvalidated_request: Value,
+    handlers: &RequestHandlers,
+    allow_unknown_fields: bool,
+) -> Result<Response, Rejection> {
+    let unvalidated_request = match unvalidated_request {
+        Value::Object(unvalidated_request) => unvalidated_request,
+        _ => {
+            debug!("got a request which is not a json object");
+            let error = Error::new(
+                ReservedErrorCode::InvalidRequest,
+                "Expected the request to be an Object",
+            );
+            return Ok(Response::new_failure(Value::Null, error));
+        }
+    };
+
+    let response = match Request::new(unvalidated_request, allow_unknown_fields) {
+        Ok(request) => handlers.handle_request(request).await,
+        Err(ErrorOrRejection::Error { id, error }) => {
+            debug!(?error, "got an invalid request");
+            Response::new_failure(id, error)
+        }
+        Err(ErrorOrRejection::Rejection(rejection)) => {
+            debug!(?rejection, "rejecting an invalid request");
+            return Err(rejection);
         }
     };
     Ok(response)
 }
 
-/// Returns a boxed warp filter which handles parsing a JSON-RPC request from the given HTTP body,
-/// executing it using the appropriate handler, and providing a reply.
+/// Returns a boxed warp filter which handles parsing a JSON-RPC request or batch of requests from
+/// the given HTTP body, executing them using the appropriate handlers, and providing a reply.
 ///
-/// The reply will normally be built from a JSON-RPC response (which could be a success or failure).
+/// The reply will normally be built from a JSON-RPC response, or an array of JSON-RPC responses in
+/// the case of a batch request (each of which could be a success or failure).
 ///
 /// However, the reply could be built from a [`Rejection`] if the request is a Notification as per
-/// the JSON-RPC specification, i.e. the request doesn't contain an "id" field.  In this case, no
-/// JSON-RPC response is sent to the client, only an HTTP response.
+/// the JSON-RPC specification, i.e. the request doesn't contain an "id" field, or if the request is
+/// a batch comprising only Notifications.  In this case, no JSON-RPC response is sent to the
+/// client, only an HTTP response.
 ///
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
+///
+/// `max_batch_size` sets an upper limit for the number of requests in a single batch.  Larger
+/// batches are not executed, and a single failure response is returned.
 pub fn main_filter(
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
+    max_batch_size: u32,
 ) -> BoxedFilter<(WithStatus<reply::Json>,)> {
     body::bytes()
         .and_then(move |body| {
             let handlers = handlers.clone();
-            async move { handle_body(body, handlers, allow_unknown_fields).await }
+            async move { handle_body(body, handlers, allow_unknown_fields, max_batch_size).await }
         })
-        .map(|response| reply::with_status(reply::json(&response), StatusCode::OK))
+        .map(|response_body| reply::with_status(reply::json(&response_body), StatusCode::OK))
         .boxed()
 }
 
@@ -16,6 +16,7 @@ use crate::{
 
 const GET_GOOD_THING: &str = "get good thing";
 const GET_BAD_THING: &str = "get bad thing";
+const MAX_BATCH_SIZE: u32 = 3;
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug)]
 struct GoodThing {
@@ -49,13 +50,18 @@ async fn from_http_response(response: http::Response<hyper::Body>) -> Response {
     serde_json::from_slice(&body_bytes).unwrap()
 }
 
+async fn from_http_response_batch(response: http::Response<hyper::Body>) -> Vec<Response> {
+    let body_bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body_bytes).unwrap()
+}
+
 fn main_filter_with_recovery() -> BoxedFilter<(impl Reply,)> {
     let mut handlers = RequestHandlersBuilder::new();
     handlers.register_handler(GET_GOOD_THING, Arc::new(get_good_thing));
     handlers.register_handler(GET_BAD_THING, Arc::new(get_bad_thing));
     let handlers = handlers.build();
 
-    main_filter(handlers, false)
+    main_filter(handlers, false, MAX_BATCH_SIZE)
         .recover(handle_rejection)
         .boxed()
 }
@@ -318,3 +324,184 @@ async fn should_handle_invalid_json() {
         )
     );
 }
+
+#[tokio::test]
+async fn should_handle_valid_json_which_is_not_an_object() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_body` and return Response::Failure (invalid
+    // request) to the client as it is neither a single request nor a batch.
+    let http_response = warp::test::request()
+        .body(r#"1"#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_response = from_http_response(http_response).await;
+    assert_eq!(rpc_response.id(), &Value::Null);
+    assert_eq!(
+        rpc_response.error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Expected the request to be an Object"
+        )
+    );
+}
+
+#[tokio::test]
+async fn should_handle_valid_batch() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return an array of responses in the
+    // same order as the requests, omitting the one for the Notification.
+    let http_response = warp::test::request()
+        .body(
+            r#"[
+                {"jsonrpc":"2.0","id":1,"method":"get good thing","params":["one"]},
+                {"jsonrpc":"2.0","method":"get good thing","params":["two"]},
+                {"jsonrpc":"2.0","id":"b","method":"not registered"}
+            ]"#,
+        )
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_responses = from_http_response_batch(http_response).await;
+    assert_eq!(rpc_responses.len(), 2);
+
+    assert_eq!(rpc_responses[0].id(), 1);
+    assert_eq!(
+        rpc_responses[0].result(),
+        Some(GoodThing {
+            good_thing: "one".to_string()
+        })
+    );
+
+    assert_eq!(rpc_responses[1].id(), "b");
+    assert_eq!(
+        rpc_responses[1].error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::MethodNotFound,
+            "'not registered' is not a supported json-rpc method on this server"
+        )
+    );
+}
+
+#[tokio::test]
+async fn should_handle_batch_with_invalid_elements() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return a Response::Failure (invalid
+    // request) for each element of the batch.
+    let http_response = warp::test::request()
+        .body(r#"[1,"a"]"#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_responses = from_http_response_batch(http_response).await;
+    assert_eq!(rpc_responses.len(), 2);
+    for rpc_response in rpc_responses {
+        assert_eq!(rpc_response.id(), &Value::Null);
+        assert_eq!(
+            rpc_response.error().unwrap(),
+            &Error::new(
+                ReservedErrorCode::InvalidRequest,
+                "Expected the request to be an Object"
+            )
+        );
+    }
+}
+
+#[tokio::test]
+async fn should_handle_empty_batch() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return a single Response::Failure
+    // (invalid request) to the client.
+    let http_response = warp::test::request()
+        .body(r#"[]"#)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_response = from_http_response(http_response).await;
+    assert_eq!(rpc_response.id(), &Value::Null);
+    assert_eq!(
+        rpc_response.error().unwrap(),
+        &Error::new(ReservedErrorCode::InvalidRequest, "Empty batch")
+    );
+}
+
+#[tokio::test]
+async fn should_handle_batch_which_is_too_large() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return a single Response::Failure
+    // (invalid request) to the client as the batch exceeds `MAX_BATCH_SIZE`.
+    let request = r#"{"jsonrpc":"2.0","id":1,"method":"get good thing","params":["one"]}"#;
+    let body = format!("[{}]", vec![request; MAX_BATCH_SIZE as usize + 1].join(","));
+    let http_response = warp::test::request()
+        .body(body)
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::OK);
+    let rpc_response = from_http_response(http_response).await;
+    assert_eq!(rpc_response.id(), &Value::Null);
+    assert_eq!(
+        rpc_response.error().unwrap(),
+        &Error::new(
+            ReservedErrorCode::InvalidRequest,
+            "Batch of 4 requests exceeds the maximum allowed of 3"
+        )
+    );
+}
+
+#[tokio::test]
+async fn should_handle_batch_of_notifications() {
+    let _ = env_logger::try_init();
+
+    let filter = main_filter_with_recovery();
+
+    // This should get handled by `filters::handle_batch` and return no JSON-RPC response, only an
+    // HTTP response (bad request) to the client as every request in the batch is a Notification.
+    let http_response = warp::test::request()
+        .body(
+            r#"[
+                {"jsonrpc":"2.0","method":"get good thing","params":["one"]},
+                {"jsonrpc":"2.0","method":"get good thing","params":["two"]}
+            ]"#,
+        )
+        .filter(&filter)
+        .await
+        .unwrap()
+        .into_response();
+
+    assert_eq!(http_response.status(), StatusCode::BAD_REQUEST);
+    let response_body = ResponseBodyOnRejection::from_response(http_response).await;
+    assert_eq!(
+        response_body.message,
+        "The request is missing the 'id' field"
+    );
+}
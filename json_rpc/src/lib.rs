@@ -4,6 +4,9 @@
 //!
 //! # Usage
 //!
+//! Both single JSON-RPC requests and batches of requests (as defined in
+//! [the JSON-RPC specification](https://www.jsonrpc.org/specification#batch)) are supported.
+//!
 //! Normally usage will involve two steps:
 //!   * construct a set of request handlers using a [`RequestHandlersBuilder`]
 //!   * call [`casper_json_rpc::route`](route) to construct a boxed warp filter ready to be passed
@@ -40,7 +43,14 @@
 //!     let path = "rpc";
 //!     let max_body_bytes = 1024;
 //!     let allow_unknown_fields = false;
-//!     let route = casper_json_rpc::route(path, max_body_bytes, handlers, allow_unknown_fields);
+//!     let max_batch_size = 10;
+//!     let route = casper_json_rpc::route(
+//!         path,
+//!         max_body_bytes,
+//!         handlers,
+//!         allow_unknown_fields,
+//!         max_batch_size,
+//!     );
 //!
 //!     // Convert it into a `Service` and run it.
 //!     let make_svc = hyper::service::make_service_fn(move |_| {
@@ -118,15 +128,22 @@ pub enum CorsOrigin {
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
 ///
+/// `max_batch_size` sets an upper limit for the number of requests in a single JSON-RPC batch.
+///
 /// For further details, see the docs for the [`filters`] functions.
 pub fn route<P: AsRef<str>>(
     path: P,
     max_body_bytes: u32,
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
+    max_batch_size: u32,
 ) -> BoxedFilter<(impl Reply,)> {
     filters::base_filter(path, max_body_bytes)
-        .and(filters::main_filter(handlers, allow_unknown_fields))
+        .and(filters::main_filter(
+            handlers,
+            allow_unknown_fields,
+            max_batch_size,
+        ))
         .recover(filters::handle_rejection)
         .boxed()
 }
@@ -146,6 +163,8 @@ pub fn route<P: AsRef<str>>(
 /// If `allow_unknown_fields` is `false`, requests with unknown fields will cause the server to
 /// respond with an error.
 ///
+/// `max_batch_size` sets an upper limit for the number of requests in a single JSON-RPC batch.
+///
 /// Note that this is a convenience function combining the lower-level functions in [`filters`]
 /// along with [a warp CORS filter](https://docs.rs/warp/latest/warp/filters/cors/index.html) which
 ///   * allows any origin or specified origin
@@ -158,10 +177,15 @@ pub fn route_with_cors<P: AsRef<str>>(
     max_body_bytes: u32,
     handlers: RequestHandlers,
     allow_unknown_fields: bool,
+    max_batch_size: u32,
     cors_header: &CorsOrigin,
 ) -> BoxedFilter<(impl Reply,)> {
     filters::base_filter(path, max_body_bytes)
-        .and(filters::main_filter(handlers, allow_unknown_fields))
+        .and(filters::main_filter(
+            handlers,
+            allow_unknown_fields,
+            max_batch_size,
+        ))
         .recover(filters::handle_rejection)
         .with(match cors_header {
             CorsOrigin::Any => warp::cors()
@@ -3,6 +3,9 @@
 //! Most configuration is done via config files (see [`config`](../config/index.html) for details).
 
 pub mod arglang;
+mod block_archive;
+mod state_snapshot;
+pub mod storage;
 
 use std::{
     alloc::System,
@@ -20,6 +23,9 @@ use structopt::StructOpt;
 use toml::{value::Table, Value};
 use tracing::info;
 
+use casper_hashing::Digest;
+
+use self::storage::StorageCommand;
 use crate::{
     components::network::Identity as NetworkIdentity,
     logging,
@@ -60,6 +66,131 @@ pub enum Cli {
         /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=node.chainspec_config_path=chainspec.toml'
         config_ext: Vec<ConfigExt>,
     },
+    /// Inspect or repair the storage of a stopped node, without starting the reactor.
+    Storage {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=storage.path=/tmp/storage'
+        config_ext: Vec<ConfigExt>,
+
+        /// The storage operation to perform.
+        #[structopt(subcommand)]
+        command: StorageCommand,
+    },
+    /// Export a range of blocks from the storage of a stopped node into an archive file.
+    ///
+    /// The archive contains the headers, bodies, finality signatures, deploys, finalized
+    /// approvals and execution results of all blocks in the range.
+    ExportBlocks {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=storage.path=/tmp/storage'
+        config_ext: Vec<ConfigExt>,
+
+        /// Height of the first block to export.
+        #[structopt(long)]
+        from: u64,
+
+        /// Height of the last block to export.
+        #[structopt(long)]
+        to: u64,
+
+        /// Path of the archive file to create.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Validate the blocks of an archive file and import them into the storage of a stopped node.
+    ///
+    /// Finality signatures are verified against the validator weights of the respective era, so
+    /// the switch block of the era preceding the first archived block must either be stored
+    /// already or be part of the archive. Imported blocks are not marked complete, as their global
+    /// state is not part of the archive.
+    ImportBlocks {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=storage.path=/tmp/storage'
+        config_ext: Vec<ConfigExt>,
+
+        /// Path of the archive file to import.
+        #[structopt(long)]
+        input: PathBuf,
+    },
+    /// Export the global state under a state root of a stopped node into a snapshot file.
+    ///
+    /// The snapshot contains every trie node reachable from the state root.
+    ExportStateSnapshot {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=storage.path=/tmp/storage'
+        config_ext: Vec<ConfigExt>,
+
+        /// Hex-encoded state root hash of the global state to export.
+        #[structopt(long, parse(try_from_str = Digest::from_hex))]
+        state_root_hash: Digest,
+
+        /// Path of the snapshot file to create.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Verify a snapshot file and import its global state into a stopped node.
+    ///
+    /// Every trie node is only written once all of its children are present, and the import fails
+    /// unless the snapshot's state root is complete afterwards.
+    ImportStateSnapshot {
+        /// Path to configuration file.
+        config: PathBuf,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=storage.path=/tmp/storage'
+        config_ext: Vec<ConfigExt>,
+
+        /// Path of the snapshot file to import.
+        #[structopt(long)]
+        input: PathBuf,
+    },
     /// Migrate modified values from the old config as required after an upgrade.
     MigrateConfig {
         /// Path to configuration file of previous version of node.
@@ -193,6 +324,64 @@ impl Cli {
                 let exit_code = main_runner.run(&mut rng).await;
                 Ok(exit_code as i32)
             }
+            Cli::Storage {
+                config,
+                config_ext,
+                command,
+            } => {
+                let node_config = Self::init(&config, config_ext)?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(node_config.dir())?;
+                command.run(&node_config, &chainspec.network_config.name)
+            }
+            Cli::ExportBlocks {
+                config,
+                config_ext,
+                from,
+                to,
+                output,
+            } => {
+                let node_config = Self::init(&config, config_ext)?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(node_config.dir())?;
+                block_archive::export_blocks(&node_config, &chainspec, from, to, &output)
+            }
+            Cli::ImportBlocks {
+                config,
+                config_ext,
+                input,
+            } => {
+                let node_config = Self::init(&config, config_ext)?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(node_config.dir())?;
+                block_archive::import_blocks(&node_config, &chainspec, &input)
+            }
+            Cli::ExportStateSnapshot {
+                config,
+                config_ext,
+                state_root_hash,
+                output,
+            } => {
+                let node_config = Self::init(&config, config_ext)?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(node_config.dir())?;
+                state_snapshot::export_state_snapshot(
+                    &node_config,
+                    &chainspec,
+                    state_root_hash,
+                    &output,
+                )
+            }
+            Cli::ImportStateSnapshot {
+                config,
+                config_ext,
+                input,
+            } => {
+                let node_config = Self::init(&config, config_ext)?;
+                let (chainspec, _) =
+                    <(Chainspec, ChainspecRawBytes)>::from_path(node_config.dir())?;
+                state_snapshot::import_state_snapshot(&node_config, &chainspec, &input)
+            }
             Cli::MigrateConfig {
                 old_config,
                 new_config,
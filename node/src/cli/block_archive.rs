@@ -0,0 +1,99 @@
+//! Export and import of block ranges as portable archive files.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+use anyhow::Context;
+use tracing::info;
+
+use crate::{
+    components::storage::{
+        archive::{self, ImportSummary},
+        Storage,
+    },
+    reactor::main_reactor,
+    types::{sync_leap_validation_metadata::SyncLeapValidationMetaData, Chainspec, ExitCode},
+    utils::WithDir,
+};
+
+/// Writes the blocks at heights `from` to `to` into a new archive file at `output`.
+pub(super) fn export_blocks(
+    config: &WithDir<main_reactor::Config>,
+    chainspec: &Chainspec,
+    from: u64,
+    to: u64,
+    output: &Path,
+) -> anyhow::Result<i32> {
+    let storage = open_storage(config, chainspec)?;
+    let file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(output)
+        .with_context(|| format!("failed to create archive file {}", output.display()))?;
+
+    let result = archive::export_blocks(
+        &storage,
+        &chainspec.network_config.name,
+        from..=to,
+        BufWriter::new(file),
+    );
+    if let Err(error) = result {
+        // Do not leave an incomplete archive behind.
+        let _ = fs::remove_file(output);
+        return Err(error).context("failed to export blocks");
+    }
+
+    info!(from, to, path = %output.display(), "exported blocks");
+    Ok(ExitCode::Success as i32)
+}
+
+/// Validates the blocks of the archive file at `input` and writes them to storage.
+pub(super) fn import_blocks(
+    config: &WithDir<main_reactor::Config>,
+    chainspec: &Chainspec,
+    input: &Path,
+) -> anyhow::Result<i32> {
+    let mut storage = open_storage(config, chainspec)?;
+    let file = File::open(input)
+        .with_context(|| format!("failed to open archive file {}", input.display()))?;
+
+    let ImportSummary {
+        imported,
+        already_stored,
+    } = archive::import_blocks(
+        &mut storage,
+        &chainspec.network_config.name,
+        &SyncLeapValidationMetaData::from_chainspec(chainspec),
+        BufReader::new(file),
+    )
+    .context("failed to import blocks")?;
+
+    println!(
+        "imported {} blocks, {} were already stored",
+        imported, already_stored
+    );
+    Ok(ExitCode::Success as i32)
+}
+
+/// Opens the storage component without applying any hard reset configured in the chainspec.
+fn open_storage(
+    config: &WithDir<main_reactor::Config>,
+    chainspec: &Chainspec,
+) -> anyhow::Result<Storage> {
+    let storage_config = WithDir::new(config.dir(), config.value().storage.clone());
+    Storage::new(
+        &storage_config,
+        None,
+        chainspec.protocol_version(),
+        chainspec.protocol_config.activation_point.era_id(),
+        &chainspec.network_config.name,
+        chainspec.deploy_config.max_ttl.into(),
+        chainspec.core_config.recent_era_count(),
+        None,
+        false,
+    )
+    .context("failed to open storage")
+}
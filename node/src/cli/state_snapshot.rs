@@ -0,0 +1,107 @@
+//! Export and import of global state snapshots.
+
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use tracing::info;
+
+use casper_execution_engine::{
+    core::engine_state::{EngineConfig, EngineState},
+    storage::global_state::lmdb::LmdbGlobalState,
+};
+use casper_hashing::Digest;
+
+use crate::{
+    components::contract_runtime::{
+        self,
+        state_snapshot::{self, SnapshotSummary},
+    },
+    reactor::main_reactor,
+    types::{Chainspec, ExitCode},
+    utils::WithDir,
+};
+
+/// Writes the global state under `state_root_hash` into a new snapshot file at `output`.
+pub(super) fn export_state_snapshot(
+    config: &WithDir<main_reactor::Config>,
+    chainspec: &Chainspec,
+    state_root_hash: Digest,
+    output: &Path,
+) -> anyhow::Result<i32> {
+    let storage_dir = storage_dir(config, chainspec);
+    if !storage_dir.exists() {
+        bail!("no global state found in {}", storage_dir.display());
+    }
+    let engine_state = open_engine_state(config, &storage_dir)?;
+    let file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(output)
+        .with_context(|| format!("failed to create snapshot file {}", output.display()))?;
+
+    let result =
+        state_snapshot::export_state_snapshot(&engine_state, state_root_hash, BufWriter::new(file));
+    let summary = match result {
+        Ok((_, summary)) => summary,
+        Err(error) => {
+            // Do not leave an incomplete snapshot behind.
+            let _ = fs::remove_file(output);
+            return Err(error).context("failed to export global state snapshot");
+        }
+    };
+
+    info!(path = %output.display(), "exported global state snapshot");
+    println!(
+        "exported {} trie nodes of state root {}",
+        summary.trie_count, summary.state_root_hash
+    );
+    Ok(ExitCode::Success as i32)
+}
+
+/// Verifies the snapshot file at `input` and writes its trie nodes to global state.
+pub(super) fn import_state_snapshot(
+    config: &WithDir<main_reactor::Config>,
+    chainspec: &Chainspec,
+    input: &Path,
+) -> anyhow::Result<i32> {
+    let storage_dir = storage_dir(config, chainspec);
+    fs::create_dir_all(&storage_dir)
+        .with_context(|| format!("failed to create {}", storage_dir.display()))?;
+    let engine_state = open_engine_state(config, &storage_dir)?;
+    let file = File::open(input)
+        .with_context(|| format!("failed to open snapshot file {}", input.display()))?;
+
+    let SnapshotSummary {
+        state_root_hash,
+        trie_count,
+    } = state_snapshot::import_state_snapshot(&engine_state, BufReader::new(file))
+        .context("failed to import global state snapshot")?;
+
+    println!(
+        "imported {} trie nodes of state root {}",
+        trie_count, state_root_hash
+    );
+    Ok(ExitCode::Success as i32)
+}
+
+/// Returns the directory holding the global state of the chainspec's network.
+fn storage_dir(config: &WithDir<main_reactor::Config>, chainspec: &Chainspec) -> PathBuf {
+    config
+        .with_dir(config.value().storage.path.clone())
+        .join(&chainspec.network_config.name)
+}
+
+/// Opens the global state in `storage_dir`, creating an empty one if it does not exist yet.
+fn open_engine_state(
+    config: &WithDir<main_reactor::Config>,
+    storage_dir: &Path,
+) -> anyhow::Result<EngineState<LmdbGlobalState>> {
+    let global_state =
+        contract_runtime::open_global_state(storage_dir, &config.value().contract_runtime)
+            .context("failed to open global state")?;
+    Ok(EngineState::new(global_state, EngineConfig::default()))
+}
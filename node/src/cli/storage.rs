@@ -0,0 +1,83 @@
+//! Offline inspection and repair of the node's storage.
+
+use anyhow::Context;
+use structopt::StructOpt;
+use tracing::info;
+
+use crate::{
+    components::storage::inspect::StorageInspector, reactor::main_reactor, types::ExitCode,
+    utils::WithDir,
+};
+
+/// Exit code returned when a check finds inconsistent data.
+const ISSUES_FOUND_EXIT_CODE: i32 = 1;
+
+/// Storage subcommands.
+///
+/// The node must not be running while any of these are executed.
+#[derive(Debug, StructOpt)]
+pub enum StorageCommand {
+    /// Print the stored block heights and the heights marked complete.
+    Ranges,
+    /// Check stored blocks for missing or inconsistent headers, bodies, signatures, deploys and
+    /// execution results.
+    ///
+    /// Exits with a non-zero code if any issue is found.
+    Check {
+        /// Lowest block height to check.
+        #[structopt(long)]
+        from: Option<u64>,
+        /// Highest block height to check.
+        #[structopt(long)]
+        to: Option<u64>,
+    },
+    /// Check all stored blocks and repair the storage indices based on the findings.
+    ///
+    /// Heights with missing or inconsistent data are no longer marked complete, so the node
+    /// acquires them again, and the account deploy index is rebuilt on the next start. Stored
+    /// blocks and deploys are left untouched.
+    RebuildIndices,
+}
+
+impl StorageCommand {
+    /// Runs the command against the storage of the given network.
+    pub(super) fn run(
+        self,
+        config: &WithDir<main_reactor::Config>,
+        network_name: &str,
+    ) -> anyhow::Result<i32> {
+        let storage_config = WithDir::new(config.dir(), config.value().storage.clone());
+        let writable = matches!(self, StorageCommand::RebuildIndices);
+        let inspector = StorageInspector::open(&storage_config, network_name, writable)
+            .context("failed to open storage")?;
+        info!(path = %inspector.path().display(), writable, "opened storage");
+
+        match self {
+            StorageCommand::Ranges => {
+                println!("{}", inspector.block_ranges()?);
+                Ok(ExitCode::Success as i32)
+            }
+            StorageCommand::Check { from, to } => {
+                let report = inspector.check(from.unwrap_or(u64::MIN)..=to.unwrap_or(u64::MAX))?;
+                println!("{}", report);
+                if report.is_consistent() {
+                    Ok(ExitCode::Success as i32)
+                } else {
+                    Ok(ISSUES_FOUND_EXIT_CODE)
+                }
+            }
+            StorageCommand::RebuildIndices => {
+                let report = inspector.check(u64::MIN..=u64::MAX)?;
+                println!("{}", report);
+                let unmarked = inspector.rebuild_indices(&report)?;
+                println!(
+                    "no longer marked complete: {} heights {:?}",
+                    unmarked.len(),
+                    unmarked
+                );
+                println!("account deploy index will be rebuilt on the next start");
+                Ok(ExitCode::Success as i32)
+            }
+        }
+    }
+}
@@ -54,8 +54,9 @@ use crate::{
     types::{
         sync_leap_validation_metadata::SyncLeapValidationMetaData, ApprovalsHashes, Block,
         BlockExecutionResultsOrChunk, BlockHash, BlockHeader, BlockSignatures, Chainspec, Deploy,
-        FinalitySignature, FinalitySignatureId, FinalizedBlock, LegacyDeploy, MetaBlock,
-        MetaBlockState, NodeId, SyncLeap, SyncLeapIdentifier, TrieOrChunk, ValidatorMatrix,
+        DeployValidationMetadata, FinalitySignature, FinalitySignatureId, FinalizedBlock,
+        LegacyDeploy, MetaBlock, MetaBlockState, NodeId, SyncLeap, SyncLeapIdentifier, TrieOrChunk,
+        ValidatorMatrix,
     },
     NodeRng,
 };
@@ -252,6 +253,26 @@ impl BlockSynchronizer {
         }
     }
 
+    /// Returns `true` if global state is currently being synced.
+    ///
+    /// Synced tries are written bottom-up, so they are not reachable from any state root until
+    /// syncing completes.
+    pub(crate) fn is_syncing_global_state(&self) -> bool {
+        self.global_sync.root_being_synced().is_some()
+    }
+
+    /// Returns the state root hashes of the blocks currently being synchronized, which must not be
+    /// pruned from global state.
+    pub(crate) fn state_roots_in_use(&self) -> Vec<Digest> {
+        [&self.historical, &self.forward]
+            .into_iter()
+            .flatten()
+            .filter_map(|builder| builder.maybe_block())
+            .map(|block| *block.state_root_hash())
+            .chain(self.global_sync.root_being_synced())
+            .collect()
+    }
+
     pub(crate) fn purge(&mut self) {
         self.purge_historical();
         self.purge_forward();
@@ -668,7 +689,13 @@ impl BlockSynchronizer {
                     builder.latch_by(peers.len());
                     results.extend(peers.into_iter().flat_map(|node_id| {
                         effect_builder
-                            .fetch::<Deploy>(deploy_id, node_id, Box::new(EmptyValidationMetadata))
+                            .fetch::<Deploy>(
+                                deploy_id,
+                                node_id,
+                                Box::new(DeployValidationMetadata::from_chainspec(
+                                    chainspec.as_ref(),
+                                )),
+                            )
                             .event(move |result| Event::DeployFetched {
                                 block_hash,
                                 result: Either::Right(result),
@@ -253,6 +253,13 @@ impl GlobalStateSynchronizer {
         self.last_progress
     }
 
+    /// Returns the state root hash currently being synced, if any.
+    pub(super) fn root_being_synced(&self) -> Option<Digest> {
+        self.request_state
+            .as_ref()
+            .map(|request_state| request_state.root_hash.into_inner())
+    }
+
     fn handle_request<REv>(
         &mut self,
         request: SyncGlobalStateRequest,
@@ -23,7 +23,7 @@ use casper_types::Timestamp;
 use crate::{
     components::{
         consensus::{ClContext, ProposedBlock},
-        fetcher::{self, EmptyValidationMetadata, FetchResult, FetchedData},
+        fetcher::{self, FetchResult, FetchedData},
         Component,
     },
     effect::{
@@ -32,7 +32,7 @@ use crate::{
     },
     types::{
         ApprovalsHash, Chainspec, Deploy, DeployHashWithApprovals, DeployId, DeployOrTransferHash,
-        NodeId,
+        DeployValidationMetadata, NodeId,
     },
     NodeRng,
 };
@@ -117,7 +117,12 @@ impl BlockValidator {
                 MaybeStartFetching::Start {
                     holder,
                     missing_deploys,
-                } => fetch_deploys(effect_builder, holder, missing_deploys),
+                } => fetch_deploys(
+                    effect_builder,
+                    DeployValidationMetadata::from_chainspec(&self.chainspec),
+                    holder,
+                    missing_deploys,
+                ),
                 MaybeStartFetching::Ongoing => {
                     debug!("ongoing fetches while validating proposed block - noop");
                     Effects::new()
@@ -159,7 +164,12 @@ impl BlockValidator {
             MaybeStartFetching::Start {
                 holder,
                 missing_deploys,
-            } => fetch_deploys(effect_builder, holder, missing_deploys),
+            } => fetch_deploys(
+                effect_builder,
+                DeployValidationMetadata::from_chainspec(&self.chainspec),
+                holder,
+                missing_deploys,
+            ),
             MaybeStartFetching::ValidationSucceeded => {
                 debug!("no deploys - block validation complete");
                 debug_assert!(maybe_responder.is_some());
@@ -244,6 +254,19 @@ impl BlockValidator {
                         .flat_map(|state| state.try_mark_invalid(&dt_hash));
                     return respond(false, responders);
                 }
+                // Deploys held in storage were not necessarily validated against the current
+                // chainspec.
+                if let Err(error) =
+                    item.has_allowed_key_types(self.chainspec.core_config.allow_secp256r1_keys)
+                {
+                    warn!(deploy = %item, %dt_hash, %error, "deploy uses disallowed key types");
+                    // Hard failure - change state to Invalid.
+                    let responders = self
+                        .validation_states
+                        .values_mut()
+                        .flat_map(|state| state.try_mark_invalid(&dt_hash));
+                    return respond(false, responders);
+                }
                 let deploy_footprint = match item.footprint() {
                     Ok(footprint) => footprint,
                     Err(error) => {
@@ -280,6 +303,8 @@ impl BlockValidator {
                         // Soft failure - just mark the holder as failed and see if we can start
                         // fetching using a different holder.
                         let mut effects = Effects::new();
+                        let validation_metadata =
+                            DeployValidationMetadata::from_chainspec(&self.chainspec);
                         self.validation_states.values_mut().for_each(|state| {
                             state.try_mark_holder_failed(&peer);
                             match state.start_fetching() {
@@ -294,6 +319,7 @@ impl BlockValidator {
                                     );
                                     effects.extend(fetch_deploys(
                                         effect_builder,
+                                        validation_metadata,
                                         holder,
                                         missing_deploys,
                                     ))
@@ -365,6 +391,7 @@ where
 
 fn fetch_deploys<REv>(
     effect_builder: EffectBuilder<REv>,
+    validation_metadata: DeployValidationMetadata,
     holder: NodeId,
     missing_deploys: HashMap<DeployOrTransferHash, ApprovalsHash>,
 ) -> Effects<Event>
@@ -376,7 +403,7 @@ where
         .flat_map(|(dt_hash, approvals_hash)| {
             let deploy_id = DeployId::new(dt_hash.into(), approvals_hash);
             effect_builder
-                .fetch::<Deploy>(deploy_id, holder, Box::new(EmptyValidationMetadata))
+                .fetch::<Deploy>(deploy_id, holder, Box::new(validation_metadata))
                 .event(move |result| Event::DeployFetched { dt_hash, result })
         })
         .collect()
@@ -6,6 +6,7 @@ mod cl_context;
 mod config;
 mod consensus_protocol;
 mod era_supervisor;
+mod fault_evidence;
 #[macro_use]
 pub mod highway_core;
 pub(crate) mod error;
@@ -61,6 +62,9 @@ pub use cl_context::ClContext;
 pub(crate) use config::{ChainspecConsensusExt, Config};
 pub(crate) use consensus_protocol::{BlockContext, EraReport, ProposedBlock};
 pub(crate) use era_supervisor::{debug::EraDump, EraSupervisor, SerializedMessage};
+pub(crate) use fault_evidence::{
+    ContextFaultEvidence, FaultEvidence, FaultEvidenceOf, FaultMessageOf, SignedFaultMessageOf,
+};
 #[cfg(test)]
 pub(crate) use highway_core::highway::Vertex as HighwayVertex;
 pub(crate) use leader_sequence::LeaderSequence;
@@ -473,6 +477,9 @@ where
                 let validator_changes = self.get_validator_changes();
                 responder.respond(validator_changes).ignore()
             }
+            Event::ConsensusRequest(ConsensusRequest::Faults(responder)) => {
+                responder.respond(self.get_faults()).ignore()
+            }
             Event::DumpState(req @ DumpConsensusStateRequest { era_id, .. }) => {
                 let current_era = match self.current_era() {
                     None => {
@@ -9,10 +9,10 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use casper_hashing::Digest;
-use casper_types::{bytesrepr::ToBytes, TimeDiff, Timestamp};
+use casper_types::{bytesrepr::ToBytes, EraId, TimeDiff, Timestamp};
 
 use crate::{
-    components::consensus::{traits::Context, ActionId, TimerId},
+    components::consensus::{traits::Context, ActionId, ContextFaultEvidence, TimerId},
     types::NodeId,
     NodeRng,
 };
@@ -306,6 +306,9 @@ pub(crate) trait ConsensusProtocol<C: Context>: Send {
     /// Marks the validator `vid` as faulty, based on evidence from a different instance.
     fn mark_faulty(&mut self, vid: &C::ValidatorId);
 
+    /// Returns the evidence against the validator `vid` in era `era_id`, if we have it.
+    fn evidence(&self, era_id: EraId, vid: &C::ValidatorId) -> Option<ContextFaultEvidence<C>>;
+
     /// Sends evidence for a faulty of validator `vid` to the `sender` of the request.
     fn send_evidence(&self, sender: NodeId, vid: &C::ValidatorId) -> ProtocolOutcomes<C>;
 
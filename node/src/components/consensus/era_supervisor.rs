@@ -44,7 +44,8 @@ use crate::{
             metrics::Metrics,
             validator_change::{ValidatorChange, ValidatorChanges},
             ActionId, ChainspecConsensusExt, Config, ConsensusMessage, ConsensusRequestMessage,
-            Event, HighwayProtocol, NewBlockPayload, ReactorEventT, ResolveValidity, TimerId, Zug,
+            Event, FaultEvidence, HighwayProtocol, NewBlockPayload, ReactorEventT, ResolveValidity,
+            TimerId, Zug,
         },
         network::blocklist::BlocklistJustification,
     },
@@ -259,6 +260,23 @@ impl EraSupervisor {
         result
     }
 
+    /// Returns the validators accused or proven to be faulty in each open era, together with the
+    /// evidence against them if this node has it.
+    pub(super) fn get_faults(&self) -> BTreeMap<EraId, BTreeMap<PublicKey, Option<FaultEvidence>>> {
+        self.open_eras
+            .iter()
+            .map(|(era_id, era)| {
+                let faulty = era
+                    .accusations
+                    .iter()
+                    .chain(era.consensus.validators_with_evidence())
+                    .map(|pub_key| (pub_key.clone(), era.consensus.evidence(*era_id, pub_key)))
+                    .collect();
+                (*era_id, faulty)
+            })
+            .collect()
+    }
+
     fn era_seed(booking_block_hash: BlockHash, key_block_seed: Digest) -> u64 {
         let result = Digest::hash_pair(booking_block_hash, key_block_seed).value();
         u64::from_le_bytes(result[0..std::mem::size_of::<u64>()].try_into().unwrap())
@@ -1197,8 +1215,12 @@ impl EraSupervisor {
                 .ignore(),
             ProtocolOutcome::NewEvidence(pub_key) => {
                 info!(%pub_key, era = era_id.value(), "validator equivocated");
+                let evidence = self
+                    .open_eras
+                    .get(&era_id)
+                    .and_then(|era| era.consensus.evidence(era_id, &pub_key));
                 let mut effects = effect_builder
-                    .announce_fault_event(era_id, pub_key.clone(), Timestamp::now())
+                    .announce_fault_event(era_id, pub_key.clone(), Timestamp::now(), evidence)
                     .ignore();
                 for e_id in self.iter_future(era_id, PAST_EVIDENCE_ERAS) {
                     let proposed_blocks = if let Some(era) = self.open_eras.get_mut(&e_id) {
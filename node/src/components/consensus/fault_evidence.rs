@@ -0,0 +1,152 @@
+use datasize::DataSize;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_hashing::Digest;
+use casper_types::{bytesrepr::Bytes, crypto, EraId, PublicKey, SecretKey, Signature, Timestamp};
+
+use crate::components::{consensus::traits::Context, rpc_server::rpcs::docs::DocExample};
+
+static FAULT_EVIDENCE: Lazy<FaultEvidence> = Lazy::new(|| {
+    let secret_key = SecretKey::doc_example();
+    let public_key = PublicKey::from(secret_key);
+    let instance_id = Digest::hash([1u8]);
+    let signed_vote = |vote: bool| {
+        // The round ID, instance ID, `Content::Vote` and validator index, as serialized by Zug.
+        let serialized = bincode::serialize(&(7u32, instance_id, (1u32, vote), 0u32))
+            .expect("should serialize vote");
+        let hash = Digest::hash(&serialized);
+        SignedFaultMessage {
+            message: FaultMessage::ZugVote {
+                round_id: 7,
+                vote,
+                validator_idx: 0,
+            },
+            serialized: serialized.into(),
+            hash,
+            signature: crypto::sign(hash, secret_key, &public_key),
+        }
+    };
+    FaultEvidence {
+        era_id: EraId::new(1),
+        public_key: public_key.clone(),
+        instance_id,
+        message1: signed_vote(true),
+        message2: signed_vote(false),
+    }
+});
+
+/// Proof that a validator is faulty, with the validator ID, instance ID, hash and signature types
+/// of the node's consensus context.
+pub type FaultEvidence = FaultEvidenceOf<PublicKey, Digest, Digest, Signature>;
+
+/// Proof that a validator is faulty, with the validator ID, instance ID, hash and signature types
+/// of the consensus context `C`.
+pub(crate) type ContextFaultEvidence<C> = FaultEvidenceOf<
+    <C as Context>::ValidatorId,
+    <C as Context>::InstanceId,
+    <C as Context>::Hash,
+    <C as Context>::Signature,
+>;
+
+/// A consensus message signed by a faulty validator, with the hash and signature types of the
+/// node's consensus context.
+pub type SignedFaultMessage = SignedFaultMessageOf<Digest, Signature>;
+
+/// The content of a consensus message signed by a faulty validator, with the hash type of the
+/// node's consensus context.
+pub type FaultMessage = FaultMessageOf<Digest>;
+
+/// Proof that a validator is faulty: two conflicting messages signed by the same validator in the
+/// same era.
+///
+/// Each message carries the exact bytes the validator signed the hash of, so the evidence can be
+/// verified independently: hash the serialized message, check the hash, and verify the signature
+/// against the validator's public key.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "FaultEvidence")]
+pub struct FaultEvidenceOf<V, I, H, S> {
+    /// The era in which the conflicting messages were signed.
+    pub era_id: EraId,
+    /// The public key of the faulty validator.
+    pub public_key: V,
+    /// The ID of the era's consensus instance, which is part of every signed message.
+    pub instance_id: I,
+    /// The first of the conflicting messages.
+    pub message1: SignedFaultMessageOf<H, S>,
+    /// The second of the conflicting messages.
+    pub message2: SignedFaultMessageOf<H, S>,
+}
+
+impl DocExample for FaultEvidence {
+    fn doc_example() -> &'static Self {
+        &FAULT_EVIDENCE
+    }
+}
+
+/// A consensus message signed by a faulty validator.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "SignedFaultMessage")]
+pub struct SignedFaultMessageOf<H, S> {
+    /// The signed message.
+    pub message: FaultMessageOf<H>,
+    #[schemars(
+        with = "String",
+        description = "Hex-encoded bytes of the full message as serialized with `bincode` by the \
+        consensus protocol. `hash` is the hash of these bytes."
+    )]
+    pub serialized: Bytes,
+    /// The hash of the serialized message, which is what the validator signed.
+    pub hash: H,
+    /// The validator's signature of `hash`.
+    pub signature: S,
+}
+
+/// The content of a consensus message signed by a faulty validator.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "FaultMessage")]
+pub enum FaultMessageOf<H> {
+    /// A Highway unit.  Two units with the same sequence number are an equivocation.
+    HighwayUnit {
+        /// The index of the unit's creator in the era's list of validators.
+        creator: u32,
+        /// The sequence number of the unit in the validator's swimlane.
+        seq_number: u64,
+        /// The validator's previous unit, if any.
+        previous: Option<H>,
+        /// The time at which the unit was created.
+        timestamp: Timestamp,
+        /// The validator's round exponent at the time the unit was created.
+        round_exp: u8,
+    },
+    /// A Highway endorsement.  Endorsing two units on conflicting forks of the same validator is a
+    /// fault.
+    HighwayEndorsement {
+        /// The index of the endorsing validator in the era's list of validators.
+        creator: u32,
+        /// The hash of the endorsed unit.
+        unit: H,
+    },
+    /// A Zug echo of a proposal.  Echoing two different proposals in the same round is a fault.
+    ZugEcho {
+        /// The round in which the echo was sent.
+        round_id: u32,
+        /// The hash of the echoed proposal.
+        proposal_hash: H,
+        /// The index of the sender in the era's list of validators.
+        validator_idx: u32,
+    },
+    /// A Zug vote.  Voting both `true` and `false` in the same round is a fault.
+    ZugVote {
+        /// The round in which the vote was sent.
+        round_id: u32,
+        /// Whether the validator voted to finalize the round's proposal.
+        vote: bool,
+        /// The index of the sender in the era's list of validators.
+        validator_idx: u32,
+    },
+}
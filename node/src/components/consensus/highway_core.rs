@@ -38,12 +38,13 @@ pub mod highway;
 pub(crate) mod state;
 pub(super) mod synchronizer;
 
-mod endorsement;
+pub(crate) mod endorsement;
 mod evidence;
 #[cfg(test)]
 pub(crate) mod highway_testing;
 
-pub use state::{Observation, Panorama, State};
+pub use evidence::Evidence;
+pub use state::{Fault, Observation, Panorama, State};
 
 // Enables the endorsement mechanism.
 const ENABLE_ENDORSEMENTS: bool = false;
@@ -44,9 +44,12 @@ impl<C: Context> Endorsement<C> {
 
     /// Returns the hash of the endorsement.
     pub fn hash(&self) -> C::Hash {
-        <C as Context>::hash(
-            &bincode::serialize(&(self.unit, self.creator)).expect("serialize endorsement"),
-        )
+        <C as Context>::hash(&self.signed_bytes())
+    }
+
+    /// Returns the serialized endorsement, whose hash is signed.
+    pub(crate) fn signed_bytes(&self) -> Vec<u8> {
+        bincode::serialize(&(self.unit, self.creator)).expect("serialize endorsement")
     }
 }
 
@@ -112,4 +115,9 @@ impl<C: Context> SignedEndorsement<C> {
     pub fn hash(&self) -> C::Hash {
         self.endorsement.hash()
     }
+
+    /// Returns the serialized endorsement, whose hash is signed.
+    pub(crate) fn signed_bytes(&self) -> Vec<u8> {
+        self.endorsement.signed_bytes()
+    }
 }
@@ -459,7 +459,12 @@ impl<C: Context> WireUnit<C> {
     /// Returns the unit's hash, which is used as a unit identifier.
     fn compute_hash(&self) -> C::Hash {
         // TODO: Use serialize_into to avoid allocation?
-        <C as Context>::hash(&bincode::serialize(self).expect("serialize WireUnit"))
+        <C as Context>::hash(&self.signed_bytes())
+    }
+
+    /// Returns the serialized unit, whose hash is signed.
+    pub(crate) fn signed_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("serialize WireUnit")
     }
 }
 
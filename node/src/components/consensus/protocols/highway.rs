@@ -19,7 +19,7 @@ use num_traits::CheckedMul;
 use rand::RngCore;
 use tracing::{debug, error, info, trace, warn};
 
-use casper_types::{system::auction::BLOCK_REWARD, TimeDiff, Timestamp, U512};
+use casper_types::{system::auction::BLOCK_REWARD, EraId, TimeDiff, Timestamp, U512};
 
 use crate::{
     components::consensus::{
@@ -30,18 +30,21 @@ use crate::{
         era_supervisor::SerializedMessage,
         highway_core::{
             active_validator::Effect as AvEffect,
+            endorsement::SignedEndorsement,
             finality_detector::{FinalityDetector, FttExceeded},
             highway::{
-                Dependency, GetDepOutcome, Highway, Params, PreValidatedVertex, ValidVertex,
-                Vertex, VertexError,
+                Dependency, GetDepOutcome, Highway, Params, PreValidatedVertex, SignedWireUnit,
+                ValidVertex, Vertex, VertexError,
             },
             state::{IndexObservation, IndexPanorama, Observation},
             synchronizer::Synchronizer,
+            Evidence,
         },
         protocols,
         traits::{ConsensusValueT, Context},
         utils::ValidatorIndex,
-        ActionId, TimerId,
+        ActionId, ContextFaultEvidence, FaultEvidenceOf, FaultMessageOf, SignedFaultMessageOf,
+        TimerId,
     },
     types::{Chainspec, NodeId},
     NodeRng,
@@ -1091,6 +1094,30 @@ where
         self.highway.mark_faulty(vid);
     }
 
+    fn evidence(&self, era_id: EraId, vid: &C::ValidatorId) -> Option<ContextFaultEvidence<C>> {
+        let vidx = self.highway.validators().get_index(vid)?;
+        let (message1, message2) = match self.highway.state().maybe_evidence(vidx)? {
+            Evidence::Equivocation(unit1, unit2) => {
+                (signed_unit_message(unit1), signed_unit_message(unit2))
+            }
+            Evidence::Endorsements {
+                endorsement1,
+                endorsement2,
+                ..
+            } => (
+                signed_endorsement_message(endorsement1),
+                signed_endorsement_message(endorsement2),
+            ),
+        };
+        Some(FaultEvidenceOf {
+            era_id,
+            public_key: vid.clone(),
+            instance_id: *self.highway.instance_id(),
+            message1,
+            message2,
+        })
+    }
+
     fn send_evidence(&self, sender: NodeId, vid: &C::ValidatorId) -> ProtocolOutcomes<C> {
         self.highway
             .validators()
@@ -1153,3 +1180,37 @@ pub fn max_rounds_per_era(
     #[allow(clippy::arithmetic_side_effects)] // minimum_round_length is guaranteed to be > 0.
     minimum_era_height.max((era_duration.saturating_add(1)) / minimum_round_length)
 }
+
+/// Returns the unit as a message in fault evidence.
+fn signed_unit_message<C: Context>(
+    unit: &SignedWireUnit<C>,
+) -> SignedFaultMessageOf<C::Hash, C::Signature> {
+    let wire_unit = unit.wire_unit();
+    SignedFaultMessageOf {
+        message: FaultMessageOf::HighwayUnit {
+            creator: wire_unit.creator.0,
+            seq_number: wire_unit.seq_number,
+            previous: wire_unit.previous().copied(),
+            timestamp: wire_unit.timestamp,
+            round_exp: wire_unit.round_exp,
+        },
+        serialized: wire_unit.signed_bytes().into(),
+        hash: unit.hash(),
+        signature: unit.signature,
+    }
+}
+
+/// Returns the endorsement as a message in fault evidence.
+fn signed_endorsement_message<C: Context>(
+    endorsement: &SignedEndorsement<C>,
+) -> SignedFaultMessageOf<C::Hash, C::Signature> {
+    SignedFaultMessageOf {
+        message: FaultMessageOf::HighwayEndorsement {
+            creator: endorsement.validator_idx().0,
+            unit: *endorsement.unit(),
+        },
+        serialized: endorsement.signed_bytes().into(),
+        hash: endorsement.hash(),
+        signature: *endorsement.signature(),
+    }
+}
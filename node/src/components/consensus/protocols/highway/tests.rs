@@ -1,6 +1,6 @@
 use std::{collections::BTreeSet, sync::Arc};
 
-use casper_types::{testing::TestRng, PublicKey, TimeDiff, Timestamp, U512};
+use casper_types::{crypto, testing::TestRng, EraId, PublicKey, TimeDiff, Timestamp, U512};
 
 use crate::{
     components::consensus::{
@@ -22,7 +22,7 @@ use crate::{
         },
         traits::Context,
         utils::{ValidatorIndex, Weight},
-        SerializedMessage,
+        FaultEvidence, FaultMessageOf, SerializedMessage,
     },
     types::BlockPayload,
 };
@@ -223,6 +223,87 @@ fn detect_doppelganger() {
     panic!("failed to return DoppelgangerDetected effect");
 }
 
+#[test]
+fn provide_evidence_of_equivocation() {
+    let mut rng = TestRng::new();
+    let validators = vec![
+        (ALICE_PUBLIC_KEY.clone(), 100),
+        (BOB_PUBLIC_KEY.clone(), 100),
+    ];
+    let now = Timestamp::zero();
+    let alice_keypair: Keypair = Keypair::from(Arc::clone(&*ALICE_SECRET_KEY));
+    let mut highway_protocol = new_test_highway_protocol(validators.clone(), vec![]);
+    let sender = *ALICE_NODE_ID;
+
+    // ALICE creates two different units with the same sequence number.
+    for random_bit in [false, true] {
+        let wunit: WireUnit<ClContext> = WireUnit {
+            panorama: Panorama::from(vec![N, N]),
+            creator: ALICE,
+            instance_id: ClContext::hash(INSTANCE_ID_DATA),
+            value: Some(Arc::new(BlockPayload::new(
+                vec![],
+                vec![],
+                vec![],
+                random_bit,
+            ))),
+            seq_number: 0,
+            timestamp: now,
+            round_exp: 0,
+            endorsed: BTreeSet::new(),
+        };
+        let highway_message: HighwayMessage<ClContext> = HighwayMessage::NewVertex(Vertex::Unit(
+            SignedWireUnit::new(wunit.into_hashed(), &alice_keypair),
+        ));
+        let msg = SerializedMessage::from_message(&highway_message);
+        let mut outcomes = highway_protocol.handle_message(&mut rng, sender, msg, now);
+        while let Some(outcome) = outcomes.pop() {
+            if let ProtocolOutcome::QueueAction(ACTION_ID_VERTEX) = outcome {
+                outcomes.extend(highway_protocol.handle_action(ACTION_ID_VERTEX, now))
+            }
+        }
+    }
+
+    let era_id = EraId::new(1);
+    assert_eq!(highway_protocol.evidence(era_id, &BOB_PUBLIC_KEY), None);
+    let FaultEvidence {
+        era_id: evidence_era_id,
+        public_key,
+        instance_id,
+        message1,
+        message2,
+    } = highway_protocol
+        .evidence(era_id, &ALICE_PUBLIC_KEY)
+        .expect("should have evidence against Alice");
+    assert_eq!(evidence_era_id, era_id);
+    assert_eq!(public_key, *ALICE_PUBLIC_KEY);
+    assert_eq!(instance_id, ClContext::hash(INSTANCE_ID_DATA));
+    assert_ne!(message1.hash, message2.hash);
+    for message in [message1, message2] {
+        match message.message {
+            FaultMessageOf::HighwayUnit {
+                creator,
+                seq_number,
+                previous,
+                ..
+            } => {
+                assert_eq!(creator, ALICE.0);
+                assert_eq!(seq_number, 0);
+                assert_eq!(previous, None);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        // The evidence can be verified from the exported bytes alone.
+        let wunit: WireUnit<ClContext> =
+            bincode::deserialize(&message.serialized).expect("should deserialize the unit");
+        assert_eq!(wunit.creator, ALICE);
+        assert_eq!(wunit.instance_id, instance_id);
+        assert_eq!(wunit.seq_number, 0);
+        assert_eq!(ClContext::hash(&message.serialized), message.hash);
+        assert!(crypto::verify(message.hash, &message.signature, &public_key).is_ok());
+    }
+}
+
 #[test]
 fn max_rounds_per_era_returns_the_correct_value_for_prod_chainspec_value() {
     let max_rounds_per_era = max_rounds_per_era(
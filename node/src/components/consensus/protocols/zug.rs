@@ -81,7 +81,7 @@ use itertools::Itertools;
 use rand::{seq::IteratorRandom, Rng};
 use tracing::{debug, error, event, info, warn, Level};
 
-use casper_types::{system::auction::BLOCK_REWARD, TimeDiff, Timestamp, U512};
+use casper_types::{system::auction::BLOCK_REWARD, EraId, TimeDiff, Timestamp, U512};
 
 use crate::{
     components::consensus::{
@@ -94,7 +94,8 @@ use crate::{
         protocols,
         traits::{ConsensusValueT, Context},
         utils::{ValidatorIndex, ValidatorMap, Validators, Weight},
-        ActionId, LeaderSequence, TimerId,
+        ActionId, ContextFaultEvidence, FaultEvidenceOf, FaultMessageOf, LeaderSequence,
+        SignedFaultMessageOf, TimerId,
     },
     types::{Chainspec, NodeId},
     utils, NodeRng,
@@ -2362,6 +2363,20 @@ where
         }
     }
 
+    fn evidence(&self, era_id: EraId, vid: &C::ValidatorId) -> Option<ContextFaultEvidence<C>> {
+        let idx = self.validators.get_index(vid)?;
+        match self.faults.get(&idx)? {
+            Fault::Direct(msg, content, sign) => Some(FaultEvidenceOf {
+                era_id,
+                public_key: vid.clone(),
+                instance_id: msg.instance_id,
+                message1: signed_fault_message(msg),
+                message2: signed_fault_message(&msg.with(*content, *sign)),
+            }),
+            Fault::Banned | Fault::Indirect => None,
+        }
+    }
+
     fn send_evidence(&self, peer: NodeId, vid: &C::ValidatorId) -> ProtocolOutcomes<C> {
         self.validators
             .get_index(vid)
@@ -2429,6 +2444,30 @@ where
     }
 }
 
+/// Returns the signed echo or vote as a message in fault evidence.
+fn signed_fault_message<C: Context>(
+    msg: &SignedMessage<C>,
+) -> SignedFaultMessageOf<C::Hash, C::Signature> {
+    let message = match msg.content {
+        Content::Echo(proposal_hash) => FaultMessageOf::ZugEcho {
+            round_id: msg.round_id,
+            proposal_hash,
+            validator_idx: msg.validator_idx.0,
+        },
+        Content::Vote(vote) => FaultMessageOf::ZugVote {
+            round_id: msg.round_id,
+            vote,
+            validator_idx: msg.validator_idx.0,
+        },
+    };
+    SignedFaultMessageOf {
+        message,
+        serialized: msg.signed_bytes().into(),
+        hash: msg.hash(),
+        signature: msg.signature,
+    }
+}
+
 mod specimen_support {
     use std::collections::BTreeSet;
 
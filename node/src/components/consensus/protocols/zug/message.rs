@@ -157,13 +157,17 @@ impl<C: Context> SignedMessage<C> {
 
     /// Returns whether the signature is valid.
     pub(crate) fn verify_signature(&self, validator_id: &C::ValidatorId) -> bool {
-        let hash = Self::hash_fields(
+        C::verify_signature(&self.hash(), validator_id, &self.signature)
+    }
+
+    /// Returns the hash of all fields except the signature, which is what the signature signs.
+    pub(crate) fn hash(&self) -> C::Hash {
+        Self::hash_fields(
             self.round_id,
             &self.instance_id,
             &self.content,
             self.validator_idx,
-        );
-        C::verify_signature(&hash, validator_id, &self.signature)
+        )
     }
 
     /// Returns the hash of all fields except the signature.
@@ -174,10 +178,30 @@ impl<C: Context> SignedMessage<C> {
         validator_idx: ValidatorIndex,
     ) -> C::Hash {
         let serialized_fields =
-            bincode::serialize(&(round_id, instance_id, content, validator_idx))
-                .expect("failed to serialize fields");
+            Self::serialize_fields(round_id, instance_id, content, validator_idx);
         <C as Context>::hash(&serialized_fields)
     }
+
+    /// Returns all fields except the signature, serialized as they are hashed.
+    pub(crate) fn signed_bytes(&self) -> Vec<u8> {
+        Self::serialize_fields(
+            self.round_id,
+            &self.instance_id,
+            &self.content,
+            self.validator_idx,
+        )
+    }
+
+    /// Returns the serialized fields, except the signature.
+    fn serialize_fields(
+        round_id: RoundId,
+        instance_id: &C::InstanceId,
+        content: &Content<C>,
+        validator_idx: ValidatorIndex,
+    ) -> Vec<u8> {
+        bincode::serialize(&(round_id, instance_id, content, validator_idx))
+            .expect("failed to serialize fields")
+    }
 }
 
 /// Partial information about the sender's protocol state. The receiver should send missing data.
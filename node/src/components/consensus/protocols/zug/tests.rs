@@ -2,7 +2,8 @@ use super::*;
 
 use std::{collections::BTreeSet, sync::Arc};
 
-use casper_types::{PublicKey, SecretKey, Timestamp, U512};
+use casper_hashing::Digest;
+use casper_types::{crypto, EraId, PublicKey, SecretKey, Timestamp, U512};
 use tempfile::tempdir;
 use tracing::info;
 
@@ -604,6 +605,44 @@ fn zug_faults() {
     let outcomes = zug.handle_message(&mut rng, sender, msg, timestamp);
     expect_finalized(&outcomes, &[(&proposal1, 0), (&proposal2, 1)]);
 
+    // The evidence against Bob contains both of his conflicting signed votes.
+    let era_id = EraId::new(1);
+    assert_eq!(zug.evidence(era_id, &*ALICE_PUBLIC_KEY), None);
+    let evidence = zug
+        .evidence(era_id, &*BOB_PUBLIC_KEY)
+        .expect("should have evidence against Bob");
+    assert_eq!(evidence.era_id, era_id);
+    assert_eq!(evidence.public_key, *BOB_PUBLIC_KEY);
+    assert_eq!(evidence.instance_id, ClContext::hash(INSTANCE_ID_DATA));
+    let bob_idx = validators.get_index(&*BOB_PUBLIC_KEY).unwrap();
+    assert_eq!(
+        evidence.message1.message,
+        FaultMessageOf::ZugVote {
+            round_id: 3,
+            vote: false,
+            validator_idx: bob_idx.0,
+        }
+    );
+    assert_eq!(
+        evidence.message2.message,
+        FaultMessageOf::ZugVote {
+            round_id: 3,
+            vote: true,
+            validator_idx: bob_idx.0,
+        }
+    );
+    for (message, vote) in [(evidence.message1, false), (evidence.message2, true)] {
+        // The evidence can be verified from the exported bytes alone.
+        let fields: (RoundId, Digest, Content<ClContext>, ValidatorIndex) =
+            bincode::deserialize(&message.serialized).expect("should deserialize the vote");
+        assert_eq!(
+            fields,
+            (3, evidence.instance_id, Content::Vote(vote), bob_idx)
+        );
+        assert_eq!(ClContext::hash(&message.serialized), message.hash);
+        assert!(crypto::verify(message.hash, &message.signature, &BOB_PUBLIC_KEY).is_ok());
+    }
+
     // Now Carol starts two nodes by mistake, and equivocates. That crosses the FTT.
     let msg = create_message(&validators, 3, vote(true), &carol_kp);
     expect_no_gossip_block_finalized(zug.handle_message(&mut rng, sender, msg, timestamp));
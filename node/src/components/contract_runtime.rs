@@ -4,6 +4,7 @@ mod config;
 mod error;
 mod metrics;
 mod operations;
+pub(crate) mod state_snapshot;
 #[cfg(test)]
 mod tests;
 mod types;
@@ -13,8 +14,12 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     convert::TryInto,
     fmt::{self, Debug, Display, Formatter},
+    iter,
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc, Mutex, MutexGuard, TryLockError,
+    },
     time::Instant,
 };
 
@@ -28,21 +33,27 @@ use thiserror::Error;
 use tracing::{debug, error, info, trace};
 
 use casper_execution_engine::{
-    core::engine_state::{
-        self,
-        engine_config::{FeeHandling, RefundHandling},
-        genesis::GenesisError,
-        ChainspecRegistry, DeployItem, EngineConfigBuilder, EngineState, GenesisSuccess,
-        SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+    core::{
+        engine_state::{
+            self,
+            engine_config::{FeeHandling, RefundHandling},
+            genesis::GenesisError,
+            ChainspecRegistry, DeployItem, EngineConfigBuilder, EngineState, GenesisSuccess,
+            SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+        },
+        runtime::ExecutionTrace,
     },
     shared::{newtypes::CorrelationId, system_config::SystemConfig, wasm_config::WasmConfig},
     storage::{
-        global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
+        global_state::{lmdb::LmdbGlobalState, trie_pruner::TriePruner},
+        transaction_source::lmdb::LmdbEnvironment,
         trie_store::lmdb::LmdbTrieStore,
     },
 };
 use casper_hashing::Digest;
-use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, PublicKey, Timestamp};
+use casper_types::{
+    bytesrepr::Bytes, EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp,
+};
 
 use crate::{
     components::{fetcher::FetchResponse, Component, ComponentState},
@@ -63,7 +74,7 @@ use crate::{
     },
     NodeRng,
 };
-pub(crate) use config::Config;
+pub(crate) use config::{Config, PruningMode};
 pub(crate) use error::{BlockExecutionError, ConfigError};
 use metrics::Metrics;
 pub use operations::execute_finalized_block;
@@ -128,6 +139,10 @@ pub struct SpeculativeExecutionState {
     pub protocol_version: ProtocolVersion,
 }
 
+/// The result of speculatively executing a deploy, along with its execution trace if one was
+/// requested.  `None` if execution produced no result.
+pub(crate) type SpeculativeExecutionResult = Option<(ExecutionResult, Option<ExecutionTrace>)>;
+
 /// State to use to construct the next block in the blockchain. Includes the state root hash for the
 /// execution engine as well as certain values the next header will be based on.
 #[derive(DataSize, Debug, Clone, Serialize)]
@@ -169,6 +184,11 @@ impl ExecutionPreState {
             parent_seed: block_header.accumulated_seed(),
         }
     }
+
+    /// The height of the next block to be executed.
+    pub(crate) fn next_block_height(&self) -> u64 {
+        self.next_block_height
+    }
 }
 
 type ExecQueue = Arc<Mutex<BTreeMap<u64, (FinalizedBlock, Vec<Deploy>, MetaBlockState)>>>;
@@ -212,6 +232,14 @@ pub(crate) struct ContractRuntime {
     system_contract_registry: Option<SystemContractRegistry>,
     activation_point: ActivationPoint,
     prune_batch_size: u64,
+    /// Prunes global state unreachable from recent blocks, if pruning is enabled.
+    #[data_size(skip)]
+    state_pruner: Option<Arc<Mutex<TriePruner>>>,
+    /// The number of most recent blocks whose global state is retained when pruning.
+    retained_state_roots: u64,
+    /// Whether a pruning step is currently running.
+    #[data_size(skip)]
+    pruning_step_in_flight: Arc<AtomicBool>,
 }
 
 impl Debug for ContractRuntime {
@@ -494,6 +522,7 @@ impl ContractRuntime {
                         let shared_pre_state = Arc::clone(&self.execution_pre_state);
                         let activation_point = self.activation_point;
                         let prune_batch_size = self.prune_batch_size;
+                        let state_pruner = self.state_pruner.clone();
                         effects.extend(
                             Self::execute_finalized_block_or_requeue(
                                 engine_state,
@@ -508,6 +537,7 @@ impl ContractRuntime {
                                 activation_point,
                                 key_block_height_for_activation_point,
                                 prune_batch_size,
+                                state_pruner,
                                 meta_block_state,
                             )
                             .ignore(),
@@ -551,6 +581,30 @@ impl ContractRuntime {
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::GetKeysWithPrefix {
+                state_root_hash,
+                prefix,
+                start_after,
+                limit,
+                responder,
+            } => {
+                trace!(%state_root_hash, ?prefix, ?start_after, limit, "get keys with prefix");
+                let engine_state = Arc::clone(&self.engine_state);
+                async move {
+                    let result = run_intensive_task(move || {
+                        engine_state.get_keys_with_prefix(
+                            CorrelationId::new(),
+                            state_root_hash,
+                            &prefix,
+                            start_after.as_ref(),
+                            limit,
+                        )
+                    })
+                    .await;
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash,
                 responder,
@@ -569,6 +623,7 @@ impl ContractRuntime {
             ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
                 deploy,
+                with_trace,
                 responder,
             } => {
                 let engine_state = Arc::clone(&self.engine_state);
@@ -578,6 +633,7 @@ impl ContractRuntime {
                             engine_state.as_ref(),
                             execution_prestate,
                             DeployItem::from((*deploy).clone()),
+                            with_trace,
                         )
                     })
                     .await;
@@ -589,6 +645,27 @@ impl ContractRuntime {
     }
 }
 
+/// Opens the global state in `storage_dir`, creating an empty one if it does not exist yet.
+pub(crate) fn open_global_state(
+    storage_dir: &Path,
+    contract_runtime_config: &Config,
+) -> Result<LmdbGlobalState, ConfigError> {
+    let environment = Arc::new(LmdbEnvironment::new(
+        storage_dir,
+        contract_runtime_config.max_global_state_size_or_default(),
+        contract_runtime_config.max_readers_or_default(),
+        contract_runtime_config.manual_sync_enabled_or_default(),
+    )?);
+
+    let trie_store = Arc::new(LmdbTrieStore::new(
+        &environment,
+        None,
+        DatabaseFlags::empty(),
+    )?);
+
+    Ok(LmdbGlobalState::empty(environment, trie_store)?)
+}
+
 impl ContractRuntime {
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
@@ -609,6 +686,7 @@ impl ContractRuntime {
         administrative_accounts: BTreeSet<PublicKey>,
         allow_auction_bids: bool,
         allow_unrestricted_transfers: bool,
+        allow_secp256r1_keys: bool,
         refund_handling: RefundHandling,
         fee_handling: FeeHandling,
     ) -> Result<Self, ConfigError> {
@@ -620,22 +698,10 @@ impl ContractRuntime {
             parent_seed: Default::default(),
         }));
 
-        let environment = Arc::new(LmdbEnvironment::new(
-            storage_dir,
-            contract_runtime_config.max_global_state_size_or_default(),
-            contract_runtime_config.max_readers_or_default(),
-            contract_runtime_config.manual_sync_enabled_or_default(),
-        )?);
-
-        let trie_store = Arc::new(LmdbTrieStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
-
-        let global_state = LmdbGlobalState::empty(environment, trie_store)?;
+        let global_state = open_global_state(storage_dir, contract_runtime_config)?;
         let engine_config = EngineConfigBuilder::new()
             .with_max_query_depth(contract_runtime_config.max_query_depth_or_default())
+            .with_max_trace_entries(contract_runtime_config.max_trace_entries_or_default())
             .with_max_associated_keys(max_associated_keys)
             .with_max_runtime_call_stack_height(max_runtime_call_stack_height)
             .with_minimum_delegation_amount(minimum_delegation_amount)
@@ -647,6 +713,7 @@ impl ContractRuntime {
             .with_administrative_accounts(administrative_accounts)
             .with_allow_auction_bids(allow_auction_bids)
             .with_allow_unrestricted_transfers(allow_unrestricted_transfers)
+            .with_allow_secp256r1_keys(allow_secp256r1_keys)
             .with_refund_handling(refund_handling)
             .with_fee_handling(fee_handling)
             .build();
@@ -655,6 +722,14 @@ impl ContractRuntime {
 
         let metrics = Arc::new(Metrics::new(registry)?);
 
+        let state_pruner = match contract_runtime_config.pruning_mode_or_default() {
+            PruningMode::Archive => None,
+            PruningMode::Pruned => Some(Arc::new(Mutex::new(TriePruner::new(
+                engine_state.get_state(),
+                contract_runtime_config.pruning_batch_size_or_default() as usize,
+            )?))),
+        };
+
         Ok(ContractRuntime {
             state: ComponentState::Initialized,
             execution_pre_state,
@@ -665,15 +740,27 @@ impl ContractRuntime {
             system_contract_registry: None,
             activation_point,
             prune_batch_size,
+            state_pruner,
+            retained_state_roots: contract_runtime_config.retained_state_roots_or_default(),
+            pruning_step_in_flight: Arc::new(AtomicBool::new(false)),
         })
     }
 
     /// Commits a genesis request.
+    ///
+    /// Returns `Ok(None)` without committing if global state is currently being pruned or blocks
+    /// are being executed; the caller should retry later.
     pub(crate) fn commit_genesis(
         &self,
         chainspec: &Chainspec,
         chainspec_raw_bytes: &ChainspecRawBytes,
-    ) -> Result<GenesisSuccess, engine_state::Error> {
+    ) -> Result<Option<GenesisSuccess>, engine_state::Error> {
+        // Hold the pruner until the new root is retained so that no pruning step can delete tries
+        // written by the commit in the meantime.
+        let mut maybe_pruner = match self.try_lock_state_pruner() {
+            Ok(maybe_pruner) => maybe_pruner,
+            Err(()) => return Ok(None),
+        };
         let correlation_id = CorrelationId::new();
         let genesis_config_hash = chainspec.hash();
         let protocol_version = chainspec.protocol_config.version;
@@ -700,13 +787,27 @@ impl ContractRuntime {
             chainspec_registry,
         );
         self.engine_state.flush_environment()?;
-        result
+        let genesis_success = result?;
+        if let Some(pruner) = &mut maybe_pruner {
+            pruner.retain_root(genesis_success.post_state_hash);
+        }
+        Ok(Some(genesis_success))
     }
 
+    /// Commits an upgrade.
+    ///
+    /// Returns `Ok(None)` without committing if global state is currently being pruned or blocks
+    /// are being executed; the caller should retry later.
     pub(crate) fn commit_upgrade(
         &self,
         upgrade_config: UpgradeConfig,
-    ) -> Result<UpgradeSuccess, engine_state::Error> {
+    ) -> Result<Option<UpgradeSuccess>, engine_state::Error> {
+        // Hold the pruner until the new root is retained so that no pruning step can delete tries
+        // written by the commit in the meantime.
+        let mut maybe_pruner = match self.try_lock_state_pruner() {
+            Ok(maybe_pruner) => maybe_pruner,
+            Err(()) => return Ok(None),
+        };
         debug!(?upgrade_config, "upgrade");
         let start = Instant::now();
         let scratch_state = self.engine_state.get_scratch_engine_state();
@@ -716,11 +817,14 @@ impl ContractRuntime {
             .engine_state
             .write_scratch_to_db(pre_state_hash, scratch_state.into_inner())?;
         self.engine_state.flush_environment()?;
+        if let Some(pruner) = &mut maybe_pruner {
+            pruner.retain_root(result.post_state_hash);
+        }
         self.metrics
             .commit_upgrade
             .observe(start.elapsed().as_secs_f64());
         debug!(?result, "upgrade result");
-        Ok(result)
+        Ok(Some(result))
     }
 
     pub(crate) fn set_initial_state(&mut self, sequential_block_state: ExecutionPreState) {
@@ -756,6 +860,7 @@ impl ContractRuntime {
         activation_point: ActivationPoint,
         key_block_height_for_activation_point: u64,
         prune_batch_size: u64,
+        state_pruner: Option<Arc<Mutex<TriePruner>>>,
         mut meta_block_state: MetaBlockState,
     ) where
         REv: From<ContractRuntimeRequest>
@@ -774,7 +879,12 @@ impl ContractRuntime {
             maybe_step_effect_and_upcoming_era_validators,
         } = match run_intensive_task(move || {
             debug!("ContractRuntime: execute_finalized_block");
-            execute_finalized_block(
+            // Hold the pruner for the duration of execution so that no pruning step can run until
+            // the new state root has been retained.
+            let mut maybe_pruner = state_pruner
+                .as_ref()
+                .map(|pruner| pruner.lock().expect("state pruner mutex poisoned"));
+            let result = execute_finalized_block(
                 engine_state.as_ref(),
                 Some(contract_runtime_metrics),
                 protocol_version,
@@ -784,7 +894,11 @@ impl ContractRuntime {
                 activation_point.era_id(),
                 key_block_height_for_activation_point,
                 prune_batch_size,
-            )
+            );
+            if let (Some(pruner), Ok(block_and_execution_results)) = (&mut maybe_pruner, &result) {
+                pruner.retain_root(*block_and_execution_results.block.state_root_hash());
+            }
+            result
         })
         .await
         {
@@ -909,6 +1023,112 @@ impl ContractRuntime {
         }
     }
 
+    /// Returns `true` if global state is pruned, i.e. this is not an archive node.
+    pub(crate) fn is_pruning_enabled(&self) -> bool {
+        self.state_pruner.is_some()
+    }
+
+    /// Locks the state pruner without blocking the calling thread, so that no pruning step can
+    /// run until the returned guard is dropped.
+    ///
+    /// Returns `Ok(None)` if pruning is disabled, or `Err(())` if the pruner is currently held by
+    /// a pruning step or a block execution.
+    fn try_lock_state_pruner(&self) -> Result<Option<MutexGuard<'_, TriePruner>>, ()> {
+        match &self.state_pruner {
+            None => Ok(None),
+            Some(state_pruner) => match state_pruner.try_lock() {
+                Ok(pruner) => Ok(Some(pruner)),
+                Err(TryLockError::WouldBlock) => Err(()),
+                Err(TryLockError::Poisoned(_)) => panic!("state pruner mutex poisoned"),
+            },
+        }
+    }
+
+    /// Runs a single step of pruning global state, if pruning is enabled and no step is already
+    /// running.
+    ///
+    /// If no pruning pass is in progress, a new one is started which retains the state roots
+    /// returned by `retained_roots`, given the configured number of state roots to retain and the
+    /// current execution pre-state.  The current pre-state root is always retained.  If
+    /// `retained_roots` returns no roots, no pass is started.
+    ///
+    /// `protected_roots` are retained in addition, e.g. roots still needed by the block
+    /// synchronizer.  If `allow_deletion` is `false`, the step only marks reachable tries.
+    pub(crate) fn prune_global_state<REv>(
+        &self,
+        retained_roots: impl FnOnce(u64, &ExecutionPreState) -> Vec<Digest>,
+        protected_roots: Vec<Digest>,
+        allow_deletion: bool,
+    ) -> Effects<REv>
+    where
+        REv: Send,
+    {
+        let state_pruner = match &self.state_pruner {
+            Some(state_pruner) => Arc::clone(state_pruner),
+            None => return Effects::new(),
+        };
+        if self.pruning_step_in_flight.load(atomic::Ordering::SeqCst) {
+            return Effects::new();
+        }
+        {
+            // Blocks are being executed if the pruner is locked; never block the reactor on it.
+            let mut pruner = match state_pruner.try_lock() {
+                Ok(pruner) => pruner,
+                Err(TryLockError::WouldBlock) => return Effects::new(),
+                Err(TryLockError::Poisoned(_)) => panic!("state pruner mutex poisoned"),
+            };
+            if pruner.is_idle() {
+                let pre_state = self.execution_pre_state.lock().unwrap().clone();
+                let roots = retained_roots(self.retained_state_roots, &pre_state);
+                if roots.is_empty() {
+                    return Effects::new();
+                }
+                debug!(
+                    retained_roots = roots.len(),
+                    "ContractRuntime: starting global state pruning pass"
+                );
+                pruner.start_pass(
+                    self.engine_state.get_state(),
+                    roots
+                        .into_iter()
+                        .chain(iter::once(pre_state.pre_state_root_hash)),
+                );
+            }
+            for root in protected_roots {
+                pruner.retain_root(root);
+            }
+        }
+
+        self.pruning_step_in_flight
+            .store(true, atomic::Ordering::SeqCst);
+        let engine_state = Arc::clone(&self.engine_state);
+        let metrics = Arc::clone(&self.metrics);
+        let pruning_step_in_flight = Arc::clone(&self.pruning_step_in_flight);
+        async move {
+            run_intensive_task(move || {
+                let start = Instant::now();
+                let mut pruner = state_pruner.lock().expect("state pruner mutex poisoned");
+                match pruner.step(engine_state.get_state(), allow_deletion) {
+                    Ok(step_result) => {
+                        metrics.pruned_tries.inc_by(step_result.deleted as u64);
+                        if step_result.pass_completed {
+                            metrics.pruning_passes.inc();
+                            debug!("ContractRuntime: completed global state pruning pass");
+                        }
+                    }
+                    Err(error) => error!(%error, "failed to prune global state"),
+                }
+                metrics.prune_step.observe(start.elapsed().as_secs_f64());
+                metrics
+                    .pruning_reachable_tries
+                    .set(pruner.reachable_count().try_into().unwrap_or(i64::MAX));
+            })
+            .await;
+            pruning_step_in_flight.store(false, atomic::Ordering::SeqCst);
+        }
+        .ignore()
+    }
+
     /// Reads the trie (or chunk of a trie) under the given key and index.
     pub(crate) fn get_trie(
         &self,
@@ -1069,6 +1289,7 @@ mod trie_chunking_tests {
             Default::default(),
             true,
             true,
+            false,
             DEFAULT_REFUND_HANDLING,
             DEFAULT_FEE_HANDLING,
         )
@@ -7,6 +7,20 @@ const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_RETAINED_STATE_ROOTS: u64 = 1_000;
+const DEFAULT_PRUNING_BATCH_SIZE: u64 = 10_000;
+const DEFAULT_MAX_TRACE_ENTRIES: u32 = 10_000;
+
+/// Whether global state no longer reachable from recent blocks is retained.
+#[derive(Clone, Copy, DataSize, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PruningMode {
+    /// Retain the global state of every block.
+    #[default]
+    Archive,
+    /// Only retain the global state of the most recent blocks, as set by `retained_state_roots`.
+    Pruned,
+}
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -31,6 +45,23 @@ pub struct Config {
     ///
     /// Defaults to `true`.
     pub enable_manual_sync: Option<bool>,
+    /// Whether to prune global state no longer reachable from recent blocks.
+    ///
+    /// Defaults to `archive`.
+    pub pruning_mode: Option<PruningMode>,
+    /// The number of most recent blocks whose global state is retained in `pruned` mode.
+    ///
+    /// Defaults to 1,000.
+    pub retained_state_roots: Option<u64>,
+    /// The maximum number of trie nodes marked or swept per pruning step in `pruned` mode.
+    ///
+    /// Defaults to 10,000.
+    pub pruning_batch_size: Option<u64>,
+    /// The maximum number of frames and host function calls recorded in the execution trace of a
+    /// speculatively executed deploy.
+    ///
+    /// Defaults to 10,000.
+    pub max_trace_entries: Option<u32>,
 }
 
 impl Config {
@@ -58,6 +89,29 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    /// Pruning mode.
+    pub fn pruning_mode_or_default(&self) -> PruningMode {
+        self.pruning_mode.unwrap_or_default()
+    }
+
+    /// Number of most recent blocks whose global state is retained when pruning.
+    pub fn retained_state_roots_or_default(&self) -> u64 {
+        self.retained_state_roots
+            .unwrap_or(DEFAULT_RETAINED_STATE_ROOTS)
+            .max(1)
+    }
+
+    /// Max trie nodes marked or swept per pruning step.
+    pub fn pruning_batch_size_or_default(&self) -> u64 {
+        self.pruning_batch_size
+            .unwrap_or(DEFAULT_PRUNING_BATCH_SIZE)
+    }
+
+    /// Max entries recorded in an execution trace.
+    pub fn max_trace_entries_or_default(&self) -> u32 {
+        self.max_trace_entries.unwrap_or(DEFAULT_MAX_TRACE_ENTRIES)
+    }
 }
 
 impl Default for Config {
@@ -67,6 +121,10 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            pruning_mode: Some(PruningMode::default()),
+            retained_state_roots: Some(DEFAULT_RETAINED_STATE_ROOTS),
+            pruning_batch_size: Some(DEFAULT_PRUNING_BATCH_SIZE),
+            max_trace_entries: Some(DEFAULT_MAX_TRACE_ENTRIES),
         }
     }
 }
@@ -1,4 +1,4 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
 
 use crate::{unregister_metric, utils};
 
@@ -55,6 +55,19 @@ const EXEC_QUEUE_SIZE_NAME: &str = "execution_queue_size";
 const EXEC_QUEUE_SIZE_HELP: &str =
     "number of blocks that are currently enqueued and waiting for execution";
 
+const PRUNE_STEP_NAME: &str = "contract_runtime_prune_step";
+const PRUNE_STEP_HELP: &str = "time in seconds to perform a step of global state pruning";
+
+const PRUNED_TRIES_NAME: &str = "contract_runtime_pruned_tries";
+const PRUNED_TRIES_HELP: &str = "number of unreachable trie nodes deleted from global state";
+
+const PRUNING_PASSES_NAME: &str = "contract_runtime_pruning_passes";
+const PRUNING_PASSES_HELP: &str = "number of completed global state pruning passes";
+
+const PRUNING_REACHABLE_TRIES_NAME: &str = "contract_runtime_pruning_reachable_tries";
+const PRUNING_REACHABLE_TRIES_HELP: &str =
+    "number of trie nodes marked as reachable in the current global state pruning pass";
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -71,6 +84,10 @@ pub struct Metrics {
     pub(super) exec_block: Histogram,
     pub(super) latest_commit_step: Gauge,
     pub(super) exec_queue_size: IntGauge,
+    pub(super) prune_step: Histogram,
+    pub(super) pruned_tries: IntCounter,
+    pub(super) pruning_passes: IntCounter,
+    pub(super) pruning_reachable_tries: IntGauge,
     registry: Registry,
 }
 
@@ -95,6 +112,16 @@ impl Metrics {
         let exec_queue_size = IntGauge::new(EXEC_QUEUE_SIZE_NAME, EXEC_QUEUE_SIZE_HELP)?;
         registry.register(Box::new(exec_queue_size.clone()))?;
 
+        let pruned_tries = IntCounter::new(PRUNED_TRIES_NAME, PRUNED_TRIES_HELP)?;
+        registry.register(Box::new(pruned_tries.clone()))?;
+
+        let pruning_passes = IntCounter::new(PRUNING_PASSES_NAME, PRUNING_PASSES_HELP)?;
+        registry.register(Box::new(pruning_passes.clone()))?;
+
+        let pruning_reachable_tries =
+            IntGauge::new(PRUNING_REACHABLE_TRIES_NAME, PRUNING_REACHABLE_TRIES_HELP)?;
+        registry.register(Box::new(pruning_reachable_tries.clone()))?;
+
         Ok(Metrics {
             run_execute: utils::register_histogram_metric(
                 registry,
@@ -160,10 +187,19 @@ impl Metrics {
                 registry,
                 EXEC_BLOCK_NAME,
                 EXEC_BLOCK_HELP,
-                common_buckets,
+                common_buckets.clone(),
             )?,
             latest_commit_step,
             exec_queue_size,
+            prune_step: utils::register_histogram_metric(
+                registry,
+                PRUNE_STEP_NAME,
+                PRUNE_STEP_HELP,
+                common_buckets,
+            )?,
+            pruned_tries,
+            pruning_passes,
+            pruning_reachable_tries,
             registry: registry.clone(),
         })
     }
@@ -184,5 +220,9 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.exec_block);
         unregister_metric!(self.registry, self.latest_commit_step);
         unregister_metric!(self.registry, self.exec_queue_size);
+        unregister_metric!(self.registry, self.prune_step);
+        unregister_metric!(self.registry, self.pruned_tries);
+        unregister_metric!(self.registry, self.pruning_passes);
+        unregister_metric!(self.registry, self.pruning_reachable_tries);
     }
 }
@@ -12,6 +12,7 @@ use casper_execution_engine::{
             StepSuccess,
         },
         execution,
+        runtime::ExecutionTrace,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::global_state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
@@ -26,8 +27,8 @@ use crate::{
         consensus::EraReport,
         contract_runtime::{
             error::BlockExecutionError, types::StepEffectAndUpcomingEraValidators,
-            BlockAndExecutionResults, ExecutionPreState, Metrics, SpeculativeExecutionState,
-            APPROVALS_CHECKSUM_NAME, EXECUTION_RESULTS_CHECKSUM_NAME,
+            BlockAndExecutionResults, ExecutionPreState, Metrics, SpeculativeExecutionResult,
+            SpeculativeExecutionState, APPROVALS_CHECKSUM_NAME, EXECUTION_RESULTS_CHECKSUM_NAME,
         },
         fetcher::FetchItem,
     },
@@ -412,7 +413,8 @@ pub fn execute_only<S>(
     engine_state: &EngineState<S>,
     execution_state: SpeculativeExecutionState,
     deploy: DeployItem,
-) -> Result<Option<ExecutionResult>, engine_state::Error>
+    with_trace: bool,
+) -> Result<SpeculativeExecutionResult, engine_state::Error>
 where
     S: StateProvider + CommitProvider,
     S::Error: Into<execution::Error>,
@@ -430,23 +432,33 @@ where
         protocol_version,
         PublicKey::System,
     );
-    let results = execute(engine_state, None, execute_request);
-    results.map(|mut execution_results| {
-        let len = execution_results.len();
-        if len != 1 {
-            warn!(
-                ?deploy_hash,
-                "got more ({}) execution results from a single transaction", len
-            );
-            None
-        } else {
-            // We know it must be 1, we could unwrap and then wrap
-            // with `Some(_)` but `pop_front` already returns an `Option`.
-            // We need to transform the `engine_state::ExecutionResult` into
-            // `casper_types::ExecutionResult` as well.
-            execution_results.pop_front().map(Into::into)
-        }
-    })
+    let mut results: Vec<(EngineExecutionResult, Option<ExecutionTrace>)> = if with_trace {
+        trace!(?execute_request, "execute with traces");
+        engine_state
+            .run_execute_with_traces(CorrelationId::new(), execute_request)?
+            .into_iter()
+            .map(|(execution_result, trace)| (execution_result, Some(trace)))
+            .collect()
+    } else {
+        execute(engine_state, None, execute_request)?
+            .into_iter()
+            .map(|execution_result| (execution_result, None))
+            .collect()
+    };
+    let len = results.len();
+    if len != 1 {
+        warn!(
+            ?deploy_hash,
+            "got more ({}) execution results from a single transaction", len
+        );
+        return Ok(None);
+    }
+    // We know it must be 1, we could unwrap and then wrap with `Some(_)` but `pop` already returns
+    // an `Option`.  We need to transform the `engine_state::ExecutionResult` into
+    // `casper_types::ExecutionResult` as well.
+    Ok(results
+        .pop()
+        .map(|(execution_result, trace)| (execution_result.into(), trace)))
 }
 
 fn execute<S>(
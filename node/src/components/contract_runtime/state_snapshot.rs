@@ -0,0 +1,557 @@
+//! Portable snapshots of the global state.
+//!
+//! A snapshot holds every trie node reachable from a single state root. It allows restoring the
+//! global state of a node from a local file instead of synchronizing it trie by trie from peers.
+//!
+//! A snapshot starts with an 8 byte magic value and the format version as a little-endian `u32`,
+//! followed by a gzip stream. The stream holds the state root hash and then the trie nodes in
+//! post-order, so that every node is preceded by all of its children. The nodes are grouped into
+//! segments of roughly [`ChunkWithProof::CHUNK_SIZE_BYTES`], and every segment is split into
+//! [`ChunkWithProof`]s, each written as a record consisting of its length as a little-endian `u64`
+//! and its bytesrepr encoding. A record of length zero, followed by the total number of trie nodes
+//! as a little-endian `u64`, ends the stream.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    io::{self, Read, Write},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use thiserror::Error;
+use tracing::{debug, info};
+
+use casper_execution_engine::{
+    core::engine_state::{self, EngineState},
+    shared::newtypes::CorrelationId,
+    storage::{
+        global_state::lmdb::LmdbGlobalState,
+        trie::{Trie, TrieRaw},
+    },
+};
+use casper_hashing::{
+    ChunkWithProof, ChunkWithProofVerificationError, Digest, MerkleConstructionError,
+};
+use casper_types::{
+    bytesrepr::{self, Bytes, ToBytes},
+    Key, StoredValue,
+};
+
+/// Magic value every snapshot starts with.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"CSPRSNAP";
+
+/// Current version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Upper bound for the size of a single record, to avoid huge allocations on corrupt input.
+const MAX_RECORD_SIZE: u64 = 2 * ChunkWithProof::CHUNK_SIZE_BYTES as u64;
+
+/// Target size of a segment of trie nodes.
+const SEGMENT_SIZE: usize = ChunkWithProof::CHUNK_SIZE_BYTES;
+
+/// Number of imported trie nodes between progress messages.
+const PROGRESS_INTERVAL: u64 = 100_000;
+
+/// An error exporting or importing a global state snapshot.
+#[derive(Debug, Error)]
+pub(crate) enum SnapshotError {
+    /// Failure to read or write the snapshot.
+    #[error("snapshot i/o error: {0}")]
+    Io(#[from] io::Error),
+    /// Failure to encode or decode a segment or chunk.
+    #[error("failed to encode or decode snapshot data: {0}")]
+    Serialization(bytesrepr::Error),
+    /// Failure to split a segment into chunks.
+    #[error("failed to chunk snapshot segment: {0}")]
+    Chunking(#[from] MerkleConstructionError),
+    /// The input does not start with the snapshot magic value.
+    #[error("not a global state snapshot")]
+    NotASnapshot,
+    /// The snapshot was written in an unsupported format version.
+    #[error("unsupported snapshot version {0}, expected {SNAPSHOT_VERSION}")]
+    UnsupportedVersion(u32),
+    /// A record exceeds [`MAX_RECORD_SIZE`].
+    #[error("snapshot record {index} is too large ({size} bytes)")]
+    RecordTooLarge { index: u64, size: u64 },
+    /// A chunk fails to verify against its proof.
+    #[error("invalid chunk in snapshot record {index}: {error}")]
+    InvalidChunk {
+        index: u64,
+        error: Box<ChunkWithProofVerificationError>,
+    },
+    /// A chunk does not continue the segment started by the preceding chunks.
+    #[error("snapshot record {0} does not continue the current segment")]
+    UnexpectedChunk(u64),
+    /// There is data after the end of the snapshot.
+    #[error("unexpected data after the end of the snapshot")]
+    TrailingData,
+    /// The number of trie nodes read differs from the number declared at the end of the snapshot.
+    #[error("snapshot declares {declared} trie nodes, but contains {found}")]
+    TrieCountMismatch { declared: u64, found: u64 },
+    /// A trie node reachable from the exported state root is not stored.
+    #[error("trie node {0} is missing from global state")]
+    MissingTrie(Digest),
+    /// A trie node of the snapshot precedes some of its children.
+    #[error("trie node {index} of the snapshot is missing {missing} children")]
+    MissingChildren { index: u64, missing: usize },
+    /// The state root is not complete after all trie nodes were imported.
+    #[error("state root {0} is incomplete after importing the snapshot")]
+    IncompleteState(Digest),
+    /// Failure to read from or write to global state.
+    #[error(transparent)]
+    EngineState(#[from] engine_state::Error),
+    /// Failure to sync global state to disk.
+    #[error("failed to sync global state: {0}")]
+    Lmdb(#[from] lmdb::Error),
+}
+
+impl From<bytesrepr::Error> for SnapshotError {
+    fn from(error: bytesrepr::Error) -> Self {
+        SnapshotError::Serialization(error)
+    }
+}
+
+/// A summary of an exported or imported snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SnapshotSummary {
+    /// The state root hash of the snapshot.
+    pub(crate) state_root_hash: Digest,
+    /// The number of trie nodes in the snapshot.
+    pub(crate) trie_count: u64,
+}
+
+/// Writes all trie nodes reachable from `state_root_hash` as a snapshot to `writer`.
+///
+/// Returns the writer along with a summary of the snapshot.
+pub(crate) fn export_state_snapshot<W: Write>(
+    engine_state: &EngineState<LmdbGlobalState>,
+    state_root_hash: Digest,
+    writer: W,
+) -> Result<(W, SnapshotSummary), SnapshotError> {
+    write_snapshot(engine_state, state_root_hash, writer, SEGMENT_SIZE)
+}
+
+/// Verifies the snapshot read from `reader` and writes its trie nodes to global state.
+///
+/// Trie nodes are written as they are read, so an invalid snapshot may leave some of its nodes
+/// behind. As a node is only written once all of its children are present, global state never
+/// holds an incomplete trie below any node.
+pub(crate) fn import_state_snapshot<R: Read>(
+    engine_state: &EngineState<LmdbGlobalState>,
+    reader: R,
+) -> Result<SnapshotSummary, SnapshotError> {
+    let correlation_id = CorrelationId::new();
+    let mut reader = SnapshotReader::new(reader)?;
+    let state_root_hash = reader.state_root_hash;
+    info!(%state_root_hash, "importing global state snapshot");
+
+    let mut trie_count = 0;
+    while let Some(trie) = reader.next_trie()? {
+        match engine_state.put_trie_if_all_children_present(correlation_id, trie.as_ref()) {
+            Ok(_) => (),
+            Err(engine_state::Error::MissingTrieNodeChildren(missing)) => {
+                return Err(SnapshotError::MissingChildren {
+                    index: trie_count,
+                    missing: missing.len(),
+                });
+            }
+            Err(error) => return Err(error.into()),
+        }
+        trie_count += 1;
+        if trie_count % PROGRESS_INTERVAL == 0 {
+            debug!(trie_count, "imported trie nodes");
+        }
+    }
+    engine_state.flush_environment()?;
+
+    let declared = reader.declared_trie_count.unwrap_or_default();
+    if declared != trie_count {
+        return Err(SnapshotError::TrieCountMismatch {
+            declared,
+            found: trie_count,
+        });
+    }
+    // Nodes are only written with all their children present, so the whole trie is present if its
+    // root is.
+    if engine_state
+        .get_trie_full(correlation_id, state_root_hash)?
+        .is_none()
+    {
+        return Err(SnapshotError::IncompleteState(state_root_hash));
+    }
+
+    info!(%state_root_hash, trie_count, "imported global state snapshot");
+    Ok(SnapshotSummary {
+        state_root_hash,
+        trie_count,
+    })
+}
+
+/// Writes a snapshot, grouping trie nodes into segments of roughly `segment_size` bytes.
+fn write_snapshot<W: Write>(
+    engine_state: &EngineState<LmdbGlobalState>,
+    state_root_hash: Digest,
+    writer: W,
+    segment_size: usize,
+) -> Result<(W, SnapshotSummary), SnapshotError> {
+    /// A step of the post-order traversal.
+    enum Visit {
+        /// The node with the given hash has yet to be read.
+        Enter(Digest),
+        /// All children of the node have been written.
+        Exit(TrieRaw),
+    }
+
+    let correlation_id = CorrelationId::new();
+    let mut snapshot_writer = SnapshotWriter::new(writer, state_root_hash, segment_size)?;
+
+    // A node is entered at most once. It cannot be entered again before it is written, as that
+    // would require it to be its own descendant.
+    let mut visited = HashSet::new();
+    let mut stack = vec![Visit::Enter(state_root_hash)];
+    while let Some(visit) = stack.pop() {
+        match visit {
+            Visit::Enter(trie_key) => {
+                if !visited.insert(trie_key) {
+                    continue;
+                }
+                let trie_raw = engine_state
+                    .get_trie_full(correlation_id, trie_key)?
+                    .ok_or(SnapshotError::MissingTrie(trie_key))?;
+                let trie: Trie<Key, StoredValue> =
+                    bytesrepr::deserialize_from_slice(trie_raw.inner())?;
+                let children: Vec<Digest> = trie.iter_children().collect();
+                stack.push(Visit::Exit(trie_raw));
+                stack.extend(children.into_iter().map(Visit::Enter));
+            }
+            Visit::Exit(trie_raw) => snapshot_writer.append(trie_raw.into_inner())?,
+        }
+    }
+
+    let trie_count = snapshot_writer.trie_count;
+    let writer = snapshot_writer.finish()?;
+    info!(%state_root_hash, trie_count, "exported global state snapshot");
+    Ok((
+        writer,
+        SnapshotSummary {
+            state_root_hash,
+            trie_count,
+        },
+    ))
+}
+
+/// Writes a snapshot, one segment of trie nodes at a time.
+struct SnapshotWriter<W: Write> {
+    encoder: GzEncoder<W>,
+    /// Target size of a segment.
+    segment_size: usize,
+    /// The trie nodes of the current segment.
+    segment: Vec<Bytes>,
+    /// The serialized size of the current segment.
+    segment_len: usize,
+    /// Number of trie nodes appended so far.
+    trie_count: u64,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    /// Starts a new snapshot of the given state root.
+    fn new(
+        mut writer: W,
+        state_root_hash: Digest,
+        segment_size: usize,
+    ) -> Result<Self, SnapshotError> {
+        writer.write_all(&SNAPSHOT_MAGIC)?;
+        writer.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        let mut encoder = GzEncoder::new(writer, Compression::default());
+        encoder.write_all(state_root_hash.as_ref())?;
+        Ok(SnapshotWriter {
+            encoder,
+            segment_size,
+            segment: Vec::new(),
+            segment_len: 0,
+            trie_count: 0,
+        })
+    }
+
+    /// Appends the next trie node, writing the current segment first if the node does not fit.
+    fn append(&mut self, trie: Bytes) -> Result<(), SnapshotError> {
+        let trie_len = trie.serialized_length();
+        if !self.segment.is_empty() && self.segment_len + trie_len > self.segment_size {
+            self.write_segment()?;
+        }
+        self.segment_len += trie_len;
+        self.segment.push(trie);
+        self.trie_count += 1;
+        Ok(())
+    }
+
+    /// Writes the chunks of the current segment.
+    fn write_segment(&mut self) -> Result<(), SnapshotError> {
+        let data = self.segment.to_bytes()?;
+        self.segment.clear();
+        self.segment_len = 0;
+
+        let chunk_count = data.chunks(ChunkWithProof::CHUNK_SIZE_BYTES).count() as u64;
+        for index in 0..chunk_count {
+            let chunk = ChunkWithProof::new(&data, index)?.to_bytes()?;
+            self.encoder
+                .write_all(&(chunk.len() as u64).to_le_bytes())?;
+            self.encoder.write_all(&chunk)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the remaining trie nodes and the end of the snapshot, then flushes and returns the
+    /// underlying writer.
+    fn finish(mut self) -> Result<W, SnapshotError> {
+        if !self.segment.is_empty() {
+            self.write_segment()?;
+        }
+        self.encoder.write_all(&0u64.to_le_bytes())?;
+        self.encoder.write_all(&self.trie_count.to_le_bytes())?;
+        let mut writer = self.encoder.finish()?;
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+/// Reads a snapshot, verifying every chunk against its proof.
+struct SnapshotReader<R: Read> {
+    decoder: GzDecoder<R>,
+    state_root_hash: Digest,
+    /// Trie nodes of the current segment which have yet to be returned.
+    pending: VecDeque<Bytes>,
+    /// Index of the next record.
+    next_index: u64,
+    /// The number of trie nodes declared at the end of the snapshot, once it has been reached.
+    declared_trie_count: Option<u64>,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    /// Reads the snapshot header.
+    fn new(mut reader: R) -> Result<Self, SnapshotError> {
+        let mut magic = [0; SNAPSHOT_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let mut version = [0; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let mut decoder = GzDecoder::new(reader);
+        let mut state_root_hash = [0; Digest::LENGTH];
+        decoder.read_exact(&mut state_root_hash)?;
+        Ok(SnapshotReader {
+            decoder,
+            state_root_hash: Digest::from(state_root_hash),
+            pending: VecDeque::new(),
+            next_index: 0,
+            declared_trie_count: None,
+        })
+    }
+
+    /// Returns the next trie node, or `None` at the end of the snapshot.
+    fn next_trie(&mut self) -> Result<Option<Bytes>, SnapshotError> {
+        loop {
+            if let Some(trie) = self.pending.pop_front() {
+                return Ok(Some(trie));
+            }
+            if self.declared_trie_count.is_some() {
+                return Ok(None);
+            }
+            self.read_segment()?;
+        }
+    }
+
+    /// Reads all chunks of the next segment, or the end of the snapshot.
+    fn read_segment(&mut self) -> Result<(), SnapshotError> {
+        let first = match self.read_chunk()? {
+            Some(chunk) => chunk,
+            None => {
+                let mut trie_count = [0; 8];
+                self.decoder.read_exact(&mut trie_count)?;
+                if self.decoder.read(&mut [0])? != 0 {
+                    return Err(SnapshotError::TrailingData);
+                }
+                self.declared_trie_count = Some(u64::from_le_bytes(trie_count));
+                return Ok(());
+            }
+        };
+        let proof = first.proof();
+        if proof.index() != 0 {
+            return Err(SnapshotError::UnexpectedChunk(self.next_index - 1));
+        }
+        let (count, root_hash) = (proof.count(), proof.root_hash());
+
+        let mut data = Vec::from(first.into_chunk());
+        for index in 1..count {
+            let chunk = self
+                .read_chunk()?
+                .ok_or(SnapshotError::UnexpectedChunk(self.next_index))?;
+            let proof = chunk.proof();
+            if proof.index() != index || proof.count() != count || proof.root_hash() != root_hash {
+                return Err(SnapshotError::UnexpectedChunk(self.next_index - 1));
+            }
+            data.extend_from_slice(chunk.chunk());
+        }
+
+        let segment: Vec<Bytes> = bytesrepr::deserialize(data)?;
+        self.pending.extend(segment);
+        Ok(())
+    }
+
+    /// Reads and verifies the next chunk, or returns `None` at the end marker.
+    fn read_chunk(&mut self) -> Result<Option<ChunkWithProof>, SnapshotError> {
+        let index = self.next_index;
+        let mut len = [0; 8];
+        self.decoder.read_exact(&mut len)?;
+        let len = u64::from_le_bytes(len);
+        if len == 0 {
+            return Ok(None);
+        }
+        if len > MAX_RECORD_SIZE {
+            return Err(SnapshotError::RecordTooLarge { index, size: len });
+        }
+        let mut bytes = vec![0; len as usize];
+        self.decoder.read_exact(&mut bytes)?;
+        self.next_index += 1;
+
+        let chunk: ChunkWithProof = bytesrepr::deserialize(bytes)?;
+        chunk
+            .verify()
+            .map_err(|error| SnapshotError::InvalidChunk {
+                index,
+                error: Box::new(error),
+            })?;
+        Ok(Some(chunk))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::Rng;
+    use tempfile::TempDir;
+
+    use casper_execution_engine::{
+        core::engine_state::EngineConfig,
+        storage::global_state::{StateProvider, StateReader},
+    };
+    use casper_types::{testing::TestRng, CLValue};
+
+    use super::*;
+    use crate::components::contract_runtime::{open_global_state, Config};
+
+    fn new_engine_state() -> (EngineState<LmdbGlobalState>, TempDir) {
+        let tempdir = tempfile::tempdir().unwrap();
+        let global_state = open_global_state(tempdir.path(), &Config::default()).unwrap();
+        (
+            EngineState::new(global_state, EngineConfig::default()),
+            tempdir,
+        )
+    }
+
+    fn random_values(rng: &mut TestRng, count: usize) -> HashMap<Key, StoredValue> {
+        (0..count)
+            .map(|_| {
+                let value = CLValue::from_t(rng.gen::<u64>()).unwrap();
+                (Key::Hash(rng.gen()), StoredValue::CLValue(value))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_export_and_import_state_snapshot() {
+        let mut rng = TestRng::new();
+        let correlation_id = CorrelationId::new();
+        let (source, _source_dir) = new_engine_state();
+        let values = random_values(&mut rng, 300);
+        let empty_root = source.get_state().empty_state_root_hash();
+        let first_root = source
+            .get_state()
+            .put_stored_values(correlation_id, empty_root, values.clone())
+            .unwrap();
+        // A second state root sharing most trie nodes with the first one.
+        let state_root_hash = source
+            .get_state()
+            .put_stored_values(correlation_id, first_root, random_values(&mut rng, 10))
+            .unwrap();
+
+        // Use small segments to exercise multiple segments.
+        let (snapshot, summary) =
+            write_snapshot(&source, state_root_hash, Vec::new(), 1024).unwrap();
+        assert_eq!(summary.state_root_hash, state_root_hash);
+        assert!(summary.trie_count > 310);
+
+        let (target, _target_dir) = new_engine_state();
+        assert_eq!(
+            import_state_snapshot(&target, snapshot.as_slice()).unwrap(),
+            summary
+        );
+        let reader = target
+            .get_state()
+            .checkout(state_root_hash)
+            .unwrap()
+            .unwrap();
+        for (key, value) in &values {
+            assert_eq!(
+                reader.read(correlation_id, key).unwrap().as_ref(),
+                Some(value)
+            );
+        }
+
+        // Importing the same snapshot again is a no-op.
+        assert_eq!(
+            import_state_snapshot(&target, snapshot.as_slice()).unwrap(),
+            summary
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_state_snapshot() {
+        let mut rng = TestRng::new();
+        let correlation_id = CorrelationId::new();
+        let (source, _source_dir) = new_engine_state();
+        let empty_root = source.get_state().empty_state_root_hash();
+        let state_root_hash = source
+            .get_state()
+            .put_stored_values(correlation_id, empty_root, random_values(&mut rng, 100))
+            .unwrap();
+        let (snapshot, _) = export_state_snapshot(&source, state_root_hash, Vec::new()).unwrap();
+
+        let missing_root = Digest::hash([1]);
+        assert!(matches!(
+            export_state_snapshot(&source, missing_root, Vec::new()),
+            Err(SnapshotError::MissingTrie(digest)) if digest == missing_root
+        ));
+
+        let (target, _target_dir) = new_engine_state();
+        let mut wrong_magic = snapshot.clone();
+        wrong_magic[0] ^= 1;
+        assert!(matches!(
+            import_state_snapshot(&target, wrong_magic.as_slice()),
+            Err(SnapshotError::NotASnapshot)
+        ));
+
+        let truncated = &snapshot[..snapshot.len() - 16];
+        assert!(import_state_snapshot(&target, truncated).is_err());
+
+        // A snapshot holding only the root node, which is missing its children.
+        let root = source
+            .get_trie_full(correlation_id, state_root_hash)
+            .unwrap()
+            .unwrap();
+        let mut writer = SnapshotWriter::new(Vec::new(), state_root_hash, SEGMENT_SIZE).unwrap();
+        writer.append(root.into_inner()).unwrap();
+        let incomplete = writer.finish().unwrap();
+        let (fresh_target, _fresh_target_dir) = new_engine_state();
+        assert!(matches!(
+            import_state_snapshot(&fresh_target, incomplete.as_slice()),
+            Err(SnapshotError::MissingChildren { index: 0, .. })
+        ));
+    }
+}
@@ -5,9 +5,12 @@ use prometheus::Registry;
 use rand::RngCore;
 use serde::Serialize;
 use tempfile::TempDir;
+use tokio::sync::oneshot;
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_types::{runtime_args, EraId, PublicKey, RuntimeArgs, SecretKey, TimeDiff, U512};
+use casper_types::{
+    runtime_args, system::mint, EraId, PublicKey, RuntimeArgs, SecretKey, TimeDiff, U512,
+};
 
 use super::*;
 use crate::{
@@ -123,6 +126,7 @@ impl reactor::Reactor for Reactor {
             chainspec.core_config.administrators.clone(),
             chainspec.core_config.allow_auction_bids,
             chainspec.core_config.allow_unrestricted_transfers,
+            chainspec.core_config.allow_secp256r1_keys,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
         )?;
@@ -233,6 +237,7 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
         .contract_runtime
         .commit_genesis(chainspec.as_ref(), chainspec_raw_bytes.as_ref())
         .unwrap()
+        .expect("pruning is disabled")
         .post_state_hash;
 
     let initial_pre_state = ExecutionPreState::new(
@@ -392,3 +397,129 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
         next_block_height
     );
 }
+
+/// Speculatively executes the deploy, waiting for the result.
+async fn speculatively_execute(
+    runner: &mut Runner<ConditionCheckReactor<Reactor>>,
+    rng: &mut NodeRng,
+    execution_prestate: SpeculativeExecutionState,
+    deploy: Arc<Deploy>,
+    with_trace: bool,
+) -> (ExecutionResult, Option<ExecutionTrace>) {
+    let (sender, receiver) = oneshot::channel();
+    runner
+        .process_injected_effects(|effect_builder| {
+            async move {
+                let result = effect_builder
+                    .speculative_execute_deploy(execution_prestate, deploy, with_trace)
+                    .await;
+                let _ = sender.send(result);
+            }
+            .ignore()
+        })
+        .await;
+    // Handle the request, then wait for the execution to finish.
+    runner.crank(rng).await;
+    tokio::time::timeout(TEST_TIMEOUT, receiver)
+        .await
+        .expect("speculative execution should finish in time")
+        .unwrap()
+        .expect("speculative execution should not fail")
+        .expect("should return an execution result")
+}
+
+#[tokio::test]
+async fn should_return_trace_of_speculative_execution() {
+    testing::init_logging();
+
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let chainspec = Arc::new(chainspec);
+    let chainspec_raw_bytes = Arc::new(chainspec_raw_bytes);
+
+    let mut rng = crate::new_rng();
+    let rng = &mut rng;
+
+    let mut runner: Runner<ConditionCheckReactor<Reactor>> = Runner::new(
+        Config::default(),
+        Arc::clone(&chainspec),
+        Arc::clone(&chainspec_raw_bytes),
+        rng,
+    )
+    .await
+    .unwrap();
+
+    let post_commit_genesis_state_hash = runner
+        .reactor()
+        .inner()
+        .contract_runtime
+        .commit_genesis(chainspec.as_ref(), chainspec_raw_bytes.as_ref())
+        .unwrap()
+        .expect("pruning is disabled")
+        .post_state_hash;
+    let execution_prestate = SpeculativeExecutionState {
+        state_root_hash: post_commit_genesis_state_hash,
+        block_time: Timestamp::now(),
+        protocol_version: chainspec.protocol_version(),
+    };
+
+    // A native transfer from node-1's main account to a new random public key.
+    let node_1_secret_key = SecretKey::from_file(
+        RESOURCES_PATH
+            .join("local")
+            .join("secret_keys")
+            .join("node-1.pem"),
+    )
+    .unwrap();
+    let payment = ExecutableDeployItem::ModuleBytes {
+        module_bytes: Bytes::new(),
+        args: runtime_args! {
+          "amount" => U512::from(chainspec.system_costs_config.wasmless_transfer_cost()),
+        },
+    };
+    let session = ExecutableDeployItem::Transfer {
+        args: runtime_args! {
+          "amount" => U512::from(chainspec.deploy_config.native_transfer_minimum_motes),
+          "target" => PublicKey::random(rng),
+          "id" => Some(9_u64),
+        },
+    };
+    let deploy = Arc::new(Deploy::new(
+        Timestamp::now(),
+        TimeDiff::from_seconds(100),
+        1,
+        vec![],
+        chainspec.network_config.name.clone(),
+        payment,
+        session,
+        &node_1_secret_key,
+        None,
+    ));
+
+    let (execution_result, maybe_trace) = speculatively_execute(
+        &mut runner,
+        rng,
+        execution_prestate.clone(),
+        Arc::clone(&deploy),
+        true,
+    )
+    .await;
+    assert!(matches!(execution_result, ExecutionResult::Success { .. }));
+    let trace = maybe_trace.expect("should return a trace when requested");
+    assert!(trace.revert.is_none());
+    assert_eq!(trace.omitted_entries, 0);
+    assert!(trace
+        .frames
+        .iter()
+        .all(|frame| frame.depth >= 1 && frame.error.is_none()));
+    assert!(trace
+        .frames
+        .iter()
+        .any(|frame| frame.entry_point == mint::METHOD_TRANSFER));
+
+    // Without tracing, the same result is returned, but no trace.
+    let (untraced_execution_result, maybe_trace) =
+        speculatively_execute(&mut runner, rng, execution_prestate, deploy, false).await;
+    assert_eq!(untraced_execution_result, execution_result);
+    assert!(maybe_trace.is_none());
+}
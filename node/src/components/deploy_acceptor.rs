@@ -895,6 +895,18 @@ impl DeployAcceptor {
         event_metadata: Box<EventMetadata>,
         verification_start_timestamp: Timestamp,
     ) -> Effects<Event> {
+        if let Err(deploy_configuration_failure) = event_metadata
+            .deploy
+            .has_allowed_key_types(self.core_config.allow_secp256r1_keys)
+        {
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                Error::InvalidDeployConfiguration(deploy_configuration_failure),
+                verification_start_timestamp,
+            );
+        }
+
         if let Err(deploy_configuration_failure) = event_metadata.deploy.is_valid() {
             // The client has submitted a deploy with one or more invalid signatures.
             // Return an error to the RPC component via the responder.
@@ -196,6 +196,9 @@ enum TestScenario {
         administrators: BTreeSet<PublicKey>,
         secret_keys: Vec<[u8; 32]>,
     },
+    FromPeerSecp256r1SignedDeploy {
+        allow_secp256r1_keys: bool,
+    },
 }
 
 impl TestScenario {
@@ -212,7 +215,8 @@ impl TestScenario {
             | TestScenario::FromPeerCustomPaymentContractPackage(_)
             | TestScenario::FromPeerSessionContract(_)
             | TestScenario::FromPeerSessionContractPackage(_)
-            | TestScenario::ShouldAcceptExpiredDeploySentByPeer => {
+            | TestScenario::ShouldAcceptExpiredDeploySentByPeer
+            | TestScenario::FromPeerSecp256r1SignedDeploy { .. } => {
                 Source::Peer(NodeId::random(rng))
             }
             TestScenario::FromClientInvalidDeploy
@@ -358,6 +362,12 @@ impl TestScenario {
 
                 deploy
             }
+            TestScenario::FromPeerSecp256r1SignedDeploy { .. } => {
+                let mut deploy = Deploy::random_valid_native_transfer(rng);
+                deploy.replace_approvals(BTreeSet::new());
+                deploy.sign(&SecretKey::random_secp256r1(rng));
+                deploy
+            }
             TestScenario::FromClientSlightlyFutureDatedDeploy => {
                 let timestamp = Timestamp::now() + (Config::default().timestamp_leeway / 2);
                 let ttl = TimeDiff::from_seconds(300);
@@ -423,7 +433,10 @@ impl TestScenario {
                 }
             }
             TestScenario::ShouldAcceptDeployFromAdministrator { .. } => true,
-            TestScenario::ShouldRejectDeployFromNonAdministrator { .. } => false
+            TestScenario::ShouldRejectDeployFromNonAdministrator { .. } => false,
+            TestScenario::FromPeerSecp256r1SignedDeploy { allow_secp256r1_keys } => {
+                *allow_secp256r1_keys
+            }
         }
     }
 
@@ -456,6 +469,21 @@ impl TestScenario {
 
                 Arc::new(chainspec)
             }
+            TestScenario::FromPeerSecp256r1SignedDeploy {
+                allow_secp256r1_keys,
+            } => {
+                let mut chainspec = Chainspec {
+                    protocol_config: chainspec.protocol_config.clone(),
+                    network_config: chainspec.network_config.clone(),
+                    core_config: chainspec.core_config.clone(),
+                    highway_config: chainspec.highway_config,
+                    deploy_config: chainspec.deploy_config,
+                    wasm_config: chainspec.wasm_config,
+                    system_costs_config: chainspec.system_costs_config,
+                };
+                chainspec.core_config.allow_secp256r1_keys = *allow_secp256r1_keys;
+                Arc::new(chainspec)
+            }
             _ => chainspec,
         }
     }
@@ -1034,6 +1062,25 @@ async fn run_deploy_acceptor_without_timeout(
                     )
                 )
             }
+            TestScenario::FromPeerSecp256r1SignedDeploy {
+                allow_secp256r1_keys,
+            } => {
+                if allow_secp256r1_keys {
+                    matches!(
+                        event,
+                        Event::DeployAcceptorAnnouncement(
+                            DeployAcceptorAnnouncement::AcceptedNewDeploy { .. }
+                        )
+                    )
+                } else {
+                    matches!(
+                        event,
+                        Event::DeployAcceptorAnnouncement(
+                            DeployAcceptorAnnouncement::InvalidDeploy { .. }
+                        )
+                    )
+                }
+            }
         }
     };
     runner
@@ -1709,3 +1756,26 @@ async fn should_reject_valid_deploy_from_peer_signed_by_non_administrator() {
     let result = run_deploy_acceptor(test_scenario).await;
     assert!(result.is_err())
 }
+
+#[tokio::test]
+async fn should_reject_secp256r1_signed_deploy_if_not_allowed() {
+    let test_scenario = TestScenario::FromPeerSecp256r1SignedDeploy {
+        allow_secp256r1_keys: false,
+    };
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::InvalidDeployConfiguration(
+            DeployConfigurationFailure::Secp256r1KeysNotAllowed
+        ))
+    ))
+}
+
+#[tokio::test]
+async fn should_accept_secp256r1_signed_deploy_if_allowed() {
+    let test_scenario = TestScenario::FromPeerSecp256r1SignedDeploy {
+        allow_secp256r1_keys: true,
+    };
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(result.is_ok())
+}
@@ -5,6 +5,7 @@ mod metrics;
 mod tests;
 
 use std::{
+    cmp::Ordering,
     collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryInto,
     iter::FromIterator,
@@ -18,7 +19,7 @@ use prometheus::Registry;
 use smallvec::smallvec;
 use tracing::{debug, error, info, warn};
 
-use casper_types::Timestamp;
+use casper_types::{PublicKey, Timestamp};
 
 use crate::{
     components::{
@@ -42,7 +43,7 @@ use crate::{
     utils::DisplayIter,
     NodeRng,
 };
-pub(crate) use config::Config;
+pub(crate) use config::{Config, ProposalPolicy};
 pub(crate) use event::Event;
 
 use metrics::Metrics;
@@ -308,10 +309,12 @@ impl DeployBuffer {
         self.register_deploys(timestamp, finalized_block.deploy_and_transfer_hashes());
     }
 
-    /// Returns eligible deploys that are buffered and not held or dead.
+    /// Returns eligible deploys that are buffered and not held or dead, ordered according to the
+    /// configured proposal policy.
     fn proposable(&self) -> Vec<(DeployHashWithApprovals, DeployFootprint)> {
         debug!("DeployBuffer: getting proposable deploys");
-        self.buffer
+        let proposable = self
+            .buffer
             .iter()
             .filter(|(dh, _)| !self.hold.values().any(|hs| hs.contains(dh)))
             .filter(|(dh, _)| !self.dead.contains(dh))
@@ -323,7 +326,11 @@ impl DeployBuffer {
                     )
                 })
             })
-            .collect()
+            .collect();
+        match self.cfg.proposal_policy {
+            ProposalPolicy::Unordered => proposable,
+            ProposalPolicy::GasPrice => order_by_gas_price(proposable),
+        }
     }
 
     /// Returns a right-sized payload of deploys that can be proposed.
@@ -332,7 +339,26 @@ impl DeployBuffer {
         let mut holds = HashSet::new();
         let mut have_hit_transfer_limit = false;
         let mut have_hit_deploy_limit = false;
-        for (with_approvals, footprint) in self.proposable() {
+        let proposable = self.proposable();
+        // only summarize the deploys left behind when proposals are ordered by gas price, so the
+        // unordered policy pays no extra cost
+        let maybe_candidates: Option<Vec<(DeployHash, u64, Timestamp)>> =
+            match self.cfg.proposal_policy {
+                ProposalPolicy::Unordered => None,
+                ProposalPolicy::GasPrice => Some(
+                    proposable
+                        .iter()
+                        .map(|(with_approvals, footprint)| {
+                            (
+                                *with_approvals.deploy_hash(),
+                                footprint.header.gas_price(),
+                                footprint.header.timestamp(),
+                            )
+                        })
+                        .collect(),
+                ),
+            };
+        for (with_approvals, footprint) in proposable {
             if footprint.is_transfer && have_hit_transfer_limit {
                 continue;
             }
@@ -344,6 +370,9 @@ impl DeployBuffer {
             match ret.add(with_approvals, &footprint) {
                 Ok(_) => {
                     debug!(%deploy_hash, "DeployBuffer: proposing deploy");
+                    self.metrics
+                        .proposed_gas_price
+                        .observe(footprint.header.gas_price() as f64);
                     holds.insert(deploy_hash);
                 }
                 Err(error) => {
@@ -413,6 +442,29 @@ impl DeployBuffer {
             }
         }
 
+        // summarize the deploys which were left behind but remain eligible for future proposals,
+        // including how long they have been waiting since their creation
+        if let Some(candidates) = maybe_candidates {
+            let mut left_behind_deploys = 0;
+            let mut max_left_behind_gas_price = 0;
+            for (deploy_hash, gas_price, deploy_timestamp) in candidates {
+                if holds.contains(&deploy_hash) || self.dead.contains(&deploy_hash) {
+                    continue;
+                }
+                left_behind_deploys += 1;
+                max_left_behind_gas_price = max_left_behind_gas_price.max(gas_price);
+                self.metrics.left_behind_gas_price.observe(gas_price as f64);
+                let age = timestamp.saturating_diff(deploy_timestamp);
+                self.metrics
+                    .left_behind_deploy_age
+                    .observe(age.millis() as f64 / 1000.0);
+            }
+            self.metrics.left_behind_deploys.set(left_behind_deploys);
+            self.metrics
+                .max_left_behind_gas_price
+                .set(i64::try_from(max_left_behind_gas_price).unwrap_or(i64::MAX));
+        }
+
         // put a hold on all proposed deploys / transfers and update metrics
         match self.hold.entry(timestamp) {
             btree_map::Entry::Vacant(entry) => {
@@ -459,6 +511,53 @@ impl DeployBuffer {
     }
 }
 
+/// Orders the given deploys for proposal by gas price.
+///
+/// Each account's deploys are sorted by descending gas price, then by ascending timestamp.  The
+/// first deploy of every account forms the first round, the second deploy of every account the
+/// second round, and so on.  Rounds are returned in order, with the deploys within each round
+/// sorted the same way as those of a single account.
+fn order_by_gas_price(
+    proposable: Vec<(DeployHashWithApprovals, DeployFootprint)>,
+) -> Vec<(DeployHashWithApprovals, DeployFootprint)> {
+    fn priority(
+        (lhs_deploy, lhs_footprint): &(DeployHashWithApprovals, DeployFootprint),
+        (rhs_deploy, rhs_footprint): &(DeployHashWithApprovals, DeployFootprint),
+    ) -> Ordering {
+        rhs_footprint
+            .header
+            .gas_price()
+            .cmp(&lhs_footprint.header.gas_price())
+            .then_with(|| {
+                lhs_footprint
+                    .header
+                    .timestamp()
+                    .cmp(&rhs_footprint.header.timestamp())
+            })
+            .then_with(|| lhs_deploy.deploy_hash().cmp(rhs_deploy.deploy_hash()))
+    }
+
+    let mut by_account: BTreeMap<PublicKey, Vec<_>> = BTreeMap::new();
+    for (with_approvals, footprint) in proposable {
+        by_account
+            .entry(footprint.header.account().clone())
+            .or_default()
+            .push((with_approvals, footprint));
+    }
+
+    let mut ranked: Vec<(usize, _)> = by_account
+        .into_values()
+        .flat_map(|mut deploys| {
+            deploys.sort_by(priority);
+            deploys.into_iter().enumerate()
+        })
+        .collect();
+    ranked.sort_by(|(lhs_round, lhs), (rhs_round, rhs)| {
+        lhs_round.cmp(rhs_round).then_with(|| priority(lhs, rhs))
+    });
+    ranked.into_iter().map(|(_, deploy)| deploy).collect()
+}
+
 impl<REv> InitializedComponent<REv> for DeployBuffer
 where
     REv: From<Event> + From<DeployBufferAnnouncement> + From<StorageRequest> + Send + 'static,
@@ -5,11 +5,30 @@ use casper_types::TimeDiff;
 
 const DEFAULT_EXPIRY_CHECK_INTERVAL: &str = "1min";
 
+/// The policy used to order buffered deploys when filling a proposed block.
+#[derive(Copy, Clone, DataSize, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalPolicy {
+    /// Deploys are proposed in no particular order.
+    #[default]
+    Unordered,
+    /// Deploys are proposed in descending order of gas price, with older deploys preferred where
+    /// gas prices are equal.
+    ///
+    /// To avoid a single account crowding out all others, deploys are proposed in rounds: each
+    /// round contains at most one deploy per account, and a round is only started once all
+    /// deploys of the previous round have been considered.
+    GasPrice,
+}
+
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
     /// The interval of checking for expired deploys.
     pub expiry_check_interval: TimeDiff,
+    /// The policy used to order deploys when proposing a block.
+    #[serde(default)]
+    pub proposal_policy: ProposalPolicy,
 }
 
 impl Config {
@@ -23,6 +42,7 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             expiry_check_interval: DEFAULT_EXPIRY_CHECK_INTERVAL.parse().unwrap(),
+            proposal_policy: ProposalPolicy::default(),
         }
     }
 }
@@ -1,6 +1,31 @@
-use prometheus::{IntGauge, Registry};
+use prometheus::{Histogram, IntGauge, Registry};
 
-use crate::unregister_metric;
+use crate::{unregister_metric, utils};
+
+const PROPOSED_GAS_PRICE_NAME: &str = "deploy_buffer_proposed_gas_price";
+const PROPOSED_GAS_PRICE_HELP: &str = "gas price of deploys included in proposed blocks.";
+const LEFT_BEHIND_GAS_PRICE_NAME: &str = "deploy_buffer_left_behind_gas_price";
+const LEFT_BEHIND_GAS_PRICE_HELP: &str =
+    "gas price of proposable deploys not included in proposed blocks.";
+const LEFT_BEHIND_DEPLOY_AGE_NAME: &str = "deploy_buffer_left_behind_deploy_age";
+const LEFT_BEHIND_DEPLOY_AGE_HELP: &str =
+    "age in seconds of proposable deploys not included in proposed blocks.";
+
+/// Value of upper bound of the first bucket.
+const EXPONENTIAL_BUCKET_START: f64 = 1.0;
+
+/// Multiplier of previous upper bound for next bound.
+const EXPONENTIAL_BUCKET_FACTOR: f64 = 2.0;
+
+/// Bucket count, with the last bucket going to +Inf which will not be included in the results.
+const EXPONENTIAL_BUCKET_COUNT: usize = 12;
+
+/// Value of upper bound of the first bucket of deploy ages, in seconds.
+const AGE_BUCKET_START: f64 = 5.0;
+
+/// Bucket count of deploy ages, with the last finite bound at about 23 hours, close to the
+/// maximum TTL of a day.
+const AGE_BUCKET_COUNT: usize = 15;
 
 /// Metrics for the deploy_buffer component.
 #[derive(Debug)]
@@ -11,6 +36,17 @@ pub(super) struct Metrics {
     pub(super) held_deploys: IntGauge,
     /// Number of deploys that should not be included in future proposals ever again.
     pub(super) dead_deploys: IntGauge,
+    /// Gas prices of deploys included in proposed blocks.
+    pub(super) proposed_gas_price: Histogram,
+    /// Gas prices of the proposable deploys not included in each proposed block.
+    pub(super) left_behind_gas_price: Histogram,
+    /// Number of proposable deploys not included in the most recently proposed block.
+    pub(super) left_behind_deploys: IntGauge,
+    /// Highest gas price of the proposable deploys not included in the most recently proposed
+    /// block.
+    pub(super) max_left_behind_gas_price: IntGauge,
+    /// Ages of the proposable deploys not included in each proposed block, in seconds.
+    pub(super) left_behind_deploy_age: Histogram,
     registry: Registry,
 }
 
@@ -29,15 +65,62 @@ impl Metrics {
             "deploy_buffer_dead_deploys".to_string(),
             "number of deploys that should not be included in future proposals.".to_string(),
         )?;
+        let left_behind_deploys = IntGauge::new(
+            "deploy_buffer_left_behind_deploys".to_string(),
+            "number of proposable deploys not included in the most recently proposed block."
+                .to_string(),
+        )?;
+        let max_left_behind_gas_price = IntGauge::new(
+            "deploy_buffer_max_left_behind_gas_price".to_string(),
+            "highest gas price of proposable deploys not included in the most recently proposed \
+            block."
+                .to_string(),
+        )?;
 
         registry.register(Box::new(total_deploys.clone()))?;
         registry.register(Box::new(held_deploys.clone()))?;
         registry.register(Box::new(dead_deploys.clone()))?;
+        registry.register(Box::new(left_behind_deploys.clone()))?;
+        registry.register(Box::new(max_left_behind_gas_price.clone()))?;
+
+        let gas_price_buckets = prometheus::exponential_buckets(
+            EXPONENTIAL_BUCKET_START,
+            EXPONENTIAL_BUCKET_FACTOR,
+            EXPONENTIAL_BUCKET_COUNT,
+        )?;
+        let proposed_gas_price = utils::register_histogram_metric(
+            registry,
+            PROPOSED_GAS_PRICE_NAME,
+            PROPOSED_GAS_PRICE_HELP,
+            gas_price_buckets.clone(),
+        )?;
+        let left_behind_gas_price = utils::register_histogram_metric(
+            registry,
+            LEFT_BEHIND_GAS_PRICE_NAME,
+            LEFT_BEHIND_GAS_PRICE_HELP,
+            gas_price_buckets,
+        )?;
+        let age_buckets = prometheus::exponential_buckets(
+            AGE_BUCKET_START,
+            EXPONENTIAL_BUCKET_FACTOR,
+            AGE_BUCKET_COUNT,
+        )?;
+        let left_behind_deploy_age = utils::register_histogram_metric(
+            registry,
+            LEFT_BEHIND_DEPLOY_AGE_NAME,
+            LEFT_BEHIND_DEPLOY_AGE_HELP,
+            age_buckets,
+        )?;
 
         Ok(Metrics {
             total_deploys,
             held_deploys,
             dead_deploys,
+            proposed_gas_price,
+            left_behind_gas_price,
+            left_behind_deploys,
+            max_left_behind_gas_price,
+            left_behind_deploy_age,
             registry: registry.clone(),
         })
     }
@@ -48,5 +131,10 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.total_deploys);
         unregister_metric!(self.registry, self.held_deploys);
         unregister_metric!(self.registry, self.dead_deploys);
+        unregister_metric!(self.registry, self.proposed_gas_price);
+        unregister_metric!(self.registry, self.left_behind_gas_price);
+        unregister_metric!(self.registry, self.left_behind_deploys);
+        unregister_metric!(self.registry, self.max_left_behind_gas_price);
+        unregister_metric!(self.registry, self.left_behind_deploy_age);
     }
 }
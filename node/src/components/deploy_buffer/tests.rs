@@ -5,7 +5,7 @@ use crate::{
     types::{Block, FinalizedBlock},
     utils,
 };
-use casper_types::{testing::TestRng, EraId, TimeDiff};
+use casper_types::{testing::TestRng, EraId, SecretKey, TimeDiff};
 use prometheus::Registry;
 use rand::Rng;
 
@@ -73,6 +73,31 @@ fn create_invalid_deploys(rng: &mut TestRng, size: usize) -> Vec<Deploy> {
     deploys
 }
 
+// Generates a valid native transfer from the account of `secret_key`, with the given gas price.
+fn create_transfer_with_gas_price(
+    rng: &mut TestRng,
+    secret_key: &SecretKey,
+    gas_price: u64,
+    timestamp: Timestamp,
+) -> Deploy {
+    let deploy = Deploy::random_valid_native_transfer_with_timestamp_and_ttl(
+        rng,
+        timestamp,
+        TimeDiff::from_seconds(100),
+    );
+    Deploy::new(
+        timestamp,
+        deploy.header().ttl(),
+        gas_price,
+        vec![],
+        deploy.header().chain_name().to_string(),
+        deploy.payment().clone(),
+        deploy.session().clone(),
+        secret_key,
+        None,
+    )
+}
+
 /// Checks sizes of the deploy_buffer containers. Also checks the metrics recorded.
 #[track_caller]
 fn assert_container_sizes(
@@ -307,6 +332,172 @@ fn get_appendable_block(
     );
 }
 
+#[test]
+fn get_appendable_block_ordered_by_gas_price() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_transfer_count: 5,
+        ..Default::default()
+    };
+    let config = Config {
+        proposal_policy: ProposalPolicy::GasPrice,
+        ..Default::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+
+    // populate the buffer with transfers from distinct accounts with gas prices 1 to 10
+    let now = Timestamp::now();
+    let deploys: Vec<_> = (1..=10)
+        .map(|gas_price| {
+            let secret_key = SecretKey::random(&mut rng);
+            create_transfer_with_gas_price(&mut rng, &secret_key, gas_price, now)
+        })
+        .collect();
+    deploys
+        .iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+
+    // the five most expensive transfers should be proposed, in descending order of gas price
+    let proposable = deploy_buffer.proposable();
+    let gas_prices: Vec<_> = proposable
+        .iter()
+        .map(|(_, footprint)| footprint.header.gas_price())
+        .collect();
+    assert_eq!(gas_prices, (1..=10).rev().collect::<Vec<_>>());
+
+    // propose a minute after the deploys were created
+    let appendable_block = deploy_buffer.appendable_block(now + TimeDiff::from_seconds(60));
+    let proposed = appendable_block.deploy_and_transfer_set();
+    assert_eq!(proposed.len(), 5);
+    for deploy in &deploys {
+        assert_eq!(
+            proposed.contains(deploy.hash()),
+            deploy.header().gas_price() > 5
+        );
+    }
+
+    assert_eq!(
+        deploy_buffer.metrics.proposed_gas_price.get_sample_count(),
+        5
+    );
+    assert_eq!(
+        deploy_buffer.metrics.proposed_gas_price.get_sample_sum(),
+        40.0
+    );
+    assert_eq!(
+        deploy_buffer
+            .metrics
+            .left_behind_gas_price
+            .get_sample_count(),
+        5
+    );
+    assert_eq!(
+        deploy_buffer.metrics.left_behind_gas_price.get_sample_sum(),
+        15.0
+    );
+    assert_eq!(deploy_buffer.metrics.left_behind_deploys.get(), 5);
+    assert_eq!(deploy_buffer.metrics.max_left_behind_gas_price.get(), 5);
+    assert_eq!(
+        deploy_buffer
+            .metrics
+            .left_behind_deploy_age
+            .get_sample_count(),
+        5
+    );
+    assert_eq!(
+        deploy_buffer
+            .metrics
+            .left_behind_deploy_age
+            .get_sample_sum(),
+        300.0
+    );
+}
+
+#[test]
+fn get_appendable_block_unordered_should_not_record_left_behind_deploys() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_transfer_count: 5,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+
+    let now = Timestamp::now();
+    for gas_price in 1..=10 {
+        let secret_key = SecretKey::random(&mut rng);
+        let deploy = create_transfer_with_gas_price(&mut rng, &secret_key, gas_price, now);
+        deploy_buffer.register_deploy(deploy);
+    }
+
+    let appendable_block = deploy_buffer.appendable_block(now + TimeDiff::from_seconds(60));
+    assert_eq!(appendable_block.deploy_and_transfer_set().len(), 5);
+
+    assert_eq!(
+        deploy_buffer.metrics.proposed_gas_price.get_sample_count(),
+        5
+    );
+    assert_eq!(
+        deploy_buffer
+            .metrics
+            .left_behind_gas_price
+            .get_sample_count(),
+        0
+    );
+    assert_eq!(deploy_buffer.metrics.left_behind_deploys.get(), 0);
+    assert_eq!(
+        deploy_buffer
+            .metrics
+            .left_behind_deploy_age
+            .get_sample_count(),
+        0
+    );
+}
+
+#[test]
+fn get_appendable_block_ordered_by_gas_price_is_fair_across_accounts() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_transfer_count: 3,
+        ..Default::default()
+    };
+    let config = Config {
+        proposal_policy: ProposalPolicy::GasPrice,
+        ..Default::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+
+    // one account submits many expensive transfers, each a little later than the previous
+    let now = Timestamp::now();
+    let busy_account = SecretKey::random(&mut rng);
+    let busy_deploys: Vec<_> = (0..10)
+        .map(|age| {
+            let timestamp = now - TimeDiff::from_millis(10 - age);
+            create_transfer_with_gas_price(&mut rng, &busy_account, 10, timestamp)
+        })
+        .collect();
+    // two other accounts submit a single cheap transfer each
+    let other_deploys: Vec<_> = (1..=2)
+        .map(|gas_price| {
+            let secret_key = SecretKey::random(&mut rng);
+            create_transfer_with_gas_price(&mut rng, &secret_key, gas_price, now)
+        })
+        .collect();
+    busy_deploys
+        .iter()
+        .chain(other_deploys.iter())
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+
+    // the oldest transfer of the busy account should be proposed alongside the cheap transfers
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    let proposed = appendable_block.deploy_and_transfer_set();
+    assert_eq!(proposed.len(), 3);
+    assert!(proposed.contains(busy_deploys[0].hash()));
+    for deploy in &other_deploys {
+        assert!(proposed.contains(deploy.hash()));
+    }
+}
+
 #[test]
 fn register_deploys_and_blocks() {
     let mut rng = TestRng::new();
@@ -7,6 +7,7 @@ mod command;
 mod stop_at;
 mod tasks;
 mod util;
+mod watchdog;
 
 use std::{
     fmt::{self, Display, Formatter},
@@ -25,7 +26,7 @@ use crate::{
     effect::{
         announcements::ControlAnnouncement,
         diagnostics_port::DumpConsensusStateRequest,
-        requests::{NetworkInfoRequest, SetNodeStopRequest},
+        requests::{BlocklistRequest, NetworkInfoRequest, SetNodeStopRequest},
         EffectBuilder, EffectExt, Effects,
     },
     reactor::main_reactor::MainEvent,
@@ -36,6 +37,8 @@ use crate::{
 pub(crate) use stop_at::StopAtSpec;
 pub use tasks::FileSerializer;
 use util::ShowUnixAddr;
+pub(crate) use watchdog::Watchdog;
+pub use watchdog::WatchdogConfig;
 
 const COMPONENT_NAME: &str = "diagnostics_port";
 
@@ -48,6 +51,9 @@ pub struct Config {
     pub socket_path: PathBuf,
     /// `umask` to apply before creating the socket.
     pub socket_umask: u16,
+    /// Watchdog writing diagnostics snapshots when thresholds are exceeded.
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
 }
 
 impl Default for Config {
@@ -56,6 +62,7 @@ impl Default for Config {
             enabled: false,
             socket_path: "debug.socket".into(),
             socket_umask: 0o077,
+            watchdog: WatchdogConfig::default(),
         }
     }
 }
@@ -107,6 +114,7 @@ where
         + From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
         + From<NetworkInfoRequest>
+        + From<BlocklistRequest>
         + From<SetNodeStopRequest>
         + Send,
 {
@@ -161,6 +169,7 @@ where
         + From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
         + From<NetworkInfoRequest>
+        + From<BlocklistRequest>
         + From<SetNodeStopRequest>
         + Send,
 {
@@ -185,6 +194,7 @@ where
         + From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
         + From<NetworkInfoRequest>
+        + From<BlocklistRequest>
         + From<SetNodeStopRequest>
         + Send,
 {
@@ -8,6 +8,7 @@ use structopt::StructOpt;
 use thiserror::Error;
 
 use super::StopAtSpec;
+use crate::components::network::blocklist::BlockedPeer;
 
 /// Command processing error.
 ///
@@ -91,6 +92,23 @@ pub(super) enum Action {
     DumpQueues,
     /// Get detailed networking insights.
     NetInfo,
+    /// Block a peer until it is unblocked again.
+    ///
+    /// Any current connection to the peer is dropped, and new ones are refused.  If the network
+    /// option `persist_blocklist` is set, the peer remains blocked across restarts.
+    BlockPeer {
+        /// The peer to block, given as an IP address or full hex-encoded node ID.
+        peer: BlockedPeer,
+    },
+    /// Unblock a peer previously blocked via `block-peer`.
+    ///
+    /// Peers on the static deny-list given in the config cannot be unblocked.
+    UnblockPeer {
+        /// The peer to unblock, given as an IP address or full hex-encoded node ID.
+        peer: BlockedPeer,
+    },
+    /// List the peers blocked by the operator, along with all currently blocked addresses.
+    ListBlocked,
     /// Stop the node at a certain condition.
     Stop {
         /// When to stop the node.
@@ -153,7 +171,10 @@ impl Command {
 
 #[cfg(test)]
 mod tests {
-    use crate::components::diagnostics_port::command::{Action, Command};
+    use crate::components::{
+        diagnostics_port::command::{Action, Command},
+        network::blocklist::BlockedPeer,
+    };
 
     #[test]
     fn can_parse_simple_commands() {
@@ -162,5 +183,16 @@ mod tests {
 
         let cmd = Command::from_line("dump-queues").expect("command parsing failed");
         assert!(matches!(cmd.action, Action::DumpQueues));
+
+        let cmd = Command::from_line("block-peer 10.0.0.1").expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::BlockPeer {
+                peer: BlockedPeer::Ip(_)
+            }
+        ));
+
+        let cmd = Command::from_line("list-blocked").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::ListBlocked));
     }
 }
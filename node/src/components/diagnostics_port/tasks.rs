@@ -34,7 +34,7 @@ use crate::{
     effect::{
         announcements::{ControlAnnouncement, QueueDumpFormat},
         diagnostics_port::DumpConsensusStateRequest,
-        requests::{NetworkInfoRequest, SetNodeStopRequest},
+        requests::{BlocklistRequest, NetworkInfoRequest, SetNodeStopRequest},
         EffectBuilder,
     },
     failpoints::FailpointActivation,
@@ -203,6 +203,7 @@ impl Session {
         REv: From<DumpConsensusStateRequest>
             + From<ControlAnnouncement>
             + From<NetworkInfoRequest>
+            + From<BlocklistRequest>
             + From<SetNodeStopRequest>
             + Send,
     {
@@ -324,6 +325,62 @@ impl Session {
                         let insights = effect_builder.get_network_insights().await;
                         self.send_to_client(writer, &insights).await?;
                     }
+                    Action::BlockPeer { peer } => {
+                        match effect_builder.block_peer_by_operator(peer).await {
+                            Ok(true) => {
+                                self.send_outcome(writer, &Outcome::success("peer blocked"))
+                                    .await?;
+                            }
+                            Ok(false) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::success("peer already blocked"),
+                                )
+                                .await?;
+                            }
+                            Err(ref err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!(
+                                        "failed to block peer: {}",
+                                        display_error(err)
+                                    )),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::UnblockPeer { peer } => {
+                        match effect_builder.unblock_peer_by_operator(peer).await {
+                            Ok(true) => {
+                                self.send_outcome(writer, &Outcome::success("peer unblocked"))
+                                    .await?;
+                            }
+                            Ok(false) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::success("peer was not blocked by operator"),
+                                )
+                                .await?;
+                            }
+                            Err(ref err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!(
+                                        "failed to unblock peer: {}",
+                                        display_error(err)
+                                    )),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::ListBlocked => {
+                        self.send_outcome(writer, &Outcome::success("listing blocked peers"))
+                            .await?;
+                        let blocklist = effect_builder.list_blocked_peers().await;
+                        self.send_to_client(writer, &blocklist).await?;
+                    }
                     Action::Stop { at, clear } => {
                         let (msg, stop_at) = if clear {
                             ("clearing stopping point", None)
@@ -502,6 +559,7 @@ where
     REv: From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
         + From<NetworkInfoRequest>
+        + From<BlocklistRequest>
         + From<SetNodeStopRequest>
         + Send,
 {
@@ -546,6 +604,7 @@ pub(super) async fn server<REv>(
     REv: From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
         + From<NetworkInfoRequest>
+        + From<BlocklistRequest>
         + From<SetNodeStopRequest>
         + Send,
 {
@@ -626,7 +685,7 @@ mod tests {
         effect::{
             announcements::ControlAnnouncement,
             diagnostics_port::DumpConsensusStateRequest,
-            requests::{NetworkInfoRequest, SetNodeStopRequest},
+            requests::{BlocklistRequest, NetworkInfoRequest, SetNodeStopRequest},
             EffectBuilder, EffectExt, Effects,
         },
         reactor::{
@@ -656,6 +715,7 @@ mod tests {
                     enabled: true,
                     socket_path: format!("node_{}.socket", idx).into(),
                     socket_umask: 0o022,
+                    watchdog: Default::default(),
                 },
             }
         }
@@ -685,6 +745,8 @@ mod tests {
         #[from]
         NetworkInfoRequest(NetworkInfoRequest),
         #[from]
+        BlocklistRequest(BlocklistRequest),
+        #[from]
         SetNodeStopRequest(SetNodeStopRequest),
     }
 
@@ -732,7 +794,8 @@ mod tests {
                 Event::DumpConsensusStateRequest(_)
                 | Event::SetNodeStopRequest(_)
                 | Event::ControlAnnouncement(_)
-                | Event::NetworkInfoRequest(_) => {
+                | Event::NetworkInfoRequest(_)
+                | Event::BlocklistRequest(_) => {
                     panic!("unexpected: {}", event)
                 }
             }
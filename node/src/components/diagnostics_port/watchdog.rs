@@ -0,0 +1,264 @@
+//! Diagnostics watchdog.
+//!
+//! Whenever the main reactor updates its metrics, the watchdog compares the total event queue
+//! length and the estimated heap size of every component against the configured thresholds. If one
+//! of them is exceeded, it writes the sizes and a debug dump of the event queues to a timestamped
+//! file, so that transient memory blowups can be diagnosed after the fact.
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use casper_types::{TimeDiff, Timestamp};
+
+use crate::{
+    effect::{
+        announcements::{ControlAnnouncement, QueueDumpFormat},
+        EffectBuilder,
+    },
+    reactor::{EventQueueHandle, QueueKind},
+};
+
+/// The name of the subdirectory of the storage directory the snapshots are written to.
+const SNAPSHOT_DIR_NAME: &str = "diagnostics";
+
+/// Configuration of the diagnostics watchdog.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields, default)]
+pub struct WatchdogConfig {
+    /// Total number of queued events above which a snapshot is written. Disabled if 0.
+    pub max_total_queue_length: usize,
+    /// Estimated heap size of a single component in bytes above which a snapshot is written.
+    /// Disabled if 0.
+    pub max_component_heap_size: u64,
+    /// Minimum time between two snapshots.
+    pub min_interval: TimeDiff,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        WatchdogConfig {
+            max_total_queue_length: 0,
+            max_component_heap_size: 0,
+            min_interval: TimeDiff::from_seconds(600),
+        }
+    }
+}
+
+/// Writes a snapshot of the event queues and memory usage if a threshold is exceeded.
+#[derive(DataSize, Debug)]
+pub(crate) struct Watchdog {
+    config: WatchdogConfig,
+    /// The directory the snapshots are written to.
+    dir: PathBuf,
+    /// When the last snapshot was written.
+    #[data_size(skip)]
+    last_snapshot: Option<Instant>,
+}
+
+impl Watchdog {
+    /// Creates a new watchdog writing its snapshots to a subdirectory of `storage_dir`.
+    pub(crate) fn new(config: WatchdogConfig, storage_dir: PathBuf) -> Self {
+        Watchdog {
+            config,
+            dir: storage_dir.join(SNAPSHOT_DIR_NAME),
+            last_snapshot: None,
+        }
+    }
+
+    /// Checks the current queue lengths and component heap sizes against the thresholds, and
+    /// writes a snapshot if one of them is exceeded and the last snapshot is old enough.
+    ///
+    /// Must be called from within a tokio runtime: the queue dump is requested asynchronously.
+    pub(crate) fn check<REv>(
+        &mut self,
+        event_queue: EventQueueHandle<REv>,
+        heap_sizes: &[(&'static str, i64)],
+    ) where
+        REv: From<ControlAnnouncement> + Send,
+    {
+        let queue_counts = event_queue.event_queues_counts();
+        let now = Instant::now();
+        let reason = match self.trigger_reason(&queue_counts, heap_sizes, now) {
+            Some(reason) => reason,
+            None => return,
+        };
+        self.last_snapshot = Some(now);
+
+        let path = self
+            .dir
+            .join(format!("snapshot-{}.txt", Timestamp::now()).replace(':', "-"));
+        warn!(%reason, path = %path.display(), "diagnostics watchdog writing snapshot");
+        let file = match self.write_header(&path, &reason, &queue_counts, heap_sizes) {
+            Ok(file) => file,
+            Err(err) => {
+                warn!(%err, path = %path.display(), "failed to write diagnostics snapshot");
+                return;
+            }
+        };
+
+        let effect_builder = EffectBuilder::new(event_queue);
+        tokio::spawn(async move {
+            effect_builder
+                .diagnostics_port_dump_queue(QueueDumpFormat::debug(file))
+                .await;
+            info!(path = %path.display(), "diagnostics watchdog finished writing snapshot");
+        });
+    }
+
+    /// Returns a description of the exceeded thresholds, or `None` if none is exceeded or the
+    /// previous snapshot was written less than `min_interval` before `now`.
+    fn trigger_reason(
+        &self,
+        queue_counts: &HashMap<QueueKind, usize>,
+        heap_sizes: &[(&'static str, i64)],
+        now: Instant,
+    ) -> Option<String> {
+        if let Some(last_snapshot) = self.last_snapshot {
+            if now.saturating_duration_since(last_snapshot)
+                < Duration::from(self.config.min_interval)
+            {
+                return None;
+            }
+        }
+
+        let mut reasons = vec![];
+        let total_queue_length: usize = queue_counts.values().sum();
+        let max_queue_length = self.config.max_total_queue_length;
+        if max_queue_length != 0 && total_queue_length > max_queue_length {
+            reasons.push(format!(
+                "total queue length {} exceeds {}",
+                total_queue_length, max_queue_length
+            ));
+        }
+        let max_heap_size = self.config.max_component_heap_size;
+        if max_heap_size != 0 {
+            for (component, heap_size) in heap_sizes {
+                if u64::try_from(*heap_size).unwrap_or_default() > max_heap_size {
+                    reasons.push(format!(
+                        "{} heap size of {} bytes exceeds {}",
+                        component, heap_size, max_heap_size
+                    ));
+                }
+            }
+        }
+
+        if reasons.is_empty() {
+            None
+        } else {
+            Some(reasons.join("; "))
+        }
+    }
+
+    /// Creates the snapshot file and writes the trigger reason, the queue lengths and the heap
+    /// sizes, largest first. Returns the file so the queue dump can be appended.
+    fn write_header(
+        &self,
+        path: &Path,
+        reason: &str,
+        queue_counts: &HashMap<QueueKind, usize>,
+        heap_sizes: &[(&'static str, i64)],
+    ) -> io::Result<File> {
+        let mut header = String::new();
+        let _ = writeln!(header, "reason: {}", reason);
+
+        let mut queue_counts: Vec<_> = queue_counts.iter().collect();
+        queue_counts.sort_unstable_by(|(kind0, count0), (kind1, count1)| {
+            count1.cmp(count0).then(kind0.cmp(kind1))
+        });
+        let _ = writeln!(
+            header,
+            "\nqueue lengths (total {}):",
+            queue_counts.iter().map(|(_, count)| **count).sum::<usize>()
+        );
+        for (kind, count) in queue_counts {
+            let _ = writeln!(header, "  {}: {}", kind, count);
+        }
+
+        let mut heap_sizes = heap_sizes.to_vec();
+        heap_sizes.sort_unstable_by(|(name0, size0), (name1, size1)| {
+            size1.cmp(size0).then(name0.cmp(name1))
+        });
+        let _ = writeln!(
+            header,
+            "\nestimated heap sizes in bytes (total {}):",
+            heap_sizes.iter().map(|(_, size)| size).sum::<i64>()
+        );
+        for (component, size) in heap_sizes {
+            let _ = writeln!(header, "  {}: {}", component, size);
+        }
+        let _ = writeln!(header, "\nqueue dump:");
+
+        fs::create_dir_all(&self.dir)?;
+        let mut file = File::create(path)?;
+        file.write_all(header.as_bytes())?;
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchdog(max_total_queue_length: usize, max_component_heap_size: u64) -> Watchdog {
+        let config = WatchdogConfig {
+            max_total_queue_length,
+            max_component_heap_size,
+            min_interval: TimeDiff::from_seconds(60),
+        };
+        Watchdog::new(config, PathBuf::from("storage"))
+    }
+
+    #[test]
+    fn should_trigger_only_above_thresholds() {
+        let queue_counts: HashMap<_, _> = [(QueueKind::Control, 3), (QueueKind::Regular, 5)]
+            .into_iter()
+            .collect();
+        let heap_sizes = [("storage", 1_000), ("consensus", 2_000)];
+        let now = Instant::now();
+
+        // Both thresholds disabled.
+        assert!(watchdog(0, 0)
+            .trigger_reason(&queue_counts, &heap_sizes, now)
+            .is_none());
+        // Neither threshold exceeded.
+        assert!(watchdog(8, 2_000)
+            .trigger_reason(&queue_counts, &heap_sizes, now)
+            .is_none());
+
+        let reason = watchdog(7, 0)
+            .trigger_reason(&queue_counts, &heap_sizes, now)
+            .unwrap();
+        assert_eq!(reason, "total queue length 8 exceeds 7");
+
+        let reason = watchdog(0, 1_999)
+            .trigger_reason(&queue_counts, &heap_sizes, now)
+            .unwrap();
+        assert_eq!(reason, "consensus heap size of 2000 bytes exceeds 1999");
+    }
+
+    #[test]
+    fn should_rate_limit_snapshots() {
+        let queue_counts: HashMap<_, _> = [(QueueKind::Regular, 10)].into_iter().collect();
+        let mut watchdog = watchdog(1, 0);
+        let start = Instant::now();
+        watchdog.last_snapshot = Some(start);
+
+        assert!(watchdog
+            .trigger_reason(&queue_counts, &[], start + Duration::from_secs(59))
+            .is_none());
+        assert!(watchdog
+            .trigger_reason(&queue_counts, &[], start + Duration::from_secs(60))
+            .is_some());
+    }
+}
@@ -16,18 +16,25 @@
 //! This component uses a ring buffer for outbound events providing some robustness against
 //! unintended subscriber disconnects, if a disconnected subscriber re-subscribes before the buffer
 //! has advanced past their last received event.
+//!
+//! Optionally, all events are also written to an on-disk journal, bounded by size and age, allowing
+//! subscribers to re-subscribe from events which have been evicted from the ring buffer, including
+//! across node restarts.
 
 mod config;
 mod event;
 mod event_indexer;
+mod event_journal;
 mod http_server;
+mod metrics;
 mod sse_server;
 #[cfg(test)]
 mod tests;
 
-use std::{fmt::Debug, net::SocketAddr, path::PathBuf};
+use std::{fmt::Debug, net::SocketAddr, path::PathBuf, sync::Arc};
 
 use datasize::DataSize;
+use prometheus::Registry;
 use tokio::sync::{
     mpsc::{self, UnboundedSender},
     oneshot,
@@ -49,6 +56,8 @@ use crate::{
 pub use config::Config;
 pub(crate) use event::Event;
 use event_indexer::{EventIndex, EventIndexer};
+use event_journal::EventJournal;
+use metrics::Metrics;
 use sse_server::ChannelsAndFilter;
 pub(crate) use sse_server::SseData;
 
@@ -87,17 +96,45 @@ pub(crate) struct EventStreamServer {
     storage_path: PathBuf,
     api_version: ProtocolVersion,
     sse_server: Option<InnerServer>,
+    #[data_size(skip)]
+    metrics: Arc<Metrics>,
 }
 
 impl EventStreamServer {
-    pub(crate) fn new(config: Config, storage_path: PathBuf, api_version: ProtocolVersion) -> Self {
-        EventStreamServer {
+    pub(crate) fn new(
+        config: Config,
+        storage_path: PathBuf,
+        api_version: ProtocolVersion,
+        registry: &Registry,
+    ) -> Result<Self, prometheus::Error> {
+        Ok(EventStreamServer {
             state: ComponentState::Uninitialized,
             config,
             storage_path,
             api_version,
             sse_server: None,
+            metrics: Arc::new(Metrics::new(registry)?),
+        })
+    }
+
+    /// Opens the on-disk event journal if it is enabled in the config.
+    ///
+    /// Failure to open the journal is not fatal; the server simply runs without it.
+    fn open_journal(&self, next_index: EventIndex) -> Option<EventJournal> {
+        if self.config.event_journal_max_bytes == 0 {
+            return None;
         }
+        EventJournal::open(
+            &self.storage_path,
+            self.config.event_journal_max_bytes,
+            self.config.event_journal_max_age,
+            next_index,
+            Arc::clone(&self.metrics),
+        )
+        .map_err(|error| {
+            warn!(%error, "failed to open sse journal: continuing without it");
+        })
+        .ok()
     }
 
     fn listen(&mut self) -> Result<(), ListeningError> {
@@ -128,6 +165,9 @@ impl EventStreamServer {
 
         let (sse_data_sender, sse_data_receiver) = mpsc::unbounded_channel();
 
+        let event_indexer = EventIndexer::new(self.storage_path.clone());
+        let journal = self.open_journal(event_indexer.current_index());
+
         let listening_address = match self.config.cors_origin.as_str() {
             "" => {
                 let (listening_address, server_with_shutdown) = warp::serve(sse_filter)
@@ -147,6 +187,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    journal,
                 ));
                 listening_address
             }
@@ -169,6 +210,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    journal,
                 ));
                 listening_address
             }
@@ -192,6 +234,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    journal,
                 ));
                 listening_address
             }
@@ -199,8 +242,6 @@ impl EventStreamServer {
 
         info!(address=%listening_address, "started event stream server");
 
-        let event_indexer = EventIndexer::new(self.storage_path.clone());
-
         self.sse_server = Some(InnerServer {
             sse_data_sender,
             event_indexer,
@@ -312,10 +353,12 @@ where
                     era_id,
                     public_key,
                     timestamp,
+                    evidence,
                 } => self.broadcast(SseData::Fault {
                     era_id,
                     public_key,
                     timestamp,
+                    evidence: evidence.filter(|_| self.config.include_fault_evidence),
                 }),
                 Event::FinalitySignature(fs) => self.broadcast(SseData::FinalitySignature(fs)),
                 Event::Step {
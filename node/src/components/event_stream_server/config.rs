@@ -1,6 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use casper_types::TimeDiff;
+
 /// Default binding address for the SSE HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
@@ -12,6 +14,15 @@ const DEFAULT_EVENT_STREAM_BUFFER_LENGTH: u32 = 5000;
 /// Default maximum number of subscribers.
 const DEFAULT_MAX_CONCURRENT_SUBSCRIBERS: u32 = 100;
 
+/// Default maximum size in bytes of the on-disk event journal.  Zero disables the journal.
+const DEFAULT_EVENT_JOURNAL_MAX_BYTES: u64 = 0;
+
+/// Default maximum age of events retained in the on-disk event journal (one day).
+const DEFAULT_EVENT_JOURNAL_MAX_AGE: TimeDiff = TimeDiff::from_seconds(86_400);
+
+/// Default setting for including the evidence in `Fault` events.
+const DEFAULT_INCLUDE_FAULT_EVIDENCE: bool = false;
+
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
 
@@ -34,6 +45,21 @@ pub struct Config {
 
     /// CORS origin.
     pub cors_origin: String,
+
+    /// Maximum size in bytes of the on-disk event journal, allowing clients to replay events via
+    /// `start_from` beyond those held in the in-memory buffer and across node restarts.  Zero
+    /// disables the journal.
+    #[serde(default = "default_event_journal_max_bytes")]
+    pub event_journal_max_bytes: u64,
+
+    /// Maximum age of events retained in the on-disk event journal.
+    #[serde(default = "default_event_journal_max_age")]
+    pub event_journal_max_age: TimeDiff,
+
+    /// Whether to include the evidence in `Fault` events, i.e. the validator's two conflicting
+    /// signed messages, so that subscribers can verify the fault independently.
+    #[serde(default = "default_include_fault_evidence")]
+    pub include_fault_evidence: bool,
 }
 
 impl Config {
@@ -45,10 +71,28 @@ impl Config {
             event_stream_buffer_length: DEFAULT_EVENT_STREAM_BUFFER_LENGTH,
             max_concurrent_subscribers: DEFAULT_MAX_CONCURRENT_SUBSCRIBERS,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            event_journal_max_bytes: DEFAULT_EVENT_JOURNAL_MAX_BYTES,
+            event_journal_max_age: DEFAULT_EVENT_JOURNAL_MAX_AGE,
+            include_fault_evidence: DEFAULT_INCLUDE_FAULT_EVIDENCE,
         }
     }
 }
 
+/// The default for `Config::event_journal_max_bytes`.
+fn default_event_journal_max_bytes() -> u64 {
+    DEFAULT_EVENT_JOURNAL_MAX_BYTES
+}
+
+/// The default for `Config::event_journal_max_age`.
+fn default_event_journal_max_age() -> TimeDiff {
+    DEFAULT_EVENT_JOURNAL_MAX_AGE
+}
+
+/// The default for `Config::include_fault_evidence`.
+fn default_include_fault_evidence() -> bool {
+    DEFAULT_INCLUDE_FAULT_EVIDENCE
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config::new()
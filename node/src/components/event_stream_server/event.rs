@@ -6,7 +6,10 @@ use std::{
 use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey, Timestamp};
 use itertools::Itertools;
 
-use crate::types::{Block, BlockHash, Deploy, DeployHash, DeployHeader, FinalitySignature};
+use crate::{
+    components::consensus::FaultEvidence,
+    types::{Block, BlockHash, Deploy, DeployHash, DeployHeader, FinalitySignature},
+};
 
 #[derive(Debug)]
 pub enum Event {
@@ -24,6 +27,7 @@ pub enum Event {
         era_id: EraId,
         public_key: Box<PublicKey>,
         timestamp: Timestamp,
+        evidence: Option<Box<FaultEvidence>>,
     },
     FinalitySignature(Box<FinalitySignature>),
     Step {
@@ -54,6 +58,7 @@ impl Display for Event {
                 era_id,
                 public_key,
                 timestamp,
+                ..
             } => write!(
                 formatter,
                 "An equivocator with public key: {} has been identified at time: {} in era: {}",
@@ -55,7 +55,6 @@ impl EventIndexer {
         index
     }
 
-    #[cfg(test)]
     pub(super) fn current_index(&self) -> EventIndex {
         self.index
     }
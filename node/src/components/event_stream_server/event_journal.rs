@@ -0,0 +1,977 @@
+//! An on-disk journal of the events emitted by the event stream server.
+//!
+//! The journal allows clients to request events via the `start_from` query which have already been
+//! evicted from the in-memory buffer, including events emitted before the node was last restarted.
+//!
+//! Events are appended to a sequence of segment files held in the `sse_journal` directory under
+//! the storage path.  Each record in a segment comprises a fixed-length header holding the event
+//! ID, the timestamp at which the event was journaled and the length of the data, followed by the
+//! JSON-encoded `SseData`.
+//!
+//! Once the active segment reaches its size limit, a new one is started.  Whole segments are
+//! deleted, oldest first, once the total size of the journal exceeds the configured maximum, or
+//! once all the events held in a segment are older than the configured maximum age.
+//!
+//! Each segment holds a contiguous range of event IDs.  Small forward gaps between segments are
+//! tolerated, as events emitted just before the node stops (e.g. the final `Shutdown` event) may
+//! not have been journaled.  However, if the event IDs are found to have gone backwards (e.g. due
+//! to the `sse_index` file being lost), the existing journal is discarded.
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use tokio::{sync::mpsc, task};
+use tracing::{debug, info, warn};
+
+use casper_types::{TimeDiff, Timestamp};
+
+use super::{
+    metrics::Metrics,
+    sse_server::{Id, ServerSentEvent},
+    SseData,
+};
+
+/// The name of the directory under the storage path which holds the journal segments.
+const JOURNAL_DIR_NAME: &str = "sse_journal";
+/// The file extension of journal segment files.
+const SEGMENT_FILE_EXTENSION: &str = "seg";
+/// The maximum size of the journal is divided evenly between this many segments, so that eviction
+/// of a single segment only removes a small proportion of the journaled events.
+const SEGMENTS_PER_JOURNAL: u64 = 16;
+/// The length of a record header: event ID (`u32`), timestamp (`u64`) and data length (`u32`).
+const RECORD_HEADER_LENGTH: usize = 16;
+/// The largest difference between consecutive event IDs which is treated as a forward gap rather
+/// than the IDs having gone backwards.
+const MAX_FORWARD_GAP: Id = Id::MAX / 2;
+
+/// Returns `true` if `id` follows on from `expected_id`, allowing for wrapping and a forward gap.
+fn is_forward(expected_id: Id, id: Id) -> bool {
+    id.wrapping_sub(expected_id) <= MAX_FORWARD_GAP
+}
+
+/// The header preceding the JSON-encoded data of each journaled event.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct RecordHeader {
+    id: Id,
+    timestamp: Timestamp,
+    data_length: u32,
+}
+
+impl RecordHeader {
+    fn to_bytes(self) -> [u8; RECORD_HEADER_LENGTH] {
+        let mut bytes = [0; RECORD_HEADER_LENGTH];
+        bytes[..4].copy_from_slice(&self.id.to_le_bytes());
+        bytes[4..12].copy_from_slice(&self.timestamp.millis().to_le_bytes());
+        bytes[12..].copy_from_slice(&self.data_length.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; RECORD_HEADER_LENGTH]) -> Self {
+        let mut id_bytes = [0; 4];
+        id_bytes.copy_from_slice(&bytes[..4]);
+        let mut timestamp_bytes = [0; 8];
+        timestamp_bytes.copy_from_slice(&bytes[4..12]);
+        let mut data_length_bytes = [0; 4];
+        data_length_bytes.copy_from_slice(&bytes[12..]);
+        RecordHeader {
+            id: Id::from_le_bytes(id_bytes),
+            timestamp: Timestamp::from(u64::from_le_bytes(timestamp_bytes)),
+            data_length: u32::from_le_bytes(data_length_bytes),
+        }
+    }
+
+    fn record_length(&self) -> u64 {
+        RECORD_HEADER_LENGTH as u64 + self.data_length as u64
+    }
+}
+
+/// Reads the next record header, returning `None` if the reader is exhausted.
+fn read_header<R: Read>(reader: &mut R) -> io::Result<Option<RecordHeader>> {
+    let mut header_bytes = [0; RECORD_HEADER_LENGTH];
+    match reader.read_exact(&mut header_bytes) {
+        Ok(()) => Ok(Some(RecordHeader::from_bytes(&header_bytes))),
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// The in-memory summary of a single segment file.
+#[derive(Debug)]
+struct Segment {
+    sequence: u64,
+    path: PathBuf,
+    first_id: Id,
+    event_count: u32,
+    byte_count: u64,
+    first_timestamp: Timestamp,
+    last_timestamp: Timestamp,
+}
+
+impl Segment {
+    fn new(dir: &Path, sequence: u64, first_id: Id) -> Self {
+        let now = Timestamp::now();
+        Segment {
+            sequence,
+            path: segment_path(dir, sequence),
+            first_id,
+            event_count: 0,
+            byte_count: 0,
+            first_timestamp: now,
+            last_timestamp: now,
+        }
+    }
+
+    /// Scans the segment file's record headers to build its summary.
+    ///
+    /// A trailing partial record (e.g. due to the node crashing mid-write) is truncated.  Returns
+    /// `None` if the segment holds no complete records.
+    fn scan(dir: &Path, sequence: u64) -> io::Result<Option<Self>> {
+        let path = segment_path(dir, sequence);
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        let file_length = file.metadata()?.len();
+        let mut reader = BufReader::new(&file);
+        let mut segment: Option<Segment> = None;
+        let mut offset = 0_u64;
+
+        while let Some(header) = read_header(&mut reader)? {
+            if offset + header.record_length() > file_length {
+                break;
+            }
+            reader.seek_relative(header.data_length as i64)?;
+            offset += header.record_length();
+            match segment.as_mut() {
+                Some(segment) => {
+                    if header.id != segment.next_id() {
+                        return Err(io::Error::new(
+                            ErrorKind::InvalidData,
+                            format!(
+                                "expected event {} but found {} in {}",
+                                segment.next_id(),
+                                header.id,
+                                path.display()
+                            ),
+                        ));
+                    }
+                    segment.event_count += 1;
+                    segment.byte_count = offset;
+                    segment.last_timestamp = header.timestamp;
+                }
+                None => {
+                    segment = Some(Segment {
+                        sequence,
+                        path: path.clone(),
+                        first_id: header.id,
+                        event_count: 1,
+                        byte_count: offset,
+                        first_timestamp: header.timestamp,
+                        last_timestamp: header.timestamp,
+                    })
+                }
+            }
+        }
+
+        if offset < file_length {
+            warn!(
+                file = %path.display(),
+                valid_bytes = offset,
+                file_length,
+                "truncating partial record in sse journal segment"
+            );
+            file.set_len(offset)?;
+        }
+
+        Ok(segment)
+    }
+
+    /// Returns `true` if the segment holds the event with the given ID.
+    fn contains(&self, id: Id) -> bool {
+        id.wrapping_sub(self.first_id) < self.event_count
+    }
+
+    /// Returns the ID of the event which would follow the last one held in this segment.
+    fn next_id(&self) -> Id {
+        self.first_id.wrapping_add(self.event_count)
+    }
+}
+
+fn segment_path(dir: &Path, sequence: u64) -> PathBuf {
+    dir.join(format!("{:020}.{}", sequence, SEGMENT_FILE_EXTENSION))
+}
+
+/// The on-disk, size- and age-bounded journal of events.
+pub(super) struct EventJournal {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_segment_bytes: u64,
+    max_age: TimeDiff,
+    segments: VecDeque<Segment>,
+    /// The writer for the last segment in `segments`.  `None` until the first event is appended
+    /// after opening the journal, as appending always starts a new segment in that case.
+    writer: Option<BufWriter<File>>,
+    metrics: Arc<Metrics>,
+}
+
+impl EventJournal {
+    /// Opens the journal held under `storage_path`, creating it if it doesn't exist.
+    ///
+    /// `next_id` is the ID which will be assigned to the next event.  If the existing journal
+    /// doesn't end immediately before `next_id`, it is discarded.
+    pub(super) fn open(
+        storage_path: &Path,
+        max_bytes: u64,
+        max_age: TimeDiff,
+        next_id: Id,
+        metrics: Arc<Metrics>,
+    ) -> io::Result<Self> {
+        let dir = storage_path.join(JOURNAL_DIR_NAME);
+        fs::create_dir_all(&dir)?;
+
+        let mut sequences = vec![];
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str())
+                != Some(SEGMENT_FILE_EXTENSION)
+            {
+                continue;
+            }
+            match path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok())
+            {
+                Some(sequence) => sequences.push(sequence),
+                None => warn!(file = %path.display(), "ignoring unexpected file in sse journal"),
+            }
+        }
+        sequences.sort_unstable();
+
+        let mut journal = EventJournal {
+            dir,
+            max_bytes,
+            max_segment_bytes: (max_bytes / SEGMENTS_PER_JOURNAL).max(1),
+            max_age,
+            segments: VecDeque::new(),
+            writer: None,
+            metrics,
+        };
+
+        for sequence in sequences.iter().copied() {
+            match Segment::scan(&journal.dir, sequence) {
+                Ok(Some(segment)) => journal.segments.push_back(segment),
+                Ok(None) => remove_file(&segment_path(&journal.dir, sequence)),
+                Err(error) => {
+                    warn!(%error, "failed to read sse journal: discarding it");
+                    journal.segments.clear();
+                    for sequence in &sequences {
+                        remove_file(&segment_path(&journal.dir, *sequence));
+                    }
+                    break;
+                }
+            }
+        }
+
+        if !journal.is_consistent(next_id) {
+            warn!(%next_id, "sse journal is inconsistent with next event id: discarding it");
+            journal.clear();
+        }
+
+        journal.evict();
+        journal.update_metrics();
+        info!(
+            dir = %journal.dir.display(),
+            segment_count = journal.segments.len(),
+            event_count = journal.event_count(),
+            "opened sse journal"
+        );
+        Ok(journal)
+    }
+
+    /// Appends the given event to the journal, evicting old segments if required.
+    pub(super) fn append(&mut self, id: Id, data: &SseData) -> io::Result<()> {
+        let data = serde_json::to_vec(data)?;
+        let header = RecordHeader {
+            id,
+            timestamp: Timestamp::now(),
+            data_length: u32::try_from(data.len())
+                .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "sse data too large"))?,
+        };
+
+        if let Some(expected_id) = self.segments.back().map(Segment::next_id) {
+            if !is_forward(expected_id, id) {
+                warn!(
+                    expected = %expected_id,
+                    got = %id,
+                    "event id went backwards: discarding sse journal"
+                );
+                self.clear();
+            }
+        }
+
+        // Start a new segment if there's a gap in the event IDs, so that each segment remains
+        // contiguous.
+        let needs_new_segment = match (self.writer.as_ref(), self.segments.back()) {
+            (Some(_), Some(segment)) => {
+                segment.next_id() != id || segment.byte_count >= self.max_segment_bytes
+            }
+            _ => true,
+        };
+        if needs_new_segment {
+            self.start_segment(id)?;
+        }
+
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or_else(|| io::Error::new(ErrorKind::Other, "no active sse journal segment"))?;
+        writer.write_all(&header.to_bytes())?;
+        writer.write_all(&data)?;
+        writer.flush()?;
+
+        if let Some(segment) = self.segments.back_mut() {
+            if segment.event_count == 0 {
+                segment.first_timestamp = header.timestamp;
+            }
+            segment.event_count += 1;
+            segment.byte_count += header.record_length();
+            segment.last_timestamp = header.timestamp;
+        }
+
+        self.evict();
+        self.update_metrics();
+        Ok(())
+    }
+
+    /// Returns `true` if the journal holds the event with the given ID.
+    #[cfg(test)]
+    fn contains(&self, id: Id) -> bool {
+        self.segments.iter().any(|segment| segment.contains(id))
+    }
+
+    /// Returns a replay of all journaled events from `start_from` onwards, or `None` if the journal
+    /// doesn't hold `start_from`.
+    ///
+    /// The segment files are opened immediately, so the replay is unaffected by subsequent eviction
+    /// of segments.  Events appended after this call are not included in the replay.
+    pub(super) fn replay_from(&mut self, start_from: Id) -> Option<JournalReplay> {
+        if let Some(writer) = self.writer.as_mut() {
+            if let Err(error) = writer.flush() {
+                warn!(%error, "failed to flush sse journal");
+                return None;
+            }
+        }
+
+        let index = self
+            .segments
+            .iter()
+            .position(|segment| segment.contains(start_from))?;
+        let skip_count = start_from.wrapping_sub(self.segments[index].first_id);
+
+        let mut segments = Vec::with_capacity(self.segments.len() - index);
+        for segment in self.segments.iter().skip(index) {
+            match File::open(&segment.path) {
+                Ok(file) => segments.push((file, segment.byte_count)),
+                Err(error) => {
+                    warn!(
+                        file = %segment.path.display(),
+                        %error,
+                        "failed to open sse journal segment for replay"
+                    );
+                    return None;
+                }
+            }
+        }
+
+        Some(JournalReplay {
+            segments,
+            skip_count,
+        })
+    }
+
+    fn event_count(&self) -> u64 {
+        self.segments
+            .iter()
+            .map(|segment| segment.event_count as u64)
+            .sum()
+    }
+
+    fn byte_count(&self) -> u64 {
+        self.segments.iter().map(|segment| segment.byte_count).sum()
+    }
+
+    /// Returns `true` if the event IDs held in the journal's segments, followed by `next_id`, only
+    /// ever move forwards.
+    fn is_consistent(&self, next_id: Id) -> bool {
+        let mut expected_id = match self.segments.front() {
+            Some(segment) => segment.first_id,
+            None => return true,
+        };
+        for segment in &self.segments {
+            if !is_forward(expected_id, segment.first_id) {
+                return false;
+            }
+            expected_id = segment.next_id();
+        }
+        is_forward(expected_id, next_id)
+    }
+
+    fn start_segment(&mut self, first_id: Id) -> io::Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+        }
+        let sequence = self
+            .segments
+            .back()
+            .map_or(0, |segment| segment.sequence + 1);
+        let segment = Segment::new(&self.dir, sequence, first_id);
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&segment.path)?;
+        debug!(file = %segment.path.display(), "started new sse journal segment");
+        self.writer = Some(BufWriter::new(file));
+        self.segments.push_back(segment);
+        Ok(())
+    }
+
+    /// Deletes the oldest segments while the journal exceeds its maximum size, or while they only
+    /// hold events older than the maximum age.  The active segment is never deleted.
+    fn evict(&mut self) {
+        let now = Timestamp::now();
+        while self.segments.len() > 1 {
+            let oldest = &self.segments[0];
+            let too_large = self.byte_count() > self.max_bytes;
+            let too_old = now.saturating_diff(oldest.last_timestamp) > self.max_age;
+            if !too_large && !too_old {
+                break;
+            }
+            if let Some(segment) = self.segments.pop_front() {
+                debug!(
+                    file = %segment.path.display(),
+                    too_large,
+                    too_old,
+                    "evicting sse journal segment"
+                );
+                remove_file(&segment.path);
+                self.metrics.journal_evicted_segments.inc();
+            }
+        }
+    }
+
+    /// Deletes all segments.
+    fn clear(&mut self) {
+        self.writer = None;
+        for segment in self.segments.drain(..) {
+            remove_file(&segment.path);
+        }
+    }
+
+    fn update_metrics(&self) {
+        self.metrics
+            .journal_size_bytes
+            .set(self.byte_count().try_into().unwrap_or(i64::MAX));
+        self.metrics
+            .journal_event_count
+            .set(self.event_count().try_into().unwrap_or(i64::MAX));
+        let retention = self
+            .segments
+            .front()
+            .map(|segment| segment.first_timestamp.elapsed())
+            .unwrap_or_default();
+        self.metrics
+            .journal_retention_seconds
+            .set((retention.millis() / 1000).try_into().unwrap_or(i64::MAX));
+    }
+}
+
+fn remove_file(path: &Path) {
+    if let Err(error) = fs::remove_file(path) {
+        warn!(file = %path.display(), %error, "failed to remove sse journal segment");
+    }
+}
+
+/// A replay of journaled events.
+pub(super) struct JournalReplay {
+    /// The open segment files along with the number of bytes to read from each.
+    segments: Vec<(File, u64)>,
+    /// The number of events to skip at the start of the first segment.
+    skip_count: u32,
+}
+
+impl JournalReplay {
+    /// Reads the journaled events and sends them via `sender`, blocking the current thread.
+    ///
+    /// `Shutdown` events are not replayed, as they would cause the client to believe the server is
+    /// shutting down.  Stops early if the client disconnects.
+    pub(super) fn run(self, sender: &mpsc::Sender<ServerSentEvent>) {
+        let mut skip_count = self.skip_count;
+        for (file, byte_count) in self.segments {
+            let mut reader = BufReader::new(file.take(byte_count));
+            loop {
+                let header = match read_header(&mut reader) {
+                    Ok(Some(header)) => header,
+                    Ok(None) => break,
+                    Err(error) => {
+                        warn!(%error, "failed to read sse journal during replay");
+                        return;
+                    }
+                };
+
+                if skip_count > 0 {
+                    skip_count -= 1;
+                    let mut data = (&mut reader).take(header.data_length as u64);
+                    if let Err(error) = io::copy(&mut data, &mut io::sink()) {
+                        warn!(%error, "failed to read sse journal during replay");
+                        return;
+                    }
+                    continue;
+                }
+
+                let mut data_bytes = vec![0; header.data_length as usize];
+                if let Err(error) = reader.read_exact(&mut data_bytes) {
+                    warn!(%error, "failed to read sse journal during replay");
+                    return;
+                }
+                let data = match serde_json::from_slice::<SseData>(&data_bytes) {
+                    Ok(data) => data,
+                    Err(error) => {
+                        warn!(%error, id = %header.id, "failed to parse journaled sse event");
+                        return;
+                    }
+                };
+                if matches!(data, SseData::Shutdown) {
+                    continue;
+                }
+
+                let event = ServerSentEvent {
+                    id: Some(header.id),
+                    data,
+                };
+                if sender.blocking_send(event).is_err() {
+                    debug!("client disconnected during sse journal replay");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// A request handled by the task which owns the journal.
+enum JournalRequest {
+    /// Append the event to the journal.
+    Append { id: Id, data: SseData },
+    /// Send the `initial_event` followed by the journaled events from `start_from` onwards to a
+    /// new client, or the `initial_event` followed by the `buffered_events` if the journal doesn't
+    /// hold `start_from`.
+    Replay {
+        start_from: Id,
+        initial_event: ServerSentEvent,
+        buffered_events: Vec<ServerSentEvent>,
+        sender: mpsc::Sender<ServerSentEvent>,
+    },
+}
+
+/// A handle to an [`EventJournal`] owned by a dedicated blocking task, so that its file I/O never
+/// blocks the async runtime.
+///
+/// Requests are handled in the order in which they are made, so a replay includes all events
+/// appended before it was requested.  Once the handle is dropped, the task finishes handling the
+/// outstanding requests and then closes the journal.
+pub(super) struct JournalWriter {
+    sender: mpsc::UnboundedSender<JournalRequest>,
+}
+
+impl JournalWriter {
+    /// Moves the journal to a new blocking task.  Must be called from within a Tokio runtime.
+    pub(super) fn spawn(journal: EventJournal) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        task::spawn_blocking(move || Self::run(journal, receiver));
+        JournalWriter { sender }
+    }
+
+    /// Appends the given event to the journal.
+    pub(super) fn append(&self, id: Id, data: SseData) {
+        // The task only exits once all senders have been dropped, so this can't fail.
+        let _ = self.sender.send(JournalRequest::Append { id, data });
+    }
+
+    /// Sends `initial_event` to the client via `sender`, followed by the journaled events from
+    /// `start_from` onwards if the journal holds `start_from`, or by `buffered_events` otherwise.
+    pub(super) fn replay_from(
+        &self,
+        start_from: Id,
+        initial_event: ServerSentEvent,
+        buffered_events: Vec<ServerSentEvent>,
+        sender: mpsc::Sender<ServerSentEvent>,
+    ) {
+        let _ = self.sender.send(JournalRequest::Replay {
+            start_from,
+            initial_event,
+            buffered_events,
+            sender,
+        });
+    }
+
+    /// Handles requests until all senders have been dropped.  If writing to the journal fails, it
+    /// is disabled, and all subsequent replays fall back to the buffered events.
+    fn run(journal: EventJournal, mut receiver: mpsc::UnboundedReceiver<JournalRequest>) {
+        let mut maybe_journal = Some(journal);
+        while let Some(request) = receiver.blocking_recv() {
+            match request {
+                JournalRequest::Append { id, data } => {
+                    if let Some(Err(error)) = maybe_journal
+                        .as_mut()
+                        .map(|journal| journal.append(id, &data))
+                    {
+                        warn!(%error, "failed to write to sse journal: disabling it");
+                        maybe_journal = None;
+                    }
+                }
+                JournalRequest::Replay {
+                    start_from,
+                    initial_event,
+                    buffered_events,
+                    sender,
+                } => {
+                    let maybe_replay = maybe_journal
+                        .as_mut()
+                        .and_then(|journal| journal.replay_from(start_from));
+                    // Replaying can take a while, and the client may be slow to consume the
+                    // events, so don't hold up the journal while sending them.
+                    task::spawn_blocking(move || {
+                        if sender.blocking_send(initial_event).is_err() {
+                            return;
+                        }
+                        match maybe_replay {
+                            Some(replay) => replay.run(&sender),
+                            None => {
+                                for event in buffered_events {
+                                    if sender.blocking_send(event).is_err() {
+                                        return;
+                                    }
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use prometheus::Registry;
+
+    use casper_types::{testing::TestRng, ProtocolVersion};
+
+    use super::*;
+    use crate::logging;
+
+    const MAX_BYTES: u64 = 1024 * 1024;
+    const MAX_AGE: TimeDiff = TimeDiff::from_seconds(3600);
+
+    fn random_events(rng: &mut TestRng, count: usize) -> Vec<SseData> {
+        (0..count)
+            .map(|_| SseData::random_deploy_processed(rng))
+            .collect()
+    }
+
+    fn open(storage_path: &Path, max_bytes: u64, next_id: Id) -> EventJournal {
+        let metrics = Arc::new(Metrics::new(&Registry::new()).unwrap());
+        EventJournal::open(storage_path, max_bytes, MAX_AGE, next_id, metrics).unwrap()
+    }
+
+    fn replay(journal: &mut EventJournal, start_from: Id) -> Vec<ServerSentEvent> {
+        let replay = journal.replay_from(start_from).expect("should replay");
+        let (sender, mut receiver) = mpsc::channel(1_000);
+        replay.run(&sender);
+        drop(sender);
+        let mut events = vec![];
+        while let Ok(event) = receiver.try_recv() {
+            events.push(event);
+        }
+        events
+    }
+
+    async fn replay_via_writer(
+        writer: &JournalWriter,
+        start_from: Id,
+        initial_event: ServerSentEvent,
+        buffered_events: Vec<ServerSentEvent>,
+    ) -> Vec<ServerSentEvent> {
+        let (sender, mut receiver) = mpsc::channel(1_000);
+        writer.replay_from(start_from, initial_event, buffered_events, sender);
+        let mut events = vec![];
+        while let Some(event) = receiver.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    #[test]
+    fn should_replay_from_start_id() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 10);
+
+        let mut journal = open(tempdir.path(), MAX_BYTES, 5);
+        for (index, data) in events.iter().enumerate() {
+            journal.append(index as Id + 5, data).unwrap();
+        }
+
+        assert!(!journal.contains(4));
+        assert!(journal.contains(5));
+        assert!(journal.contains(14));
+        assert!(!journal.contains(15));
+        assert!(journal.replay_from(4).is_none());
+
+        let replayed = replay(&mut journal, 8);
+        let expected: Vec<_> = events
+            .iter()
+            .enumerate()
+            .skip(3)
+            .map(|(index, data)| ServerSentEvent {
+                id: Some(index as Id + 5),
+                data: data.clone(),
+            })
+            .collect();
+        assert_eq!(replayed, expected);
+    }
+
+    #[test]
+    fn should_persist_across_restarts_and_skip_shutdown() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 4);
+
+        {
+            let mut journal = open(tempdir.path(), MAX_BYTES, 0);
+            journal.append(0, &events[0]).unwrap();
+            journal.append(1, &events[1]).unwrap();
+            journal.append(2, &SseData::Shutdown).unwrap();
+        }
+
+        let mut journal = open(tempdir.path(), MAX_BYTES, 3);
+        assert!(journal.contains(0));
+        journal.append(3, &events[2]).unwrap();
+        journal.append(4, &events[3]).unwrap();
+
+        let replayed_ids: Vec<_> = replay(&mut journal, 0)
+            .into_iter()
+            .map(|event| event.id.unwrap())
+            .collect();
+        assert_eq!(replayed_ids, vec![0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn should_tolerate_forward_gap() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 3);
+
+        {
+            let mut journal = open(tempdir.path(), MAX_BYTES, 0);
+            journal.append(0, &events[0]).unwrap();
+            journal.append(1, &events[1]).unwrap();
+        }
+
+        // Event 2 was never journaled, e.g. as the node stopped before it could be written.
+        let mut journal = open(tempdir.path(), MAX_BYTES, 3);
+        journal.append(3, &events[2]).unwrap();
+        assert!(!journal.contains(2));
+
+        let replayed_ids: Vec<_> = replay(&mut journal, 1)
+            .into_iter()
+            .map(|event| event.id.unwrap())
+            .collect();
+        assert_eq!(replayed_ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn should_discard_journal_if_ids_go_backwards() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 2);
+
+        {
+            let mut journal = open(tempdir.path(), MAX_BYTES, 0);
+            journal.append(0, &events[0]).unwrap();
+            journal.append(1, &events[1]).unwrap();
+        }
+
+        // The next ID precedes the journaled events, so the journal is discarded.
+        let journal = open(tempdir.path(), MAX_BYTES, 0);
+        assert!(!journal.contains(0));
+        assert_eq!(journal.event_count(), 0);
+        assert_eq!(
+            fs::read_dir(tempdir.path().join(JOURNAL_DIR_NAME))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn should_truncate_partial_record() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 2);
+
+        let segment_path = {
+            let mut journal = open(tempdir.path(), MAX_BYTES, 0);
+            journal.append(0, &events[0]).unwrap();
+            journal.append(1, &events[1]).unwrap();
+            journal.segments[0].path.clone()
+        };
+
+        // Chop the end off the last record, as if the node crashed mid-write.
+        let file = OpenOptions::new().write(true).open(segment_path).unwrap();
+        let length = file.metadata().unwrap().len();
+        file.set_len(length - 1).unwrap();
+
+        let mut journal = open(tempdir.path(), MAX_BYTES, 1);
+        assert!(journal.contains(0));
+        assert!(!journal.contains(1));
+        journal.append(1, &events[1]).unwrap();
+        assert_eq!(replay(&mut journal, 0).len(), 2);
+    }
+
+    #[test]
+    fn should_evict_oldest_segments_when_too_large() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 200);
+
+        // Use a maximum size which will hold only a fraction of the events.
+        let event_size =
+            serde_json::to_vec(&events[0]).unwrap().len() as u64 + RECORD_HEADER_LENGTH as u64;
+        let max_bytes = event_size * 50;
+        let mut journal = open(tempdir.path(), max_bytes, 0);
+        for (index, data) in events.iter().enumerate() {
+            journal.append(index as Id, data).unwrap();
+            assert!(journal.byte_count() <= max_bytes + journal.max_segment_bytes + event_size);
+        }
+
+        assert!(!journal.contains(0));
+        assert!(journal.contains(199));
+        assert!(journal.metrics.journal_evicted_segments.get() > 0);
+        assert_eq!(
+            journal.metrics.journal_event_count.get() as u64,
+            journal.event_count()
+        );
+
+        // The remaining events should still be replayable in full.
+        let first_id = journal.segments[0].first_id;
+        let replayed_ids: Vec<_> = replay(&mut journal, first_id)
+            .into_iter()
+            .map(|event| event.id.unwrap())
+            .collect();
+        assert_eq!(replayed_ids, (first_id..200).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn should_evict_segments_when_too_old() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 3);
+
+        let mut journal = open(tempdir.path(), MAX_BYTES, 0);
+        journal.append(0, &events[0]).unwrap();
+        // Force a new segment and backdate the first one.
+        journal.max_segment_bytes = 1;
+        journal.segments[0].last_timestamp = Timestamp::zero();
+        journal.append(1, &events[1]).unwrap();
+
+        assert!(!journal.contains(0));
+        assert!(journal.contains(1));
+    }
+
+    #[test]
+    fn should_handle_wrapping_ids() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 4);
+
+        let first_id = Id::MAX - 1;
+        let mut journal = open(tempdir.path(), MAX_BYTES, first_id);
+        for (index, data) in events.iter().enumerate() {
+            journal
+                .append(first_id.wrapping_add(index as Id), data)
+                .unwrap();
+        }
+        assert!(journal.contains(Id::MAX));
+        assert!(journal.contains(1));
+        assert!(!journal.contains(2));
+
+        let replayed_ids: Vec<_> = replay(&mut journal, Id::MAX)
+            .into_iter()
+            .map(|event| event.id.unwrap())
+            .collect();
+        assert_eq!(replayed_ids, vec![Id::MAX, 0, 1]);
+    }
+
+    #[tokio::test]
+    async fn should_append_and_replay_via_writer_task() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let tempdir = tempfile::tempdir().unwrap();
+        let events = random_events(&mut rng, 10);
+        let initial_event = ServerSentEvent::initial_event(ProtocolVersion::V1_0_0);
+
+        let writer = JournalWriter::spawn(open(tempdir.path(), MAX_BYTES, 0));
+        for (index, data) in events.iter().enumerate() {
+            writer.append(index as Id, data.clone());
+        }
+
+        // The journal holds the requested event, so the events appended before the request are
+        // replayed rather than the buffered ones.
+        let buffered_event = ServerSentEvent {
+            id: Some(9),
+            data: events[9].clone(),
+        };
+        let replayed =
+            replay_via_writer(&writer, 3, initial_event.clone(), vec![buffered_event]).await;
+        let expected: Vec<_> = iter::once(initial_event.clone())
+            .chain(
+                events
+                    .iter()
+                    .enumerate()
+                    .skip(3)
+                    .map(|(index, data)| ServerSentEvent {
+                        id: Some(index as Id),
+                        data: data.clone(),
+                    }),
+            )
+            .collect();
+        assert_eq!(replayed, expected);
+
+        // The journal doesn't hold the requested event, so the buffered events are sent instead.
+        let buffered_event = ServerSentEvent {
+            id: Some(20),
+            data: events[0].clone(),
+        };
+        let replayed = replay_via_writer(
+            &writer,
+            20,
+            initial_event.clone(),
+            vec![buffered_event.clone()],
+        )
+        .await;
+        assert_eq!(replayed, vec![initial_event, buffered_event]);
+    }
+}
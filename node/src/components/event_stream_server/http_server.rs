@@ -10,6 +10,7 @@ use wheelbuf::WheelBuf;
 use casper_types::ProtocolVersion;
 
 use super::{
+    event_journal::{EventJournal, JournalWriter},
     sse_server::{BroadcastChannelMessage, Id, NewSubscriberInfo, ServerSentEvent},
     Config, EventIndex, SseData,
 };
@@ -25,6 +26,10 @@ use super::{
 /// * `new_subscriber_info_receiver` is used to notify the server of the details of a new client
 ///   having subscribed to the event stream.  It allows the server to populate that client's stream
 ///   with the requested number of historical events.
+/// * `journal` is the optional on-disk journal to which all events are written, and from which
+///   events no longer held in the in-memory buffer can be replayed to new clients.  It is moved to
+///   a dedicated blocking task, so that its file I/O doesn't hold up this one.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run(
     config: Config,
     api_version: ProtocolVersion,
@@ -33,8 +38,10 @@ pub(super) async fn run(
     mut data_receiver: mpsc::UnboundedReceiver<(EventIndex, SseData)>,
     broadcaster: broadcast::Sender<BroadcastChannelMessage>,
     mut new_subscriber_info_receiver: mpsc::UnboundedReceiver<NewSubscriberInfo>,
+    journal: Option<EventJournal>,
 ) {
     let server_joiner = task::spawn(server_with_shutdown);
+    let journal = journal.map(JournalWriter::spawn);
 
     // Initialize the index and buffer for the SSEs.
     let mut buffer = WheelBuf::new(vec![
@@ -49,14 +56,19 @@ pub(super) async fn run(
             select! {
                 maybe_new_subscriber = new_subscriber_info_receiver.recv() => {
                     if let Some(subscriber) = maybe_new_subscriber {
-                        // First send the client the `ApiVersion` event.  We don't care if this
-                        // errors - the client may have disconnected already.
-                        let _ = subscriber
-                            .initial_events_sender
-                            .send(ServerSentEvent::initial_event(api_version));
+                        let NewSubscriberInfo { start_from, initial_events_sender } = subscriber;
+                        // The client is always sent the `ApiVersion` event first.
+                        let mut initial_events = vec![ServerSentEvent::initial_event(api_version)];
                         // If the client supplied a "start_from" index, provide the buffered events.
-                        // If they requested more than is buffered, just provide the whole buffer.
-                        if let Some(start_index) = subscriber.start_from {
+                        // If the requested event has been evicted from the buffer, the journal (if
+                        // any) replays the journaled events instead, provided it holds the event.
+                        // Otherwise, if they requested more than is buffered, just provide the
+                        // whole buffer.
+                        let mut maybe_replay_start = None;
+                        if let Some(start_index) = start_from {
+                            if !buffer.iter().any(|event| event.id == Some(start_index)) {
+                                maybe_replay_start = Some(start_index);
+                            }
                             // If the buffer's first event ID is in the range [0, buffer size) or
                             // (Id::MAX - buffer size, Id::MAX], then the events in the buffer are
                             // considered to have their IDs wrapping round, or that was recently the
@@ -73,18 +85,41 @@ pub(super) async fn run(
                                     id > Id::MAX - buffer_size || id < buffer_size
                                 })
                                 .unwrap_or_default();
-                            for event in buffer.iter().skip_while(|event| {
-                                if in_wraparound_zone {
-                                    event.id.unwrap().wrapping_add(buffer_size)
-                                        < start_index.wrapping_add(buffer_size)
-                                } else {
-                                    event.id.unwrap() < start_index
+                            initial_events.extend(
+                                buffer
+                                    .iter()
+                                    .skip_while(|event| {
+                                        if in_wraparound_zone {
+                                            event.id.unwrap().wrapping_add(buffer_size)
+                                                < start_index.wrapping_add(buffer_size)
+                                        } else {
+                                            event.id.unwrap() < start_index
+                                        }
+                                    })
+                                    .cloned(),
+                            );
+                        }
+                        if let (Some(start_index), Some(journal)) =
+                            (maybe_replay_start, journal.as_ref())
+                        {
+                            let initial_event = initial_events.remove(0);
+                            journal.replay_from(
+                                start_index,
+                                initial_event,
+                                initial_events,
+                                initial_events_sender,
+                            );
+                            continue;
+                        }
+                        // We don't care if sending errors - the client may have disconnected
+                        // already.
+                        task::spawn(async move {
+                            for event in initial_events {
+                                if initial_events_sender.send(event).await.is_err() {
+                                    break;
                                 }
-                            }) {
-                                // As per sending `SSE_INITIAL_EVENT`, we don't care if this errors.
-                                let _ = subscriber.initial_events_sender.send(event.clone());
                             }
-                        }
+                        });
                     }
                 }
 
@@ -93,6 +128,9 @@ pub(super) async fn run(
                         Some((event_index, data)) => {
                             // Buffer the data and broadcast it to subscribed clients.
                             trace!("Event stream server received {:?}", data);
+                            if let Some(journal) = journal.as_ref() {
+                                journal.append(event_index, data.clone());
+                            }
                             let event = ServerSentEvent { id: Some(event_index), data };
                             buffer.push(event.clone());
                             let message = BroadcastChannelMessage::ServerSentEvent(event);
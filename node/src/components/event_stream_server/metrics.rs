@@ -0,0 +1,63 @@
+use prometheus::{IntCounter, IntGauge, Registry};
+
+use crate::unregister_metric;
+
+/// Metrics for the event stream server component.
+#[derive(Debug)]
+pub(super) struct Metrics {
+    /// Total size in bytes of the on-disk event journal.
+    pub(super) journal_size_bytes: IntGauge,
+    /// Number of events held in the on-disk event journal.
+    pub(super) journal_event_count: IntGauge,
+    /// Age in seconds of the oldest event held in the on-disk event journal.
+    pub(super) journal_retention_seconds: IntGauge,
+    /// Number of journal segments deleted due to the size or age limits.
+    pub(super) journal_evicted_segments: IntCounter,
+    registry: Registry,
+}
+
+impl Metrics {
+    /// Creates a new instance of the event stream server metrics.
+    pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let journal_size_bytes = IntGauge::new(
+            "event_stream_journal_size_bytes".to_string(),
+            "total size in bytes of the on-disk event stream journal.".to_string(),
+        )?;
+        let journal_event_count = IntGauge::new(
+            "event_stream_journal_event_count".to_string(),
+            "number of events held in the on-disk event stream journal.".to_string(),
+        )?;
+        let journal_retention_seconds = IntGauge::new(
+            "event_stream_journal_retention_seconds".to_string(),
+            "age in seconds of the oldest event held in the on-disk event stream journal."
+                .to_string(),
+        )?;
+        let journal_evicted_segments = IntCounter::new(
+            "event_stream_journal_evicted_segments".to_string(),
+            "number of event stream journal segments deleted due to size or age limits."
+                .to_string(),
+        )?;
+
+        registry.register(Box::new(journal_size_bytes.clone()))?;
+        registry.register(Box::new(journal_event_count.clone()))?;
+        registry.register(Box::new(journal_retention_seconds.clone()))?;
+        registry.register(Box::new(journal_evicted_segments.clone()))?;
+
+        Ok(Metrics {
+            journal_size_bytes,
+            journal_event_count,
+            journal_retention_seconds,
+            journal_evicted_segments,
+            registry: registry.clone(),
+        })
+    }
+}
+
+impl Drop for Metrics {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.journal_size_bytes);
+        unregister_metric!(self.registry, self.journal_event_count);
+        unregister_metric!(self.registry, self.journal_retention_seconds);
+        unregister_metric!(self.registry, self.journal_evicted_segments);
+    }
+}
@@ -18,9 +18,7 @@ use tokio::sync::{
     broadcast::{self, error::RecvError},
     mpsc,
 };
-use tokio_stream::wrappers::{
-    errors::BroadcastStreamRecvError, BroadcastStream, UnboundedReceiverStream,
-};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
 use tracing::{debug, error, info, warn};
 use warp::{
     addr,
@@ -38,7 +36,10 @@ use casper_types::{
     EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
 };
 
-use crate::types::{BlockHash, Deploy, DeployHash, FinalitySignature, JsonBlock};
+use crate::{
+    components::consensus::FaultEvidence,
+    types::{BlockHash, Deploy, DeployHash, FinalitySignature, JsonBlock},
+};
 #[cfg(test)]
 use crate::{testing, types::Block};
 
@@ -67,6 +68,11 @@ const DEPLOYS_FILTER: [EventFilter; 1] = [EventFilter::DeployAccepted];
 /// The filter associated with `/events/sigs` path.
 const SIGNATURES_FILTER: [EventFilter; 1] = [EventFilter::FinalitySignature];
 
+/// The capacity of the channel via which the initial events are sent to each new client's handler.
+///
+/// This bounds the memory used when replaying a large number of events to a slow client.
+const INITIAL_EVENTS_CHANNEL_SIZE: usize = 1_000;
+
 /// The "id" field of the events sent on the event stream to clients.
 pub type Id = u32;
 
@@ -105,6 +111,9 @@ pub enum SseData {
         era_id: EraId,
         public_key: Box<PublicKey>,
         timestamp: Timestamp,
+        /// The evidence of the fault, if the server is configured to include it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        evidence: Option<Box<FaultEvidence>>,
     },
     /// New finality signature received.
     FinalitySignature(Box<FinalitySignature>),
@@ -191,6 +200,7 @@ impl SseData {
             era_id: EraId::new(rng.gen()),
             public_key: Box::new(PublicKey::random(rng)),
             timestamp: Timestamp::random(rng),
+            evidence: None,
         }
     }
 
@@ -259,8 +269,9 @@ pub(super) struct NewSubscriberInfo {
     /// The event ID from which the stream should start for this client.
     pub(super) start_from: Option<Id>,
     /// A channel to send the initial events to the client's handler.  This will always send the
-    /// ApiVersion as the first event, and then any buffered events as indicated by `start_from`.
-    pub(super) initial_events_sender: mpsc::UnboundedSender<ServerSentEvent>,
+    /// ApiVersion as the first event, and then any buffered or journaled events as indicated by
+    /// `start_from`.
+    pub(super) initial_events_sender: mpsc::Sender<ServerSentEvent>,
 }
 
 /// A filter for event types a client has subscribed to receive.
@@ -447,7 +458,8 @@ impl ChannelsAndFilter {
             };
 
             // Create a channel for the client's handler to receive the stream of initial events.
-            let (initial_events_sender, initial_events_receiver) = mpsc::unbounded_channel();
+            let (initial_events_sender, initial_events_receiver) =
+                mpsc::channel(INITIAL_EVENTS_CHANNEL_SIZE);
 
             // Supply the server with the sender part of the channel along with the client's
             // requested starting point.
@@ -497,7 +509,7 @@ impl ChannelsAndFilter {
 ///
 /// The initial events receiver (an mpsc receiver) is exhausted first, and contains an initial
 /// `ApiVersion` message, followed by any historical events the client requested using the query
-/// string.
+/// string, whether from the in-memory buffer or the on-disk journal.
 ///
 /// The ongoing events channel (a broadcast receiver) is then consumed, and will remain in use until
 /// either the client disconnects, or the server shuts down (indicated by sending a `Shutdown`
@@ -507,7 +519,7 @@ impl ChannelsAndFilter {
 /// It also takes an `EventFilter` which causes events to which the client didn't subscribe to be
 /// skipped.
 fn stream_to_client(
-    initial_events: mpsc::UnboundedReceiver<ServerSentEvent>,
+    initial_events: mpsc::Receiver<ServerSentEvent>,
     ongoing_events: broadcast::Receiver<BroadcastChannelMessage>,
     event_filter: &'static [EventFilter],
     remote_address: String,
@@ -549,7 +561,7 @@ fn stream_to_client(
 
     // Serve the initial events followed by the ongoing ones, filtering as dictated by the
     // `event_filter`.
-    UnboundedReceiverStream::new(initial_events)
+    ReceiverStream::new(initial_events)
         .map(move |event| {
             if let Some(id) = event.id {
                 let _ = initial_stream_ids.write().unwrap().insert(id);
@@ -833,13 +845,14 @@ mod tests {
                 &mut deploys,
             );
 
-            let (initial_events_sender, initial_events_receiver) = mpsc::unbounded_channel();
+            let (initial_events_sender, initial_events_receiver) =
+                mpsc::channel(INITIAL_EVENTS_CHANNEL_SIZE);
             let (ongoing_events_sender, ongoing_events_receiver) =
                 broadcast::channel(NUM_INITIAL_EVENTS + NUM_ONGOING_EVENTS + 1);
 
             // Send all the events.
             for event in initial_events.iter().cloned() {
-                initial_events_sender.send(event).unwrap();
+                initial_events_sender.try_send(event).unwrap();
             }
             for event in ongoing_events.iter().cloned() {
                 let _ = ongoing_events_sender
@@ -12,6 +12,7 @@ use std::{
 use futures::{join, StreamExt};
 use http::StatusCode;
 use pretty_assertions::assert_eq;
+use prometheus::Registry;
 use reqwest::Response;
 use schemars::schema_for;
 use tempfile::TempDir;
@@ -44,6 +45,8 @@ const BUFFER_LENGTH: u32 = EVENT_COUNT / 2;
 /// The maximum amount of time to wait for a test server to complete.  If this time is exceeded, the
 /// test has probably hung, and should be deemed to have failed.
 const MAX_TEST_TIME: Duration = Duration::from_secs(2);
+/// The maximum size of the event journal, if enabled in the server's config.
+const EVENT_JOURNAL_MAX_BYTES: u64 = 10 * 1024 * 1024;
 /// The duration of the sleep called between each event being sent by the server.
 const DELAY_BETWEEN_EVENTS: Duration = Duration::from_millis(1);
 
@@ -82,6 +85,8 @@ struct ServerBehavior {
     /// If `Some`, sets the `max_concurrent_subscribers` server config value, otherwise uses the
     /// config default.
     max_concurrent_subscribers: Option<u32>,
+    /// Whether the server should write events to the on-disk journal.
+    enable_event_journal: bool,
     clients: Vec<ClientSyncBehavior>,
 }
 
@@ -94,6 +99,7 @@ impl ServerBehavior {
             has_delay_between_events: true,
             repeat_events: false,
             max_concurrent_subscribers: None,
+            enable_event_journal: false,
             clients: Vec::new(),
         }
     }
@@ -107,6 +113,7 @@ impl ServerBehavior {
             has_delay_between_events: false,
             repeat_events: true,
             max_concurrent_subscribers: None,
+            enable_event_journal: false,
             clients: Vec::new(),
         }
     }
@@ -124,6 +131,11 @@ impl ServerBehavior {
         self.max_concurrent_subscribers = Some(count);
     }
 
+    /// Enables the on-disk event journal in the server config.
+    fn enable_event_journal(&mut self) {
+        self.enable_event_journal = true;
+    }
+
     /// Waits for all clients which specified they wanted to join just before the given event ID.
     async fn wait_for_clients(&self, id: Id) {
         for client_behavior in &self.clients {
@@ -262,13 +274,20 @@ impl TestFixture {
             max_concurrent_subscribers: server_behavior
                 .max_concurrent_subscribers
                 .unwrap_or(Config::default().max_concurrent_subscribers),
+            event_journal_max_bytes: if server_behavior.enable_event_journal {
+                EVENT_JOURNAL_MAX_BYTES
+            } else {
+                0
+            },
             ..Default::default()
         };
         let mut server = EventStreamServer::new(
             config,
             self.storage_dir.path().to_path_buf(),
             self.protocol_version,
-        );
+            &Registry::new(),
+        )
+        .unwrap();
         server.listen().unwrap();
         assert!(server.sse_server.is_some());
 
@@ -700,6 +719,49 @@ async fn should_serve_remaining_signature_events_with_query() {
     should_serve_remaining_events_with_query(SIGS_PATH).await;
 }
 
+/// Client setup:
+///   * `<IP:port>/events/<path>?start_from=0`
+///   * connected just before event ID 75
+///   * server has the event journal enabled
+///
+/// Expected to receive main, deploy-accepted or signature events (depending on `path`) from ID 0
+/// onwards, as events 0 to 24 have been purged from the server buffer, but are replayed from the
+/// journal.
+async fn should_serve_evicted_events_from_journal(path: &str) {
+    let mut rng = crate::new_rng();
+    let mut fixture = TestFixture::new(&mut rng);
+
+    let connect_at_event_id = BUFFER_LENGTH * 3 / 2;
+    let start_from_event_id = 0;
+
+    let mut server_behavior = ServerBehavior::new();
+    server_behavior.enable_event_journal();
+    let barrier = server_behavior.add_client_sync_before_event(connect_at_event_id);
+    let server_address = fixture.run_server(server_behavior).await;
+
+    let url = url(server_address, path, Some(start_from_event_id));
+    let (expected_events, final_id) = fixture.filtered_events(path, start_from_event_id);
+    let received_events = subscribe(&url, barrier, final_id, "client").await.unwrap();
+    fixture.stop_server().await;
+
+    assert_eq!(received_events, expected_events);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_evicted_main_events_from_journal() {
+    should_serve_evicted_events_from_journal(MAIN_PATH).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_evicted_deploy_accepted_events_from_journal() {
+    should_serve_evicted_events_from_journal(DEPLOYS_PATH).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_serve_evicted_signature_events_from_journal() {
+    should_serve_evicted_events_from_journal(SIGS_PATH).await;
+}
+
 /// Client setup:
 ///   * `<IP:port>/events/<path>?start_from=25`
 ///   * connected before first event
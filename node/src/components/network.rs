@@ -43,12 +43,14 @@ mod symmetry;
 pub(crate) mod tasks;
 #[cfg(test)]
 mod tests;
+mod traffic;
 
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     io,
     net::{SocketAddr, TcpListener},
+    path::Path,
     sync::{Arc, Weak},
     time::{Duration, Instant},
 };
@@ -90,9 +92,9 @@ pub(crate) use self::{
     },
 };
 use self::{
-    blocklist::BlocklistJustification,
+    blocklist::{BlockedPeer, BlocklistJustification, OperatorBlocklist},
     chain_info::ChainInfo,
-    counting_format::{ConnectionId, CountingFormat, Role},
+    counting_format::{ConnectionId, CountingFormat, LastFrameSize, Role},
     error::{ConnectionError, Result},
     event::{IncomingConnection, OutgoingConnection},
     health::{HealthConfig, TaggedTimestamp},
@@ -102,12 +104,16 @@ use self::{
     outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
     symmetry::ConnectionSymmetry,
     tasks::{MessageQueueItem, NetworkContext},
+    traffic::PeerTraffic,
 };
 use crate::{
     components::{gossiper::GossipItem, Component, ComponentState, InitializedComponent},
     effect::{
         announcements::PeerBehaviorAnnouncement,
-        requests::{BeginGossipRequest, NetworkInfoRequest, NetworkRequest, StorageRequest},
+        requests::{
+            BeginGossipRequest, BlocklistRequest, NetworkInfoRequest, NetworkRequest,
+            StorageRequest,
+        },
         AutoClosingResponder, EffectBuilder, EffectExt, Effects, GossipTarget,
     },
     reactor::{Finalize, ReactorEvent},
@@ -134,6 +140,9 @@ const BASE_RECONNECTION_TIMEOUT: Duration = Duration::from_secs(1);
 /// Interval during which to perform outgoing manager housekeeping.
 const OUTGOING_MANAGER_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
 
+/// How long to keep traffic counters of peers we are no longer connected to.
+const DISCONNECTED_PEER_TRAFFIC_RETENTION: Duration = Duration::from_secs(10 * 60);
+
 /// How often to send a ping down a healthy connection.
 const PING_INTERVAL: Duration = Duration::from_secs(30);
 
@@ -180,6 +189,9 @@ where
     /// Tracks nodes that have announced themselves as nodes that are syncing.
     syncing_nodes: HashSet<NodeId>,
 
+    /// Peers blocked by the node operator.
+    operator_blocklist: OperatorBlocklist,
+
     channel_management: Option<ChannelManagement>,
 
     /// Networking metrics.
@@ -241,6 +253,7 @@ where
     #[allow(clippy::type_complexity)]
     pub(crate) fn new<C: Into<ChainInfo>>(
         cfg: Config,
+        storage_path: &Path,
         our_identity: Identity,
         node_key_pair: Option<(Arc<SecretKey>, PublicKey)>,
         registry: &Registry,
@@ -249,16 +262,27 @@ where
     ) -> Result<Network<REv, P>> {
         let net_metrics = Arc::new(Metrics::new(registry)?);
 
+        let operator_blocklist =
+            OperatorBlocklist::new(&cfg.blocked_peers, cfg.persist_blocklist, storage_path)?;
+
         let outgoing_limiter = Limiter::new(
             cfg.max_outgoing_byte_rate_non_validators,
             net_metrics.accumulated_outgoing_limiter_delay.clone(),
             validator_matrix.clone(),
+        )
+        .with_class_quotas(
+            cfg.message_class_quotas.clone(),
+            net_metrics.out_quota_exceeded.clone(),
         );
 
         let incoming_limiter = Limiter::new(
             cfg.max_incoming_message_rate_non_validators,
             net_metrics.accumulated_incoming_limiter_delay.clone(),
             validator_matrix,
+        )
+        .with_class_quotas(
+            cfg.message_class_quotas.clone(),
+            net_metrics.in_quota_exceeded.clone(),
         );
 
         let outgoing_manager = OutgoingManager::with_metrics(
@@ -291,6 +315,7 @@ where
             outgoing_manager,
             connection_symmetries: HashMap::new(),
             syncing_nodes: HashSet::new(),
+            operator_blocklist,
             channel_management: None,
             net_metrics,
             outgoing_limiter,
@@ -551,7 +576,17 @@ where
                 peer_id,
                 peer_consensus_public_key,
                 stream,
+                last_frame_size,
             } => {
+                if self
+                    .operator_blocklist
+                    .is_blocked(Some(peer_id), Some(peer_addr))
+                    || self.operator_blocklist.is_blocked(None, Some(public_addr))
+                {
+                    info!(%public_addr, %peer_id, "rejecting new incoming connection from peer blocked by operator");
+                    return Effects::new();
+                }
+
                 if self.cfg.max_incoming_peer_connections != 0 {
                     if let Some(symmetries) = self.connection_symmetries.get(&peer_id) {
                         let incoming_count = symmetries
@@ -605,6 +640,7 @@ where
                     tasks::message_reader(
                         self.context.clone(),
                         stream,
+                        last_frame_size,
                         self.incoming_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.channel_management().close_incoming_receiver.clone(),
@@ -756,6 +792,21 @@ where
                 sink,
                 is_syncing,
             } => {
+                if self
+                    .operator_blocklist
+                    .is_blocked(Some(peer_id), Some(peer_addr))
+                {
+                    // The connection is closed by dropping its sink; blocking the address ensures
+                    // it is not redialed.
+                    info!(%peer_id, "dropping new outgoing connection to peer blocked by operator");
+                    let request = self.outgoing_manager.block_addr(
+                        peer_addr,
+                        now,
+                        BlocklistJustification::Operator,
+                    );
+                    return self.process_dial_requests(request);
+                }
+
                 info!("new outgoing connection established");
 
                 let (sender, receiver) = mpsc::unbounded_channel();
@@ -879,6 +930,102 @@ where
         self.process_dial_requests(requests)
     }
 
+    /// Returns `true` if the peer, or any address it is connected through, is blocked by the node
+    /// operator.
+    fn is_blocked_by_operator(&self, peer_id: NodeId) -> bool {
+        let incoming_addrs = self
+            .connection_symmetries
+            .get(&peer_id)
+            .and_then(ConnectionSymmetry::incoming_addrs)
+            .into_iter()
+            .flatten()
+            .copied();
+        self.operator_blocklist.is_blocked(Some(peer_id), None)
+            || self
+                .outgoing_manager
+                .get_addr(peer_id)
+                .into_iter()
+                .chain(incoming_addrs)
+                .any(|addr| self.operator_blocklist.is_blocked(None, Some(addr)))
+    }
+
+    /// Handles a request to manage the operator blocklist.
+    fn handle_blocklist_request(&mut self, request: BlocklistRequest) -> Effects<Event<P>> {
+        match request {
+            BlocklistRequest::BlockPeer { peer, responder } => {
+                let result = self.operator_blocklist.block(peer);
+                let mut effects = Effects::new();
+                if result.is_ok() {
+                    info!(%peer, "peer blocked by operator");
+                    // Immediately disconnect from any outgoing addresses of the peer. Incoming
+                    // messages from it are discarded from now on.
+                    let addrs: Vec<_> = match peer {
+                        BlockedPeer::NodeId(node_id) => self
+                            .outgoing_manager
+                            .get_addr(node_id)
+                            .into_iter()
+                            .collect(),
+                        BlockedPeer::Ip(ip) => self
+                            .outgoing_manager
+                            .known_addrs()
+                            .filter(|addr| addr.ip() == ip)
+                            .collect(),
+                    };
+                    let now = Instant::now();
+                    let requests: Vec<_> = addrs
+                        .into_iter()
+                        .filter_map(|addr| {
+                            self.outgoing_manager.block_addr(
+                                addr,
+                                now,
+                                BlocklistJustification::Operator,
+                            )
+                        })
+                        .collect();
+                    effects.extend(self.process_dial_requests(requests));
+                }
+                effects.extend(responder.respond(result).ignore());
+                effects
+            }
+            BlocklistRequest::UnblockPeer { peer, responder } => {
+                let result = self.operator_blocklist.unblock(peer);
+                let mut effects = Effects::new();
+                if let Ok(true) = result {
+                    info!(%peer, "peer unblocked by operator");
+                    // We do not know which node IDs the blocked addresses belong to, so redeem all
+                    // of them; any still blocked will be blocked again once connected.
+                    let addrs: Vec<_> = self
+                        .outgoing_manager
+                        .blocked_addrs()
+                        .filter(|(addr, justification)| {
+                            justification.is_permanent()
+                                && !self.operator_blocklist.is_blocked(None, Some(*addr))
+                        })
+                        .map(|(addr, _)| addr)
+                        .collect();
+                    let now = Instant::now();
+                    let requests: Vec<_> = addrs
+                        .into_iter()
+                        .filter_map(|addr| self.outgoing_manager.redeem_addr(addr, now))
+                        .collect();
+                    effects.extend(self.process_dial_requests(requests));
+                }
+                effects.extend(responder.respond(result).ignore());
+                effects
+            }
+            BlocklistRequest::ListBlocked { responder } => {
+                let blocked_addrs = self
+                    .outgoing_manager
+                    .blocked_addrs()
+                    .map(|(addr, justification)| (addr, justification.to_string()))
+                    .collect();
+                responder
+                    .respond(self.operator_blocklist.snapshot(blocked_addrs))
+                    .ignore()
+            }
+        }
+    }
+
     /// Processes a set of `DialRequest`s, updating the component and emitting needed effects.
     fn process_dial_requests<T>(&mut self, requests: T) -> Effects<Event<P>>
     where
@@ -928,6 +1075,11 @@ where
     where
         REv: FromIncoming<P> + From<PeerBehaviorAnnouncement>,
     {
+        if self.is_blocked_by_operator(peer_id) {
+            debug!(%peer_id, "dropping message from peer blocked by operator");
+            return Effects::new();
+        }
+
         span.in_scope(|| match msg {
             Message::Handshake { .. } => {
                 // We should never receive a handshake message on an established connection. Simply
@@ -1163,6 +1315,7 @@ where
                 | Event::OutgoingDropped { .. }
                 | Event::NetworkRequest { .. }
                 | Event::NetworkInfoRequest { .. }
+                | Event::BlocklistRequest { .. }
                 | Event::GossipOurAddress
                 | Event::PeerAddressReceived(_)
                 | Event::SweepOutgoing
@@ -1216,6 +1369,7 @@ where
                         .respond(NetworkInsights::collect_from_component(self))
                         .ignore(),
                 },
+                Event::BlocklistRequest { req } => self.handle_blocklist_request(*req),
                 Event::GossipOurAddress => {
                     let our_address = GossipedAddress::new(
                         self.context
@@ -1245,6 +1399,10 @@ where
                     let now = Instant::now();
                     let requests = self.outgoing_manager.perform_housekeeping(rng, now);
 
+                    let traffic = self.context.traffic();
+                    traffic.sweep(now, DISCONNECTED_PEER_TRAFFIC_RETENTION);
+                    self.net_metrics.update_top_peers(&traffic.snapshot());
+
                     let mut effects = self.process_dial_requests(requests);
 
                     effects.extend(
@@ -1330,6 +1488,8 @@ pub(crate) type FramedTransport = tokio_util::codec::Framed<Transport, LengthDel
 /// A full transport contains the framing as well as the encoding scheme used to send messages.
 fn full_transport<P>(
     metrics: Weak<Metrics>,
+    traffic: Arc<PeerTraffic>,
+    last_frame_size: LastFrameSize,
     connection_id: ConnectionId,
     framed: FramedTransport,
     role: Role,
@@ -1340,7 +1500,14 @@ where
 {
     tokio_serde::Framed::new(
         framed,
-        CountingFormat::new(metrics, connection_id, role, BincodeFormat::default()),
+        CountingFormat::new(
+            metrics,
+            traffic,
+            last_frame_size,
+            connection_id,
+            role,
+            BincodeFormat::default(),
+        ),
     )
 }
 
@@ -1,15 +1,36 @@
 //! Blocklisting support.
 //!
 //! Blocked peers are prevented from interacting with the node through a variety of means.
+//!
+//! Peers are blocked either automatically, for misbehavior, or manually by the node operator.
+//! Automatic blocks expire after the configured `blocklist_retain_duration`, while peers blocked by
+//! the operator remain blocked until explicitly unblocked, and can optionally be persisted to disk.
 
-use std::fmt::{self, Display, Formatter};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use casper_hashing::Digest;
 use casper_types::EraId;
 use datasize::DataSize;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+use super::limiter::MessageClass;
+use crate::{
+    components::{block_accumulator, fetcher::Tag},
+    types::{NodeId, ParseNodeIdError},
+    utils::DisplayIter,
+};
 
-use crate::components::{block_accumulator, fetcher::Tag};
+/// Filename of the persisted operator blocklist, within the storage directory.
+const OPERATOR_BLOCKLIST_FILENAME: &str = "operator_blocklist.json";
 
 /// Reasons why a peer was blocked.
 #[derive(DataSize, Debug, Serialize)]
@@ -57,6 +78,17 @@ pub(crate) enum BlocklistJustification {
     DishonestPeer,
     /// Peer sent too many finality signatures.
     SentTooManyFinalitySignatures { max_allowed: u32 },
+    /// Peer continuously exceeded one of its message class quotas.
+    ExceededMessageQuota { class: MessageClass },
+    /// Peer was blocked by the node operator.
+    Operator,
+}
+
+impl BlocklistJustification {
+    /// Returns `true` if the block should not expire after `blocklist_retain_duration`.
+    pub(crate) fn is_permanent(&self) -> bool {
+        matches!(self, BlocklistJustification::Operator)
+    }
 }
 
 impl Display for BlocklistJustification {
@@ -105,6 +137,294 @@ impl Display for BlocklistJustification {
                 f,
                 "sent too many finality signatures: maximum {max_allowed} signatures are allowed"
             ),
+            BlocklistJustification::ExceededMessageQuota { class } => {
+                write!(f, "continuously exceeded its {} message quota", class)
+            }
+            BlocklistJustification::Operator => f.write_str("blocked by the node operator"),
+        }
+    }
+}
+
+/// A peer blocked by the node operator.
+#[derive(Clone, Copy, DataSize, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BlockedPeer {
+    /// The peer with the given node ID, regardless of its address.
+    NodeId(NodeId),
+    /// Any peer connecting from or listening on the given IP address.
+    Ip(IpAddr),
+}
+
+impl BlockedPeer {
+    /// Returns `true` if the peer with the given ID and address is matched by this entry.
+    fn matches(&self, node_id: Option<NodeId>, addr: Option<SocketAddr>) -> bool {
+        match self {
+            BlockedPeer::NodeId(blocked_id) => node_id == Some(*blocked_id),
+            BlockedPeer::Ip(blocked_ip) => addr.map(|addr| addr.ip()) == Some(*blocked_ip),
+        }
+    }
+}
+
+impl Display for BlockedPeer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            // Unlike the `Display` impl of `NodeId`, show the full ID so it can be passed back in.
+            BlockedPeer::NodeId(node_id) => {
+                write!(f, "tls:{}", base16::encode_lower(node_id.hash_bytes()))
+            }
+            BlockedPeer::Ip(ip) => Display::fmt(ip, f),
+        }
+    }
+}
+
+/// Error parsing a [`BlockedPeer`].
+#[derive(Debug, Error)]
+#[error("{input:?} is neither an IP address nor a node ID ({error})")]
+pub struct ParseBlockedPeerError {
+    input: String,
+    #[source]
+    error: ParseNodeIdError,
+}
+
+/// Parses either an IP address or a full, hex-encoded node ID.
+impl FromStr for BlockedPeer {
+    type Err = ParseBlockedPeerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(ip) = s.parse() {
+            return Ok(BlockedPeer::Ip(ip));
         }
+        s.parse()
+            .map(BlockedPeer::NodeId)
+            .map_err(|error| ParseBlockedPeerError {
+                input: s.to_string(),
+                error,
+            })
+    }
+}
+
+/// Error managing the operator blocklist.
+#[derive(Debug, Error)]
+pub enum OperatorBlocklistError {
+    /// An entry of the static deny-list could not be parsed.
+    #[error("invalid entry in blocked_peers: {0}")]
+    InvalidDenyListEntry(#[source] ParseBlockedPeerError),
+    /// The persisted blocklist could not be read.
+    #[error("could not read operator blocklist from {}: {error}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+    /// The persisted blocklist could not be parsed.
+    #[error("could not parse operator blocklist at {}: {error}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        error: serde_json::Error,
+    },
+    /// The blocklist could not be persisted.
+    #[error("could not persist operator blocklist to {}: {error}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+    /// The peer is on the static deny-list, which can only be changed via the config.
+    #[error("{0} is on the static deny-list and cannot be unblocked at runtime")]
+    OnDenyList(BlockedPeer),
+}
+
+/// The peers blocked by the node operator.
+///
+/// Peers are either on the static deny-list given in the config, or blocked at runtime via the
+/// diagnostics port.  The latter are persisted to disk if enabled, and can be unblocked again.
+#[derive(DataSize, Debug, Default)]
+pub(crate) struct OperatorBlocklist {
+    /// Peers blocked via the config.
+    deny_list: BTreeSet<BlockedPeer>,
+    /// Peers blocked at runtime.
+    blocked: BTreeSet<BlockedPeer>,
+    /// Path to which the peers blocked at runtime are persisted, if enabled.
+    persistence_path: Option<PathBuf>,
+}
+
+impl OperatorBlocklist {
+    /// Creates a new operator blocklist from the config's deny-list.
+    ///
+    /// If `persist` is `true`, any peers previously blocked at runtime are loaded from
+    /// `storage_path`, and future changes are persisted there.
+    pub(crate) fn new(
+        deny_list: &[String],
+        persist: bool,
+        storage_path: &Path,
+    ) -> Result<Self, OperatorBlocklistError> {
+        let deny_list = deny_list
+            .iter()
+            .map(|entry| entry.parse())
+            .collect::<Result<_, _>>()
+            .map_err(OperatorBlocklistError::InvalidDenyListEntry)?;
+        let persistence_path = persist.then(|| storage_path.join(OPERATOR_BLOCKLIST_FILENAME));
+        let blocked = match &persistence_path {
+            Some(path) if path.exists() => {
+                let contents = fs::read(path).map_err(|error| OperatorBlocklistError::Read {
+                    path: path.clone(),
+                    error,
+                })?;
+                let blocked: BTreeSet<BlockedPeer> =
+                    serde_json::from_slice(&contents).map_err(|error| {
+                        OperatorBlocklistError::Parse {
+                            path: path.clone(),
+                            error,
+                        }
+                    })?;
+                info!(count = blocked.len(), path = %path.display(), "loaded operator blocklist");
+                blocked
+            }
+            _ => BTreeSet::new(),
+        };
+        Ok(OperatorBlocklist {
+            deny_list,
+            blocked,
+            persistence_path,
+        })
+    }
+
+    /// Returns `true` if the peer with the given ID and/or address is blocked.
+    pub(crate) fn is_blocked(&self, node_id: Option<NodeId>, addr: Option<SocketAddr>) -> bool {
+        self.deny_list
+            .iter()
+            .chain(self.blocked.iter())
+            .any(|blocked_peer| blocked_peer.matches(node_id, addr))
+    }
+
+    /// Blocks the given peer, returning `false` if it was already blocked.
+    pub(crate) fn block(&mut self, peer: BlockedPeer) -> Result<bool, OperatorBlocklistError> {
+        if self.deny_list.contains(&peer) || !self.blocked.insert(peer) {
+            return Ok(false);
+        }
+        self.persist()?;
+        Ok(true)
+    }
+
+    /// Unblocks the given peer, returning `false` if it was not blocked.
+    pub(crate) fn unblock(&mut self, peer: BlockedPeer) -> Result<bool, OperatorBlocklistError> {
+        if self.deny_list.contains(&peer) {
+            return Err(OperatorBlocklistError::OnDenyList(peer));
+        }
+        if !self.blocked.remove(&peer) {
+            return Ok(false);
+        }
+        self.persist()?;
+        Ok(true)
+    }
+
+    /// Writes the peers blocked at runtime to disk, if persistence is enabled.
+    fn persist(&self) -> Result<(), OperatorBlocklistError> {
+        let path = match &self.persistence_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let write_error = |error| OperatorBlocklistError::Write {
+            path: path.clone(),
+            error,
+        };
+        let contents = serde_json::to_vec_pretty(&self.blocked)
+            .map_err(|error| write_error(io::Error::new(io::ErrorKind::Other, error)))?;
+        // Write to a temporary file first, so a crash cannot leave a truncated blocklist behind.
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, contents).map_err(write_error)?;
+        fs::rename(&temp_path, path).map_err(write_error)
+    }
+
+    /// Creates a snapshot of the blocked peers, along with any addresses currently blocked.
+    pub(crate) fn snapshot(
+        &self,
+        blocked_addrs: Vec<(SocketAddr, String)>,
+    ) -> OperatorBlocklistSnapshot {
+        OperatorBlocklistSnapshot {
+            deny_list: self.deny_list.iter().copied().collect(),
+            blocked: self.blocked.iter().copied().collect(),
+            blocked_addrs,
+        }
+    }
+}
+
+/// A snapshot of the blocked peers, as listed via the diagnostics port.
+#[derive(Debug, Serialize)]
+pub(crate) struct OperatorBlocklistSnapshot {
+    /// Peers blocked via the config.
+    deny_list: Vec<BlockedPeer>,
+    /// Peers blocked at runtime by the operator.
+    blocked: Vec<BlockedPeer>,
+    /// Outgoing addresses currently blocked, along with the justification for blocking them.
+    blocked_addrs: Vec<(SocketAddr, String)>,
+}
+
+impl Display for OperatorBlocklistSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "deny-list: [{}]", DisplayIter::new(&self.deny_list))?;
+        writeln!(
+            f,
+            "blocked by operator: [{}]",
+            DisplayIter::new(&self.blocked)
+        )?;
+        write!(f, "blocked addresses:")?;
+        if self.blocked_addrs.is_empty() {
+            write!(f, " none")?;
+        }
+        for (addr, justification) in &self.blocked_addrs {
+            write!(f, "\n  {}: {}", addr, justification)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_persist_and_reload_operator_blocklist() {
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+        let node_id = NodeId::random(&mut rng);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let deny_list = vec!["10.0.0.2".to_string()];
+
+        let mut blocklist = OperatorBlocklist::new(&deny_list, true, tempdir.path()).unwrap();
+        assert!(blocklist.block(BlockedPeer::NodeId(node_id)).unwrap());
+        assert!(!blocklist.block(BlockedPeer::NodeId(node_id)).unwrap());
+        assert!(blocklist.block(BlockedPeer::Ip(ip)).unwrap());
+
+        let blocklist = OperatorBlocklist::new(&deny_list, true, tempdir.path()).unwrap();
+        assert!(blocklist.is_blocked(Some(node_id), None));
+        assert!(blocklist.is_blocked(None, Some(SocketAddr::new(ip, 34553))));
+        assert!(blocklist.is_blocked(None, Some("10.0.0.2:1".parse().unwrap())));
+        assert!(!blocklist.is_blocked(None, Some("10.0.0.3:1".parse().unwrap())));
+
+        let mut blocklist = OperatorBlocklist::new(&deny_list, true, tempdir.path()).unwrap();
+        assert!(blocklist.unblock(BlockedPeer::Ip(ip)).unwrap());
+        assert!(!blocklist.unblock(BlockedPeer::Ip(ip)).unwrap());
+        assert!(matches!(
+            blocklist.unblock("10.0.0.2".parse().unwrap()),
+            Err(OperatorBlocklistError::OnDenyList(_))
+        ));
+
+        let blocklist = OperatorBlocklist::new(&deny_list, false, tempdir.path()).unwrap();
+        assert!(!blocklist.is_blocked(Some(node_id), None));
+    }
+
+    #[test]
+    fn should_parse_blocked_peer() {
+        let node_id = NodeId::random(&mut TestRng::new());
+        let peer = BlockedPeer::NodeId(node_id);
+        assert_eq!(peer.to_string().parse::<BlockedPeer>().unwrap(), peer);
+        assert_eq!(
+            "::1".parse::<BlockedPeer>().unwrap(),
+            BlockedPeer::Ip("::1".parse().unwrap())
+        );
+        assert!("not-a-peer".parse::<BlockedPeer>().is_err());
     }
 }
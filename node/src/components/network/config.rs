@@ -6,7 +6,7 @@ use casper_types::{ProtocolVersion, TimeDiff};
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use super::EstimatorWeights;
+use super::{limiter::MessageClass, EstimatorWeights};
 
 /// Default binding address.
 ///
@@ -52,6 +52,9 @@ impl Default for Config {
             tarpit_chance: 0.2,
             max_in_flight_demands: 50,
             blocklist_retain_duration: TimeDiff::from_seconds(600),
+            persist_blocklist: false,
+            blocked_peers: Vec::new(),
+            message_class_quotas: MessageClassQuotas::default(),
             identity: None,
         }
     }
@@ -111,6 +114,16 @@ pub struct Config {
     pub max_in_flight_demands: u32,
     /// Duration peers are kept on the block list, before being redeemed.
     pub blocklist_retain_duration: TimeDiff,
+    /// Whether to persist peers blocked via the diagnostics port, keeping them blocked across
+    /// restarts.
+    #[serde(default)]
+    pub persist_blocklist: bool,
+    /// Peers which are always blocked, given as IP addresses or hex-encoded node IDs.
+    #[serde(default)]
+    pub blocked_peers: Vec<String>,
+    /// Per-peer quotas for individual classes of messages.
+    #[serde(default)]
+    pub message_class_quotas: MessageClassQuotas,
     /// Network identity configuration option.
     ///
     /// An identity will be automatically generated when starting up a node if this option is
@@ -118,6 +131,50 @@ pub struct Config {
     pub identity: Option<IdentityConfig>,
 }
 
+/// Per-peer quota for a single class of messages, in bytes per second.
+#[derive(DataSize, Debug, Default, Clone, Copy, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct MessageClassQuota {
+    /// Quota for active and upcoming validators. Unlimited if 0.
+    #[serde(default)]
+    pub validators: u32,
+    /// Quota for all other peers. Unlimited if 0.
+    #[serde(default)]
+    pub non_validators: u32,
+}
+
+/// Per-peer quotas for individual classes of messages, applied to both incoming and outgoing
+/// messages.
+#[derive(DataSize, Debug, Default, Clone, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields, default)]
+pub struct MessageClassQuotas {
+    /// Quota for consensus messages.
+    pub consensus: MessageClassQuota,
+    /// Quota for deploy gossip.
+    pub deploy_gossip: MessageClassQuota,
+    /// Quota for block, deploy and trie fetch requests and responses.
+    pub fetch: MessageClassQuota,
+    /// Quota for address gossip.
+    pub address_gossip: MessageClassQuota,
+    /// Duration after which a peer continuously exceeding one of its incoming quotas is blocked.
+    /// Peers are never blocked for exceeding quotas if 0.
+    pub block_after_violating_for: TimeDiff,
+}
+
+impl MessageClassQuotas {
+    /// Returns the quota for the given message class.
+    pub(super) fn quota(&self, class: MessageClass) -> MessageClassQuota {
+        match class {
+            MessageClass::Consensus => self.consensus,
+            MessageClass::DeployGossip => self.deploy_gossip,
+            MessageClass::Fetch => self.fetch,
+            MessageClass::AddressGossip => self.address_gossip,
+        }
+    }
+}
+
 #[cfg(test)]
 /// Reduced gossip interval for local testing.
 const DEFAULT_TEST_GOSSIP_INTERVAL: TimeDiff = TimeDiff::from_seconds(1);
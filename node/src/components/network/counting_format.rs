@@ -9,7 +9,10 @@ use std::{
     convert::TryFrom,
     fmt::{self, Display, Formatter},
     pin::Pin,
-    sync::{Arc, Weak},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Weak,
+    },
 };
 
 use bytes::{Bytes, BytesMut};
@@ -25,7 +28,7 @@ use casper_hashing::Digest;
 #[cfg(test)]
 use casper_types::testing::TestRng;
 
-use super::{tls::KeyFingerprint, Message, Metrics, Payload};
+use super::{tls::KeyFingerprint, traffic::PeerTraffic, Message, Metrics, Payload};
 use crate::{types::NodeId, utils};
 
 /// Lazily-evaluated network message ID generator.
@@ -40,11 +43,30 @@ impl Display for TraceId {
     }
 }
 
+/// The size of the most recently received frame on a connection.
+///
+/// Shared between the [`CountingFormat`] of an incoming connection and the task reading from it,
+/// which charges message quotas by the size of each message on the wire.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LastFrameSize(Arc<AtomicU32>);
+
+impl LastFrameSize {
+    /// Returns the size in bytes of the most recently received frame.
+    pub(super) fn get(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Records the size of a received frame.
+    fn set(&self, size: u32) {
+        self.0.store(size, Ordering::Relaxed)
+    }
+}
+
 /// A metric-updating serializer/deserializer wrapper for network messages.
 ///
-/// Classifies each message given and updates the `NetworkingMetrics` accordingly. Also emits a
-/// TRACE-level message to the `net_out` and `net_in` target with a per-message unique hash when
-/// a message is sent or received.
+/// Classifies each message given and updates the `NetworkingMetrics` and the traffic counters of
+/// the peer accordingly. Also emits a TRACE-level message to the `net_out` and `net_in` target with
+/// a per-message unique hash when a message is sent or received.
 #[pin_project]
 #[derive(Debug)]
 pub struct CountingFormat<F> {
@@ -61,6 +83,10 @@ pub struct CountingFormat<F> {
     role: Role,
     /// Metrics to update.
     metrics: Weak<Metrics>,
+    /// Traffic counters of the peer on the other end of the connection.
+    traffic: Arc<PeerTraffic>,
+    /// Size of the most recently received frame.
+    last_frame_size: LastFrameSize,
 }
 
 impl<F> CountingFormat<F> {
@@ -68,12 +94,16 @@ impl<F> CountingFormat<F> {
     #[inline]
     pub(super) fn new(
         metrics: Weak<Metrics>,
+        traffic: Arc<PeerTraffic>,
+        last_frame_size: LastFrameSize,
         connection_id: ConnectionId,
         role: Role,
         inner: F,
     ) -> Self {
         Self {
             metrics,
+            traffic,
+            last_frame_size,
             connection_id,
             out_count: 0,
             in_count: 0,
@@ -98,7 +128,9 @@ where
         let serialized = F::serialize(projection, item)?;
         let msg_size = serialized.len() as u64;
         let msg_kind = item.classify();
-        Metrics::record_payload_out(this.metrics, msg_kind, msg_size);
+        let msg_type = item.message_type();
+        Metrics::record_payload_out(this.metrics, msg_kind, msg_type, msg_size);
+        this.traffic.record_out(msg_type, msg_size);
 
         let trace_id = this
             .connection_id
@@ -127,10 +159,14 @@ where
         let projection: Pin<&mut F> = this.inner;
 
         let msg_size = src.len() as u64;
+        this.last_frame_size
+            .set(u32::try_from(src.len()).unwrap_or(u32::MAX));
 
         let deserialized = F::deserialize(projection, src)?;
         let msg_kind = deserialized.classify();
-        Metrics::record_payload_in(this.metrics, msg_kind, msg_size);
+        let msg_type = deserialized.message_type();
+        Metrics::record_payload_in(this.metrics, msg_kind, msg_type, msg_size);
+        this.traffic.record_in(msg_type, msg_size);
 
         let trace_id = this
             .connection_id
@@ -314,9 +350,22 @@ impl Role {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::NodeId;
+    use std::{
+        pin::Pin,
+        sync::{Arc, Weak},
+    };
+
+    use bytes::BytesMut;
+    use rand::Rng;
+    use tokio_serde::{Deserializer, Serializer};
 
-    use super::{ConnectionId, Role, TlsRandomData, TraceId};
+    use crate::{
+        components::network::{traffic::TrafficTable, BincodeFormat, Message},
+        protocol::Message as ProtocolMessage,
+        types::NodeId,
+    };
+
+    use super::{ConnectionId, CountingFormat, LastFrameSize, Role, TlsRandomData, TraceId};
 
     #[test]
     fn trace_id_has_16_character() {
@@ -377,4 +426,27 @@ mod tests {
         assert_eq!(msg_ba_0_on_b, msg_ba_0_on_a);
         assert_ne!(msg_ba_0_on_b, msg_ab_0_on_b);
     }
+
+    #[test]
+    fn records_size_of_last_received_frame() {
+        let mut rng = crate::new_rng();
+
+        let last_frame_size = LastFrameSize::default();
+        let mut format = CountingFormat::new(
+            Weak::new(),
+            TrafficTable::default().peer(NodeId::random(&mut rng)),
+            last_frame_size.clone(),
+            ConnectionId::random(&mut rng),
+            Role::Listener,
+            BincodeFormat::default(),
+        );
+
+        let msg: Arc<Message<ProtocolMessage>> = Arc::new(Message::Ping { nonce: rng.gen() });
+        let serialized = Pin::new(&mut format).serialize(&msg).unwrap();
+        let _: Message<ProtocolMessage> = Pin::new(&mut format)
+            .deserialize(&BytesMut::from(&serialized[..]))
+            .unwrap();
+
+        assert_eq!(last_frame_size.get() as usize, serialized.len());
+    }
 }
@@ -8,6 +8,7 @@ use thiserror::Error;
 use casper_hashing::Digest;
 use casper_types::{crypto, ProtocolVersion};
 
+use super::blocklist::OperatorBlocklistError;
 use crate::{
     tls::{LoadCertError, ValidationError},
     utils::ResolveAddressError,
@@ -71,6 +72,13 @@ pub enum Error {
         #[from]
         LoadCertError,
     ),
+    /// Failed to set up the operator blocklist.
+    #[error(transparent)]
+    OperatorBlocklist(
+        #[serde(skip_serializing)]
+        #[from]
+        OperatorBlocklistError,
+    ),
 }
 
 // Manual implementation for `DataSize` - the type contains too many FFI variants that are hard to
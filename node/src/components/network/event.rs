@@ -13,11 +13,14 @@ use tracing::Span;
 
 use casper_types::PublicKey;
 
-use super::{error::ConnectionError, FullTransport, GossipedAddress, Message, NodeId};
+use super::{
+    counting_format::LastFrameSize, error::ConnectionError, FullTransport, GossipedAddress,
+    Message, NodeId,
+};
 use crate::{
     effect::{
         announcements::PeerBehaviorAnnouncement,
-        requests::{NetworkInfoRequest, NetworkRequest},
+        requests::{BlocklistRequest, NetworkInfoRequest, NetworkRequest},
     },
     protocol::Message as ProtocolMessage,
 };
@@ -82,6 +85,13 @@ pub(crate) enum Event<P> {
         req: Box<NetworkInfoRequest>,
     },
 
+    /// Incoming request to manage the operator blocklist.
+    #[from]
+    BlocklistRequest {
+        #[serde(skip_serializing)]
+        req: Box<BlocklistRequest>,
+    },
+
     /// The node should gossip its own public listening address.
     GossipOurAddress,
 
@@ -108,6 +118,12 @@ impl From<NetworkInfoRequest> for Event<ProtocolMessage> {
     }
 }
 
+impl From<BlocklistRequest> for Event<ProtocolMessage> {
+    fn from(req: BlocklistRequest) -> Self {
+        Self::BlocklistRequest { req: Box::new(req) }
+    }
+}
+
 impl<P: Display> Display for Event<P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -131,6 +147,7 @@ impl<P: Display> Display for Event<P> {
             }
             Event::NetworkRequest { req } => write!(f, "request: {}", req),
             Event::NetworkInfoRequest { req } => write!(f, "request: {}", req),
+            Event::BlocklistRequest { req } => write!(f, "request: {}", req),
             Event::GossipOurAddress => write!(f, "gossip our address"),
             Event::PeerAddressReceived(gossiped_address) => {
                 write!(f, "received gossiped peer address {}", gossiped_address)
@@ -179,6 +196,9 @@ pub(crate) enum IncomingConnection<P> {
         /// Stream of incoming messages. for incoming connections.
         #[serde(skip_serializing)]
         stream: SplitStream<FullTransport<P>>,
+        /// Size of the most recently received frame on the connection.
+        #[serde(skip_serializing)]
+        last_frame_size: LastFrameSize,
     },
 }
 
@@ -200,6 +220,7 @@ impl<P> Display for IncomingConnection<P> {
                 peer_id,
                 peer_consensus_public_key,
                 stream: _,
+                last_frame_size: _,
             } => {
                 write!(
                     f,
@@ -22,8 +22,8 @@ use crate::{
 };
 
 use super::{
-    error::ConnectionError, outgoing::OutgoingState, symmetry::ConnectionSymmetry, Network,
-    OutgoingHandle, Payload,
+    error::ConnectionError, outgoing::OutgoingState, symmetry::ConnectionSymmetry,
+    traffic::PeerTrafficSnapshot, Network, OutgoingHandle, Payload,
 };
 
 /// A collection of insights into the active networking component.
@@ -49,6 +49,8 @@ pub(crate) struct NetworkInsights {
     outgoing_connections: Vec<(SocketAddr, OutgoingInsight)>,
     /// Map of incoming connections.
     connection_symmetries: Vec<(NodeId, ConnectionSymmetryInsight)>,
+    /// Traffic exchanged with each peer, ordered by descending number of bytes received.
+    peer_traffic: Vec<(NodeId, PeerTrafficSnapshot)>,
 }
 
 /// Insight into an outgoing connection.
@@ -319,6 +321,7 @@ impl NetworkInsights {
                 .debug_inspect_unspent_allowance(),
             outgoing_connections,
             connection_symmetries,
+            peer_traffic: net.context.traffic().snapshot(),
         }
     }
 }
@@ -380,6 +383,37 @@ impl Display for NetworkInsights {
             f.write_str("\n")?;
         }
 
+        f.write_str("peer traffic:\n")?;
+        writeln!(
+            f,
+            "peer ID         {:40}  {:>10}  {:>14}  {:>10}  {:>14}",
+            "message type", "in msgs", "in bytes", "out msgs", "out bytes"
+        )?;
+        for (peer_id, traffic) in &self.peer_traffic {
+            writeln!(
+                f,
+                "{:10}  {:40}  {:>10}  {:>14}  {:>10}  {:>14}",
+                peer_id,
+                "total",
+                traffic.total_in.count,
+                traffic.total_in.bytes,
+                traffic.total_out.count,
+                traffic.total_out.bytes
+            )?;
+            for (message_type, incoming, outgoing) in traffic.by_message_type() {
+                writeln!(
+                    f,
+                    "{:14}  {:40}  {:>10}  {:>14}  {:>10}  {:>14}",
+                    "",
+                    message_type,
+                    incoming.count,
+                    incoming.bytes,
+                    outgoing.count,
+                    outgoing.bytes
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
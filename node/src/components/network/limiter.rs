@@ -5,21 +5,72 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
     sync::{Arc, RwLock},
     time::{Duration, Instant},
 };
 
-use prometheus::Counter;
+use datasize::DataSize;
+use prometheus::{Counter, IntCounterVec};
+use serde::Serialize;
 use tokio::{runtime::Handle, sync::Mutex, task};
 use tracing::{error, trace, warn};
 
 use casper_types::{EraId, PublicKey};
 
+use super::{config::MessageClassQuotas, MessageKind};
 use crate::types::{NodeId, ValidatorMatrix};
 
 /// Amount of resource allowed to buffer in `Limiter`.
 const STORED_BUFFER_SECS: Duration = Duration::from_secs(2);
 
+/// A class of messages subject to its own per-peer quota.
+#[derive(Copy, Clone, DataSize, Debug, Eq, Hash, PartialEq, Serialize)]
+pub(crate) enum MessageClass {
+    /// Consensus messages.
+    Consensus,
+    /// Deploys being gossiped.
+    DeployGossip,
+    /// Blocks, deploys and tries being fetched, both requests and responses.
+    Fetch,
+    /// Addresses being gossiped.
+    AddressGossip,
+}
+
+impl MessageClass {
+    /// Determines the quota class of a message of the given kind, if it is subject to one.
+    pub(super) fn from_message_kind(kind: MessageKind) -> Option<Self> {
+        match kind {
+            MessageKind::Consensus => Some(MessageClass::Consensus),
+            MessageKind::DeployGossip => Some(MessageClass::DeployGossip),
+            MessageKind::DeployTransfer
+            | MessageKind::BlockTransfer
+            | MessageKind::TrieTransfer => Some(MessageClass::Fetch),
+            MessageKind::AddressGossip => Some(MessageClass::AddressGossip),
+            MessageKind::Protocol
+            | MessageKind::BlockGossip
+            | MessageKind::FinalitySignatureGossip
+            | MessageKind::Other => None,
+        }
+    }
+
+    /// Returns the label used for this class in metrics.
+    fn label(self) -> &'static str {
+        match self {
+            MessageClass::Consensus => "consensus",
+            MessageClass::DeployGossip => "deploy_gossip",
+            MessageClass::Fetch => "fetch",
+            MessageClass::AddressGossip => "address_gossip",
+        }
+    }
+}
+
+impl Display for MessageClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 /// A limiter dividing resources into two classes based on their validator status.
 ///
 /// Any consumer of a specific resource is expected to call `create_handle` for every peer and use
@@ -32,6 +83,8 @@ pub(super) struct Limiter {
     data: Arc<LimiterData>,
     /// Set of active and upcoming validators shared across all handles.
     validator_matrix: ValidatorMatrix,
+    /// Per-peer quotas for individual message classes, if enabled.
+    class_quotas: Option<Arc<ClassQuotaData>>,
 }
 
 impl Limiter {
@@ -46,9 +99,26 @@ impl Limiter {
         Limiter {
             data: Arc::new(LimiterData::new(resources_per_second, wait_time_sec)),
             validator_matrix,
+            class_quotas: None,
         }
     }
 
+    /// Enables per-peer quotas for individual message classes, in addition to the overall limit.
+    ///
+    /// Every time a peer exceeds one of its quotas, `quota_exceeded` is incremented for the
+    /// respective class.
+    pub(super) fn with_class_quotas(
+        mut self,
+        quotas: MessageClassQuotas,
+        quota_exceeded: IntCounterVec,
+    ) -> Self {
+        self.class_quotas = Some(Arc::new(ClassQuotaData {
+            quotas,
+            quota_exceeded,
+        }));
+        self
+    }
+
     /// Create a handle for a connection using the given peer and optional consensus key.
     pub(super) fn create_handle(
         &self,
@@ -74,6 +144,8 @@ impl Limiter {
                 _peer_id: peer_id,
                 consensus_key,
             },
+            class_quotas: self.class_quotas.clone(),
+            quota_buckets: HashMap::new(),
         }
     }
 
@@ -177,6 +249,38 @@ impl LimiterData {
     }
 }
 
+/// Configuration of per-class quotas, shared across all handles.
+#[derive(Debug)]
+struct ClassQuotaData {
+    /// The configured quotas.
+    quotas: MessageClassQuotas,
+    /// Number of times a quota was exceeded, by message class.
+    quota_exceeded: IntCounterVec,
+}
+
+/// State of a single per-peer quota.
+#[derive(Debug)]
+struct QuotaBucket {
+    /// How many bytes are buffered, may go negative in the case of a deficit.
+    available: i64,
+    /// Last time the bucket was refilled.
+    last_refill: Instant,
+    /// Since when the peer has been continuously exceeding the quota, if it is.
+    violating_since: Option<Instant>,
+}
+
+impl QuotaBucket {
+    /// Refills the bucket at the given rate for the time elapsed since the last refill.
+    fn refill(&mut self, now: Instant, bytes_per_second: u32) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.last_refill = now;
+
+        let max_stored = (bytes_per_second as f64 * STORED_BUFFER_SECS.as_secs_f64()) as i64;
+        self.available += ((elapsed.as_nanos() * bytes_per_second as u128) / 1_000_000_000) as i64;
+        self.available = self.available.min(max_stored);
+    }
+}
+
 /// Peer class for the `Limiter`.
 enum PeerClass {
     /// A validator.
@@ -194,9 +298,90 @@ pub(super) struct LimiterHandle {
     validator_matrix: ValidatorMatrix,
     /// Consumer ID for the sender holding this handle.
     consumer_id: ConsumerId,
+    /// Per-class quota configuration, if enabled.
+    class_quotas: Option<Arc<ClassQuotaData>>,
+    /// The peer's quota state for each message class.
+    quota_buckets: HashMap<MessageClass, QuotaBucket>,
 }
 
 impl LimiterHandle {
+    /// Determines the class of the peer by checking if its key is in the validator set.
+    fn peer_class(&self) -> PeerClass {
+        if let Some(ref public_key) = self.consumer_id.consensus_key {
+            if self
+                .validator_matrix
+                .is_active_or_upcoming_validator(public_key)
+            {
+                return PeerClass::Validator;
+            }
+        }
+        PeerClass::NonValidator
+    }
+
+    /// Waits until the peer is allocated `amount` additional bytes of its quota for messages of
+    /// the given class.
+    ///
+    /// Returns `true` if the peer has been exceeding the quota continuously for longer than the
+    /// configured `block_after_violating_for` duration.
+    pub(super) async fn request_class_allowance(
+        &mut self,
+        class: MessageClass,
+        amount: u32,
+    ) -> bool {
+        let class_quotas = match self.class_quotas {
+            Some(ref class_quotas) => class_quotas.clone(),
+            None => return false,
+        };
+
+        if self.validator_matrix.is_empty() {
+            // Same as with the overall limit, do not limit at all when not initialized.
+            trace!("empty set of validators, not limiting message classes at all");
+            return false;
+        }
+
+        let quota = class_quotas.quotas.quota(class);
+        let bytes_per_second = match self.peer_class() {
+            PeerClass::Validator => quota.validators,
+            PeerClass::NonValidator => quota.non_validators,
+        };
+        if bytes_per_second == 0 {
+            return false;
+        }
+
+        let now = Instant::now();
+        let bucket = self.quota_buckets.entry(class).or_insert(QuotaBucket {
+            available: 0,
+            last_refill: now,
+            violating_since: None,
+        });
+        bucket.refill(now, bytes_per_second);
+
+        let mut sustained_violation = false;
+        if bucket.available < 0 {
+            class_quotas
+                .quota_exceeded
+                .with_label_values(&[class.label()])
+                .inc();
+
+            let violating_since = *bucket.violating_since.get_or_insert(now);
+            let block_after: Duration = class_quotas.quotas.block_after_violating_for.into();
+            sustained_violation =
+                !block_after.is_zero() && now.duration_since(violating_since) >= block_after;
+
+            let estimated_time_remaining =
+                Duration::from_millis((-bucket.available) as u64 * 1000 / bytes_per_second as u64);
+            tokio::time::sleep(estimated_time_remaining).await;
+            bucket.refill(Instant::now(), bytes_per_second);
+        } else {
+            bucket.violating_since = None;
+        }
+
+        // As with the overall limit, a resulting deficit is the next request's problem.
+        bucket.available -= amount as i64;
+
+        sustained_violation
+    }
+
     /// Waits until the requester is allocated `amount` additional resources.
     pub(super) async fn request_allowance(&self, amount: u32) {
         // As a first step, determine the peer class by checking if our id is in the validator set.
@@ -209,20 +394,7 @@ impl LimiterHandle {
             return;
         }
 
-        let peer_class = if let Some(ref public_key) = self.consumer_id.consensus_key {
-            if self
-                .validator_matrix
-                .is_active_or_upcoming_validator(public_key)
-            {
-                PeerClass::Validator
-            } else {
-                PeerClass::NonValidator
-            }
-        } else {
-            PeerClass::NonValidator
-        };
-
-        match peer_class {
+        match self.peer_class() {
             PeerClass::Validator => {
                 // No limit imposed on validators.
             }
@@ -293,13 +465,17 @@ struct ConsumerId {
 mod tests {
     use std::{sync::Arc, time::Duration};
 
-    use casper_types::{EraId, SecretKey};
+    use casper_types::{EraId, SecretKey, TimeDiff};
     use num_rational::Ratio;
-    use prometheus::Counter;
+    use prometheus::{Counter, IntCounterVec, Opts};
     use tokio::time::Instant;
 
-    use super::{Limiter, NodeId, PublicKey};
-    use crate::{testing::init_logging, types::ValidatorMatrix};
+    use super::{Limiter, MessageClass, NodeId, PublicKey};
+    use crate::{
+        components::network::config::{MessageClassQuota, MessageClassQuotas},
+        testing::init_logging,
+        types::ValidatorMatrix,
+    };
 
     /// Something that happens almost immediately, with some allowance for test jitter.
     const SHORT_TIME: Duration = Duration::from_millis(250);
@@ -547,4 +723,73 @@ mod tests {
             v_completed
         );
     }
+
+    #[tokio::test]
+    async fn class_quotas_limit_each_peer_and_detect_sustained_violations() {
+        let mut rng = crate::new_rng();
+
+        // We insert one unrelated active validator to avoid triggering the automatic disabling of
+        // the limiter in case there are no active validators.
+        let validator_matrix =
+            ValidatorMatrix::new_with_validator(Arc::new(SecretKey::random(&mut rng)));
+        let quota_exceeded = IntCounterVec::new(
+            Opts::new(
+                "test_quota_exceeded",
+                "quota exceeded counter used in tests",
+            ),
+            &["message_class"],
+        )
+        .expect("could not create new counter");
+        let quotas = MessageClassQuotas {
+            fetch: MessageClassQuota {
+                validators: 0,
+                non_validators: 1_000,
+            },
+            block_after_violating_for: TimeDiff::from_seconds(1),
+            ..Default::default()
+        };
+        let limiter = Limiter::new(0, new_wait_time_sec(), validator_matrix)
+            .with_class_quotas(quotas, quota_exceeded.clone());
+
+        let mut handle = limiter.create_handle(NodeId::random(&mut rng), None);
+        let start = Instant::now();
+
+        // Classes without a quota are not limited, the first fetch puts the peer into a deficit.
+        assert!(
+            !handle
+                .request_class_allowance(MessageClass::DeployGossip, 10_000)
+                .await
+        );
+        assert!(
+            !handle
+                .request_class_allowance(MessageClass::Fetch, 1_000)
+                .await
+        );
+        assert!(start.elapsed() < SHORT_TIME);
+
+        // The next two fetches have to wait for the deficit to be paid off, the latter one after
+        // the peer has been exceeding its quota for a full second.
+        assert!(
+            !handle
+                .request_class_allowance(MessageClass::Fetch, 1_000)
+                .await
+        );
+        assert!(
+            handle
+                .request_class_allowance(MessageClass::Fetch, 1_000)
+                .await
+        );
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert_eq!(quota_exceeded.with_label_values(&["fetch"]).get(), 2);
+
+        // Another peer is not affected by the first peer's deficit.
+        let mut other_handle = limiter.create_handle(NodeId::random(&mut rng), None);
+        let other_start = Instant::now();
+        assert!(
+            !other_handle
+                .request_class_allowance(MessageClass::Fetch, 1_000)
+                .await
+        );
+        assert!(other_start.elapsed() < SHORT_TIME);
+    }
 }
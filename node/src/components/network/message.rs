@@ -81,6 +81,17 @@ impl<P: Payload> Message<P> {
         }
     }
 
+    /// Returns the fine-grained message type used for per-peer traffic accounting.
+    #[inline]
+    pub(super) fn message_type(&self) -> &'static str {
+        match self {
+            Message::Handshake { .. } => "handshake",
+            Message::Ping { .. } => "ping",
+            Message::Pong { .. } => "pong",
+            Message::Payload(payload) => payload.message_type(),
+        }
+    }
+
     /// Determines whether or not a message is low priority.
     #[inline]
     pub(super) fn is_low_priority(&self) -> bool {
@@ -364,6 +375,11 @@ pub(crate) trait Payload:
     /// Classifies the payload based on its contents.
     fn message_kind(&self) -> MessageKind;
 
+    /// Returns a fine-grained label for the type of payload, used in traffic accounting.
+    ///
+    /// Must be drawn from a small, fixed set of values, as it is used as a metrics label.
+    fn message_type(&self) -> &'static str;
+
     /// The penalty for resource usage of a message to be applied when processed as incoming.
     fn incoming_resource_estimate(&self, _weights: &EstimatorWeights) -> u32;
 
@@ -1,10 +1,13 @@
 use std::sync::Weak;
 
-use prometheus::{Counter, IntCounter, IntGauge, Registry};
+use prometheus::{Counter, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry};
 use tracing::debug;
 
-use super::{outgoing::OutgoingMetrics, MessageKind};
-use crate::unregister_metric;
+use super::{outgoing::OutgoingMetrics, traffic::PeerTrafficSnapshot, MessageKind};
+use crate::{types::NodeId, unregister_metric};
+
+/// Maximum number of peers exported with individual traffic metrics, to bound label cardinality.
+const MAX_PEERS_WITH_TRAFFIC_METRICS: usize = 10;
 
 /// Network-type agnostic networking metrics.
 #[derive(Debug)]
@@ -105,6 +108,23 @@ pub(super) struct Metrics {
     /// Count of incoming messages with other payload.
     pub(super) in_count_other: IntCounter,
 
+    /// Volume in bytes of outgoing messages, by message type.
+    pub(super) out_traffic_bytes: IntCounterVec,
+    /// Count of outgoing messages, by message type.
+    pub(super) out_traffic_count: IntCounterVec,
+    /// Volume in bytes of incoming messages, by message type.
+    pub(super) in_traffic_bytes: IntCounterVec,
+    /// Count of incoming messages, by message type.
+    pub(super) in_traffic_count: IntCounterVec,
+    /// Number of times a peer exceeded its quota for outgoing messages, by message class.
+    pub(super) out_quota_exceeded: IntCounterVec,
+    /// Number of times a peer exceeded its quota for incoming messages, by message class.
+    pub(super) in_quota_exceeded: IntCounterVec,
+    /// Volume in bytes received from the peers we received the most data from.
+    pub(super) top_peer_in_bytes: IntGaugeVec,
+    /// Volume in bytes sent to the peers we received the most data from.
+    pub(super) top_peer_out_bytes: IntGaugeVec,
+
     /// Number of trie requests accepted for processing.
     pub(super) requests_for_trie_accepted: IntCounter,
     /// Number of trie requests finished (successful or unsuccessful).
@@ -328,6 +348,63 @@ impl Metrics {
             "number of trie requests finished, successful or not",
         )?;
 
+        let out_traffic_bytes = IntCounterVec::new(
+            Opts::new(
+                "net_out_traffic_bytes",
+                "volume in bytes of outgoing messages, by message type",
+            ),
+            &["message_type"],
+        )?;
+        let out_traffic_count = IntCounterVec::new(
+            Opts::new(
+                "net_out_traffic_count",
+                "count of outgoing messages, by message type",
+            ),
+            &["message_type"],
+        )?;
+        let in_traffic_bytes = IntCounterVec::new(
+            Opts::new(
+                "net_in_traffic_bytes",
+                "volume in bytes of incoming messages, by message type",
+            ),
+            &["message_type"],
+        )?;
+        let in_traffic_count = IntCounterVec::new(
+            Opts::new(
+                "net_in_traffic_count",
+                "count of incoming messages, by message type",
+            ),
+            &["message_type"],
+        )?;
+        let out_quota_exceeded = IntCounterVec::new(
+            Opts::new(
+                "net_out_quota_exceeded",
+                "number of times a peer exceeded its quota for outgoing messages, by message class",
+            ),
+            &["message_class"],
+        )?;
+        let in_quota_exceeded = IntCounterVec::new(
+            Opts::new(
+                "net_in_quota_exceeded",
+                "number of times a peer exceeded its quota for incoming messages, by message class",
+            ),
+            &["message_class"],
+        )?;
+        let top_peer_in_bytes = IntGaugeVec::new(
+            Opts::new(
+                "net_top_peer_in_bytes",
+                "volume in bytes received from the peers we received the most data from",
+            ),
+            &["peer"],
+        )?;
+        let top_peer_out_bytes = IntGaugeVec::new(
+            Opts::new(
+                "net_top_peer_out_bytes",
+                "volume in bytes sent to the peers we received the most data from",
+            ),
+            &["peer"],
+        )?;
+
         let accumulated_outgoing_limiter_delay = Counter::new(
             "accumulated_outgoing_limiter_delay",
             "seconds spent delaying outgoing traffic to non-validators due to limiter, in seconds",
@@ -392,6 +469,15 @@ impl Metrics {
         registry.register(Box::new(in_bytes_trie_transfer.clone()))?;
         registry.register(Box::new(in_bytes_other.clone()))?;
 
+        registry.register(Box::new(out_traffic_bytes.clone()))?;
+        registry.register(Box::new(out_traffic_count.clone()))?;
+        registry.register(Box::new(in_traffic_bytes.clone()))?;
+        registry.register(Box::new(in_traffic_count.clone()))?;
+        registry.register(Box::new(out_quota_exceeded.clone()))?;
+        registry.register(Box::new(in_quota_exceeded.clone()))?;
+        registry.register(Box::new(top_peer_in_bytes.clone()))?;
+        registry.register(Box::new(top_peer_out_bytes.clone()))?;
+
         registry.register(Box::new(requests_for_trie_accepted.clone()))?;
         registry.register(Box::new(requests_for_trie_finished.clone()))?;
 
@@ -448,6 +534,14 @@ impl Metrics {
             in_bytes_block_transfer,
             in_bytes_trie_transfer,
             in_bytes_other,
+            out_traffic_bytes,
+            out_traffic_count,
+            in_traffic_bytes,
+            in_traffic_count,
+            out_quota_exceeded,
+            in_quota_exceeded,
+            top_peer_in_bytes,
+            top_peer_out_bytes,
             requests_for_trie_accepted,
             requests_for_trie_finished,
             accumulated_outgoing_limiter_delay,
@@ -457,8 +551,21 @@ impl Metrics {
     }
 
     /// Records an outgoing payload.
-    pub(crate) fn record_payload_out(this: &Weak<Self>, kind: MessageKind, size: u64) {
+    pub(crate) fn record_payload_out(
+        this: &Weak<Self>,
+        kind: MessageKind,
+        message_type: &'static str,
+        size: u64,
+    ) {
         if let Some(metrics) = this.upgrade() {
+            metrics
+                .out_traffic_bytes
+                .with_label_values(&[message_type])
+                .inc_by(size);
+            metrics
+                .out_traffic_count
+                .with_label_values(&[message_type])
+                .inc();
             match kind {
                 MessageKind::Protocol => {
                     metrics.out_bytes_protocol.inc_by(size);
@@ -507,8 +614,21 @@ impl Metrics {
     }
 
     /// Records an incoming payload.
-    pub(crate) fn record_payload_in(this: &Weak<Self>, kind: MessageKind, size: u64) {
+    pub(crate) fn record_payload_in(
+        this: &Weak<Self>,
+        kind: MessageKind,
+        message_type: &'static str,
+        size: u64,
+    ) {
         if let Some(metrics) = this.upgrade() {
+            metrics
+                .in_traffic_bytes
+                .with_label_values(&[message_type])
+                .inc_by(size);
+            metrics
+                .in_traffic_count
+                .with_label_values(&[message_type])
+                .inc();
             match kind {
                 MessageKind::Protocol => {
                     metrics.in_bytes_protocol.inc_by(size);
@@ -567,6 +687,27 @@ impl Metrics {
         }
     }
 
+    /// Updates the per-peer traffic metrics from a traffic snapshot.
+    ///
+    /// Only the peers we received the most data from are exported, all others are dropped.
+    pub(super) fn update_top_peers(&self, traffic: &[(NodeId, PeerTrafficSnapshot)]) {
+        self.top_peer_in_bytes.reset();
+        self.top_peer_out_bytes.reset();
+
+        let mut top_peers: Vec<_> = traffic.iter().collect();
+        top_peers.sort_by_key(|(_, snapshot)| std::cmp::Reverse(snapshot.total_in.bytes));
+
+        for (peer_id, snapshot) in top_peers.into_iter().take(MAX_PEERS_WITH_TRAFFIC_METRICS) {
+            let label = format!("tls:{}", base16::encode_lower(peer_id.hash_bytes()));
+            self.top_peer_in_bytes
+                .with_label_values(&[&label])
+                .set(snapshot.total_in.bytes as i64);
+            self.top_peer_out_bytes
+                .with_label_values(&[&label])
+                .set(snapshot.total_out.bytes as i64);
+        }
+    }
+
     /// Records that a trie request has been started.
     pub(super) fn record_trie_request_start(this: &Weak<Self>) {
         if let Some(metrics) = this.upgrade() {
@@ -643,6 +784,15 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.in_bytes_trie_transfer);
         unregister_metric!(self.registry, self.in_bytes_other);
 
+        unregister_metric!(self.registry, self.out_traffic_bytes);
+        unregister_metric!(self.registry, self.out_traffic_count);
+        unregister_metric!(self.registry, self.in_traffic_bytes);
+        unregister_metric!(self.registry, self.in_traffic_count);
+        unregister_metric!(self.registry, self.out_quota_exceeded);
+        unregister_metric!(self.registry, self.in_quota_exceeded);
+        unregister_metric!(self.registry, self.top_peer_in_bytes);
+        unregister_metric!(self.registry, self.top_peer_out_bytes);
+
         unregister_metric!(self.registry, self.requests_for_trie_accepted);
         unregister_metric!(self.registry, self.requests_for_trie_finished);
 
@@ -291,7 +291,7 @@ pub struct OutgoingConfig {
     pub(crate) retry_attempts: u8,
     /// The basic time slot for exponential backoff when reconnecting.
     pub(crate) base_timeout: Duration,
-    /// Time until an outgoing address is unblocked.
+    /// Time until an outgoing address is unblocked, unless it was blocked permanently.
     pub(crate) unblock_after: Duration,
     /// Safety timeout, after which a connection is no longer expected to finish dialing.
     pub(crate) sweep_timeout: Duration,
@@ -650,11 +650,28 @@ where
         }
     }
 
+    /// Returns an iterator over all blocked addresses, along with the justification for blocking.
+    pub(crate) fn blocked_addrs(
+        &self,
+    ) -> impl Iterator<Item = (SocketAddr, &BlocklistJustification)> + '_ {
+        self.outgoing
+            .iter()
+            .filter_map(|(addr, outgoing)| match outgoing.state {
+                OutgoingState::Blocked {
+                    ref justification, ..
+                } => Some((*addr, justification)),
+                _ => None,
+            })
+    }
+
+    /// Returns an iterator over all known addresses, regardless of their state.
+    pub(crate) fn known_addrs(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        self.outgoing.keys().copied()
+    }
+
     /// Removes an address from the block list.
     ///
     /// Does nothing if the address was not blocked.
-    // This function is currently not in use by `network` itself.
-    #[allow(dead_code)]
     pub(crate) fn redeem_addr(&mut self, addr: SocketAddr, now: Instant) -> Option<DialRequest<H>> {
         let span = make_span(addr, self.outgoing.get(&addr));
         span.clone()
@@ -753,8 +770,11 @@ where
                     }
                 }
 
-                OutgoingState::Blocked { since, .. } => {
-                    if now >= since + self.config.unblock_after {
+                OutgoingState::Blocked {
+                    since,
+                    ref justification,
+                } => {
+                    if !justification.is_permanent() && now >= since + self.config.unblock_after {
                         info!("address unblocked");
 
                         to_reconnect.push((addr, 0));
@@ -1674,6 +1694,35 @@ mod tests {
         assert!(manager.is_blocked(addr_a));
     }
 
+    #[test]
+    fn permanent_blocks_do_not_expire() {
+        init_logging();
+
+        let mut rng = crate::new_rng();
+        let mut clock = TestClock::new();
+
+        let addr_a: SocketAddr = "1.2.3.4:1234".parse().unwrap();
+
+        let mut manager = OutgoingManager::<u32, TestDialerError>::new(test_config());
+
+        assert!(manager
+            .block_addr(addr_a, clock.now(), BlocklistJustification::Operator)
+            .is_none());
+        assert_eq!(manager.blocked_addrs().count(), 1);
+
+        // Well past `unblock_after`, the address is still blocked.
+        clock.advance_time(600_000);
+        assert!(manager
+            .perform_housekeeping(&mut rng, clock.now())
+            .is_empty());
+        assert!(manager.is_blocked(addr_a));
+
+        // It is only unblocked once redeemed.
+        assert!(dials(addr_a, &manager.redeem_addr(addr_a, clock.now())));
+        assert!(!manager.is_blocked(addr_a));
+        assert_eq!(manager.blocked_addrs().count(), 0);
+    }
+
     #[test]
     fn emits_and_accepts_pings() {
         init_logging();
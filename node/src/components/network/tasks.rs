@@ -42,14 +42,16 @@ use tracing::{
 use casper_types::{ProtocolVersion, PublicKey, TimeDiff};
 
 use super::{
+    blocklist::BlocklistJustification,
     chain_info::ChainInfo,
-    counting_format::{ConnectionId, Role},
+    counting_format::{ConnectionId, LastFrameSize, Role},
     error::{ConnectionError, IoError},
     event::{IncomingConnection, OutgoingConnection},
     full_transport,
-    limiter::LimiterHandle,
+    limiter::{LimiterHandle, MessageClass},
     message::NodeKeyPair,
     message_pack_format::MessagePackFormat,
+    traffic::TrafficTable,
     EstimatorWeights, Event, FramedTransport, FullTransport, Identity, Message, Metrics, Payload,
     Transport,
 };
@@ -176,6 +178,8 @@ where
             // Setup full framed transport, then close down receiving end of the transport.
             let full_transport = full_transport::<P>(
                 context.net_metrics.clone(),
+                context.traffic.peer(peer_id),
+                LastFrameSize::default(),
                 connection_id,
                 framed_transport,
                 Role::Dialer,
@@ -216,6 +220,8 @@ where
     secret_key: Arc<PKey<Private>>,
     /// Weak reference to the networking metrics shared by all sender/receiver tasks.
     net_metrics: Weak<Metrics>,
+    /// Per-peer traffic counters shared by all sender/receiver tasks.
+    traffic: TrafficTable,
     /// Chain info extract from chainspec.
     chain_info: ChainInfo,
     /// Optional set of signing keys, to identify as a node during handshake.
@@ -268,6 +274,7 @@ impl<REv> NetworkContext<REv> {
             network_ca,
             secret_key,
             net_metrics: Arc::downgrade(net_metrics),
+            traffic: TrafficTable::default(),
             chain_info,
             node_key_pair,
             handshake_timeout: cfg.handshake_timeout,
@@ -299,6 +306,11 @@ impl<REv> NetworkContext<REv> {
         self.public_addr
     }
 
+    /// Per-peer traffic counters.
+    pub(super) fn traffic(&self) -> &TrafficTable {
+        &self.traffic
+    }
+
     /// Chain info extract from chainspec.
     pub(super) fn chain_info(&self) -> &ChainInfo {
         &self.chain_info
@@ -368,8 +380,11 @@ where
             }
 
             // Establish full transport and close the receiving end.
+            let last_frame_size = LastFrameSize::default();
             let full_transport = full_transport::<P>(
                 context.net_metrics.clone(),
+                context.traffic.peer(peer_id),
+                last_frame_size.clone(),
                 connection_id,
                 framed_transport,
                 Role::Listener,
@@ -383,6 +398,7 @@ where
                 peer_id,
                 peer_consensus_public_key,
                 stream,
+                last_frame_size,
             }
         }
         Err(error) => IncomingConnection::Failed {
@@ -657,7 +673,8 @@ pub(super) async fn server<P, REv>(
 pub(super) async fn message_reader<REv, P>(
     context: Arc<NetworkContext<REv>>,
     mut stream: SplitStream<FullTransport<P>>,
-    limiter: LimiterHandle,
+    last_frame_size: LastFrameSize,
+    mut limiter: LimiterHandle,
     mut close_incoming_receiver: watch::Receiver<()>,
     peer_id: NodeId,
     span: Span,
@@ -681,6 +698,21 @@ where
 
                     let effect_builder = EffectBuilder::new(event_queue);
 
+                    // Enforce the peer's quota for this class of message, if any. Unlike the
+                    // overall limit, quotas apply to demands as well.
+                    if let Some(class) = MessageClass::from_message_kind(msg.classify()) {
+                        let size = last_frame_size.get();
+                        if limiter.request_class_allowance(class, size).await {
+                            effect_builder
+                                .announce_block_peer_with_justification(
+                                    peer_id,
+                                    BlocklistJustification::ExceededMessageQuota { class },
+                                )
+                                .await;
+                            return Ok(());
+                        }
+                    }
+
                     match msg.try_into_demand(effect_builder, peer_id) {
                         Ok((event, wait_for_response)) => {
                             // Note: For now, demands bypass the limiter, as we expect the
@@ -797,7 +829,7 @@ where
 pub(super) async fn message_sender<P>(
     mut queue: UnboundedReceiver<MessageQueueItem<P>>,
     mut sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
-    limiter: LimiterHandle,
+    mut limiter: LimiterHandle,
     counter: IntGauge,
 ) where
     P: Payload,
@@ -815,6 +847,12 @@ pub(super) async fn message_sender<P>(
                 break;
             }
         };
+        if let Some(class) = MessageClass::from_message_kind(message.classify()) {
+            // Outgoing quotas only delay sending, they never cause peers to be blocked.
+            let _ = limiter
+                .request_class_allowance(class, estimated_wire_size)
+                .await;
+        }
         limiter.request_allowance(estimated_wire_size).await;
 
         let mut outcome = sink.send(message).await;
@@ -5,6 +5,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    env,
     fmt::{self, Debug, Display, Formatter},
     sync::Arc,
     time::{Duration, Instant},
@@ -159,6 +160,12 @@ impl Payload for Message {
         }
     }
 
+    fn message_type(&self) -> &'static str {
+        match self {
+            Message::AddressGossiper(_) => "address_gossip",
+        }
+    }
+
     fn incoming_resource_estimate(&self, _weights: &super::EstimatorWeights) -> u32 {
         0
     }
@@ -194,6 +201,7 @@ impl Reactor for TestReactor {
         let secret_key = SecretKey::random(rng);
         let mut net = Network::new(
             cfg,
+            &env::temp_dir(),
             our_identity,
             None,
             registry,
@@ -0,0 +1,244 @@
+//! Per-peer traffic accounting.
+//!
+//! Every established connection holds a handle to the [`PeerTraffic`] entry of its peer, updating
+//! byte and message counters keyed by the fine-grained message type (see
+//! [`Payload::message_type`](super::Payload::message_type)) whenever a message is sent or received.
+//! Entries of peers that are no longer connected are kept around for a while, so that a peer that
+//! was disconnected for misbehaving can still be identified afterwards.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::types::NodeId;
+
+/// Byte and message counters for a single message type in a single direction.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub(super) struct TrafficCounter {
+    /// Number of messages.
+    pub(super) count: u64,
+    /// Total number of serialized bytes.
+    pub(super) bytes: u64,
+}
+
+impl TrafficCounter {
+    /// Records a single message of the given size.
+    fn record(&mut self, size: u64) {
+        self.count = self.count.saturating_add(1);
+        self.bytes = self.bytes.saturating_add(size);
+    }
+
+    /// Adds the values of another counter to this one.
+    fn add(&mut self, other: &TrafficCounter) {
+        self.count = self.count.saturating_add(other.count);
+        self.bytes = self.bytes.saturating_add(other.bytes);
+    }
+}
+
+/// Traffic exchanged with a single peer, broken down by message type.
+#[derive(Clone, Debug, Default, Serialize)]
+pub(super) struct PeerTrafficSnapshot {
+    /// Totals of all incoming messages.
+    pub(super) total_in: TrafficCounter,
+    /// Totals of all outgoing messages.
+    pub(super) total_out: TrafficCounter,
+    /// Incoming traffic by message type.
+    pub(super) incoming: BTreeMap<&'static str, TrafficCounter>,
+    /// Outgoing traffic by message type.
+    pub(super) outgoing: BTreeMap<&'static str, TrafficCounter>,
+}
+
+impl PeerTrafficSnapshot {
+    /// Returns the message types seen, along with their incoming and outgoing counters, ordered
+    /// by descending total byte count.
+    pub(super) fn by_message_type(&self) -> Vec<(&'static str, TrafficCounter, TrafficCounter)> {
+        let mut rows: BTreeMap<&'static str, (TrafficCounter, TrafficCounter)> = BTreeMap::new();
+        for (message_type, counter) in &self.incoming {
+            rows.entry(message_type).or_default().0.add(counter);
+        }
+        for (message_type, counter) in &self.outgoing {
+            rows.entry(message_type).or_default().1.add(counter);
+        }
+
+        let mut rows: Vec<_> = rows
+            .into_iter()
+            .map(|(message_type, (incoming, outgoing))| (message_type, incoming, outgoing))
+            .collect();
+        rows.sort_by_key(|(_, incoming, outgoing)| {
+            std::cmp::Reverse(incoming.bytes.saturating_add(outgoing.bytes))
+        });
+        rows
+    }
+}
+
+/// Mutable state of a [`PeerTraffic`] entry.
+#[derive(Debug)]
+struct PeerTrafficState {
+    /// The accumulated counters.
+    snapshot: PeerTrafficSnapshot,
+    /// When traffic was last recorded.
+    last_activity: Instant,
+}
+
+/// Traffic counters of a single peer, shared by all connections to and from it.
+#[derive(Debug)]
+pub(super) struct PeerTraffic(Mutex<PeerTrafficState>);
+
+impl PeerTraffic {
+    fn new(now: Instant) -> Self {
+        PeerTraffic(Mutex::new(PeerTrafficState {
+            snapshot: PeerTrafficSnapshot::default(),
+            last_activity: now,
+        }))
+    }
+
+    /// Records a message received from the peer.
+    pub(super) fn record_in(&self, message_type: &'static str, size: u64) {
+        let mut state = self.lock();
+        state.snapshot.total_in.record(size);
+        state
+            .snapshot
+            .incoming
+            .entry(message_type)
+            .or_default()
+            .record(size);
+        state.last_activity = Instant::now();
+    }
+
+    /// Records a message sent to the peer.
+    pub(super) fn record_out(&self, message_type: &'static str, size: u64) {
+        let mut state = self.lock();
+        state.snapshot.total_out.record(size);
+        state
+            .snapshot
+            .outgoing
+            .entry(message_type)
+            .or_default()
+            .record(size);
+        state.last_activity = Instant::now();
+    }
+
+    /// Locks the state, ignoring poisoning, as counters are always left in a consistent state.
+    fn lock(&self) -> std::sync::MutexGuard<'_, PeerTrafficState> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Table of per-peer traffic counters.
+#[derive(Debug, Default)]
+pub(super) struct TrafficTable {
+    peers: Mutex<HashMap<NodeId, Arc<PeerTraffic>>>,
+}
+
+impl TrafficTable {
+    /// Returns the traffic entry of the given peer, creating it if necessary.
+    pub(super) fn peer(&self, peer_id: NodeId) -> Arc<PeerTraffic> {
+        self.lock()
+            .entry(peer_id)
+            .or_insert_with(|| Arc::new(PeerTraffic::new(Instant::now())))
+            .clone()
+    }
+
+    /// Removes entries of peers that have no live connection and have been idle for longer than
+    /// `retention`.
+    pub(super) fn sweep(&self, now: Instant, retention: Duration) {
+        self.lock().retain(|_, traffic| {
+            // The table itself holds one reference, every connection to the peer another.
+            Arc::strong_count(traffic) > 1
+                || now.saturating_duration_since(traffic.lock().last_activity) < retention
+        });
+    }
+
+    /// Returns a snapshot of all entries, ordered by descending number of bytes received.
+    pub(super) fn snapshot(&self) -> Vec<(NodeId, PeerTrafficSnapshot)> {
+        let mut entries: Vec<_> = self
+            .lock()
+            .iter()
+            .map(|(peer_id, traffic)| (*peer_id, traffic.lock().snapshot.clone()))
+            .collect();
+        entries.sort_by_key(|(_, snapshot)| std::cmp::Reverse(snapshot.total_in.bytes));
+        entries
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<NodeId, Arc<PeerTraffic>>> {
+        self.peers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use casper_types::testing::TestRng;
+
+    use super::{TrafficCounter, TrafficTable};
+    use crate::types::NodeId;
+
+    #[test]
+    fn accounts_traffic_per_peer_and_message_type() {
+        let mut rng = TestRng::new();
+        let flooder = NodeId::random(&mut rng);
+        let quiet = NodeId::random(&mut rng);
+        let table = TrafficTable::default();
+
+        let flooder_traffic = table.peer(flooder);
+        flooder_traffic.record_in("get_response.trie_or_chunk", 1000);
+        flooder_traffic.record_in("get_response.trie_or_chunk", 500);
+        flooder_traffic.record_in("consensus", 10);
+        flooder_traffic.record_out("get_request.trie_or_chunk", 20);
+        table.peer(quiet).record_in("consensus", 100);
+
+        let snapshot = table.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].0, flooder);
+
+        let flooder_snapshot = &snapshot[0].1;
+        assert_eq!(
+            flooder_snapshot.total_in,
+            TrafficCounter {
+                count: 3,
+                bytes: 1510
+            }
+        );
+        assert_eq!(
+            flooder_snapshot.incoming["get_response.trie_or_chunk"],
+            TrafficCounter {
+                count: 2,
+                bytes: 1500
+            }
+        );
+        let rows = flooder_snapshot.by_message_type();
+        assert_eq!(rows[0].0, "get_response.trie_or_chunk");
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[test]
+    fn sweep_keeps_connected_and_recently_active_peers() {
+        let mut rng = TestRng::new();
+        let connected = NodeId::random(&mut rng);
+        let disconnected = NodeId::random(&mut rng);
+        let table = TrafficTable::default();
+
+        let handle = table.peer(connected);
+        table.peer(disconnected).record_in("consensus", 1);
+
+        let retention = Duration::from_secs(60);
+        let now = Instant::now();
+        table.sweep(now, retention);
+        assert_eq!(table.snapshot().len(), 2);
+
+        table.sweep(now + retention * 2, retention);
+        let remaining = table.snapshot();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].0, connected);
+        drop(handle);
+    }
+}
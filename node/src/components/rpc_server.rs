@@ -453,6 +453,7 @@ where
                 self.api_version,
                 cfg.qps_limit,
                 cfg.max_body_bytes,
+                cfg.max_batch_size,
                 cfg.cors_origin.clone(),
             ));
             Some(())
@@ -468,7 +469,9 @@ where
             self.api_version,
             cfg.qps_limit,
             cfg.max_body_bytes,
+            cfg.max_batch_size,
             cfg.cors_origin.clone(),
+            cfg.enable_get_keys_with_prefix,
         ));
 
         Ok(Effects::new())
@@ -19,9 +19,11 @@ use hyper::server::{conn::AddrIncoming, Builder};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tokio::sync::oneshot;
-use tower::ServiceBuilder;
-use tracing::info;
+use tokio::{
+    sync::{oneshot, Mutex},
+    time::{self, Instant},
+};
+use tracing::{info, warn};
 use warp::Filter;
 
 use casper_json_rpc::{
@@ -85,14 +87,22 @@ pub(super) trait RpcWithParams {
 
     /// Registers this RPC as the handler for JSON-RPC requests whose "method" field is the same as
     /// `Self::METHOD`.
+    ///
+    /// Every request waits for an allowance from `limiter` before being handled.
     fn register_as_handler<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
         api_version: ProtocolVersion,
+        limiter: &RequestLimiter,
         handlers_builder: &mut RequestHandlersBuilder,
     ) {
-        let handler = move |maybe_params| async move {
-            let params = Self::try_parse_params(maybe_params)?;
-            Self::do_handle_request(effect_builder, api_version, params).await
+        let limiter = limiter.clone();
+        let handler = move |maybe_params| {
+            let limiter = limiter.clone();
+            async move {
+                limiter.request_allowance().await;
+                let params = Self::try_parse_params(maybe_params)?;
+                Self::do_handle_request(effect_builder, api_version, params).await
+            }
         };
         handlers_builder.register_handler(Self::METHOD, Arc::new(handler))
     }
@@ -144,14 +154,22 @@ pub(super) trait RpcWithoutParams {
 
     /// Registers this RPC as the handler for JSON-RPC requests whose "method" field is the same as
     /// `Self::METHOD`.
+    ///
+    /// Every request waits for an allowance from `limiter` before being handled.
     fn register_as_handler<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
         api_version: ProtocolVersion,
+        limiter: &RequestLimiter,
         handlers_builder: &mut RequestHandlersBuilder,
     ) {
-        let handler = move |maybe_params| async move {
-            Self::check_no_params(maybe_params)?;
-            Self::do_handle_request(effect_builder, api_version).await
+        let limiter = limiter.clone();
+        let handler = move |maybe_params| {
+            let limiter = limiter.clone();
+            async move {
+                limiter.request_allowance().await;
+                Self::check_no_params(maybe_params)?;
+                Self::do_handle_request(effect_builder, api_version).await
+            }
         };
         handlers_builder.register_handler(Self::METHOD, Arc::new(handler))
     }
@@ -224,14 +242,22 @@ pub(super) trait RpcWithOptionalParams {
 
     /// Registers this RPC as the handler for JSON-RPC requests whose "method" field is the same as
     /// `Self::METHOD`.
+    ///
+    /// Every request waits for an allowance from `limiter` before being handled.
     fn register_as_handler<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
         api_version: ProtocolVersion,
+        limiter: &RequestLimiter,
         handlers_builder: &mut RequestHandlersBuilder,
     ) {
-        let handler = move |maybe_params| async move {
-            let params = Self::try_parse_params(maybe_params)?;
-            Self::do_handle_request(effect_builder, api_version, params).await
+        let limiter = limiter.clone();
+        let handler = move |maybe_params| {
+            let limiter = limiter.clone();
+            async move {
+                limiter.request_allowance().await;
+                let params = Self::try_parse_params(maybe_params)?;
+                Self::do_handle_request(effect_builder, api_version, params).await
+            }
         };
         handlers_builder.register_handler(Self::METHOD, Arc::new(handler))
     }
@@ -255,21 +281,25 @@ pub(super) trait RpcWithOptionalParams {
 }
 
 /// Start JSON RPC server with CORS enabled in a background.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run_with_cors(
     builder: Builder<AddrIncoming>,
     handlers: RequestHandlers,
     qps_limit: u64,
     max_body_bytes: u32,
+    max_batch_size: u32,
     api_path: &'static str,
     server_name: &'static str,
     cors_header: CorsOrigin,
 ) {
+    let max_batch_size = max_batch_size_within_qps_limit(max_batch_size, qps_limit);
     let make_svc = hyper::service::make_service_fn(move |_| {
         let service_routes = casper_json_rpc::route_with_cors(
             api_path,
             max_body_bytes,
             handlers.clone(),
             ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+            max_batch_size,
             &cors_header,
         );
 
@@ -283,10 +313,6 @@ pub(super) async fn run_with_cors(
         async move { Ok::<_, Infallible>(service.clone()) }
     });
 
-    let make_svc = ServiceBuilder::new()
-        .rate_limit(qps_limit, Duration::from_secs(1))
-        .service(make_svc);
-
     let server = builder.serve(make_svc);
     info!(address = %server.local_addr(), "started {} server", server_name);
 
@@ -306,15 +332,18 @@ pub(super) async fn run(
     handlers: RequestHandlers,
     qps_limit: u64,
     max_body_bytes: u32,
+    max_batch_size: u32,
     api_path: &'static str,
     server_name: &'static str,
 ) {
+    let max_batch_size = max_batch_size_within_qps_limit(max_batch_size, qps_limit);
     let make_svc = hyper::service::make_service_fn(move |_| {
         let service_routes = casper_json_rpc::route(
             api_path,
             max_body_bytes,
             handlers.clone(),
             ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+            max_batch_size,
         );
 
         // Supports content negotiation for gzip responses. This is an interim fix until
@@ -327,10 +356,6 @@ pub(super) async fn run(
         async move { Ok::<_, Infallible>(service.clone()) }
     });
 
-    let make_svc = ServiceBuilder::new()
-        .rate_limit(qps_limit, Duration::from_secs(1))
-        .service(make_svc);
-
     let server = builder.serve(make_svc);
     info!(address = %server.local_addr(), "started {} server", server_name);
 
@@ -344,10 +369,79 @@ pub(super) async fn run(
     info!("{} server shut down", server_name);
 }
 
+/// Limits the rate at which JSON-RPC requests are handled to `qps_limit` requests per second.
+///
+/// Each request of a batch is charged separately, so batching does not allow a client to exceed
+/// the limit.
+#[derive(Clone, Debug)]
+pub(super) struct RequestLimiter(Arc<Mutex<RequestLimiterState>>);
+
+/// The state of a [`RequestLimiter`].
+#[derive(Debug)]
+struct RequestLimiterState {
+    /// The maximum number of requests per second.
+    qps_limit: u64,
+    /// The number of requests still allowed before the end of the current period.
+    remaining: u64,
+    /// The end of the current period.
+    period_end: Instant,
+}
+
+impl RequestLimiter {
+    /// The period over which `qps_limit` requests are allowed.
+    const PERIOD: Duration = Duration::from_secs(1);
+
+    /// Creates a new limiter allowing `qps_limit` requests per second.
+    pub(super) fn new(qps_limit: u64) -> Self {
+        RequestLimiter(Arc::new(Mutex::new(RequestLimiterState {
+            qps_limit,
+            remaining: qps_limit,
+            period_end: Instant::now() + Self::PERIOD,
+        })))
+    }
+
+    /// Waits until the next request may be handled.
+    ///
+    /// Requests are admitted in the order in which they call this method.
+    async fn request_allowance(&self) {
+        let mut state = self.0.lock().await;
+        let now = Instant::now();
+        if now >= state.period_end {
+            state.remaining = state.qps_limit;
+            state.period_end = now + Self::PERIOD;
+        }
+        if state.remaining == 0 {
+            time::sleep_until(state.period_end).await;
+            state.remaining = state.qps_limit;
+            state.period_end += Self::PERIOD;
+        }
+        state.remaining = state.remaining.saturating_sub(1);
+    }
+}
+
+/// Returns `max_batch_size` capped at `qps_limit`, warning if it had to be capped.
+///
+/// Every request of a batch is charged against the `qps_limit`, so a larger batch could not be
+/// handled within a second.
+fn max_batch_size_within_qps_limit(max_batch_size: u32, qps_limit: u64) -> u32 {
+    match u32::try_from(qps_limit) {
+        Ok(qps_limit) if qps_limit < max_batch_size => {
+            warn!(
+                %max_batch_size,
+                %qps_limit,
+                "configured max_batch_size exceeds qps_limit, limiting batches to qps_limit requests"
+            );
+            qps_limit
+        }
+        _ => max_batch_size,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Write;
 
+    use futures::future;
     use http::StatusCode;
     use warp::{filters::BoxedFilter, Filter, Reply};
 
@@ -356,6 +450,8 @@ mod tests {
     use super::*;
     use crate::types::DeployHash;
 
+    const MAX_BATCH_SIZE: u32 = 1;
+
     async fn send_request(
         method: &str,
         maybe_params: Option<&str>,
@@ -381,6 +477,35 @@ mod tests {
         serde_json::from_slice(&body_bytes).unwrap()
     }
 
+    #[tokio::test]
+    async fn should_charge_every_request_against_qps_limit() {
+        time::pause();
+        let limiter = RequestLimiter::new(3);
+        let start = Instant::now();
+        // The paused clock may wake a sleeping task slightly after its deadline, so only count the
+        // number of whole periods elapsed.
+        let elapsed_periods = || (Instant::now() - start).as_secs();
+
+        // A batch of four requests exceeds the limit by one, delaying that request.
+        future::join_all((0..4).map(|_| limiter.request_allowance())).await;
+        assert_eq!(elapsed_periods(), 1);
+
+        // The other two requests of the second period are admitted immediately.
+        limiter.request_allowance().await;
+        limiter.request_allowance().await;
+        assert_eq!(elapsed_periods(), 1);
+
+        limiter.request_allowance().await;
+        assert_eq!(elapsed_periods(), 2);
+    }
+
+    #[test]
+    fn should_limit_batch_size_to_qps_limit() {
+        assert_eq!(max_batch_size_within_qps_limit(20, 100), 20);
+        assert_eq!(max_batch_size_within_qps_limit(20, 5), 5);
+        assert_eq!(max_batch_size_within_qps_limit(20, u64::MAX), 20);
+    }
+
     mod rpc_with_params {
         use super::*;
         use crate::components::rpc_server::rpcs::info::{
@@ -392,9 +517,13 @@ mod tests {
             GetDeploy::register_as_test_handler(&mut handlers);
             let handlers = handlers.build();
 
-            filters::main_filter(handlers, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST)
-                .recover(filters::handle_rejection)
-                .boxed()
+            filters::main_filter(
+                handlers,
+                ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                MAX_BATCH_SIZE,
+            )
+            .recover(filters::handle_rejection)
+            .boxed()
         }
 
         #[tokio::test]
@@ -459,9 +588,13 @@ mod tests {
             GetPeers::register_as_test_handler(&mut handlers);
             let handlers = handlers.build();
 
-            filters::main_filter(handlers, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST)
-                .recover(filters::handle_rejection)
-                .boxed()
+            filters::main_filter(
+                handlers,
+                ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                MAX_BATCH_SIZE,
+            )
+            .recover(filters::handle_rejection)
+            .boxed()
         }
 
         #[tokio::test]
@@ -513,9 +646,13 @@ mod tests {
             GetBlock::register_as_test_handler(&mut handlers);
             let handlers = handlers.build();
 
-            filters::main_filter(handlers, ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST)
-                .recover(filters::handle_rejection)
-                .boxed()
+            filters::main_filter(
+                handlers,
+                ALLOW_UNKNOWN_FIELDS_IN_JSON_RPC_REQUEST,
+                MAX_BATCH_SIZE,
+            )
+            .recover(filters::handle_rejection)
+            .boxed()
         }
 
         #[tokio::test]
@@ -5,26 +5,32 @@
 
 mod era_summary;
 
-use std::{clone::Clone, num::ParseIntError, str};
+use std::{clone::Clone, collections::BTreeSet, num::ParseIntError, str};
 
 use async_trait::async_trait;
 use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use tracing::info;
 
-use casper_execution_engine::core::engine_state::{self, QueryResult};
+use casper_execution_engine::core::engine_state::{
+    self, executable_deploy_item::ExecutableDeployItem, QueryResult,
+};
 use casper_hashing::Digest;
-use casper_types::{Key, ProtocolVersion, Transfer};
+use casper_types::{ExecutionResult, Key, ProtocolVersion, Transfer};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, ReservedErrorCode, RpcRequest, RpcWithOptionalParams,
+    RpcWithParams,
 };
 use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::{common, state},
-    types::{Block, BlockHash, BlockWithMetadata, JsonBlock},
+    types::{
+        Approval, Block, BlockHash, BlockWithMetadata, Deploy, DeployHash, DeployHeader, JsonBlock,
+    },
 };
 pub use era_summary::EraSummary;
 use era_summary::ERA_SUMMARY;
@@ -36,6 +42,21 @@ static GET_BLOCK_RESULT: Lazy<GetBlockResult> = Lazy::new(|| GetBlockResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block: Some(JsonBlock::doc_example().clone()),
 });
+static GET_BLOCK_WITH_DEPLOYS_PARAMS: Lazy<GetBlockWithDeploysParams> =
+    Lazy::new(|| GetBlockWithDeploysParams {
+        block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+        omit_deploy_bodies: false,
+    });
+static GET_BLOCK_WITH_DEPLOYS_RESULT: Lazy<GetBlockWithDeploysResult> =
+    Lazy::new(|| GetBlockWithDeploysResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        block: JsonBlock::doc_example().clone(),
+        deploys: vec![JsonBlockDeploy::new(
+            Deploy::doc_example().clone(),
+            Some(ExecutionResult::example().clone()),
+            false,
+        )],
+    });
 static GET_BLOCK_TRANSFERS_PARAMS: Lazy<GetBlockTransfersParams> =
     Lazy::new(|| GetBlockTransfersParams {
         block_identifier: BlockIdentifier::Hash(*Block::doc_example().hash()),
@@ -185,6 +206,148 @@ impl RpcWithOptionalParams for GetBlock {
     }
 }
 
+/// Params for "chain_get_block_with_deploys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockWithDeploysParams {
+    /// The block identifier.  If none is passed, the highest block will be used.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// Whether to omit the payment and session code of the deploys.  Defaults to `false`.
+    #[serde(default)]
+    pub omit_deploy_bodies: bool,
+}
+
+impl DocExample for GetBlockWithDeploysParams {
+    fn doc_example() -> &'static Self {
+        &GET_BLOCK_WITH_DEPLOYS_PARAMS
+    }
+}
+
+/// A deploy included in a block, along with the result of executing it in that block.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonBlockDeploy {
+    /// The deploy hash.
+    pub hash: DeployHash,
+    /// The deploy header.
+    pub header: DeployHeader,
+    /// The payment code, omitted if deploy bodies were not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payment: Option<ExecutableDeployItem>,
+    /// The session code, omitted if deploy bodies were not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<ExecutableDeployItem>,
+    /// The finalized approvals of the deploy.
+    pub approvals: BTreeSet<Approval>,
+    /// The result of executing the deploy in the block, if known to this node.
+    pub execution_result: Option<ExecutionResult>,
+}
+
+impl JsonBlockDeploy {
+    fn new(
+        deploy: Deploy,
+        execution_result: Option<ExecutionResult>,
+        omit_deploy_body: bool,
+    ) -> Self {
+        let (payment, session) = if omit_deploy_body {
+            (None, None)
+        } else {
+            (
+                Some(deploy.payment().clone()),
+                Some(deploy.session().clone()),
+            )
+        };
+        JsonBlockDeploy {
+            hash: *deploy.hash(),
+            header: deploy.header().clone(),
+            payment,
+            session,
+            approvals: deploy.approvals().clone(),
+            execution_result,
+        }
+    }
+}
+
+/// Result for "chain_get_block_with_deploys" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockWithDeploysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The block.
+    pub block: JsonBlock,
+    /// The block's deploys and transfers, in the order they appear in the block.
+    pub deploys: Vec<JsonBlockDeploy>,
+}
+
+impl DocExample for GetBlockWithDeploysResult {
+    fn doc_example() -> &'static Self {
+        &GET_BLOCK_WITH_DEPLOYS_RESULT
+    }
+}
+
+/// "chain_get_block_with_deploys" RPC.
+pub struct GetBlockWithDeploys {}
+
+#[async_trait]
+impl RpcWithParams for GetBlockWithDeploys {
+    const METHOD: &'static str = "chain_get_block_with_deploys";
+    type RequestParams = GetBlockWithDeploysParams;
+    type ResponseResult = GetBlockWithDeploysResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        // Get the block.
+        let BlockWithMetadata {
+            block,
+            block_signatures,
+        } = get_block_with_metadata(
+            params.block_identifier,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+
+        // Get the block's deploys along with their execution results.
+        let deploys_and_results = effect_builder
+            .get_block_deploys_and_execution_results_from_storage(*block.hash())
+            .await
+            .ok_or_else(|| {
+                let message = format!(
+                    "failed to get deploys of block {} from storage",
+                    block.hash()
+                );
+                info!("{}", message);
+                Error::new(ErrorCode::NoSuchDeploy, message)
+            })?;
+        let deploys = deploys_and_results
+            .into_iter()
+            .map(|(deploy, execution_result)| {
+                JsonBlockDeploy::new(
+                    deploy.into_naive(),
+                    execution_result,
+                    params.omit_deploy_bodies,
+                )
+            })
+            .collect();
+
+        // Return the result.
+        let result = Self::ResponseResult {
+            api_version,
+            block: JsonBlock::new(&block, Some(block_signatures)),
+            deploys,
+        };
+        Ok(result)
+    }
+}
+
 /// Params for "chain_get_block_transfers" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -18,12 +18,13 @@ use casper_types::ProtocolVersion;
 use super::{
     account::PutDeploy,
     chain::{
-        GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+        GetBlock, GetBlockTransfers, GetBlockWithDeploys, GetEraInfoBySwitchBlock, GetEraSummary,
+        GetStateRootHash,
     },
-    info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+    info::{GetChainspec, GetDeploy, GetFaults, GetPeers, GetStatus, GetValidatorChanges},
     state::{
-        GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, QueryBalance,
-        QueryGlobalState,
+        GetAccountDeploys, GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem,
+        GetKeysWithPrefix, QueryBalance, QueryGlobalState,
     },
     Error, ReactorEventT, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
 };
@@ -71,6 +72,9 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     schema.push_with_params::<PutDeploy>("receives a Deploy to be executed by the network");
     schema.push_with_params::<GetDeploy>("returns a Deploy from the network");
     schema.push_with_params::<GetAccountInfo>("returns an Account from the network");
+    schema.push_with_params::<GetAccountDeploys>(
+        "returns the deploys sent by an Account, most recent first",
+    );
     schema.push_with_params::<GetDictionaryItem>("returns an item from a Dictionary");
     schema.push_with_params::<QueryGlobalState>(
         "a query to global state using either a Block hash or state root hash",
@@ -78,10 +82,17 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     schema.push_with_params::<QueryBalance>(
         "query for a balance using a purse identifier and a state identifier",
     );
+    schema.push_with_params::<GetKeysWithPrefix>(
+        "returns a page of the keys in global state with a given tag and prefix, if enabled on the \
+        node",
+    );
     schema.push_without_params::<GetPeers>("returns a list of peers connected to the node");
     schema.push_without_params::<GetStatus>("returns the current status of the node");
     schema
         .push_without_params::<GetValidatorChanges>("returns status changes of active validators");
+    schema.push_without_params::<GetFaults>(
+        "returns the faulty validators in each open era, with the evidence of their faults",
+    );
     schema.push_without_params::<GetChainspec>(
         "returns the raw bytes of the chainspec.toml, genesis accounts.toml, and \
         global_state.toml files",
@@ -90,6 +101,9 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     schema.push_with_optional_params::<GetBlockTransfers>(
         "returns all transfers for a Block from the network",
     );
+    schema.push_with_params::<GetBlockWithDeploys>(
+        "returns a Block along with its deploys and their execution results",
+    );
     schema.push_with_optional_params::<GetStateRootHash>(
         "returns a state root hash at a given Block",
     );
@@ -35,6 +35,10 @@ pub enum ErrorCode {
     FailedToGetTrie = -32011,
     /// The requested state root hash was not found.
     NoSuchStateRoot = -32012,
+    /// The account deploy index is not enabled on this node.
+    AccountDeployIndexDisabled = -32013,
+    /// The requested page size is invalid.
+    InvalidLimit = -32014,
 }
 
 impl From<ErrorCode> for (i64, &'static str) {
@@ -59,6 +63,10 @@ impl From<ErrorCode> for (i64, &'static str) {
             }
             ErrorCode::FailedToGetTrie => (error_code as i64, "Failed to get trie"),
             ErrorCode::NoSuchStateRoot => (error_code as i64, "No such state root"),
+            ErrorCode::AccountDeployIndexDisabled => {
+                (error_code as i64, "Account deploy index disabled")
+            }
+            ErrorCode::InvalidLimit => (error_code as i64, "Invalid limit"),
         }
     }
 }
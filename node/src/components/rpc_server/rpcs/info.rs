@@ -18,7 +18,7 @@ use super::{
     Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithoutParams,
 };
 use crate::{
-    components::consensus::ValidatorChange,
+    components::consensus::{FaultEvidence, ValidatorChange},
     effect::EffectBuilder,
     reactor::QueueKind,
     types::{
@@ -53,6 +53,19 @@ static GET_VALIDATOR_CHANGES_RESULT: Lazy<GetValidatorChangesResult> = Lazy::new
         changes,
     }
 });
+static GET_FAULTS_RESULT: Lazy<GetFaultsResult> = Lazy::new(|| {
+    let fault = JsonFault {
+        public_key: PublicKey::doc_example().clone(),
+        evidence: Some(FaultEvidence::doc_example().clone()),
+    };
+    GetFaultsResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        eras: vec![JsonEraFaults {
+            era_id: EraId::new(1),
+            faults: vec![fault],
+        }],
+    }
+});
 static GET_CHAINSPEC_RESULT: Lazy<GetChainspecResult> = Lazy::new(|| GetChainspecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     chainspec_bytes: ChainspecRawBytes::new(vec![42, 42].into(), None, None),
@@ -348,6 +361,84 @@ impl RpcWithoutParams for GetValidatorChanges {
     }
 }
 
+/// A validator accused or proven to be faulty.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonFault {
+    /// The public key of the validator.
+    public_key: PublicKey,
+    /// The evidence of the fault, if this node has it. Without evidence, the validator has been
+    /// accused in a finalized block.
+    evidence: Option<FaultEvidence>,
+}
+
+/// The faulty validators in an era.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonEraFaults {
+    /// The era.
+    era_id: EraId,
+    /// The validators accused or proven to be faulty in the era.
+    faults: Vec<JsonFault>,
+}
+
+/// Result for the "info_get_faults" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetFaultsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The faulty validators in each era for which this node runs consensus.
+    pub eras: Vec<JsonEraFaults>,
+}
+
+impl GetFaultsResult {
+    pub(crate) fn new(
+        api_version: ProtocolVersion,
+        faults: BTreeMap<EraId, BTreeMap<PublicKey, Option<FaultEvidence>>>,
+    ) -> Self {
+        let eras = faults
+            .into_iter()
+            .map(|(era_id, faults)| JsonEraFaults {
+                era_id,
+                faults: faults
+                    .into_iter()
+                    .map(|(public_key, evidence)| JsonFault {
+                        public_key,
+                        evidence,
+                    })
+                    .collect(),
+            })
+            .collect();
+        GetFaultsResult { api_version, eras }
+    }
+}
+
+impl DocExample for GetFaultsResult {
+    fn doc_example() -> &'static Self {
+        &GET_FAULTS_RESULT
+    }
+}
+
+/// "info_get_faults" RPC.
+pub struct GetFaults {}
+
+#[async_trait]
+impl RpcWithoutParams for GetFaults {
+    const METHOD: &'static str = "info_get_faults";
+    type ResponseResult = GetFaultsResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+    ) -> Result<Self::ResponseResult, Error> {
+        let faults = effect_builder.get_consensus_faults().await;
+        let result = Self::ResponseResult::new(api_version, faults);
+        Ok(result)
+    }
+}
+
 /// Result for the "info_get_chainspec" RPC.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetChainspecResult {
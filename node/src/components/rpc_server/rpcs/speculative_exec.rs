@@ -10,7 +10,9 @@ use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::core::engine_state::Error as EngineStateError;
+use casper_execution_engine::core::{
+    engine_state::Error as EngineStateError, runtime::ExecutionTrace,
+};
 use casper_json_rpc::ReservedErrorCode;
 use casper_types::{ExecutionResult, ProtocolVersion};
 
@@ -29,11 +31,13 @@ use crate::{
 static SPECULATIVE_EXEC_PARAMS: Lazy<SpeculativeExecParams> = Lazy::new(|| SpeculativeExecParams {
     block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
     deploy: Deploy::doc_example().clone(),
+    trace: false,
 });
 static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| SpeculativeExecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block_hash: *Block::doc_example().hash(),
     execution_result: ExecutionResult::example().clone(),
+    execution_trace: None,
 });
 
 /// Params for "speculative_exec" RPC request.
@@ -44,6 +48,9 @@ pub struct SpeculativeExecParams {
     pub block_identifier: Option<BlockIdentifier>,
     /// Deploy to execute.
     pub deploy: Deploy,
+    /// Whether to record and return a trace of the execution.
+    #[serde(default)]
+    pub trace: bool,
 }
 
 impl DocExample for SpeculativeExecParams {
@@ -63,6 +70,9 @@ pub struct SpeculativeExecResult {
     pub block_hash: BlockHash,
     /// Result of the execution.
     pub execution_result: ExecutionResult,
+    /// Trace of the execution, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_trace: Option<ExecutionTrace>,
 }
 
 impl DocExample for SpeculativeExecResult {
@@ -88,6 +98,7 @@ impl RpcWithParams for SpeculativeExec {
         let SpeculativeExecParams {
             block_identifier: maybe_block_id,
             deploy,
+            trace,
         } = params;
         let deploy = Arc::new(deploy);
         let only_from_available_block_range = true;
@@ -114,15 +125,16 @@ impl RpcWithParams for SpeculativeExec {
         }
 
         let result = effect_builder
-            .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy))
+            .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy), trace)
             .await;
 
         match result {
-            Ok(Some(execution_result)) => {
+            Ok(Some((execution_result, execution_trace))) => {
                 let result = Self::ResponseResult {
                     api_version,
                     block_hash,
                     execution_result,
+                    execution_trace,
                 };
                 Ok(result)
             }
@@ -20,8 +20,8 @@ use casper_json_rpc::ReservedErrorCode;
 use casper_types::{
     account::AccountHash,
     bytesrepr::{Bytes, ToBytes},
-    CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue, URef,
-    U512,
+    CLValue, Key, KeyTag, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue,
+    URef, U512,
 };
 
 use crate::{
@@ -35,7 +35,7 @@ use crate::{
     },
     types::{
         json_compatibility::{Account as JsonAccount, AuctionState, StoredValue},
-        Block, BlockHash, JsonBlockHeader,
+        Block, BlockHash, Deploy, DeployHash, JsonBlockHeader,
     },
 };
 
@@ -79,6 +79,28 @@ static GET_ACCOUNT_INFO_RESULT: Lazy<GetAccountInfoResult> = Lazy::new(|| GetAcc
     account: JsonAccount::doc_example().clone(),
     merkle_proof: MERKLE_PROOF.clone(),
 });
+static GET_ACCOUNT_DEPLOYS_PARAMS: Lazy<GetAccountDeploysParams> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    GetAccountDeploysParams {
+        account_identifier: AccountIdentifier::PublicKey(public_key),
+        after: None,
+        limit: Some(DEFAULT_ACCOUNT_DEPLOYS_LIMIT),
+    }
+});
+static GET_ACCOUNT_DEPLOYS_RESULT: Lazy<GetAccountDeploysResult> =
+    Lazy::new(|| GetAccountDeploysResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        deploys: vec![AccountDeploy {
+            deploy_hash: *Deploy::doc_example().hash(),
+            block_hash: *Block::doc_example().hash(),
+            block_height: Block::doc_example().header().height(),
+        }],
+        next_cursor: Some(AccountDeploysCursor {
+            deploy_hash: *Deploy::doc_example().hash(),
+            block_height: Block::doc_example().header().height(),
+        }),
+    });
 static GET_DICTIONARY_ITEM_PARAMS: Lazy<GetDictionaryItemParams> =
     Lazy::new(|| GetDictionaryItemParams {
         state_root_hash: *Block::doc_example().header().state_root_hash(),
@@ -119,6 +141,20 @@ static GET_TRIE_RESULT: Lazy<GetTrieResult> = Lazy::new(|| GetTrieResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     maybe_trie_bytes: None,
 });
+static GET_KEYS_WITH_PREFIX_PARAMS: Lazy<GetKeysWithPrefixParams> =
+    Lazy::new(|| GetKeysWithPrefixParams {
+        state_root_hash: *Block::doc_example().header().state_root_hash(),
+        key_tag: KeyTag::Bid,
+        prefix: None,
+        cursor: None,
+        limit: Some(DEFAULT_KEYS_WITH_PREFIX_LIMIT),
+    });
+static GET_KEYS_WITH_PREFIX_RESULT: Lazy<GetKeysWithPrefixResult> =
+    Lazy::new(|| GetKeysWithPrefixResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        keys: vec![Key::Bid(AccountHash::new([9u8; 32])).to_formatted_string()],
+        next_cursor: None,
+    });
 static QUERY_BALANCE_PARAMS: Lazy<QueryBalanceParams> = Lazy::new(|| QueryBalanceParams {
     state_identifier: Some(GlobalStateIdentifier::BlockHash(
         *Block::doc_example().hash(),
@@ -568,6 +604,149 @@ impl RpcWithParams for GetAccountInfo {
     }
 }
 
+/// The number of deploys returned by "state_get_account_deploys" if no limit is specified.
+const DEFAULT_ACCOUNT_DEPLOYS_LIMIT: u32 = 100;
+/// The maximum number of deploys returned by a single "state_get_account_deploys" request.
+const MAX_ACCOUNT_DEPLOYS_LIMIT: u32 = 1_000;
+
+/// Params for "state_get_account_deploys" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountDeploysParams {
+    /// The identifier of the account which sent the deploys.
+    pub account_identifier: AccountIdentifier,
+    /// The cursor returned with the previous page, if any; only deploys after it are returned.
+    #[serde(default)]
+    pub after: Option<AccountDeploysCursor>,
+    /// The maximum number of deploys to return, defaulting to 100 and capped at 1000.  Must not be
+    /// zero.
+    pub limit: Option<u32>,
+}
+
+impl DocExample for GetAccountDeploysParams {
+    fn doc_example() -> &'static Self {
+        &GET_ACCOUNT_DEPLOYS_PARAMS
+    }
+}
+
+/// The position of a deploy in the deploys returned by "state_get_account_deploys".
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountDeploysCursor {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The height of the block in which the deploy was executed.
+    pub block_height: u64,
+}
+
+/// A deploy sent by an account, along with the block in which it was executed.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountDeploy {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The hash of the block in which the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The height of the block in which the deploy was executed.
+    pub block_height: u64,
+}
+
+/// Result for "state_get_account_deploys" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAccountDeploysResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The deploys sent by the account, most recent first.
+    pub deploys: Vec<AccountDeploy>,
+    /// The cursor from which to request the next page, if there are more deploys.
+    pub next_cursor: Option<AccountDeploysCursor>,
+}
+
+impl DocExample for GetAccountDeploysResult {
+    fn doc_example() -> &'static Self {
+        &GET_ACCOUNT_DEPLOYS_RESULT
+    }
+}
+
+/// "state_get_account_deploys" RPC.
+pub struct GetAccountDeploys {}
+
+#[async_trait]
+impl RpcWithParams for GetAccountDeploys {
+    const METHOD: &'static str = "state_get_account_deploys";
+    type RequestParams = GetAccountDeploysParams;
+    type ResponseResult = GetAccountDeploysResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let account_hash = match params.account_identifier {
+            AccountIdentifier::PublicKey(public_key) => public_key.to_account_hash(),
+            AccountIdentifier::AccountHash(account_hash) => account_hash,
+        };
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_ACCOUNT_DEPLOYS_LIMIT)
+            .min(MAX_ACCOUNT_DEPLOYS_LIMIT);
+        // An empty page would carry no cursor, making it indistinguishable from the last page.
+        if limit == 0 {
+            let error_msg = "limit must not be zero".to_string();
+            info!("{}", error_msg);
+            return Err(Error::new(ErrorCode::InvalidLimit, error_msg));
+        }
+
+        // Request one more than the limit to find out whether there is a further page.
+        let mut deploys = match effect_builder
+            .get_account_deploys_from_storage(
+                account_hash,
+                params
+                    .after
+                    .map(|cursor| (cursor.deploy_hash, cursor.block_height)),
+                limit.saturating_add(1),
+            )
+            .await
+        {
+            Some(deploys) => deploys,
+            None => {
+                let error_msg = "account deploy index is not enabled on this node".to_string();
+                info!("{}", error_msg);
+                return Err(Error::new(ErrorCode::AccountDeployIndexDisabled, error_msg));
+            }
+        };
+
+        let next_cursor = if deploys.len() > limit as usize {
+            deploys.truncate(limit as usize);
+            deploys.last().map(
+                |(deploy_hash, block_hash_and_height)| AccountDeploysCursor {
+                    deploy_hash: *deploy_hash,
+                    block_height: block_hash_and_height.block_height,
+                },
+            )
+        } else {
+            None
+        };
+
+        let deploys = deploys
+            .into_iter()
+            .map(|(deploy_hash, block_hash_and_height)| AccountDeploy {
+                deploy_hash,
+                block_hash: block_hash_and_height.block_hash,
+                block_height: block_hash_and_height.block_height,
+            })
+            .collect();
+
+        Ok(Self::ResponseResult {
+            api_version,
+            deploys,
+            next_cursor,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 /// Options for dictionary item lookups.
 pub enum DictionaryIdentifier {
@@ -1098,6 +1277,140 @@ impl RpcWithParams for GetTrie {
     }
 }
 
+/// The number of keys returned by "state_get_keys_with_prefix" if no limit is specified.
+const DEFAULT_KEYS_WITH_PREFIX_LIMIT: u32 = 100;
+/// The maximum number of keys returned by a single "state_get_keys_with_prefix" request.
+const MAX_KEYS_WITH_PREFIX_LIMIT: u32 = 1_000;
+
+/// Params for "state_get_keys_with_prefix" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetKeysWithPrefixParams {
+    /// The hash of the state root.
+    pub state_root_hash: Digest,
+    /// The tag of the keys to list.
+    pub key_tag: KeyTag,
+    /// Further bytes which the serialized keys must start with, following the key tag.
+    #[serde(default)]
+    #[schemars(
+        with = "Option<String>",
+        description = "Further bytes which the serialized keys must start with, following the key \
+        tag, hex-encoded."
+    )]
+    pub prefix: Option<Bytes>,
+    /// The formatted key after which to continue listing, as returned in `next_cursor`.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// The maximum number of keys to return, defaulting to 100 and capped at 1000.  Must not be
+    /// zero.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+impl DocExample for GetKeysWithPrefixParams {
+    fn doc_example() -> &'static Self {
+        &GET_KEYS_WITH_PREFIX_PARAMS
+    }
+}
+
+/// Result for "state_get_keys_with_prefix" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetKeysWithPrefixResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The formatted keys, in ascending order of their serialized form.
+    pub keys: Vec<String>,
+    /// The cursor from which to request the next page, if there are more keys.
+    pub next_cursor: Option<String>,
+}
+
+impl DocExample for GetKeysWithPrefixResult {
+    fn doc_example() -> &'static Self {
+        &GET_KEYS_WITH_PREFIX_RESULT
+    }
+}
+
+/// "state_get_keys_with_prefix" RPC.
+pub struct GetKeysWithPrefix {}
+
+#[async_trait]
+impl RpcWithParams for GetKeysWithPrefix {
+    const METHOD: &'static str = "state_get_keys_with_prefix";
+    type RequestParams = GetKeysWithPrefixParams;
+    type ResponseResult = GetKeysWithPrefixResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let start_after = match params.cursor {
+            Some(cursor) => Some(Key::from_formatted_str(&cursor).map_err(|error| {
+                Error::new(
+                    ErrorCode::FailedToParseQueryKey,
+                    format!("failed to parse cursor: {}", error),
+                )
+            })?),
+            None => None,
+        };
+        let limit = params
+            .limit
+            .unwrap_or(DEFAULT_KEYS_WITH_PREFIX_LIMIT)
+            .min(MAX_KEYS_WITH_PREFIX_LIMIT);
+        // An empty page would carry no cursor, making it indistinguishable from the last page.
+        if limit == 0 {
+            let error_msg = "limit must not be zero".to_string();
+            info!("{}", error_msg);
+            return Err(Error::new(ErrorCode::InvalidLimit, error_msg));
+        }
+
+        let mut prefix = vec![params.key_tag as u8];
+        if let Some(further_bytes) = params.prefix {
+            prefix.extend(further_bytes.iter());
+        }
+
+        // Request one more than the limit to find out whether there is a further page.
+        let mut keys = match effect_builder
+            .get_keys_with_prefix(
+                params.state_root_hash,
+                prefix,
+                start_after,
+                limit as usize + 1,
+            )
+            .await
+        {
+            Ok(Some(keys)) => keys,
+            Ok(None) => {
+                let error_msg = format!("no such state root: {}", params.state_root_hash);
+                info!("{}", error_msg);
+                return Err(Error::new(ErrorCode::NoSuchStateRoot, error_msg));
+            }
+            Err(error) => {
+                warn!(?error, "failed to get keys with prefix");
+                return Err(Error::new(
+                    ErrorCode::QueryFailedToExecute,
+                    format!("{:?}", error),
+                ));
+            }
+        };
+
+        let next_cursor = if keys.len() > limit as usize {
+            keys.truncate(limit as usize);
+            keys.last().map(|key| key.to_formatted_string())
+        } else {
+            None
+        };
+
+        Ok(Self::ResponseResult {
+            api_version,
+            keys: keys.into_iter().map(Key::to_formatted_string).collect(),
+            next_cursor,
+        })
+    }
+}
+
 type QuerySuccess = (
     DomainStoredValue,
     Vec<TrieMerkleProof<Key, DomainStoredValue>>,
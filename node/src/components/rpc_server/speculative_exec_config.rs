@@ -9,6 +9,8 @@ const DEFAULT_ADDRESS: &str = "0.0.0.0:1";
 const DEFAULT_QPS_LIMIT: u64 = 1;
 /// Default max body bytes (2.5MB).
 const DEFAULT_MAX_BODY_BYTES: u32 = 2_621_440;
+/// Default max number of requests in a single JSON-RPC batch.
+const DEFAULT_MAX_BATCH_SIZE: u32 = 1;
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
 
@@ -25,6 +27,11 @@ pub struct Config {
     pub qps_limit: u64,
     /// Maximum number of bytes to accept in a single request body.
     pub max_body_bytes: u32,
+    /// Maximum number of requests to accept in a single JSON-RPC batch.
+    ///
+    /// Batches are additionally limited to `qps_limit` requests.
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: u32,
     /// CORS origin.
     pub cors_origin: String,
 }
@@ -37,11 +44,17 @@ impl Config {
             address: DEFAULT_ADDRESS.to_string(),
             qps_limit: DEFAULT_QPS_LIMIT,
             max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
         }
     }
 }
 
+/// The default for `Config::max_batch_size`.
+fn default_max_batch_size() -> u32 {
+    DEFAULT_MAX_BATCH_SIZE
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config::new()
@@ -6,7 +6,7 @@ use casper_types::ProtocolVersion;
 use super::ReactorEventT;
 use crate::{
     effect::EffectBuilder,
-    rpcs::{speculative_exec::SpeculativeExec, RpcWithParams},
+    rpcs::{speculative_exec::SpeculativeExec, RequestLimiter, RpcWithParams},
 };
 
 /// The URL path for all JSON-RPC requests.
@@ -21,10 +21,12 @@ pub(super) async fn run<REv: ReactorEventT>(
     api_version: ProtocolVersion,
     qps_limit: u64,
     max_body_bytes: u32,
+    max_batch_size: u32,
     cors_origin: String,
 ) {
+    let limiter = RequestLimiter::new(qps_limit);
     let mut handlers = RequestHandlersBuilder::new();
-    SpeculativeExec::register_as_handler(effect_builder, api_version, &mut handlers);
+    SpeculativeExec::register_as_handler(effect_builder, api_version, &limiter, &mut handlers);
     let handlers = handlers.build();
 
     match cors_origin.as_str() {
@@ -34,6 +36,7 @@ pub(super) async fn run<REv: ReactorEventT>(
                 handlers,
                 qps_limit,
                 max_body_bytes,
+                max_batch_size,
                 SPECULATIVE_EXEC_API_PATH,
                 SPECULATIVE_EXEC_SERVER_NAME,
             )
@@ -45,6 +48,7 @@ pub(super) async fn run<REv: ReactorEventT>(
                 handlers,
                 qps_limit,
                 max_body_bytes,
+                max_batch_size,
                 SPECULATIVE_EXEC_API_PATH,
                 SPECULATIVE_EXEC_SERVER_NAME,
                 CorsOrigin::Any,
@@ -57,6 +61,7 @@ pub(super) async fn run<REv: ReactorEventT>(
                 handlers,
                 qps_limit,
                 max_body_bytes,
+                max_batch_size,
                 SPECULATIVE_EXEC_API_PATH,
                 SPECULATIVE_EXEC_SERVER_NAME,
                 CorsOrigin::Specified(cors_origin),
@@ -30,8 +30,10 @@
 //! The storage component itself is panic free and in general reports three classes of errors:
 //! Corruption, temporary resource exhaustion and potential bugs.
 
+pub(crate) mod archive;
 pub(crate) mod disjoint_sequences;
 mod error;
+pub(crate) mod inspect;
 mod lmdb_ext;
 mod metrics;
 mod object_pool;
@@ -60,6 +62,7 @@ use lmdb::{
     Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, RwTransaction, Transaction,
     WriteFlags,
 };
+use lmdb_sys::{MDB_GET_BOTH_RANGE, MDB_NEXT_DUP};
 use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
@@ -70,6 +73,7 @@ use tracing::{debug, error, info, trace, warn};
 
 use casper_hashing::Digest;
 use casper_types::{
+    account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp, Transfer, Transform,
 };
@@ -129,9 +133,19 @@ const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 9;
+const MAX_DB_COUNT: u32 = 10;
 /// Key under which completed blocks are to be stored.
 const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
+/// Key under which the marker indicating a fully built account deploy index is stored.
+const ACCOUNT_DEPLOY_INDEX_STORAGE_KEY: &[u8] = b"account_deploy_index_built";
+/// Key under which the progress of building the account deploy index is stored: the exclusive
+/// upper bound of the block heights still to be indexed.
+const ACCOUNT_DEPLOY_INDEX_BACKFILL_STORAGE_KEY: &[u8] = b"account_deploy_index_backfill_height";
+/// Number of block heights added to the account deploy index per backfill step.
+const ACCOUNT_DEPLOY_INDEX_BACKFILL_BATCH_SIZE: u64 = 100;
+/// Length in bytes of an entry in the account deploy index: an inverted block height followed by a
+/// deploy hash.
+const ACCOUNT_DEPLOY_INDEX_ENTRY_LENGTH: usize = 8 + Digest::LENGTH;
 /// Name of the file created when initializing a force resync.
 const FORCE_RESYNC_FILE_NAME: &str = "force_resync";
 
@@ -192,6 +206,17 @@ pub struct Storage {
     /// The finalized approvals database.
     #[data_size(skip)]
     finalized_approvals_db: Database,
+    /// The account deploy index database, present only if the index is enabled.
+    ///
+    /// Keyed by account hash, holds one entry per deploy sent by that account, ordered by
+    /// descending block height.
+    #[data_size(skip)]
+    account_deploys_db: Option<Database>,
+    /// The exclusive upper bound of the block heights still to be added to the account deploy
+    /// index, if it is being built from the blocks already held in storage.
+    account_deploy_index_backfill: Option<u64>,
+    /// Whether a step of building the account deploy index is already scheduled.
+    account_deploy_index_backfill_scheduled: bool,
     /// A map of block height to block ID.
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
@@ -234,6 +259,8 @@ pub(crate) enum Event {
     /// Make block executable request.
     #[from]
     MakeBlockExecutableRequest(Box<MakeBlockExecutableRequest>),
+    /// Adds the next batch of blocks already held in storage to the account deploy index.
+    BackfillAccountDeployIndex,
 }
 
 impl Display for Event {
@@ -243,6 +270,7 @@ impl Display for Event {
             Event::NetRequestIncoming(incoming) => incoming.fmt(f),
             Event::MarkBlockCompletedRequest(req) => req.fmt(f),
             Event::MakeBlockExecutableRequest(req) => req.fmt(f),
+            Event::BackfillAccountDeployIndex => write!(f, "backfill account deploy index"),
         }
     }
 }
@@ -336,8 +364,28 @@ where
                     Err(err) => Err(err),
                 }
             }
+            Event::BackfillAccountDeployIndex => {
+                self.account_deploy_index_backfill_scheduled = false;
+                self.backfill_account_deploy_index()
+                    .map(|()| Effects::new())
+            }
         };
 
+        // Keep building the account deploy index in small steps, interleaved with other events.
+        let result = result.map(|mut effects| {
+            if self.account_deploy_index_backfill.is_some()
+                && !self.account_deploy_index_backfill_scheduled
+            {
+                self.account_deploy_index_backfill_scheduled = true;
+                effects.extend(
+                    effect_builder
+                        .immediately()
+                        .event(|()| Event::BackfillAccountDeployIndex),
+                );
+            }
+            effects
+        });
+
         // Any error is turned into a fatal effect, the component itself does not panic. Note that
         // we are dropping a lot of responders this way, but since we are crashing with fatal
         // anyway, it should not matter.
@@ -385,10 +433,7 @@ impl Storage {
         root = network_subdir;
 
         // Calculate the upper bound for the memory map that is potentially used.
-        let total_size = config
-            .max_block_store_size
-            .saturating_add(config.max_deploy_store_size)
-            .saturating_add(config.max_deploy_metadata_store_size);
+        let total_size = max_map_size(config);
 
         // Creates the environment and databases.
         let env = Environment::new()
@@ -417,6 +462,14 @@ impl Storage {
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
         let approvals_hashes_db =
             env.create_db(Some("approvals_hashes"), DatabaseFlags::empty())?;
+        let account_deploys_db = if config.enable_account_deploy_index {
+            Some(env.create_db(
+                Some("account_deploys"),
+                DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED,
+            )?)
+        } else {
+            None
+        };
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("indexing block store");
@@ -479,6 +532,7 @@ impl Storage {
         drop(cursor);
         block_txn.commit()?;
 
+        let blocks_were_deleted = !deleted_block_hashes.is_empty();
         let deleted_block_hashes_raw = deleted_block_hashes.iter().map(BlockHash::as_ref).collect();
 
         initialize_block_body_db(
@@ -508,6 +562,9 @@ impl Storage {
             transfer_db,
             state_store_db,
             finalized_approvals_db,
+            account_deploys_db,
+            account_deploy_index_backfill: None,
+            account_deploy_index_backfill_scheduled: false,
             block_height_index,
             switch_block_era_id_index,
             deploy_hash_index,
@@ -521,6 +578,8 @@ impl Storage {
             metrics,
         };
 
+        component.initialize_account_deploy_index(blocks_were_deleted)?;
+
         if force_resync {
             let force_resync_file_path = component.root_path().join(FORCE_RESYNC_FILE_NAME);
             // Check if resync is already in progress. Force resync will kick
@@ -636,6 +695,139 @@ impl Storage {
         Ok(())
     }
 
+    /// Prepares building the account deploy index from the blocks and deploys already held in
+    /// storage, unless it has been built previously and `force_rebuild` is `false`.
+    ///
+    /// The index is built incrementally by [`Event::BackfillAccountDeployIndex`] steps, resuming
+    /// from the persisted progress after a restart.  Blocks stored in the meantime are indexed as
+    /// usual.
+    ///
+    /// If the index is disabled, its markers are removed so that the index is rebuilt from scratch
+    /// should it be enabled again later.
+    fn initialize_account_deploy_index(
+        &mut self,
+        force_rebuild: bool,
+    ) -> Result<(), FatalStorageError> {
+        let account_deploys_db = match self.account_deploys_db {
+            Some(account_deploys_db) => account_deploys_db,
+            None => {
+                let mut txn = self.env.begin_rw_txn()?;
+                for key in [
+                    ACCOUNT_DEPLOY_INDEX_STORAGE_KEY,
+                    ACCOUNT_DEPLOY_INDEX_BACKFILL_STORAGE_KEY,
+                ] {
+                    match txn.del(self.state_store_db, &key, None) {
+                        Ok(()) | Err(lmdb::Error::NotFound) => (),
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+                txn.commit()?;
+                return Ok(());
+            }
+        };
+
+        if !force_rebuild {
+            if self
+                .read_state_store(&ACCOUNT_DEPLOY_INDEX_STORAGE_KEY)?
+                .is_some()
+            {
+                return Ok(());
+            }
+            if let Some(raw) = self.read_state_store(&ACCOUNT_DEPLOY_INDEX_BACKFILL_STORAGE_KEY)? {
+                let (height, _) = u64::from_bytes(&raw)
+                    .map_err(FatalStorageError::UnexpectedDeserializationFailure)?;
+                info!(%height, "resuming building account deploy index");
+                self.account_deploy_index_backfill = Some(height);
+                return Ok(());
+            }
+        }
+
+        let end_height = self
+            .block_height_index
+            .keys()
+            .next_back()
+            .map_or(0, |height| height.saturating_add(1));
+        info!(%end_height, "building account deploy index");
+        let env = Rc::clone(&self.env);
+        let mut txn = env.begin_rw_txn()?;
+        txn.clear_db(account_deploys_db)?;
+        match txn.del(self.state_store_db, &ACCOUNT_DEPLOY_INDEX_STORAGE_KEY, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => (),
+            Err(err) => return Err(err.into()),
+        }
+        self.write_account_deploy_index_backfill(&mut txn, end_height)?;
+        txn.commit()?;
+
+        Ok(())
+    }
+
+    /// Adds the deploys of the next batch of blocks below the backfill height to the account
+    /// deploy index, marking the index as built once the lowest height is reached.
+    fn backfill_account_deploy_index(&mut self) -> Result<(), FatalStorageError> {
+        let end_height = match self.account_deploy_index_backfill {
+            Some(end_height) => end_height,
+            None => return Ok(()),
+        };
+        let start_height = end_height.saturating_sub(ACCOUNT_DEPLOY_INDEX_BACKFILL_BATCH_SIZE);
+        let env = Rc::clone(&self.env);
+        let mut txn = env.begin_rw_txn()?;
+        let block_hashes: Vec<BlockHash> = self
+            .block_height_index
+            .range(start_height..end_height)
+            .map(|(_, block_hash)| *block_hash)
+            .collect();
+        for block_hash in block_hashes {
+            if let Some(block) = self.get_single_block(&mut txn, &block_hash)? {
+                self.write_account_deploy_index(&mut txn, &block)?;
+            }
+        }
+        self.write_account_deploy_index_backfill(&mut txn, start_height)?;
+        txn.commit()?;
+        debug!(%start_height, "account deploy index backfill step done");
+        if self.account_deploy_index_backfill.is_none() {
+            info!("account deploy index built");
+        }
+        Ok(())
+    }
+
+    /// Records that the blocks below `end_height` are still to be added to the account deploy
+    /// index, or that the index is complete if `end_height` is 0.
+    fn write_account_deploy_index_backfill(
+        &mut self,
+        txn: &mut RwTransaction,
+        end_height: u64,
+    ) -> Result<(), FatalStorageError> {
+        if end_height == 0 {
+            match txn.del(
+                self.state_store_db,
+                &ACCOUNT_DEPLOY_INDEX_BACKFILL_STORAGE_KEY,
+                None,
+            ) {
+                Ok(()) | Err(lmdb::Error::NotFound) => (),
+                Err(err) => return Err(err.into()),
+            }
+            txn.put(
+                self.state_store_db,
+                &ACCOUNT_DEPLOY_INDEX_STORAGE_KEY,
+                &Vec::new(),
+                WriteFlags::default(),
+            )?;
+            self.account_deploy_index_backfill = None;
+        } else {
+            let serialized = end_height
+                .to_bytes()
+                .map_err(FatalStorageError::UnexpectedSerializationFailure)?;
+            txn.put(
+                self.state_store_db,
+                &ACCOUNT_DEPLOY_INDEX_BACKFILL_STORAGE_KEY,
+                &serialized,
+                WriteFlags::default(),
+            )?;
+            self.account_deploy_index_backfill = Some(end_height);
+        }
+        Ok(())
+    }
+
     /// Returns the path to the storage folder.
     pub(crate) fn root_path(&self) -> &Path {
         &self.root
@@ -959,6 +1151,51 @@ impl Storage {
 
                 responder.respond(Some((deploy, metadata_ext))).ignore()
             }
+            StorageRequest::GetBlockDeploysAndExecutionResults {
+                block_hash,
+                responder,
+            } => {
+                let mut txn = self.env.begin_ro_txn()?;
+
+                let block = match self.get_single_block(&mut txn, &block_hash)? {
+                    Some(block) => block,
+                    None => return Ok(responder.respond(None).ignore()),
+                };
+
+                let mut deploys = Vec::with_capacity(block.deploy_and_transfer_hashes().count());
+                for deploy_hash in block.deploy_and_transfer_hashes() {
+                    let deploy =
+                        match self.get_deploy_with_finalized_approvals(&mut txn, deploy_hash)? {
+                            Some(deploy) => deploy,
+                            None => {
+                                debug!(
+                                    %block_hash,
+                                    %deploy_hash,
+                                    "missing deploy while getting block deploys"
+                                );
+                                return Ok(responder.respond(None).ignore());
+                            }
+                        };
+                    let execution_result = self
+                        .get_deploy_metadata(&mut txn, deploy_hash)?
+                        .and_then(|mut metadata| metadata.execution_results.remove(&block_hash));
+                    deploys.push((deploy, execution_result));
+                }
+
+                responder.respond(Some(deploys)).ignore()
+            }
+            StorageRequest::GetAccountDeploys {
+                account_hash,
+                after,
+                limit,
+                responder,
+            } => responder
+                .respond(self.get_account_deploys(
+                    &account_hash,
+                    after.map(|after| *after),
+                    limit,
+                )?)
+                .ignore(),
             StorageRequest::GetBlockAndMetadataByHash {
                 block_hash,
                 only_from_available_block_range,
@@ -1215,6 +1452,17 @@ impl Storage {
         }: MarkBlockCompletedRequest,
     ) -> Result<Effects<Event>, FatalStorageError> {
         let is_new = self.mark_block_complete(block_height)?;
+        if is_new && self.account_deploys_db.is_some() {
+            // Deploys of blocks acquired via sync may have been stored after the block itself.
+            if let Some(block_hash) = self.block_height_index.get(&block_height).copied() {
+                let env = Rc::clone(&self.env);
+                let mut txn = env.begin_rw_txn()?;
+                if let Some(block) = self.get_single_block(&mut txn, &block_hash)? {
+                    self.write_account_deploy_index(&mut txn, &block)?;
+                }
+                txn.commit()?;
+            }
+        }
         Ok(responder.respond(is_new).ignore())
     }
 
@@ -1277,11 +1525,106 @@ impl Storage {
 
         let _ = self.write_approvals_hashes(&mut txn, approvals_hashes)?;
         let _ = self.write_execution_results(&mut txn, block.hash(), execution_results)?;
+        self.write_account_deploy_index(&mut txn, block)?;
         txn.commit()?;
 
         Ok(true)
     }
 
+    /// Adds the deploys of the given block to the account deploy index, if enabled.
+    ///
+    /// Deploys not yet held in storage are skipped; they are indexed once the block is marked
+    /// complete.
+    fn write_account_deploy_index(
+        &self,
+        txn: &mut RwTransaction,
+        block: &Block,
+    ) -> Result<(), FatalStorageError> {
+        let account_deploys_db = match self.account_deploys_db {
+            Some(account_deploys_db) => account_deploys_db,
+            None => return Ok(()),
+        };
+        for deploy_hash in block.deploy_and_transfer_hashes() {
+            let maybe_deploy: Option<Deploy> = txn.get_value(self.deploy_db, deploy_hash)?;
+            let deploy = match maybe_deploy {
+                Some(deploy) => deploy,
+                None => continue,
+            };
+            let account_hash = deploy.header().account().to_account_hash();
+            let entry = account_deploy_index_entry(block.height(), deploy_hash);
+            match txn.put(
+                account_deploys_db,
+                &account_hash,
+                &entry,
+                WriteFlags::NO_DUP_DATA,
+            ) {
+                Ok(()) | Err(lmdb::Error::KeyExist) => (),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Retrieves up to `limit` deploys sent by the given account, most recent first, starting after
+    /// the deploy with the given hash and block height if `after` is provided.
+    ///
+    /// Deploys of blocks no longer held in storage are skipped.  Returns `None` if the account
+    /// deploy index is disabled.
+    fn get_account_deploys(
+        &self,
+        account_hash: &AccountHash,
+        after: Option<(DeployHash, u64)>,
+        limit: u32,
+    ) -> Result<Option<Vec<(DeployHash, BlockHashAndHeight)>>, FatalStorageError> {
+        let account_deploys_db = match self.account_deploys_db {
+            Some(account_deploys_db) => account_deploys_db,
+            None => return Ok(None),
+        };
+        let txn = self.env.begin_ro_txn()?;
+        let cursor = txn.open_ro_cursor(account_deploys_db)?;
+
+        // Entries are ordered by descending block height, so seeking to the entry of `after`
+        // positions the cursor at the first entry not more recent than it.  Without `after`, the
+        // all-zero entry positions the cursor at the most recent entry.
+        let after_entry = after.map(|(deploy_hash, block_height)| {
+            account_deploy_index_entry(block_height, &deploy_hash)
+        });
+        let seek_entry = after_entry
+            .clone()
+            .unwrap_or_else(|| vec![0; ACCOUNT_DEPLOY_INDEX_ENTRY_LENGTH]);
+        let mut maybe_raw_entry = match cursor.get(
+            Some(account_hash.as_ref()),
+            Some(&seek_entry),
+            MDB_GET_BOTH_RANGE,
+        ) {
+            Ok((_, raw_entry)) => Some(raw_entry),
+            Err(lmdb::Error::NotFound) => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut deploys = vec![];
+        while let Some(raw_entry) = maybe_raw_entry {
+            if deploys.len() >= limit as usize {
+                break;
+            }
+            if after_entry.as_deref() != Some(raw_entry) {
+                let (deploy_hash, block_height) = parse_account_deploy_index_entry(raw_entry)?;
+                if let Some(block_hash) = self.block_height_index.get(&block_height) {
+                    deploys.push((
+                        deploy_hash,
+                        BlockHashAndHeight::new(*block_hash, block_height),
+                    ));
+                }
+            }
+            maybe_raw_entry = match cursor.get(None, None, MDB_NEXT_DUP) {
+                Ok((_, raw_entry)) => Some(raw_entry),
+                Err(lmdb::Error::NotFound) => None,
+                Err(err) => return Err(err.into()),
+            };
+        }
+        Ok(Some(deploys))
+    }
+
     /// Retrieves a block by hash.
     pub fn read_block(&self, block_hash: &BlockHash) -> Result<Option<Block>, FatalStorageError> {
         self.get_single_block(&mut self.env.begin_ro_txn()?, block_hash)
@@ -2726,6 +3069,34 @@ fn insert_to_deploy_index(
     Ok(())
 }
 
+/// Returns the account deploy index entry for a deploy executed at the given block height: the
+/// inverted height in big-endian order, so that more recent entries sort first, followed by the
+/// deploy hash.
+fn account_deploy_index_entry(block_height: u64, deploy_hash: &DeployHash) -> Vec<u8> {
+    let mut entry = Vec::with_capacity(ACCOUNT_DEPLOY_INDEX_ENTRY_LENGTH);
+    entry.extend_from_slice(&(u64::MAX - block_height).to_be_bytes());
+    entry.extend_from_slice(deploy_hash.as_ref());
+    entry
+}
+
+/// Parses an account deploy index entry into the deploy hash and block height.
+fn parse_account_deploy_index_entry(
+    raw_entry: &[u8],
+) -> Result<(DeployHash, u64), FatalStorageError> {
+    let corrupted = || FatalStorageError::CorruptedAccountDeployIndex {
+        raw_entry: raw_entry.to_vec(),
+    };
+    if raw_entry.len() != ACCOUNT_DEPLOY_INDEX_ENTRY_LENGTH {
+        return Err(corrupted());
+    }
+    let (raw_height, raw_deploy_hash) = raw_entry.split_at(8);
+    let mut height_bytes = [0; 8];
+    height_bytes.copy_from_slice(raw_height);
+    let block_height = u64::MAX - u64::from_be_bytes(height_bytes);
+    let deploy_hash = DeployHash::new(Digest::try_from(raw_deploy_hash).map_err(|_| corrupted())?);
+    Ok((deploy_hash, block_height))
+}
+
 fn should_move_storage_files_to_network_subdir(
     root: &Path,
     file_names: &[&str],
@@ -2813,6 +3184,12 @@ pub struct Config {
     pub enable_mem_deduplication: bool,
     /// How many loads before memory duplication checks for dead references.
     pub mem_pool_prune_interval: u16,
+    /// Whether or not to maintain an index of deploys by sending account.
+    ///
+    /// When enabled for the first time, the index is built in the background from the blocks
+    /// already in storage.
+    #[serde(default)]
+    pub enable_account_deploy_index: bool,
 }
 
 impl Default for Config {
@@ -2826,6 +3203,7 @@ impl Default for Config {
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: true,
             mem_pool_prune_interval: 4096,
+            enable_account_deploy_index: false,
         }
     }
 }
@@ -2993,6 +3371,14 @@ fn initialize_block_body_db(
     Ok(())
 }
 
+/// Returns the upper bound for the memory map of the storage environment.
+fn max_map_size(config: &Config) -> usize {
+    config
+        .max_block_store_size
+        .saturating_add(config.max_deploy_store_size)
+        .saturating_add(config.max_deploy_metadata_store_size)
+}
+
 /// Retrieves the block body for the given block header.
 fn get_body_for_block_header<Tx: Transaction>(
     txn: &mut Tx,
@@ -0,0 +1,700 @@
+//! Portable archives of block ranges.
+//!
+//! An archive holds everything storage keeps about a contiguous range of blocks: headers, bodies,
+//! finality signatures, deploys along with their finalized approvals, approvals hashes and
+//! execution results. It allows seeding a node from a local file instead of fetching historical
+//! blocks from peers.
+//!
+//! An archive starts with an 8 byte magic value and the format version as a little-endian `u32`,
+//! followed by records. Every record consists of the payload length as a little-endian `u64`, the
+//! bincode-encoded payload and the 32 byte hash of the payload. The first record is an
+//! [`ArchiveHeader`], which is followed by one [`ArchivedBlock`] per height, in ascending order.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Read, Write},
+    ops::RangeInclusive,
+    rc::Rc,
+};
+
+use lmdb::Transaction;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, info};
+
+use casper_hashing::Digest;
+use casper_types::{crypto, EraId, ExecutionResult, PublicKey, U512};
+
+use super::{
+    lmdb_ext::{TransactionExt, WriteTransactionExt},
+    FatalStorageError, Storage,
+};
+use crate::{
+    components::fetcher::FetchItem,
+    types::{
+        error::BlockValidationError, sync_leap_validation_metadata::SyncLeapValidationMetaData,
+        ApprovalsHash, ApprovalsHashes, ApprovalsHashesValidationError, Block, BlockHash,
+        BlockSignatures, Deploy, DeployConfigurationFailure, DeployHash, FinalizedApprovals,
+    },
+    utils::{self, BlockSignatureError},
+};
+
+/// Magic value every archive starts with.
+const ARCHIVE_MAGIC: [u8; 8] = *b"CSPRBLKS";
+
+/// Current version of the archive format.
+const ARCHIVE_VERSION: u32 = 1;
+
+/// Upper bound for the size of a single record, to avoid huge allocations on corrupt input.
+const MAX_RECORD_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// An error exporting or importing a block archive.
+#[derive(Debug, Error)]
+pub(crate) enum ArchiveError {
+    /// Failure to read or write the archive.
+    #[error("archive i/o error: {0}")]
+    Io(#[from] io::Error),
+    /// Failure to encode or decode a record.
+    #[error("failed to encode or decode archive record: {0}")]
+    Serialization(#[from] bincode::Error),
+    /// The input does not start with the archive magic value.
+    #[error("not a block archive")]
+    NotAnArchive,
+    /// The archive was written in an unsupported format version.
+    #[error("unsupported archive version {0}, expected {ARCHIVE_VERSION}")]
+    UnsupportedVersion(u32),
+    /// A record exceeds [`MAX_RECORD_SIZE`].
+    #[error("archive record {index} is too large ({size} bytes)")]
+    RecordTooLarge { index: u64, size: u64 },
+    /// A record does not match its checksum.
+    #[error("checksum mismatch in archive record {0}")]
+    ChecksumMismatch(u64),
+    /// There is data after the last block declared in the header.
+    #[error("unexpected data after the last archived block")]
+    TrailingData,
+    /// The requested or declared height range is empty.
+    #[error("invalid block height range {from}..={to}")]
+    InvalidRange { from: u64, to: u64 },
+    /// The archive was created on a different network.
+    #[error("archive belongs to network {archive}, expected {expected}")]
+    NetworkMismatch { archive: String, expected: String },
+    /// A storage error.
+    #[error(transparent)]
+    Storage(#[from] FatalStorageError),
+    /// Data required for a complete archive entry is not stored.
+    #[error("missing {data} for block at height {height}")]
+    MissingData { height: u64, data: &'static str },
+    /// An archived block is not at the expected height.
+    #[error("expected block at height {expected}, found height {found}")]
+    UnexpectedHeight { expected: u64, found: u64 },
+    /// An archived block fails to verify.
+    #[error("invalid block at height {height}: {error}")]
+    InvalidBlock {
+        height: u64,
+        error: Box<BlockValidationError>,
+    },
+    /// An archived block is not a child of the block stored or archived at the previous height.
+    #[error("block at height {height} is not a child of the block at the previous height")]
+    NotChained { height: u64 },
+    /// An archived deploy fails to verify.
+    #[error("invalid deploy {deploy_hash} in block at height {height}: {error}")]
+    InvalidDeploy {
+        height: u64,
+        deploy_hash: DeployHash,
+        error: DeployConfigurationFailure,
+    },
+    /// The archived deploys or execution results do not match the deploys of the block.
+    #[error("archived deploys or execution results do not match block at height {height}")]
+    DeploysMismatch { height: u64 },
+    /// The finalized approvals do not match the archived approvals hashes.
+    #[error("finalized approvals do not match the approvals hashes of block at height {height}")]
+    ApprovalsMismatch { height: u64 },
+    /// The archived approvals hashes fail to verify against the block.
+    #[error("invalid approvals hashes for block at height {height}: {error}")]
+    InvalidApprovalsHashes {
+        height: u64,
+        error: ApprovalsHashesValidationError,
+    },
+    /// No switch block of the previous era is available to determine the validators of an era.
+    #[error(
+        "validators of era {era_id} are unknown; the switch block of the previous era must be \
+         stored or archived before its blocks"
+    )]
+    UnknownValidators { era_id: EraId },
+    /// Finality signatures are missing or do not belong to the archived block.
+    #[error("missing or mismatched finality signatures for block at height {height}")]
+    MissingSignatures { height: u64 },
+    /// A finality signature is cryptographically invalid.
+    #[error("invalid finality signature for block at height {height}: {error}")]
+    InvalidSignature { height: u64, error: crypto::Error },
+    /// The finality signatures do not carry enough weight.
+    #[error("insufficient finality signatures for block at height {height}: {error}")]
+    InsufficientSignatures {
+        height: u64,
+        error: Box<BlockSignatureError>,
+    },
+}
+
+/// The first record of an archive.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ArchiveHeader {
+    /// Name of the network the blocks belong to.
+    network_name: String,
+    /// Height of the first archived block.
+    from: u64,
+    /// Height of the last archived block.
+    to: u64,
+}
+
+impl ArchiveHeader {
+    /// Returns the number of archived blocks.
+    fn block_count(&self) -> u64 {
+        self.to - self.from + 1
+    }
+}
+
+/// Everything stored about a single block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ArchivedBlock {
+    /// The block.
+    block: Block,
+    /// The finality signatures, absent only for the genesis block.
+    signatures: Option<BlockSignatures>,
+    /// The deploys and transfers of the block, in the order of the block body.
+    deploys: Vec<Deploy>,
+    /// Finalized approvals differing from the approvals in the deploys themselves.
+    finalized_approvals: BTreeMap<DeployHash, FinalizedApprovals>,
+    /// The approvals hashes, if stored.
+    approvals_hashes: Option<ApprovalsHashes>,
+    /// The execution results of the deploys, in the order of the block body.
+    execution_results: Vec<(DeployHash, ExecutionResult)>,
+}
+
+/// Writes a block archive.
+struct ArchiveWriter<W> {
+    writer: W,
+    /// Number of blocks still expected according to the header.
+    remaining: u64,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Starts a new archive with the given header.
+    fn new(mut writer: W, header: &ArchiveHeader) -> Result<Self, ArchiveError> {
+        if header.from > header.to {
+            return Err(ArchiveError::InvalidRange {
+                from: header.from,
+                to: header.to,
+            });
+        }
+        writer.write_all(&ARCHIVE_MAGIC)?;
+        writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+        write_record(&mut writer, header)?;
+        Ok(ArchiveWriter {
+            writer,
+            remaining: header.block_count(),
+        })
+    }
+
+    /// Appends the next block.
+    fn append(&mut self, archived_block: &ArchivedBlock) -> Result<(), ArchiveError> {
+        if self.remaining == 0 {
+            return Err(ArchiveError::TrailingData);
+        }
+        write_record(&mut self.writer, archived_block)?;
+        self.remaining -= 1;
+        Ok(())
+    }
+
+    /// Flushes and returns the underlying writer once all blocks have been appended.
+    fn finish(mut self) -> Result<W, ArchiveError> {
+        if self.remaining != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("{} archived blocks missing", self.remaining),
+            )
+            .into());
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads a block archive, verifying the checksum of every record.
+struct ArchiveReader<R> {
+    reader: R,
+    header: ArchiveHeader,
+    /// Index of the next record.
+    next_index: u64,
+}
+
+impl<R: Read> ArchiveReader<R> {
+    /// Reads the archive header.
+    fn new(mut reader: R) -> Result<Self, ArchiveError> {
+        let mut magic = [0; ARCHIVE_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != ARCHIVE_MAGIC {
+            return Err(ArchiveError::NotAnArchive);
+        }
+        let mut version = [0; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != ARCHIVE_VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+
+        let header: ArchiveHeader = read_record(&mut reader, 0)?;
+        if header.from > header.to {
+            return Err(ArchiveError::InvalidRange {
+                from: header.from,
+                to: header.to,
+            });
+        }
+        Ok(ArchiveReader {
+            reader,
+            header,
+            next_index: 1,
+        })
+    }
+
+    /// Returns the archive header.
+    fn header(&self) -> &ArchiveHeader {
+        &self.header
+    }
+
+    /// Reads the next block, or returns `None` once all blocks have been read.
+    fn next_block(&mut self) -> Result<Option<ArchivedBlock>, ArchiveError> {
+        if self.next_index > self.header.block_count() {
+            let mut trailing = [0; 1];
+            if self.reader.read(&mut trailing)? != 0 {
+                return Err(ArchiveError::TrailingData);
+            }
+            return Ok(None);
+        }
+        let archived_block = read_record(&mut self.reader, self.next_index)?;
+        self.next_index += 1;
+        Ok(Some(archived_block))
+    }
+}
+
+/// Writes a single checksummed record.
+fn write_record<W: Write, T: Serialize>(writer: &mut W, item: &T) -> Result<(), ArchiveError> {
+    let payload = bincode::serialize(item)?;
+    writer.write_all(&(payload.len() as u64).to_le_bytes())?;
+    writer.write_all(&payload)?;
+    writer.write_all(Digest::hash(&payload).as_ref())?;
+    Ok(())
+}
+
+/// Reads a single checksummed record.
+fn read_record<R: Read, T: DeserializeOwned>(
+    reader: &mut R,
+    index: u64,
+) -> Result<T, ArchiveError> {
+    let mut size = [0; 8];
+    reader.read_exact(&mut size)?;
+    let size = u64::from_le_bytes(size);
+    if size > MAX_RECORD_SIZE {
+        return Err(ArchiveError::RecordTooLarge { index, size });
+    }
+
+    let mut payload = vec![0; size as usize];
+    reader.read_exact(&mut payload)?;
+    let mut checksum = [0; Digest::LENGTH];
+    reader.read_exact(&mut checksum)?;
+    if Digest::hash(&payload).value() != checksum {
+        return Err(ArchiveError::ChecksumMismatch(index));
+    }
+    Ok(bincode::deserialize(&payload)?)
+}
+
+/// Outcome of importing an archive.
+#[derive(Debug, Default)]
+pub(crate) struct ImportSummary {
+    /// Number of blocks written to storage.
+    pub(crate) imported: u64,
+    /// Number of blocks that were already stored.
+    pub(crate) already_stored: u64,
+}
+
+/// Writes the blocks at `heights` into a new archive.
+///
+/// Fails if any of the blocks is not fully stored.
+pub(crate) fn export_blocks<W: Write>(
+    storage: &Storage,
+    network_name: &str,
+    heights: RangeInclusive<u64>,
+    writer: W,
+) -> Result<W, ArchiveError> {
+    let header = ArchiveHeader {
+        network_name: network_name.to_string(),
+        from: *heights.start(),
+        to: *heights.end(),
+    };
+    let mut archive = ArchiveWriter::new(writer, &header)?;
+    for height in heights {
+        archive.append(&storage.read_archived_block(height)?)?;
+        debug!(height, "exported block");
+    }
+    archive.finish()
+}
+
+/// Validates all blocks of an archive and writes them to storage.
+///
+/// Blocks are checked for integrity and continuity, and their finality signatures are verified
+/// against the validator weights announced by the switch block of the previous era, which has to
+/// be stored already or precede the block in the archive. Imported blocks are not marked complete,
+/// as the corresponding global state is not part of the archive.
+///
+/// Blocks are written one by one, so on failure all blocks preceding the offending one remain
+/// imported, and importing the same archive again skips them.
+pub(crate) fn import_blocks<R: Read>(
+    storage: &mut Storage,
+    network_name: &str,
+    validation_metadata: &SyncLeapValidationMetaData,
+    reader: R,
+) -> Result<ImportSummary, ArchiveError> {
+    let mut archive = ArchiveReader::new(reader)?;
+    if archive.header().network_name != network_name {
+        return Err(ArchiveError::NetworkMismatch {
+            archive: archive.header().network_name.clone(),
+            expected: network_name.to_string(),
+        });
+    }
+
+    let mut validator = ImportValidator {
+        validation_metadata,
+        validator_weights: BTreeMap::new(),
+        previous: None,
+    };
+    let mut summary = ImportSummary::default();
+    let mut expected_height = archive.header().from;
+    while let Some(archived_block) = archive.next_block()? {
+        let height = archived_block.block.height();
+        if height != expected_height {
+            return Err(ArchiveError::UnexpectedHeight {
+                expected: expected_height,
+                found: height,
+            });
+        }
+        validator.validate(storage, &archived_block)?;
+        if storage.write_archived_block(archived_block)? {
+            summary.imported += 1;
+        } else {
+            summary.already_stored += 1;
+        }
+        expected_height += 1;
+    }
+    info!(
+        imported = summary.imported,
+        already_stored = summary.already_stored,
+        "imported block archive"
+    );
+    Ok(summary)
+}
+
+/// Checks the finalized approvals of an archived block.
+///
+/// If the approvals hashes are archived, they are verified against the block's global state, and
+/// the finalized approvals, or the deploys' own approvals if there are none, must hash to them.
+/// Otherwise the finalized approvals must be valid signatures of their deploys.
+fn validate_approvals(archived_block: &ArchivedBlock) -> Result<(), ArchiveError> {
+    let block = &archived_block.block;
+    let height = block.height();
+    let is_in_block = |deploy_hash: &DeployHash| {
+        archived_block
+            .deploys
+            .iter()
+            .any(|deploy| deploy.hash() == deploy_hash)
+    };
+    if !archived_block.finalized_approvals.keys().all(is_in_block) {
+        return Err(ArchiveError::ApprovalsMismatch { height });
+    }
+
+    let approvals_hashes = match &archived_block.approvals_hashes {
+        Some(approvals_hashes) => approvals_hashes,
+        None => {
+            for (deploy_hash, approvals) in &archived_block.finalized_approvals {
+                let invalid_deploy = |error| ArchiveError::InvalidDeploy {
+                    height,
+                    deploy_hash: *deploy_hash,
+                    error,
+                };
+                if approvals.inner().is_empty() {
+                    return Err(invalid_deploy(DeployConfigurationFailure::EmptyApprovals));
+                }
+                for (index, approval) in approvals.inner().iter().enumerate() {
+                    crypto::verify(*deploy_hash, approval.signature(), approval.signer()).map_err(
+                        |error| {
+                            invalid_deploy(DeployConfigurationFailure::InvalidApproval {
+                                index,
+                                error_msg: error.to_string(),
+                            })
+                        },
+                    )?;
+                }
+            }
+            return Ok(());
+        }
+    };
+
+    if approvals_hashes.block_hash() != block.hash()
+        || approvals_hashes.approvals_hashes().len() != archived_block.deploys.len()
+    {
+        return Err(ArchiveError::ApprovalsMismatch { height });
+    }
+    for (deploy, approvals_hash) in archived_block
+        .deploys
+        .iter()
+        .zip(approvals_hashes.approvals_hashes())
+    {
+        let approvals = archived_block
+            .finalized_approvals
+            .get(deploy.hash())
+            .map_or(deploy.approvals(), FinalizedApprovals::inner);
+        if ApprovalsHash::compute(approvals).ok().as_ref() != Some(approvals_hash) {
+            return Err(ArchiveError::ApprovalsMismatch { height });
+        }
+    }
+    approvals_hashes
+        .validate(block)
+        .map_err(|error| ArchiveError::InvalidApprovalsHashes { height, error })
+}
+
+/// State carried across the blocks of an archive during import.
+struct ImportValidator<'a> {
+    validation_metadata: &'a SyncLeapValidationMetaData,
+    /// Validator weights announced by archived switch blocks, by era.
+    validator_weights: BTreeMap<EraId, BTreeMap<PublicKey, U512>>,
+    /// Height and hash of the previously validated block.
+    previous: Option<(u64, BlockHash)>,
+}
+
+impl<'a> ImportValidator<'a> {
+    /// Validates a single archived block.
+    fn validate(
+        &mut self,
+        storage: &Storage,
+        archived_block: &ArchivedBlock,
+    ) -> Result<(), ArchiveError> {
+        let block = &archived_block.block;
+        let header = block.header();
+        let height = header.height();
+        block.verify().map_err(|error| ArchiveError::InvalidBlock {
+            height,
+            error: Box::new(error),
+        })?;
+
+        let parent_hash = match self.previous {
+            Some((previous_height, previous_hash)) if previous_height + 1 == height => {
+                Some(previous_hash)
+            }
+            _ => match height.checked_sub(1) {
+                Some(parent_height) => storage
+                    .read_block_header_by_height(parent_height, false)?
+                    .map(|parent_header| parent_header.block_hash()),
+                None => None,
+            },
+        };
+        if parent_hash.map_or(false, |parent_hash| parent_hash != *header.parent_hash()) {
+            return Err(ArchiveError::NotChained { height });
+        }
+
+        let expected_deploys: Vec<_> = block.body().deploy_and_transfer_hashes().collect();
+        let archived_deploys: Vec<_> = archived_block.deploys.iter().map(Deploy::hash).collect();
+        let executed_deploys: Vec<_> = archived_block
+            .execution_results
+            .iter()
+            .map(|(deploy_hash, _)| deploy_hash)
+            .collect();
+        if expected_deploys != archived_deploys || expected_deploys != executed_deploys {
+            return Err(ArchiveError::DeploysMismatch { height });
+        }
+        for deploy in &archived_block.deploys {
+            deploy
+                .is_valid()
+                .map_err(|error| ArchiveError::InvalidDeploy {
+                    height,
+                    deploy_hash: *deploy.hash(),
+                    error,
+                })?;
+        }
+        validate_approvals(archived_block)?;
+
+        if !header.is_genesis() {
+            let signatures = archived_block
+                .signatures
+                .as_ref()
+                .filter(|signatures| {
+                    signatures.block_hash == *block.hash() && signatures.era_id == header.era_id()
+                })
+                .ok_or(ArchiveError::MissingSignatures { height })?;
+            signatures
+                .verify()
+                .map_err(|error| ArchiveError::InvalidSignature { height, error })?;
+            let validator_weights = self.validator_weights(storage, header.era_id())?;
+            utils::check_sufficient_block_signatures(
+                &validator_weights,
+                self.validation_metadata.finality_threshold_fraction,
+                Some(signatures),
+            )
+            .map_err(|error| ArchiveError::InsufficientSignatures {
+                height,
+                error: Box::new(error),
+            })?;
+        }
+
+        if let Some(next_era_weights) = header.next_era_validator_weights() {
+            let _ = self
+                .validator_weights
+                .insert(header.next_block_era_id(), next_era_weights.clone());
+        }
+        self.previous = Some((height, *block.hash()));
+        Ok(())
+    }
+
+    /// Returns the validator weights of the given era.
+    fn validator_weights(
+        &self,
+        storage: &Storage,
+        era_id: EraId,
+    ) -> Result<BTreeMap<PublicKey, U512>, ArchiveError> {
+        let switch_block_header = match self.validator_weights.get(&era_id) {
+            Some(validator_weights) => return Ok(self.updated_weights(era_id, validator_weights)),
+            None => {
+                let previous_era_id = era_id
+                    .predecessor()
+                    .ok_or(ArchiveError::UnknownValidators { era_id })?;
+                let mut txn = storage
+                    .env
+                    .begin_ro_txn()
+                    .map_err(FatalStorageError::from)?;
+                storage.get_switch_block_header_by_era_id(&mut txn, previous_era_id)?
+            }
+        };
+        switch_block_header
+            .as_ref()
+            .and_then(|header| header.next_era_validator_weights())
+            .map(|validator_weights| self.updated_weights(era_id, validator_weights))
+            .ok_or(ArchiveError::UnknownValidators { era_id })
+    }
+
+    /// Applies validator changes made by the current protocol upgrade, like sync leaps do.
+    fn updated_weights(
+        &self,
+        era_id: EraId,
+        validator_weights: &BTreeMap<PublicKey, U512>,
+    ) -> BTreeMap<PublicKey, U512> {
+        if era_id == self.validation_metadata.activation_point.era_id() {
+            if let Some(updated_weights) = self
+                .validation_metadata
+                .global_state_update
+                .as_ref()
+                .and_then(|update| update.validators.as_ref())
+            {
+                return updated_weights.clone();
+            }
+        }
+        validator_weights.clone()
+    }
+}
+
+impl Storage {
+    /// Collects everything stored about the block at `height` into an archive entry.
+    fn read_archived_block(&self, height: u64) -> Result<ArchivedBlock, ArchiveError> {
+        let missing = |data| ArchiveError::MissingData { height, data };
+        let mut txn = self.env.begin_ro_txn().map_err(FatalStorageError::from)?;
+
+        let block = self
+            .get_block_by_height(&mut txn, height)?
+            .ok_or_else(|| missing("block"))?;
+        let signatures = self.get_block_signatures(&mut txn, block.hash())?;
+        if signatures.is_none() && !block.header().is_genesis() {
+            return Err(missing("finality signatures"));
+        }
+
+        let mut deploys = vec![];
+        let mut finalized_approvals = BTreeMap::new();
+        for deploy_hash in block.body().deploy_and_transfer_hashes() {
+            let deploy: Deploy = txn
+                .get_value(self.deploy_db, deploy_hash)
+                .map_err(FatalStorageError::from)?
+                .ok_or_else(|| missing("deploy"))?;
+            if let Some(approvals) = txn
+                .get_value(self.finalized_approvals_db, deploy_hash)
+                .map_err(FatalStorageError::from)?
+            {
+                let _ = finalized_approvals.insert(*deploy_hash, approvals);
+            }
+            deploys.push(deploy);
+        }
+        let approvals_hashes = txn
+            .get_value(self.approvals_hashes_db, block.hash())
+            .map_err(FatalStorageError::from)?;
+        let execution_results = self
+            .get_execution_results(&mut txn, block.hash())?
+            .ok_or_else(|| missing("execution results"))?;
+        txn.commit().map_err(FatalStorageError::from)?;
+
+        Ok(ArchivedBlock {
+            block,
+            signatures,
+            deploys,
+            finalized_approvals,
+            approvals_hashes,
+            execution_results,
+        })
+    }
+
+    /// Writes a validated archive entry to storage, without marking the block complete.
+    ///
+    /// Returns `Ok(false)` if the block was already stored, in which case nothing is written.
+    fn write_archived_block(
+        &mut self,
+        archived_block: ArchivedBlock,
+    ) -> Result<bool, ArchiveError> {
+        if self.block_exists(archived_block.block.hash())? {
+            return Ok(false);
+        }
+
+        let ArchivedBlock {
+            block,
+            signatures,
+            deploys,
+            finalized_approvals,
+            approvals_hashes,
+            execution_results,
+        } = archived_block;
+
+        // Deploys and finalized approvals are written in transactions of their own.
+        let deploy_hashes: BTreeSet<_> = deploys.iter().map(|deploy| *deploy.hash()).collect();
+        for deploy in &deploys {
+            let _ = self.put_deploy(deploy)?;
+        }
+        for (deploy_hash, approvals) in &finalized_approvals {
+            if deploy_hashes.contains(deploy_hash) {
+                let _ = self.store_finalized_approvals(deploy_hash, approvals)?;
+            }
+        }
+
+        let env = Rc::clone(&self.env);
+        let mut txn = env.begin_rw_txn().map_err(FatalStorageError::from)?;
+        if !self.write_validated_block(&mut txn, &block)? {
+            return Err(FatalStorageError::FailedToOverwriteBlock.into());
+        }
+        if let Some(signatures) = signatures {
+            let _ = txn
+                .put_value(self.block_metadata_db, block.hash(), &signatures, true)
+                .map_err(FatalStorageError::from)?;
+        }
+        if let Some(approvals_hashes) = approvals_hashes {
+            let _ = self.write_approvals_hashes(&mut txn, &approvals_hashes)?;
+        }
+        let _ = self.write_execution_results(
+            &mut txn,
+            block.hash(),
+            execution_results.into_iter().collect(),
+        )?;
+        txn.commit().map_err(FatalStorageError::from)?;
+        Ok(true)
+    }
+}
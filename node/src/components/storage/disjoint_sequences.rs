@@ -186,6 +186,13 @@ impl DisjointSequences {
         &self.sequences
     }
 
+    /// Returns `true` if `value` exists in the disjoint sequences.
+    pub(super) fn contains(&self, value: u64) -> bool {
+        self.sequences
+            .iter()
+            .any(|sequence| value >= sequence.low && value <= sequence.high)
+    }
+
     /// Reduces the sequence(s), keeping all entries below and including `max_value`.  If
     /// `max_value` is not already included in a sequence, it will not be added.
     ///
@@ -217,13 +224,6 @@ impl DisjointSequences {
             self.insert(height);
         })
     }
-
-    /// Returns `true` if `value` exists in the disjoint sequences.
-    fn contains(&self, value: u64) -> bool {
-        self.sequences
-            .iter()
-            .any(|sequence| value >= sequence.low && value <= sequence.high)
-    }
 }
 
 impl FromBytes for Sequence {
@@ -306,16 +306,20 @@ impl From<Vec<u64>> for DisjointSequences {
                 None => None,
                 Some(low) => {
                     let mut sequence = Sequence::single(low);
-                    while let Some(i) = iter.peek() {
-                        if *i == sequence.high + 1 {
-                            sequence.high = iter.next().unwrap();
+                    while let Some(&i) = iter.peek() {
+                        if i > sequence.high + 1 {
+                            break;
                         }
+                        // `i` is either a duplicate or extends the current sequence.
+                        sequence.high = iter.next().unwrap();
                     }
                     Some(sequence)
                 }
             })
-            .collect();
+            .collect::<Vec<_>>();
 
+        // Sequences are held in descending order.
+        let sequences = sequences.into_iter().rev().collect();
         DisjointSequences { sequences }
     }
 }
@@ -428,6 +432,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_construct_from_set_with_gaps_and_duplicates() {
+        let disjoint_sequences = DisjointSequences::from(vec![9, 1, 3, 2, 2, 8, 5, 10]);
+        assert_eq!(
+            disjoint_sequences.sequences,
+            vec![new_sequence(8, 10), new_sequence(5, 5), new_sequence(1, 3)]
+        );
+        assert!(DisjointSequences::from(vec![]).sequences.is_empty());
+    }
+
     #[test]
     fn should_get_highest_sequence() {
         let mut disjoint_sequences = DisjointSequences::default();
@@ -21,6 +21,9 @@ pub enum FatalStorageError {
     /// Failure to create the root database directory.
     #[error("failed to create database directory `{}`: {}", .0.display(), .1)]
     CreateDatabaseDirectory(PathBuf, io::Error),
+    /// The storage database file does not exist.
+    #[error("storage database not found at `{}`", .0.display())]
+    DatabaseNotFound(PathBuf),
     /// Found a duplicate block-at-height index entry.
     #[error("duplicate entries for block at height {height}: {first} / {second}")]
     DuplicateBlockIndex {
@@ -118,6 +121,12 @@ pub enum FatalStorageError {
         /// The block hash of the signatures found in the index.
         block_hash_bytes: Vec<u8>,
     },
+    /// Corrupted account deploy index.
+    #[error("malformed entry in account deploy index: {raw_entry:x?}")]
+    CorruptedAccountDeployIndex {
+        /// The raw entry found in the index.
+        raw_entry: Vec<u8>,
+    },
     /// Switch block does not contain era end.
     #[error("switch block does not contain era end: {0:?}")]
     InvalidSwitchBlock(Box<BlockHeader>),
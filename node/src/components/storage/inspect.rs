@@ -0,0 +1,546 @@
+//! Offline inspection and repair of the storage databases.
+//!
+//! The [`StorageInspector`] opens the LMDB environment of the storage component directly, without
+//! rebuilding the in-memory indices [`Storage::new`](super::Storage::new) creates on startup (and
+//! fails to create if the stored data is inconsistent). It backs the `casper-node storage`
+//! subcommands and must only be used while the node is not running.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use lmdb::{Cursor, Database, Environment, EnvironmentFlags, Transaction, WriteFlags};
+
+use casper_types::bytesrepr::{FromBytes, ToBytes};
+
+use super::{
+    disjoint_sequences::DisjointSequences,
+    lmdb_ext::{self, LmdbExtError, TransactionExt},
+    max_map_size, Config, FatalStorageError, ACCOUNT_DEPLOY_INDEX_STORAGE_KEY,
+    COMPLETED_BLOCKS_STORAGE_KEY, MAX_DB_COUNT, OS_FLAGS, STORAGE_DB_FILENAME,
+};
+use crate::{
+    types::{Block, BlockBody, BlockHash, BlockHeader, DeployHash, DeployMetadata},
+    utils::WithDir,
+};
+
+/// Direct access to the databases of a storage directory.
+pub(crate) struct StorageInspector {
+    /// Path of the opened database file.
+    path: PathBuf,
+    /// The LMDB environment, opened read-only unless repairs were requested.
+    env: Environment,
+    block_header_db: Database,
+    block_body_db: Database,
+    block_metadata_db: Database,
+    deploy_db: Database,
+    deploy_metadata_db: Database,
+    state_store_db: Database,
+}
+
+impl StorageInspector {
+    /// Opens the storage databases of the given network.
+    ///
+    /// Unless `writable` is set, the environment is opened read-only and nothing is ever written.
+    pub(crate) fn open(
+        cfg: &WithDir<Config>,
+        network_name: &str,
+        writable: bool,
+    ) -> Result<Self, FatalStorageError> {
+        let config = cfg.value();
+        let root = cfg.with_dir(config.path.clone());
+
+        // Storage files live in a per-network subdirectory, but nodes which have not been
+        // restarted since that was introduced still keep them in the root.
+        let network_path = root.join(network_name).join(STORAGE_DB_FILENAME);
+        let legacy_path = root.join(STORAGE_DB_FILENAME);
+        let path = if network_path.exists() {
+            network_path
+        } else if legacy_path.exists() {
+            legacy_path
+        } else {
+            return Err(FatalStorageError::DatabaseNotFound(network_path));
+        };
+
+        let mut flags = EnvironmentFlags::NO_SUB_DIR
+            | EnvironmentFlags::NO_TLS
+            | EnvironmentFlags::NO_READAHEAD;
+        if writable {
+            flags |= OS_FLAGS;
+        } else {
+            flags |= EnvironmentFlags::READ_ONLY;
+        }
+
+        let env = Environment::new()
+            .set_flags(flags)
+            .set_max_dbs(MAX_DB_COUNT)
+            .set_map_size(max_map_size(config))
+            .open(&path)?;
+
+        Ok(StorageInspector {
+            block_header_db: env.open_db(Some("block_header"))?,
+            block_body_db: env.open_db(Some("block_body"))?,
+            block_metadata_db: env.open_db(Some("block_metadata"))?,
+            deploy_db: env.open_db(Some("deploys"))?,
+            deploy_metadata_db: env.open_db(Some("deploy_metadata"))?,
+            state_store_db: env.open_db(Some("state_store"))?,
+            path,
+            env,
+        })
+    }
+
+    /// Returns the path of the opened database file.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the persisted set of completed block heights, if any.
+    fn completed_blocks(&self) -> Result<Option<DisjointSequences>, FatalStorageError> {
+        let txn = self.env.begin_ro_txn()?;
+        let raw = match txn.get(self.state_store_db, &COMPLETED_BLOCKS_STORAGE_KEY) {
+            Ok(raw) => raw.to_vec(),
+            Err(lmdb::Error::NotFound) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let (sequences, _) = DisjointSequences::from_vec(raw)
+            .map_err(FatalStorageError::UnexpectedDeserializationFailure)?;
+        Ok(Some(sequences))
+    }
+
+    /// Summarizes which block heights have a stored header and which are marked complete.
+    pub(crate) fn block_ranges(&self) -> Result<BlockRanges, FatalStorageError> {
+        let txn = self.env.begin_ro_txn()?;
+        let mut cursor = txn.open_ro_cursor(self.block_header_db)?;
+
+        let mut heights = Vec::new();
+        let mut undecodable_headers = 0;
+        for row in cursor.iter() {
+            let (_, raw_header) = row?;
+            match lmdb_ext::deserialize::<BlockHeader>(raw_header) {
+                Ok(block_header) => heights.push(block_header.height()),
+                Err(_) => undecodable_headers += 1,
+            }
+        }
+        drop(cursor);
+        txn.commit()?;
+
+        Ok(BlockRanges {
+            header_count: heights.len(),
+            undecodable_headers,
+            stored: DisjointSequences::from(heights),
+            completed: self.completed_blocks()?,
+        })
+    }
+
+    /// Checks the blocks with a height within `heights` for missing or inconsistent data.
+    ///
+    /// Every stored block must have a body matching its header, finality signatures (unless it is
+    /// the genesis block), and all of its deploys along with their execution results. Heights
+    /// marked complete must have a stored block.
+    pub(crate) fn check(
+        &self,
+        heights: RangeInclusive<u64>,
+    ) -> Result<ConsistencyReport, FatalStorageError> {
+        let completed_blocks = self.completed_blocks()?;
+
+        // Lookups happen in a separate transaction, as they require mutable access.
+        let header_txn = self.env.begin_ro_txn()?;
+        let mut lookup_txn = self.env.begin_ro_txn()?;
+        let mut cursor = header_txn.open_ro_cursor(self.block_header_db)?;
+
+        let mut issues = Vec::new();
+        let mut blocks_at_height: BTreeMap<u64, BlockHash> = BTreeMap::new();
+        for row in cursor.iter() {
+            let (raw_key, raw_header) = row?;
+            let block_header: BlockHeader = match lmdb_ext::deserialize(raw_header) {
+                Ok(block_header) => block_header,
+                Err(error) => {
+                    issues.push(StorageIssue::UndecodableHeader {
+                        key: base16::encode_lower(raw_key),
+                        error: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let height = block_header.height();
+            if !heights.contains(&height) {
+                continue;
+            }
+
+            let block_hash = block_header.block_hash();
+            if block_hash.as_ref() != raw_key {
+                issues.push(StorageIssue::HeaderHashMismatch { height, block_hash });
+            }
+            if let Some(first) = blocks_at_height.insert(height, block_hash) {
+                issues.push(StorageIssue::DuplicateHeight {
+                    height,
+                    first,
+                    second: block_hash,
+                });
+            }
+
+            self.check_block(&mut lookup_txn, block_header, &mut issues)?;
+        }
+        drop(cursor);
+        header_txn.commit()?;
+        lookup_txn.commit()?;
+
+        if let Some(completed_blocks) = &completed_blocks {
+            for sequence in completed_blocks.sequences().iter().rev() {
+                let low = sequence.low().max(*heights.start());
+                let high = sequence.high().min(*heights.end());
+                issues.extend(
+                    (low..=high)
+                        .filter(|height| !blocks_at_height.contains_key(height))
+                        .map(|height| StorageIssue::MissingBlock { height }),
+                );
+            }
+        }
+
+        Ok(ConsistencyReport {
+            blocks_checked: blocks_at_height.len(),
+            completed_blocks,
+            issues,
+        })
+    }
+
+    /// Checks the data belonging to a single block header.
+    fn check_block<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        block_header: BlockHeader,
+        issues: &mut Vec<StorageIssue>,
+    ) -> Result<(), FatalStorageError> {
+        let height = block_header.height();
+        let block_hash = block_header.block_hash();
+
+        if !block_header.is_genesis() && !txn.value_exists(self.block_metadata_db, &block_hash)? {
+            issues.push(StorageIssue::MissingSignatures { height, block_hash });
+        }
+
+        let block_body: BlockBody =
+            match txn.get_value(self.block_body_db, block_header.body_hash()) {
+                Ok(Some(block_body)) => block_body,
+                Ok(None) => {
+                    issues.push(StorageIssue::MissingBody { height, block_hash });
+                    return Ok(());
+                }
+                Err(LmdbExtError::DataCorrupted(error)) => {
+                    issues.push(StorageIssue::InvalidBody {
+                        height,
+                        block_hash,
+                        error: error.to_string(),
+                    });
+                    return Ok(());
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+        for deploy_hash in block_body.deploy_and_transfer_hashes() {
+            let deploy_hash = *deploy_hash;
+            if !txn.value_exists(self.deploy_db, &deploy_hash)? {
+                issues.push(StorageIssue::MissingDeploy {
+                    height,
+                    block_hash,
+                    deploy_hash,
+                });
+            }
+
+            let has_execution_result =
+                match txn.get_value::<_, DeployMetadata>(self.deploy_metadata_db, &deploy_hash) {
+                    Ok(maybe_metadata) => maybe_metadata.map_or(false, |metadata| {
+                        metadata.execution_results.contains_key(&block_hash)
+                    }),
+                    Err(LmdbExtError::DataCorrupted(_)) => false,
+                    Err(err) => return Err(err.into()),
+                };
+            if !has_execution_result {
+                issues.push(StorageIssue::MissingExecutionResult {
+                    height,
+                    block_hash,
+                    deploy_hash,
+                });
+            }
+        }
+
+        if let Err(error) = Block::new_from_header_and_body(block_header, block_body) {
+            issues.push(StorageIssue::InvalidBody {
+                height,
+                block_hash,
+                error: error.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Repairs the indices kept in the state store, based on the findings of a full [`check`].
+    ///
+    /// Heights affected by an issue are no longer marked complete, so that the node acquires the
+    /// missing data again on its next start, and the account deploy index is marked for
+    /// rebuilding. Block and deploy data itself is never modified.
+    ///
+    /// Returns the heights which were marked complete before, but are not anymore.
+    ///
+    /// [`check`]: StorageInspector::check
+    pub(crate) fn rebuild_indices(
+        &self,
+        report: &ConsistencyReport,
+    ) -> Result<Vec<u64>, FatalStorageError> {
+        let affected_heights = report.affected_heights();
+        let mut txn = self.env.begin_rw_txn()?;
+
+        let mut unmarked = Vec::new();
+        if let Some(completed_blocks) = &report.completed_blocks {
+            let mut remaining = Vec::new();
+            for sequence in completed_blocks.sequences() {
+                for height in sequence.low()..=sequence.high() {
+                    if affected_heights.contains(&height) {
+                        unmarked.push(height);
+                    } else {
+                        remaining.push(height);
+                    }
+                }
+            }
+
+            if !unmarked.is_empty() {
+                let serialized = DisjointSequences::from(remaining)
+                    .to_bytes()
+                    .map_err(FatalStorageError::UnexpectedSerializationFailure)?;
+                txn.put(
+                    self.state_store_db,
+                    &COMPLETED_BLOCKS_STORAGE_KEY,
+                    &serialized,
+                    WriteFlags::default(),
+                )?;
+            }
+        }
+
+        // The index is rebuilt from scratch on startup when its marker is missing.
+        match txn.del(self.state_store_db, &ACCOUNT_DEPLOY_INDEX_STORAGE_KEY, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => (),
+            Err(err) => return Err(err.into()),
+        }
+        txn.commit()?;
+
+        unmarked.sort_unstable();
+        Ok(unmarked)
+    }
+}
+
+/// Stored and completed block heights.
+#[derive(Debug)]
+pub(crate) struct BlockRanges {
+    /// Number of decodable block headers.
+    header_count: usize,
+    /// Number of block headers that failed to decode.
+    undecodable_headers: usize,
+    /// Heights of all decodable block headers.
+    stored: DisjointSequences,
+    /// Heights marked complete, if the set of completed blocks has been persisted.
+    completed: Option<DisjointSequences>,
+}
+
+impl Display for BlockRanges {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "block headers:    {}", self.header_count)?;
+        if self.undecodable_headers > 0 {
+            writeln!(formatter, "undecodable:      {}", self.undecodable_headers)?;
+        }
+        writeln!(formatter, "stored heights:   {}", self.stored)?;
+        match &self.completed {
+            Some(completed) => write!(formatter, "completed blocks: {}", completed),
+            None => write!(formatter, "completed blocks: not persisted"),
+        }
+    }
+}
+
+/// An inconsistency found in the stored data.
+#[derive(Clone, Debug)]
+pub(crate) enum StorageIssue {
+    /// A block header could not be decoded.
+    UndecodableHeader {
+        /// The hex-encoded key the header is stored under.
+        key: String,
+        /// The decoding error.
+        error: String,
+    },
+    /// A block header is not stored under its own hash.
+    HeaderHashMismatch { height: u64, block_hash: BlockHash },
+    /// More than one block header is stored for the same height.
+    DuplicateHeight {
+        height: u64,
+        first: BlockHash,
+        second: BlockHash,
+    },
+    /// The body of a block is missing.
+    MissingBody { height: u64, block_hash: BlockHash },
+    /// The body of a block does not decode or does not match its header.
+    InvalidBody {
+        height: u64,
+        block_hash: BlockHash,
+        error: String,
+    },
+    /// No finality signatures are stored for a block.
+    MissingSignatures { height: u64, block_hash: BlockHash },
+    /// A deploy included in a block is missing.
+    MissingDeploy {
+        height: u64,
+        block_hash: BlockHash,
+        deploy_hash: DeployHash,
+    },
+    /// The execution result of a deploy in the context of a block is missing.
+    MissingExecutionResult {
+        height: u64,
+        block_hash: BlockHash,
+        deploy_hash: DeployHash,
+    },
+    /// A height is marked complete, but no block is stored for it.
+    MissingBlock { height: u64 },
+}
+
+impl StorageIssue {
+    /// Returns the height of the affected block, if known.
+    pub(crate) fn height(&self) -> Option<u64> {
+        match self {
+            StorageIssue::UndecodableHeader { .. } => None,
+            StorageIssue::HeaderHashMismatch { height, .. }
+            | StorageIssue::DuplicateHeight { height, .. }
+            | StorageIssue::MissingBody { height, .. }
+            | StorageIssue::InvalidBody { height, .. }
+            | StorageIssue::MissingSignatures { height, .. }
+            | StorageIssue::MissingDeploy { height, .. }
+            | StorageIssue::MissingExecutionResult { height, .. }
+            | StorageIssue::MissingBlock { height } => Some(*height),
+        }
+    }
+}
+
+impl Display for StorageIssue {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageIssue::UndecodableHeader { key, error } => {
+                write!(
+                    formatter,
+                    "undecodable block header under key {}: {}",
+                    key, error
+                )
+            }
+            StorageIssue::HeaderHashMismatch { height, block_hash } => write!(
+                formatter,
+                "{} at height {} is not stored under its hash",
+                block_hash, height
+            ),
+            StorageIssue::DuplicateHeight {
+                height,
+                first,
+                second,
+            } => write!(
+                formatter,
+                "duplicate blocks at height {}: {} / {}",
+                height, first, second
+            ),
+            StorageIssue::MissingBody { height, block_hash } => write!(
+                formatter,
+                "missing body of {} at height {}",
+                block_hash, height
+            ),
+            StorageIssue::InvalidBody {
+                height,
+                block_hash,
+                error,
+            } => write!(
+                formatter,
+                "invalid body of {} at height {}: {}",
+                block_hash, height, error
+            ),
+            StorageIssue::MissingSignatures { height, block_hash } => write!(
+                formatter,
+                "missing finality signatures for {} at height {}",
+                block_hash, height
+            ),
+            StorageIssue::MissingDeploy {
+                height,
+                block_hash,
+                deploy_hash,
+            } => write!(
+                formatter,
+                "missing {} of {} at height {}",
+                deploy_hash, block_hash, height
+            ),
+            StorageIssue::MissingExecutionResult {
+                height,
+                block_hash,
+                deploy_hash,
+            } => write!(
+                formatter,
+                "missing execution result of {} in {} at height {}",
+                deploy_hash, block_hash, height
+            ),
+            StorageIssue::MissingBlock { height } => write!(
+                formatter,
+                "height {} is marked complete, but no block is stored",
+                height
+            ),
+        }
+    }
+}
+
+/// Outcome of a consistency check.
+#[derive(Debug)]
+pub(crate) struct ConsistencyReport {
+    /// Number of distinct heights for which a block was checked.
+    pub(crate) blocks_checked: usize,
+    /// Heights marked complete, if the set of completed blocks has been persisted.
+    completed_blocks: Option<DisjointSequences>,
+    /// All issues found.
+    pub(crate) issues: Vec<StorageIssue>,
+}
+
+impl ConsistencyReport {
+    /// Returns `true` if no issues were found.
+    pub(crate) fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the heights of all blocks affected by at least one issue.
+    pub(crate) fn affected_heights(&self) -> BTreeSet<u64> {
+        self.issues
+            .iter()
+            .filter_map(StorageIssue::height)
+            .collect()
+    }
+
+    /// Returns `true` if the given height is marked complete.
+    fn is_marked_complete(&self, height: u64) -> bool {
+        self.completed_blocks
+            .as_ref()
+            .map_or(false, |completed_blocks| completed_blocks.contains(height))
+    }
+}
+
+impl Display for ConsistencyReport {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            let marked_complete = issue
+                .height()
+                .map_or(false, |height| self.is_marked_complete(height));
+            if marked_complete {
+                writeln!(formatter, "{} (marked complete)", issue)?;
+            } else {
+                writeln!(formatter, "{}", issue)?;
+            }
+        }
+        write!(
+            formatter,
+            "checked {} blocks, found {} issues affecting {} heights",
+            self.blocks_checked,
+            self.issues.len(),
+            self.affected_heights().len()
+        )
+    }
+}
@@ -9,21 +9,24 @@ use std::{
 };
 
 use lmdb::Transaction;
+use num_rational::Ratio;
 use rand::{prelude::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 
 use casper_types::{
-    generate_ed25519_keypair, system::auction::UnbondingPurse, testing::TestRng, AccessRights,
-    EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion, PublicKey, SecretKey, TimeDiff,
-    Transfer, Transform, TransformEntry, URef, U512,
+    account::AccountHash, generate_ed25519_keypair, system::auction::UnbondingPurse,
+    testing::TestRng, AccessRights, EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion,
+    PublicKey, SecretKey, TimeDiff, Transfer, Transform, TransformEntry, URef, U512,
 };
 
 use super::{
+    archive::{self, ArchiveError},
     initialize_block_metadata_db,
+    inspect::StorageInspector,
     lmdb_ext::{deserialize_internal, serialize_internal, TransactionExt, WriteTransactionExt},
     move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir, Config,
-    Storage, FORCE_RESYNC_FILE_NAME,
+    Event, Storage, FORCE_RESYNC_FILE_NAME,
 };
 use crate::{
     components::fetcher::{FetchItem, FetchResponse},
@@ -33,10 +36,11 @@ use crate::{
     },
     testing::{ComponentHarness, UnitTestEvent},
     types::{
-        sync_leap_validation_metadata::SyncLeapValidationMetaData, AvailableBlockRange, Block,
-        BlockHash, BlockHashAndHeight, BlockHashHeightAndEra, BlockHeader, BlockHeaderWithMetadata,
-        BlockSignatures, Chainspec, ChainspecRawBytes, Deploy, DeployHash, DeployMetadata,
-        DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature, LegacyDeploy,
+        sync_leap_validation_metadata::SyncLeapValidationMetaData, ActivationPoint, Approval,
+        AvailableBlockRange, Block, BlockHash, BlockHashAndHeight, BlockHashHeightAndEra,
+        BlockHeader, BlockHeaderWithMetadata, BlockSignatures, Chainspec, ChainspecRawBytes,
+        Deploy, DeployConfigurationFailure, DeployHash, DeployMetadata, DeployMetadataExt,
+        DeployWithFinalizedApprovals, FinalitySignature, FinalizedApprovals, LegacyDeploy,
         SyncLeapIdentifier, TestBlockBuilder,
     },
     utils::{Loadable, WithDir},
@@ -57,6 +61,7 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: true,
         mem_pool_prune_interval: 4,
+        enable_account_deploy_index: false,
     }
 }
 
@@ -1369,6 +1374,286 @@ fn test_legacy_interface() {
         .is_none())
 }
 
+/// Requests a block's deploys and their execution results from a storage component.
+fn get_block_deploys_and_execution_results(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    block_hash: BlockHash,
+) -> Option<HashMap<DeployHash, (Deploy, Option<ExecutionResult>)>> {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetBlockDeploysAndExecutionResults {
+            block_hash,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response.map(|deploys| {
+        deploys
+            .into_iter()
+            .map(|(deploy, execution_result)| {
+                let deploy = deploy.into_naive();
+                (*deploy.hash(), (deploy, execution_result))
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn should_get_block_deploys_and_execution_results() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy_a = Deploy::random(&mut harness.rng);
+    let deploy_b = Deploy::random(&mut harness.rng);
+    let block = Block::random_with_deploys(&mut harness.rng, [&deploy_a, &deploy_b]);
+    let other_block_hash = BlockHash::random(&mut harness.rng);
+
+    // Unknown block.
+    assert!(
+        get_block_deploys_and_execution_results(&mut harness, &mut storage, *block.hash())
+            .is_none()
+    );
+
+    // Known block, but one of its deploys is missing.
+    put_complete_block(&mut harness, &mut storage, Arc::new(block.clone()));
+    put_deploy(&mut harness, &mut storage, Arc::new(deploy_a.clone()));
+    assert!(
+        get_block_deploys_and_execution_results(&mut harness, &mut storage, *block.hash())
+            .is_none()
+    );
+
+    // All deploys stored, but only `deploy_a` has an execution result for this block.
+    put_deploy(&mut harness, &mut storage, Arc::new(deploy_b.clone()));
+    let execution_result_a: ExecutionResult = harness.rng.gen();
+    let execution_result_b: ExecutionResult = harness.rng.gen();
+    let mut execution_results = HashMap::new();
+    execution_results.insert(*deploy_a.hash(), execution_result_a.clone());
+    put_execution_results(&mut harness, &mut storage, *block.hash(), execution_results);
+    let mut execution_results = HashMap::new();
+    execution_results.insert(*deploy_b.hash(), execution_result_b);
+    put_execution_results(
+        &mut harness,
+        &mut storage,
+        other_block_hash,
+        execution_results,
+    );
+
+    let deploys =
+        get_block_deploys_and_execution_results(&mut harness, &mut storage, *block.hash())
+            .expect("should have all deploys of block");
+    assert_eq!(deploys.len(), 2);
+    assert_eq!(
+        deploys[deploy_a.hash()],
+        (deploy_a.clone(), Some(execution_result_a))
+    );
+    assert_eq!(deploys[deploy_b.hash()], (deploy_b.clone(), None));
+}
+
+/// Storage component test fixture with the account deploy index enabled.
+fn storage_fixture_with_account_deploy_index(harness: &ComponentHarness<UnitTestEvent>) -> Storage {
+    let cfg = Config {
+        enable_account_deploy_index: true,
+        ..new_config(harness)
+    };
+    Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        ProtocolVersion::from_parts(1, 0, 0),
+        EraId::default(),
+        "test",
+        MAX_TTL.into(),
+        RECENT_ERA_COUNT,
+        None,
+        false,
+    )
+    .expect("could not create storage component fixture")
+}
+
+/// Returns a random deploy signed by the given secret key.
+fn random_deploy_from(rng: &mut TestRng, secret_key: &SecretKey) -> Deploy {
+    let deploy = Deploy::random(rng);
+    Deploy::new(
+        deploy.header().timestamp(),
+        deploy.header().ttl(),
+        deploy.header().gas_price(),
+        vec![],
+        deploy.header().chain_name().to_string(),
+        deploy.payment().clone(),
+        deploy.session().clone(),
+        secret_key,
+        None,
+    )
+}
+
+/// Stores one block per height in `0..count`, each holding a deploy sent by the given secret key
+/// and a deploy sent by a random account.  Returns the blocks along with the former deploys.
+fn put_blocks_with_account_deploys(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    secret_key: &SecretKey,
+    count: u64,
+) -> Vec<(Block, Deploy)> {
+    (0..count)
+        .map(|height| {
+            let deploy = random_deploy_from(&mut harness.rng, secret_key);
+            let other_deploy = Deploy::random(&mut harness.rng);
+            let block = Block::random_with_specifics(
+                &mut harness.rng,
+                EraId::new(0),
+                height,
+                ProtocolVersion::from_parts(1, 0, 0),
+                false,
+                [&deploy, &other_deploy],
+            );
+            put_deploy(harness, storage, Arc::new(deploy.clone()));
+            put_deploy(harness, storage, Arc::new(other_deploy));
+            put_complete_block(harness, storage, Arc::new(block.clone()));
+            (block, deploy)
+        })
+        .collect()
+}
+
+/// Requests the deploys sent by an account from a storage component.
+fn get_account_deploys(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    account_hash: AccountHash,
+    after: Option<&(DeployHash, BlockHashAndHeight)>,
+    limit: u32,
+) -> Option<Vec<(DeployHash, BlockHashAndHeight)>> {
+    let after = after.map(|(deploy_hash, block_hash_and_height)| {
+        Box::new((*deploy_hash, block_hash_and_height.block_height))
+    });
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::GetAccountDeploys {
+            account_hash,
+            after,
+            limit,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
+fn expected_account_deploys(
+    blocks_and_deploys: &[(Block, Deploy)],
+) -> Vec<(DeployHash, BlockHashAndHeight)> {
+    blocks_and_deploys
+        .iter()
+        .rev()
+        .map(|(block, deploy)| {
+            (
+                *deploy.hash(),
+                BlockHashAndHeight::new(*block.hash(), block.height()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn should_get_account_deploys_most_recent_first() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture_with_account_deploy_index(&harness);
+
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    let blocks_and_deploys =
+        put_blocks_with_account_deploys(&mut harness, &mut storage, &secret_key, 5);
+    let expected = expected_account_deploys(&blocks_and_deploys);
+
+    let all = get_account_deploys(&mut harness, &mut storage, account_hash, None, 10)
+        .expect("index should be enabled");
+    assert_eq!(all, expected);
+
+    let first_page =
+        get_account_deploys(&mut harness, &mut storage, account_hash, None, 2).unwrap();
+    assert_eq!(first_page, expected[0..2]);
+    let second_page = get_account_deploys(
+        &mut harness,
+        &mut storage,
+        account_hash,
+        first_page.last(),
+        2,
+    )
+    .unwrap();
+    assert_eq!(second_page, expected[2..4]);
+    let last_page = get_account_deploys(
+        &mut harness,
+        &mut storage,
+        account_hash,
+        second_page.last(),
+        2,
+    )
+    .unwrap();
+    assert_eq!(last_page, expected[4..]);
+    let beyond_end = get_account_deploys(
+        &mut harness,
+        &mut storage,
+        account_hash,
+        last_page.last(),
+        2,
+    )
+    .unwrap();
+    assert!(beyond_end.is_empty());
+
+    // Storing the same block again must not duplicate index entries.
+    let (block, _) = blocks_and_deploys[0].clone();
+    put_complete_block(&mut harness, &mut storage, Arc::new(block));
+    let all = get_account_deploys(&mut harness, &mut storage, account_hash, None, 10).unwrap();
+    assert_eq!(all, expected);
+
+    let unknown_account = AccountHash::new(harness.rng.gen());
+    let deploys =
+        get_account_deploys(&mut harness, &mut storage, unknown_account, None, 10).unwrap();
+    assert!(deploys.is_empty());
+}
+
+#[test]
+fn should_not_get_account_deploys_if_index_disabled() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    put_blocks_with_account_deploys(&mut harness, &mut storage, &secret_key, 2);
+
+    assert!(get_account_deploys(&mut harness, &mut storage, account_hash, None, 10).is_none());
+}
+
+#[test]
+fn should_build_account_deploy_index_from_existing_blocks() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let secret_key = SecretKey::random(&mut harness.rng);
+    let account_hash = PublicKey::from(&secret_key).to_account_hash();
+    let blocks_and_deploys =
+        put_blocks_with_account_deploys(&mut harness, &mut storage, &secret_key, 3);
+
+    // Restart with the index enabled; it should be built in the background from the blocks
+    // already in storage.
+    drop(storage);
+    let (on_disk, rng) = harness.into_parts();
+    let mut harness = ComponentHarness::builder()
+        .on_disk(on_disk)
+        .rng(rng)
+        .build();
+    let mut storage = storage_fixture_with_account_deploy_index(&harness);
+
+    let deploys = get_account_deploys(&mut harness, &mut storage, account_hash, None, 10)
+        .expect("index should be enabled");
+    assert!(deploys.is_empty());
+
+    let effects = harness.send_event(&mut storage, Event::BackfillAccountDeployIndex);
+    assert!(effects.is_empty());
+    let deploys = get_account_deploys(&mut harness, &mut storage, account_hash, None, 10)
+        .expect("index should be enabled");
+    assert_eq!(deploys, expected_account_deploys(&blocks_and_deploys));
+}
+
 #[test]
 fn persist_blocks_deploys_and_deploy_metadata_across_instantiations() {
     let mut harness = ComponentHarness::default();
@@ -2235,3 +2520,233 @@ fn should_initialize_block_metadata_db() {
     assert_signatures(&storage, *block_3.hash(), vec![]);
     assert_signatures(&storage, *block_4.hash(), vec![]);
 }
+
+#[test]
+fn inspector_should_report_incomplete_blocks_and_unmark_them() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    // Block 1 is complete, block 2 lacks signatures as well as its deploy and execution result.
+    let deploy_1 = Deploy::random(&mut harness.rng);
+    let block_1 = Arc::new(Block::random_with_specifics(
+        &mut harness.rng,
+        EraId::new(1),
+        1,
+        ProtocolVersion::from_parts(1, 5, 0),
+        false,
+        iter::once(&deploy_1),
+    ));
+    let deploy_2 = Deploy::random(&mut harness.rng);
+    let block_2 = Arc::new(Block::random_with_specifics(
+        &mut harness.rng,
+        EraId::new(1),
+        2,
+        ProtocolVersion::from_parts(1, 5, 0),
+        false,
+        iter::once(&deploy_2),
+    ));
+    let signatures = random_signatures(&mut harness.rng, &block_1);
+    let execution_results = iter::once((*deploy_1.hash(), harness.rng.gen())).collect();
+    let block_1_hash = *block_1.hash();
+    assert!(put_deploy(&mut harness, &mut storage, Arc::new(deploy_1)));
+    assert!(put_complete_block(&mut harness, &mut storage, block_1));
+    assert!(put_block_signatures(&mut harness, &mut storage, signatures));
+    put_execution_results(&mut harness, &mut storage, block_1_hash, execution_results);
+    assert!(put_complete_block(&mut harness, &mut storage, block_2));
+    drop(storage);
+
+    let cfg = WithDir::new(harness.tmp.path(), new_config(&harness));
+    let inspector = StorageInspector::open(&cfg, "test", false).expect("should open storage");
+    let report = inspector.check(0..=u64::MAX).expect("should check storage");
+    assert_eq!(report.blocks_checked, 2);
+    assert_eq!(report.issues.len(), 3, "{}", report);
+    assert_eq!(report.affected_heights(), BTreeSet::from([2]));
+    assert!(report.to_string().contains("(marked complete)"));
+
+    let report = inspector.check(0..=1).expect("should check storage");
+    assert!(report.is_consistent(), "{}", report);
+
+    // Repairing requires write access.
+    assert!(inspector.rebuild_indices(&report).is_err());
+    drop(inspector);
+
+    let inspector = StorageInspector::open(&cfg, "test", true).expect("should open storage");
+    let report = inspector.check(0..=u64::MAX).expect("should check storage");
+    let unmarked = inspector
+        .rebuild_indices(&report)
+        .expect("should rebuild indices");
+    assert_eq!(unmarked, vec![2]);
+    drop(inspector);
+
+    // Block 2 is no longer marked complete, so a restarted node acquires it again.
+    let storage = storage_fixture(&harness);
+    assert_eq!(
+        storage.get_available_block_range(),
+        AvailableBlockRange::new(1, 1)
+    );
+}
+
+/// Stores a chain of four signed blocks spanning three eras, each with a single executed deploy.
+fn put_signed_chain(harness: &mut ComponentHarness<UnitTestEvent>, storage: &mut Storage) {
+    let (secret_key, public_key) = generate_ed25519_keypair();
+    let validator_weights: BTreeMap<_, _> =
+        iter::once((public_key.clone(), U512::from(100))).collect();
+
+    let mut parent_hash = None;
+    for (height, era_id, is_switch) in [(0, 0, true), (1, 1, false), (2, 1, true), (3, 2, false)] {
+        let deploy = Deploy::random(&mut harness.rng);
+        let block = Block::random_with_specifics_and_parent_and_validator_weights(
+            &mut harness.rng,
+            EraId::new(era_id),
+            height,
+            ProtocolVersion::from_parts(1, 5, 0),
+            is_switch,
+            iter::once(&deploy),
+            parent_hash,
+            if is_switch {
+                validator_weights.clone()
+            } else {
+                BTreeMap::new()
+            },
+        );
+        parent_hash = Some(*block.hash());
+
+        let execution_results = iter::once((*deploy.hash(), harness.rng.gen())).collect();
+        assert!(put_deploy(harness, storage, Arc::new(deploy)));
+        put_execution_results(harness, storage, *block.hash(), execution_results);
+        if height > 0 {
+            let signature = FinalitySignature::create(
+                *block.hash(),
+                block.header().era_id(),
+                &secret_key,
+                public_key.clone(),
+            );
+            let mut signatures = BlockSignatures::new(*block.hash(), block.header().era_id());
+            signatures.insert_proof(public_key.clone(), signature.signature);
+            storage.write_finality_signatures(&signatures).unwrap();
+        }
+        assert!(put_complete_block(harness, storage, Arc::new(block)));
+    }
+}
+
+#[test]
+fn should_export_and_import_block_archive() {
+    let mut harness = ComponentHarness::default();
+    let mut source = storage_fixture(&harness);
+    put_signed_chain(&mut harness, &mut source);
+    let validation_metadata = SyncLeapValidationMetaData::new(
+        RECENT_ERA_COUNT,
+        ActivationPoint::EraId(EraId::new(0)),
+        None,
+        Ratio::new(1, 3),
+    );
+
+    let archive = archive::export_blocks(&source, "test", 0..=3, vec![]).unwrap();
+    assert!(matches!(
+        archive::export_blocks(&source, "test", 0..=4, vec![]),
+        Err(ArchiveError::MissingData { height: 4, .. })
+    ));
+
+    // Storage for different network names lives in different directories.
+    let mut target = storage_fixture_from_parts(&harness, None, None, Some("target"), None, None);
+    let summary =
+        archive::import_blocks(&mut target, "test", &validation_metadata, &archive[..]).unwrap();
+    assert_eq!((summary.imported, summary.already_stored), (4, 0));
+    for height in 0..=3 {
+        let block = source.read_block_by_height(height).unwrap().unwrap();
+        assert_eq!(
+            target.read_block_by_height(height).unwrap(),
+            Some(block.clone())
+        );
+        assert_eq!(
+            target.read_block_signatures(block.hash()).unwrap(),
+            source.read_block_signatures(block.hash()).unwrap()
+        );
+        assert_eq!(
+            target.read_execution_results(block.hash()).unwrap(),
+            source.read_execution_results(block.hash()).unwrap()
+        );
+    }
+    // Imported blocks are not complete, as their global state is missing.
+    assert_eq!(
+        target.get_available_block_range(),
+        AvailableBlockRange::RANGE_0_0
+    );
+
+    let summary =
+        archive::import_blocks(&mut target, "test", &validation_metadata, &archive[..]).unwrap();
+    assert_eq!((summary.imported, summary.already_stored), (0, 4));
+
+    // Corrupted and foreign archives are rejected.
+    let mut corrupted = archive.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    let mut empty = storage_fixture_from_parts(&harness, None, None, Some("empty"), None, None);
+    assert!(matches!(
+        archive::import_blocks(&mut empty, "test", &validation_metadata, &corrupted[..]),
+        Err(ArchiveError::ChecksumMismatch(4))
+    ));
+    assert!(matches!(
+        archive::import_blocks(&mut empty, "other", &validation_metadata, &archive[..]),
+        Err(ArchiveError::NetworkMismatch { .. })
+    ));
+
+    // Without the switch block of era 0, the signatures of era 1 cannot be checked.
+    let partial = archive::export_blocks(&source, "test", 1..=3, vec![]).unwrap();
+    let mut fresh = storage_fixture_from_parts(&harness, None, None, Some("fresh"), None, None);
+    assert!(matches!(
+        archive::import_blocks(&mut fresh, "test", &validation_metadata, &partial[..]),
+        Err(ArchiveError::UnknownValidators { era_id }) if era_id == EraId::new(1)
+    ));
+}
+
+#[test]
+fn should_import_only_valid_finalized_approvals() {
+    let mut harness = ComponentHarness::default();
+    let mut source = storage_fixture(&harness);
+    put_signed_chain(&mut harness, &mut source);
+    let validation_metadata = SyncLeapValidationMetaData::new(
+        RECENT_ERA_COUNT,
+        ActivationPoint::EraId(EraId::new(0)),
+        None,
+        Ratio::new(1, 3),
+    );
+    let block = source.read_block_by_height(2).unwrap().unwrap();
+    let deploy_hash = *block.deploy_and_transfer_hashes().next().unwrap();
+    let secret_key = SecretKey::random(&mut harness.rng);
+
+    // Finalized approvals that differ from the deploy's own are imported if they are valid.
+    let approvals =
+        FinalizedApprovals::new(iter::once(Approval::create(&deploy_hash, &secret_key)).collect());
+    assert!(source
+        .store_finalized_approvals(&deploy_hash, &approvals)
+        .unwrap());
+    let archive = archive::export_blocks(&source, "test", 0..=3, vec![]).unwrap();
+    let mut target = storage_fixture_from_parts(&harness, None, None, Some("target"), None, None);
+    archive::import_blocks(&mut target, "test", &validation_metadata, &archive[..]).unwrap();
+    assert_eq!(
+        target
+            .get_deploy_with_finalized_approvals_by_hash(&deploy_hash)
+            .unwrap()
+            .into_naive()
+            .approvals(),
+        approvals.inner()
+    );
+
+    // Approvals signing a different deploy are rejected.
+    let other_hash = DeployHash::random(&mut harness.rng);
+    let approvals =
+        FinalizedApprovals::new(iter::once(Approval::create(&other_hash, &secret_key)).collect());
+    assert!(source
+        .store_finalized_approvals(&deploy_hash, &approvals)
+        .unwrap());
+    let archive = archive::export_blocks(&source, "test", 0..=3, vec![]).unwrap();
+    let mut fresh = storage_fixture_from_parts(&harness, None, None, Some("fresh"), None, None);
+    assert!(matches!(
+        archive::import_blocks(&mut fresh, "test", &validation_metadata, &archive[..]),
+        Err(ArchiveError::InvalidDeploy {
+            height: 2,
+            error: DeployConfigurationFailure::InvalidApproval { index: 0, .. },
+            ..
+        })
+    ));
+}
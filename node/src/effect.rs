@@ -124,9 +124,11 @@ use casper_execution_engine::{
 };
 use casper_hashing::Digest;
 use casper_types::{
-    account::Account, bytesrepr::Bytes, system::auction::EraValidators, Contract, ContractPackage,
-    EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff, Timestamp, Transfer, URef,
-    U512,
+    account::{Account, AccountHash},
+    bytesrepr::Bytes,
+    system::auction::EraValidators,
+    Contract, ContractPackage, EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff,
+    Timestamp, Transfer, URef, U512,
 };
 
 use crate::{
@@ -135,25 +137,32 @@ use crate::{
             BlockSynchronizerStatus, GlobalStateSynchronizerError, GlobalStateSynchronizerResponse,
             TrieAccumulatorError, TrieAccumulatorResponse,
         },
-        consensus::{ClContext, EraDump, ProposedBlock, ValidatorChange},
+        consensus::{ClContext, EraDump, FaultEvidence, ProposedBlock, ValidatorChange},
         contract_runtime::{ContractRuntimeError, EraValidatorsRequest},
         deploy_acceptor,
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
-        network::{blocklist::BlocklistJustification, FromIncoming, NetworkInsights},
+        network::{
+            blocklist::{
+                BlockedPeer, BlocklistJustification, OperatorBlocklistError,
+                OperatorBlocklistSnapshot,
+            },
+            FromIncoming, NetworkInsights,
+        },
         upgrade_watcher::NextUpgrade,
     },
-    contract_runtime::SpeculativeExecutionState,
+    contract_runtime::{SpeculativeExecutionResult, SpeculativeExecutionState},
     failpoints::FailpointActivation,
     reactor::{main_reactor::ReactorState, EventQueueHandle, QueueKind},
     types::{
         appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
-        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
-        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlock,
-        MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId,
+        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash,
+        BlockHashAndHeight, BlockHeader, BlockSignatures, BlockWithMetadata, ChainspecRawBytes,
+        Deploy, DeployHash, DeployHeader, DeployId, DeployMetadataExt,
+        DeployWithFinalizedApprovals, FinalitySignature, FinalitySignatureId, FinalizedApprovals,
+        FinalizedBlock, LegacyDeploy, MetaBlock, MetaBlockState, NodeId, TrieOrChunk,
+        TrieOrChunkId,
     },
     utils::{fmt_limit::FmtLimit, SharedFlag, Source},
 };
@@ -167,10 +176,11 @@ use announcements::{
 use diagnostics_port::DumpConsensusStateRequest;
 use requests::{
     AcceptDeployRequest, BeginGossipRequest, BlockAccumulatorRequest, BlockSynchronizerRequest,
-    BlockValidationRequest, ChainspecRawBytesRequest, ConsensusRequest, ContractRuntimeRequest,
-    DeployBufferRequest, FetcherRequest, MakeBlockExecutableRequest, MarkBlockCompletedRequest,
-    MetricsRequest, NetworkInfoRequest, NetworkRequest, ReactorStatusRequest, SetNodeStopRequest,
-    StorageRequest, SyncGlobalStateRequest, TrieAccumulatorRequest, UpgradeWatcherRequest,
+    BlockValidationRequest, BlocklistRequest, ChainspecRawBytesRequest, ConsensusRequest,
+    ContractRuntimeRequest, DeployBufferRequest, FetcherRequest, MakeBlockExecutableRequest,
+    MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest,
+    ReactorStatusRequest, SetNodeStopRequest, StorageRequest, SyncGlobalStateRequest,
+    TrieAccumulatorRequest, UpgradeWatcherRequest,
 };
 
 /// A resource that will never be available, thus trying to acquire it will wait forever.
@@ -772,6 +782,52 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Blocks a peer on behalf of the node operator, until it is unblocked again.
+    ///
+    /// Returns `false` if the peer was already blocked.
+    pub(crate) async fn block_peer_by_operator(
+        self,
+        peer: BlockedPeer,
+    ) -> Result<bool, OperatorBlocklistError>
+    where
+        REv: From<BlocklistRequest>,
+    {
+        self.make_request(
+            |responder| BlocklistRequest::BlockPeer { peer, responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Unblocks a peer previously blocked by the node operator.
+    ///
+    /// Returns `false` if the peer was not blocked.
+    pub(crate) async fn unblock_peer_by_operator(
+        self,
+        peer: BlockedPeer,
+    ) -> Result<bool, OperatorBlocklistError>
+    where
+        REv: From<BlocklistRequest>,
+    {
+        self.make_request(
+            |responder| BlocklistRequest::UnblockPeer { peer, responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Lists the peers blocked by the node operator, along with any addresses currently blocked.
+    pub(crate) async fn list_blocked_peers(self) -> OperatorBlocklistSnapshot
+    where
+        REv: From<BlocklistRequest>,
+    {
+        self.make_request(
+            |responder| BlocklistRequest::ListBlocked { responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
     /// Gets a map of the current network peers to their socket addresses.
     pub(crate) async fn network_peers(self) -> BTreeMap<NodeId, String>
     where
@@ -1416,6 +1472,31 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests a page of the keys at the given state root hash whose serialized form starts with
+    /// `prefix`.
+    pub(crate) async fn get_keys_with_prefix(
+        self,
+        state_root_hash: Digest,
+        prefix: Vec<u8>,
+        start_after: Option<Key>,
+        limit: usize,
+    ) -> Result<Option<Vec<Key>>, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetKeysWithPrefix {
+                state_root_hash,
+                prefix,
+                start_after,
+                limit,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Get a trie by its hash key.
     pub(crate) async fn get_trie_full(
         self,
@@ -1578,6 +1659,50 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the deploys of the requested block, along with their finalized approvals and their
+    /// execution results in the context of that block.
+    pub(crate) async fn get_block_deploys_and_execution_results_from_storage(
+        self,
+        block_hash: BlockHash,
+    ) -> Option<Vec<(DeployWithFinalizedApprovals, Option<ExecutionResult>)>>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetBlockDeploysAndExecutionResults {
+                block_hash,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
+    /// Gets up to `limit` deploys sent by the given account, most recent first, starting after the
+    /// deploy with the given hash and block height if `after` is provided.
+    ///
+    /// Returns `None` if the account deploy index is disabled.
+    pub(crate) async fn get_account_deploys_from_storage(
+        self,
+        account_hash: AccountHash,
+        after: Option<(DeployHash, u64)>,
+        limit: u32,
+    ) -> Option<Vec<(DeployHash, BlockHashAndHeight)>>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetAccountDeploys {
+                account_hash,
+                after: after.map(Box::new),
+                limit,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Gets the requested block and its finality signatures.
     pub(crate) async fn get_block_at_height_with_metadata_from_storage(
         self,
@@ -1831,6 +1956,7 @@ impl<REv> EffectBuilder<REv> {
         era_id: EraId,
         public_key: PublicKey,
         timestamp: Timestamp,
+        evidence: Option<FaultEvidence>,
     ) where
         REv: From<ConsensusAnnouncement>,
     {
@@ -1840,6 +1966,7 @@ impl<REv> EffectBuilder<REv> {
                     era_id,
                     public_key: Box::new(public_key),
                     timestamp,
+                    evidence: evidence.map(Box::new),
                 },
                 QueueKind::Consensus,
             )
@@ -2063,6 +2190,18 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Returns the accused and faulty validators in each open era, with the evidence against them
+    /// if available.
+    pub(crate) async fn get_consensus_faults(
+        self,
+    ) -> BTreeMap<EraId, BTreeMap<PublicKey, Option<FaultEvidence>>>
+    where
+        REv: From<ConsensusRequest>,
+    {
+        self.make_request(ConsensusRequest::Faults, QueueKind::Consensus)
+            .await
+    }
+
     /// Dump consensus state for a specific era, using the supplied function to serialize the
     /// output.
     pub(crate) async fn diagnostics_port_dump_consensus_state(
@@ -2194,13 +2333,15 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
-    /// Requests execution of a single deploy, without commiting its effects.
+    /// Requests execution of a single deploy, without commiting its effects, optionally recording
+    /// an execution trace.
     /// Inteded to be used for debugging & discovery purposes.
     pub(crate) async fn speculative_execute_deploy(
         self,
         execution_prestate: SpeculativeExecutionState,
         deploy: Arc<Deploy>,
-    ) -> Result<Option<ExecutionResult>, engine_state::Error>
+        with_trace: bool,
+    ) -> Result<SpeculativeExecutionResult, engine_state::Error>
     where
         REv: From<ContractRuntimeRequest>,
     {
@@ -2208,6 +2349,7 @@ impl<REv> EffectBuilder<REv> {
             |responder| ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
                 deploy,
+                with_trace,
                 responder,
             },
             QueueKind::ContractRuntime,
@@ -18,7 +18,7 @@ use casper_types::{EraId, ExecutionEffect, PublicKey, Timestamp, U512};
 
 use crate::{
     components::{
-        consensus::{ClContext, ProposedBlock},
+        consensus::{ClContext, FaultEvidence, ProposedBlock},
         diagnostics_port::FileSerializer,
         fetcher::FetchItem,
         gossiper::GossipItem,
@@ -250,6 +250,8 @@ pub(crate) enum ConsensusAnnouncement {
         public_key: Box<PublicKey>,
         /// The timestamp when the evidence of the equivocation was detected.
         timestamp: Timestamp,
+        /// The evidence of the equivocation.
+        evidence: Option<Box<FaultEvidence>>,
     },
 }
 
@@ -266,6 +268,7 @@ impl Display for ConsensusAnnouncement {
                 era_id,
                 public_key,
                 timestamp,
+                ..
             } => write!(
                 formatter,
                 "Validator fault with public key: {} has been identified at time: {} in {}",
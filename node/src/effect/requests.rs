@@ -27,8 +27,8 @@ use casper_execution_engine::{
 };
 use casper_hashing::Digest;
 use casper_types::{
-    bytesrepr::Bytes, system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion,
-    PublicKey, TimeDiff, Timestamp, Transfer, URef,
+    account::AccountHash, bytesrepr::Bytes, system::auction::EraValidators, EraId, ExecutionResult,
+    Key, ProtocolVersion, PublicKey, TimeDiff, Timestamp, Transfer, URef,
 };
 
 use crate::{
@@ -37,26 +37,32 @@ use crate::{
             BlockSynchronizerStatus, GlobalStateSynchronizerError, GlobalStateSynchronizerResponse,
             TrieAccumulatorError, TrieAccumulatorResponse,
         },
-        consensus::{ClContext, ProposedBlock, ValidatorChange},
+        consensus::{ClContext, FaultEvidence, ProposedBlock, ValidatorChange},
         contract_runtime::EraValidatorsRequest,
         deploy_acceptor,
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
-        network::NetworkInsights,
+        network::{
+            blocklist::{BlockedPeer, OperatorBlocklistError, OperatorBlocklistSnapshot},
+            NetworkInsights,
+        },
         upgrade_watcher::NextUpgrade,
     },
-    contract_runtime::{ContractRuntimeError, SpeculativeExecutionState},
+    contract_runtime::{
+        ContractRuntimeError, SpeculativeExecutionResult, SpeculativeExecutionState,
+    },
     effect::{AutoClosingResponder, Responder},
     reactor::main_reactor::ReactorState,
     rpcs::docs::OpenRpcSchema,
     types::{
         appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
-        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
-        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlockState,
-        NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId,
+        BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash,
+        BlockHashAndHeight, BlockHeader, BlockSignatures, BlockWithMetadata, ChainspecRawBytes,
+        Deploy, DeployHash, DeployHeader, DeployId, DeployMetadataExt,
+        DeployWithFinalizedApprovals, FinalitySignature, FinalitySignatureId, FinalizedApprovals,
+        FinalizedBlock, LegacyDeploy, MetaBlockState, NodeId, StatusFeed, TrieOrChunk,
+        TrieOrChunkId,
     },
     utils::{DisplayIter, Source},
 };
@@ -234,6 +240,42 @@ impl Display for NetworkInfoRequest {
     }
 }
 
+/// A request to manage the peers blocked by the node operator.
+#[derive(Debug, Serialize)]
+pub(crate) enum BlocklistRequest {
+    /// Block a peer until it is unblocked again.
+    BlockPeer {
+        /// The peer to block.
+        peer: BlockedPeer,
+        /// Responder to be called with whether the peer was newly blocked.
+        responder: Responder<Result<bool, OperatorBlocklistError>>,
+    },
+    /// Unblock a peer previously blocked by the operator.
+    UnblockPeer {
+        /// The peer to unblock.
+        peer: BlockedPeer,
+        /// Responder to be called with whether the peer was blocked.
+        responder: Responder<Result<bool, OperatorBlocklistError>>,
+    },
+    /// List the blocked peers.
+    ListBlocked {
+        /// Responder to be called with the blocked peers.
+        responder: Responder<OperatorBlocklistSnapshot>,
+    },
+}
+
+impl Display for BlocklistRequest {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BlocklistRequest::BlockPeer { peer, .. } => write!(formatter, "block peer {}", peer),
+            BlocklistRequest::UnblockPeer { peer, .. } => {
+                write!(formatter, "unblock peer {}", peer)
+            }
+            BlocklistRequest::ListBlocked { .. } => formatter.write_str("list blocked peers"),
+        }
+    }
+}
+
 /// A gossip request.
 ///
 /// This request usually initiates gossiping process of the specified item. Note that the gossiper
@@ -414,6 +456,32 @@ pub(crate) enum StorageRequest {
         /// Responder to call with the results.
         responder: Responder<Option<(DeployWithFinalizedApprovals, DeployMetadataExt)>>,
     },
+    /// Retrieve the deploys of a block, along with their finalized approvals and the results of
+    /// executing them in the context of that block.
+    ///
+    /// Returns `None` if the block or any of its deploys are not stored.
+    #[allow(clippy::type_complexity)]
+    GetBlockDeploysAndExecutionResults {
+        /// The hash of the block.
+        block_hash: BlockHash,
+        /// Responder to call with the results.
+        responder: Responder<Option<Vec<(DeployWithFinalizedApprovals, Option<ExecutionResult>)>>>,
+    },
+    /// Retrieve deploys sent by the given account, most recent first, along with the blocks in
+    /// which they were executed.
+    ///
+    /// Returns `None` if the account deploy index is disabled.
+    GetAccountDeploys {
+        /// The hash of the sending account.
+        account_hash: AccountHash,
+        /// The hash and block height of the last deploy of the previous page, if any; only deploys
+        /// after it are returned.
+        after: Option<Box<(DeployHash, u64)>>,
+        /// The maximum number of deploys to return.
+        limit: u32,
+        /// Responder to call with the results.
+        responder: Responder<Option<Vec<(DeployHash, BlockHashAndHeight)>>>,
+    },
     /// Retrieve block and its metadata by its hash.
     GetBlockAndMetadataByHash {
         /// The hash of the block.
@@ -562,6 +630,30 @@ impl Display for StorageRequest {
             StorageRequest::GetDeployAndMetadata { deploy_hash, .. } => {
                 write!(formatter, "get deploy and metadata for {}", deploy_hash)
             }
+            StorageRequest::GetBlockDeploysAndExecutionResults { block_hash, .. } => {
+                write!(
+                    formatter,
+                    "get deploys and execution results for block {}",
+                    block_hash
+                )
+            }
+            StorageRequest::GetAccountDeploys {
+                account_hash,
+                after,
+                limit,
+                ..
+            } => match after.as_deref() {
+                Some((deploy_hash, block_height)) => write!(
+                    formatter,
+                    "get up to {} deploys of account {} after deploy {} at height {}",
+                    limit, account_hash, deploy_hash, block_height
+                ),
+                None => write!(
+                    formatter,
+                    "get up to {} deploys of account {}",
+                    limit, account_hash
+                ),
+            },
             StorageRequest::GetFinalitySignature { id, .. } => {
                 write!(formatter, "get finality signature {}", id)
             }
@@ -889,6 +981,21 @@ pub(crate) enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Returns a page of the keys at a given state root hash whose serialized form starts with
+    /// the given prefix.
+    GetKeysWithPrefix {
+        /// The global state hash.
+        state_root_hash: Digest,
+        /// The prefix of the serialized keys, including the key tag.
+        prefix: Vec<u8>,
+        /// If given, only keys ordered after this one are returned.
+        start_after: Option<Key>,
+        /// The maximum number of keys to return.
+        limit: usize,
+        /// Responder to call with the result.  Contains `None` if the state root hash is not
+        /// found.
+        responder: Responder<Result<Option<Vec<Key>>, engine_state::Error>>,
+    },
     /// Returns the value of the execution results checksum stored in the ChecksumRegistry for the
     /// given state root hash.
     GetExecutionResultsChecksum {
@@ -922,8 +1029,10 @@ pub(crate) enum ContractRuntimeRequest {
         execution_prestate: SpeculativeExecutionState,
         /// Deploy to execute.
         deploy: Arc<Deploy>,
+        /// Whether to record an execution trace.
+        with_trace: bool,
         /// Results
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder: Responder<Result<SpeculativeExecutionResult, engine_state::Error>>,
     },
 }
 
@@ -949,6 +1058,22 @@ impl Display for ContractRuntimeRequest {
             } => {
                 write!(formatter, "get bids request: {:?}", get_bids_request)
             }
+            ContractRuntimeRequest::GetKeysWithPrefix {
+                state_root_hash,
+                prefix,
+                start_after,
+                limit,
+                ..
+            } => write!(
+                formatter,
+                "get at most {} keys with prefix {} under {}{}",
+                limit,
+                base16::encode_lower(prefix),
+                state_root_hash,
+                start_after
+                    .map(|key| format!(" after {}", key))
+                    .unwrap_or_default()
+            ),
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash, ..
             } => write!(
@@ -1071,6 +1196,9 @@ pub(crate) enum ConsensusRequest {
     Status(Responder<Option<(PublicKey, Option<TimeDiff>)>>),
     /// Request for a list of validator status changes, by public key.
     ValidatorChanges(Responder<BTreeMap<PublicKey, Vec<(EraId, ValidatorChange)>>>),
+    /// Request for the accused and faulty validators in each open era, with the evidence against
+    /// them if available.
+    Faults(Responder<BTreeMap<EraId, BTreeMap<PublicKey, Option<FaultEvidence>>>>),
 }
 
 /// ChainspecLoader component requests.
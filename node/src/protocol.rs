@@ -94,6 +94,40 @@ impl Payload for Message {
         }
     }
 
+    fn message_type(&self) -> &'static str {
+        match self {
+            Message::Consensus(_) => "consensus",
+            Message::ConsensusRequest(_) => "consensus_request",
+            Message::BlockGossiper(_) => "block_gossip",
+            Message::DeployGossiper(_) => "deploy_gossip",
+            Message::FinalitySignatureGossiper(_) => "finality_signature_gossip",
+            Message::AddressGossiper(_) => "address_gossip",
+            Message::GetRequest { tag, .. } => match tag {
+                Tag::Deploy => "get_request.deploy",
+                Tag::LegacyDeploy => "get_request.legacy_deploy",
+                Tag::Block => "get_request.block",
+                Tag::BlockHeader => "get_request.block_header",
+                Tag::TrieOrChunk => "get_request.trie_or_chunk",
+                Tag::FinalitySignature => "get_request.finality_signature",
+                Tag::SyncLeap => "get_request.sync_leap",
+                Tag::ApprovalsHashes => "get_request.approvals_hashes",
+                Tag::BlockExecutionResults => "get_request.block_execution_results",
+            },
+            Message::GetResponse { tag, .. } => match tag {
+                Tag::Deploy => "get_response.deploy",
+                Tag::LegacyDeploy => "get_response.legacy_deploy",
+                Tag::Block => "get_response.block",
+                Tag::BlockHeader => "get_response.block_header",
+                Tag::TrieOrChunk => "get_response.trie_or_chunk",
+                Tag::FinalitySignature => "get_response.finality_signature",
+                Tag::SyncLeap => "get_response.sync_leap",
+                Tag::ApprovalsHashes => "get_response.approvals_hashes",
+                Tag::BlockExecutionResults => "get_response.block_execution_results",
+            },
+            Message::FinalitySignature(_) => "finality_signature",
+        }
+    }
+
     fn is_low_priority(&self) -> bool {
         // We only deprioritize requested trie nodes, as they are the most commonly requested item
         // during fast sync.
@@ -38,7 +38,7 @@ use crate::{
         contract_runtime::ContractRuntime,
         deploy_acceptor::{self, DeployAcceptor},
         deploy_buffer::{self, DeployBuffer},
-        diagnostics_port::DiagnosticsPort,
+        diagnostics_port::{DiagnosticsPort, Watchdog},
         event_stream_server::{self, EventStreamServer},
         gossiper::{self, GossipItem, Gossiper},
         metrics::Metrics,
@@ -171,6 +171,8 @@ pub(crate) struct MainReactor {
     memory_metrics: MemoryMetrics,
     #[data_size(skip)]
     event_queue_metrics: EventQueueMetrics,
+    //   diagnostics
+    watchdog: Watchdog,
 
     //   ambient settings / data / load-bearing config
     validator_matrix: ValidatorMatrix,
@@ -353,6 +355,10 @@ impl reactor::Reactor for MainReactor {
                 let event = MainEvent::Network(network::Event::from(req));
                 self.dispatch_event(effect_builder, rng, event)
             }
+            MainEvent::NetworkBlocklistRequest(req) => {
+                let event = MainEvent::Network(network::Event::from(req));
+                self.dispatch_event(effect_builder, rng, event)
+            }
             MainEvent::NetworkPeerBehaviorAnnouncement(ann) => {
                 let mut effects = Effects::new();
                 match &ann {
@@ -440,12 +446,14 @@ impl reactor::Reactor for MainReactor {
                         era_id,
                         public_key,
                         timestamp,
+                        evidence,
                     } => {
                         let reactor_event =
                             MainEvent::EventStreamServer(event_stream_server::Event::Fault {
                                 era_id,
                                 public_key,
                                 timestamp,
+                                evidence,
                             });
                         self.dispatch_event(effect_builder, rng, reactor_event)
                     }
@@ -1058,12 +1066,14 @@ impl reactor::Reactor for MainReactor {
             chainspec.core_config.administrators.clone(),
             chainspec.core_config.allow_auction_bids,
             chainspec.core_config.allow_unrestricted_transfers,
+            chainspec.core_config.allow_secp256r1_keys,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
         )?;
 
         let network = Network::new(
             config.network.clone(),
+            storage.root_path(),
             network_identity,
             Some((our_secret_key.clone(), our_public_key.clone())),
             registry,
@@ -1094,6 +1104,11 @@ impl reactor::Reactor for MainReactor {
             config.event_stream_server.clone(),
             storage.root_path().to_path_buf(),
             protocol_version,
+            registry,
+        )?;
+        let watchdog = Watchdog::new(
+            config.diagnostics_port.watchdog.clone(),
+            storage.root_path().to_path_buf(),
         );
         let diagnostics_port =
             DiagnosticsPort::new(WithDir::new(&root_dir, config.diagnostics_port));
@@ -1186,6 +1201,7 @@ impl reactor::Reactor for MainReactor {
             metrics,
             memory_metrics,
             event_queue_metrics,
+            watchdog,
 
             state: ReactorState::Initialize {},
             attempts: 0,
@@ -1209,9 +1225,10 @@ impl reactor::Reactor for MainReactor {
     }
 
     fn update_metrics(&mut self, event_queue_handle: EventQueueHandle<Self::Event>) {
-        self.memory_metrics.estimate(self);
+        let heap_sizes = self.memory_metrics.estimate(self);
         self.event_queue_metrics
-            .record_event_queue_counts(&event_queue_handle)
+            .record_event_queue_counts(&event_queue_handle);
+        self.watchdog.check(event_queue_handle, &heap_sizes);
     }
 
     fn activate_failpoint(&mut self, activation: &FailpointActivation) {
@@ -32,6 +32,9 @@ impl MainReactor {
             return fatal!(effect_builder, "exceeded reattempt tolerance").ignore();
         }
         let (delay, mut effects) = self.do_crank(effect_builder, rng);
+        if matches!(self.state, ReactorState::KeepUp | ReactorState::Validate) {
+            effects.extend(self.prune_global_state());
+        }
         effects.extend(
             async move {
                 if !delay.is_zero() {
@@ -105,13 +108,21 @@ impl MainReactor {
                         self.state = ReactorState::CatchUp;
                         (duration, effects)
                     }
+                    GenesisInstruction::CheckLater(msg, wait) => {
+                        debug!("CatchUp: {}", msg);
+                        (wait, Effects::new())
+                    }
                     GenesisInstruction::Fatal(msg) => (
                         Duration::ZERO,
                         fatal!(effect_builder, "failed to commit genesis: {}", msg).ignore(),
                     ),
                 },
                 CatchUpInstruction::CommitUpgrade => match self.commit_upgrade(effect_builder) {
-                    Ok(effects) => {
+                    Ok(None) => {
+                        debug!("CatchUp: global state is busy; waiting to commit upgrade");
+                        (self.control_logic_default_delay.into(), Effects::new())
+                    }
+                    Ok(Some(effects)) => {
                         info!("CatchUp: switch to Upgrading");
                         self.block_synchronizer.purge();
                         self.state = ReactorState::Upgrading;
@@ -331,7 +342,13 @@ impl MainReactor {
             self.chainspec.clone().as_ref(),
             self.chainspec_raw_bytes.clone().as_ref(),
         ) {
-            Ok(success) => success.post_state_hash,
+            Ok(Some(success)) => success.post_state_hash,
+            Ok(None) => {
+                return GenesisInstruction::CheckLater(
+                    "global state is busy; waiting to commit genesis".to_string(),
+                    self.control_logic_default_delay.into(),
+                );
+            }
             Err(error) => {
                 return GenesisInstruction::Fatal(error.to_string());
             }
@@ -405,10 +422,11 @@ impl MainReactor {
         )
     }
 
+    /// Commits the upgrade, returning `Ok(None)` if it must be retried later.
     fn commit_upgrade(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
-    ) -> Result<Effects<MainEvent>, String> {
+    ) -> Result<Option<Effects<MainEvent>>, String> {
         let header = match self.get_local_tip_header()? {
             Some(header) if header.is_switch_block() => header,
             Some(_) => {
@@ -430,7 +448,8 @@ impl MainReactor {
             chainspec_raw,
         ) {
             Ok(cfg) => match self.contract_runtime.commit_upgrade(cfg) {
-                Ok(success) => {
+                Ok(None) => Ok(None),
+                Ok(Some(success)) => {
                     let post_state_hash = success.post_state_hash;
                     info!(
                         network_name = %chainspec.network_config.name,
@@ -454,13 +473,15 @@ impl MainReactor {
                         next_block_height,
                         PublicKey::System,
                     );
-                    Ok(effect_builder
-                        .enqueue_block_for_execution(
-                            finalized_block,
-                            vec![],
-                            MetaBlockState::new_not_to_be_gossiped(),
-                        )
-                        .ignore())
+                    Ok(Some(
+                        effect_builder
+                            .enqueue_block_for_execution(
+                                finalized_block,
+                                vec![],
+                                MetaBlockState::new_not_to_be_gossiped(),
+                            )
+                            .ignore(),
+                    ))
                 }
                 Err(err) => Err(err.to_string()),
             },
@@ -570,6 +591,45 @@ impl MainReactor {
         }
     }
 
+    /// Runs a step of pruning global state, if pruning is enabled, retaining the state roots of
+    /// the most recent blocks and of any blocks still being synchronized.
+    fn prune_global_state(&self) -> Effects<MainEvent> {
+        if !self.contract_runtime.is_pruning_enabled() {
+            return Effects::new();
+        }
+        let storage = &self.storage;
+        let retained_roots = |retained_state_roots: u64, pre_state: &ExecutionPreState| {
+            let highest_block_height = match storage
+                .highest_complete_block_height()
+                .into_iter()
+                .chain(pre_state.next_block_height().checked_sub(1))
+                .max()
+            {
+                Some(highest_block_height) => highest_block_height,
+                None => return vec![],
+            };
+            let lowest_block_height =
+                highest_block_height.saturating_sub(retained_state_roots.saturating_sub(1));
+            let mut roots = vec![];
+            for height in lowest_block_height..=highest_block_height {
+                match storage.read_block_header_by_height(height, false) {
+                    Ok(Some(block_header)) => roots.push(*block_header.state_root_hash()),
+                    Ok(None) => {}
+                    Err(error) => {
+                        error!(%error, height, "failed to read block header for pruning");
+                        return vec![];
+                    }
+                }
+            }
+            roots
+        };
+        self.contract_runtime.prune_global_state(
+            retained_roots,
+            self.block_synchronizer.state_roots_in_use(),
+            !self.block_synchronizer.is_syncing_global_state(),
+        )
+    }
+
     fn deactivate_consensus_voting(&mut self) -> Result<(), String> {
         let deactivated_era_id = self.consensus.deactivate_current_era()?;
         info!(
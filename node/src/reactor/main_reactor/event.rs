@@ -34,12 +34,12 @@ use crate::{
         },
         requests::{
             AcceptDeployRequest, BeginGossipRequest, BlockAccumulatorRequest,
-            BlockSynchronizerRequest, BlockValidationRequest, ChainspecRawBytesRequest,
-            ConsensusRequest, ContractRuntimeRequest, DeployBufferRequest, FetcherRequest,
-            MakeBlockExecutableRequest, MarkBlockCompletedRequest, MetricsRequest,
-            NetworkInfoRequest, NetworkRequest, ReactorStatusRequest, RestRequest, RpcRequest,
-            SetNodeStopRequest, StorageRequest, SyncGlobalStateRequest, TrieAccumulatorRequest,
-            UpgradeWatcherRequest,
+            BlockSynchronizerRequest, BlockValidationRequest, BlocklistRequest,
+            ChainspecRawBytesRequest, ConsensusRequest, ContractRuntimeRequest,
+            DeployBufferRequest, FetcherRequest, MakeBlockExecutableRequest,
+            MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest,
+            ReactorStatusRequest, RestRequest, RpcRequest, SetNodeStopRequest, StorageRequest,
+            SyncGlobalStateRequest, TrieAccumulatorRequest, UpgradeWatcherRequest,
         },
     },
     protocol::Message,
@@ -97,6 +97,8 @@ pub(crate) enum MainEvent {
     #[from]
     NetworkInfoRequest(#[serde(skip_serializing)] NetworkInfoRequest),
     #[from]
+    NetworkBlocklistRequest(#[serde(skip_serializing)] BlocklistRequest),
+    #[from]
     NetworkPeerBehaviorAnnouncement(PeerBehaviorAnnouncement),
     #[from]
     NetworkPeerRequestingData(NetRequestIncoming),
@@ -296,6 +298,7 @@ impl ReactorEvent for MainEvent {
             MainEvent::DiagnosticsPort(_) => "DiagnosticsPort",
             MainEvent::NetworkRequest(_) => "NetworkRequest",
             MainEvent::NetworkInfoRequest(_) => "NetworkInfoRequest",
+            MainEvent::NetworkBlocklistRequest(_) => "NetworkBlocklistRequest",
             MainEvent::BlockHeaderFetcherRequest(_) => "BlockHeaderFetcherRequest",
             MainEvent::TrieOrChunkFetcherRequest(_) => "TrieOrChunkFetcherRequest",
             MainEvent::BlockExecutionResultsOrChunkFetcherRequest(_) => {
@@ -433,6 +436,9 @@ impl Display for MainEvent {
             MainEvent::NetworkInfoRequest(req) => {
                 write!(f, "network info request: {}", req)
             }
+            MainEvent::NetworkBlocklistRequest(req) => {
+                write!(f, "network blocklist request: {}", req)
+            }
             MainEvent::ChainspecRawBytesRequest(req) => {
                 write!(f, "chainspec loader request: {}", req)
             }
@@ -5,5 +5,6 @@ use crate::{effect::Effects, reactor::main_reactor::MainEvent};
 pub(super) enum GenesisInstruction {
     Validator(Duration, Effects<MainEvent>),
     NonValidator(Duration, Effects<MainEvent>),
+    CheckLater(String, Duration),
     Fatal(String),
 }
@@ -159,7 +159,9 @@ impl MemoryMetrics {
     }
 
     /// Estimates memory usage and updates metrics.
-    pub(super) fn estimate(&self, reactor: &MainReactor) {
+    ///
+    /// Returns the estimated heap size of each component.
+    pub(super) fn estimate(&self, reactor: &MainReactor) -> Vec<(&'static str, i64)> {
         let timer = self.mem_estimator_runtime_s.start_timer();
 
         let metrics = reactor.metrics.estimate_heap_size() as i64;
@@ -259,6 +261,30 @@ impl MemoryMetrics {
                %diagnostics_port,
                %upgrade_watcher,
                "Collected new set of memory metrics.");
+
+        vec![
+            ("metrics", metrics),
+            ("network", network),
+            ("address_gossiper", address_gossiper),
+            ("storage", storage),
+            ("contract_runtime", contract_runtime),
+            ("rpc_server", rpc_server),
+            ("rest_server", rest_server),
+            ("event_stream_server", event_stream_server),
+            ("consensus", consensus),
+            ("fetchers", fetchers),
+            ("deploy_gossiper", deploy_gossiper),
+            ("finality_signature_gossiper", finality_signature_gossiper),
+            ("block_gossiper", block_gossiper),
+            ("deploy_buffer", deploy_buffer),
+            ("block_validator", block_validator),
+            ("sync_leaper", sync_leaper),
+            ("deploy_acceptor", deploy_acceptor),
+            ("block_synchronizer", block_synchronizer),
+            ("block_accumulator", block_accumulator),
+            ("diagnostics_port", diagnostics_port),
+            ("upgrade_watcher", upgrade_watcher),
+        ]
     }
 }
 
@@ -27,9 +27,9 @@ use rand_chacha::ChaCha20Rng;
 
 pub use available_block_range::AvailableBlockRange;
 pub(crate) use block::{
-    compute_approvals_checksum, ApprovalsHashes, BlockHashAndHeight, BlockHeaderWithMetadata,
-    BlockPayload, BlockWithMetadata, FinalitySignatureId, MetaBlock, MetaBlockMergeError,
-    MetaBlockState,
+    compute_approvals_checksum, ApprovalsHashes, ApprovalsHashesValidationError,
+    BlockHashAndHeight, BlockHeaderWithMetadata, BlockPayload, BlockWithMetadata,
+    FinalitySignatureId, MetaBlock, MetaBlockMergeError, MetaBlockState,
 };
 pub use block::{
     json_compatibility::{JsonBlock, JsonBlockHeader},
@@ -48,13 +48,13 @@ pub use deploy::{
 };
 pub(crate) use deploy::{
     DeployFootprint, DeployHashWithApprovals, DeployId, DeployMetadata, DeployMetadataExt,
-    DeployWithFinalizedApprovals, FinalizedApprovals, LegacyDeploy,
+    DeployValidationMetadata, DeployWithFinalizedApprovals, FinalizedApprovals, LegacyDeploy,
 };
 pub use error::BlockValidationError;
 pub use exit_code::ExitCode;
 pub(crate) use max_ttl::MaxTtl;
 pub use node_config::{NodeConfig, SyncHandling};
-pub(crate) use node_id::NodeId;
+pub(crate) use node_id::{NodeId, ParseNodeIdError};
 pub use peers_map::PeersMap;
 pub use status_feed::{ChainspecInfo, GetStatusResult, StatusFeed};
 pub(crate) use sync_leap::{GlobalStatesMetadata, SyncLeap, SyncLeapIdentifier};
@@ -54,7 +54,7 @@ use crate::{
     },
     utils::{ds, DisplayIter},
 };
-pub(crate) use approvals_hashes::ApprovalsHashes;
+pub(crate) use approvals_hashes::{ApprovalsHashes, ApprovalsHashesValidationError};
 pub(crate) use meta_block::{
     MergeMismatchError as MetaBlockMergeError, MetaBlock, State as MetaBlockState,
 };
@@ -104,6 +104,10 @@ pub struct CoreConfig {
     pub(crate) allow_unrestricted_transfers: bool,
     /// If set to false then consensus doesn't compute rewards and always uses 0.
     pub(crate) compute_rewards: bool,
+    /// Whether secp256r1 keys may sign or send deploys, and be used in auction bids and
+    /// delegations.
+    #[serde(default)]
+    pub(crate) allow_secp256r1_keys: bool,
     /// Administrative accounts are valid option for a private chain only.
     #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
     pub(crate) administrators: BTreeSet<PublicKey>,
@@ -111,6 +115,7 @@ pub struct CoreConfig {
     #[data_size(skip)]
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
+    #[data_size(skip)]
     pub(crate) fee_handling: FeeHandling,
 }
 
@@ -161,6 +166,14 @@ impl CoreConfig {
             return false;
         }
 
+        if !self.fee_handling.is_valid() {
+            error!(
+                fee_handling = ?self.fee_handling,
+                "fee handling split ratios do not add up to one",
+            );
+            return false;
+        }
+
         true
     }
 }
@@ -195,6 +208,7 @@ impl CoreConfig {
         let allow_auction_bids = rng.gen();
         let allow_unrestricted_transfers = rng.gen();
         let compute_rewards = rng.gen();
+        let allow_secp256r1_keys = rng.gen();
         let administrators = (0..rng.gen_range(0..=10u32))
             .map(|_| PublicKey::random(rng))
             .collect();
@@ -204,10 +218,18 @@ impl CoreConfig {
             RefundHandling::Refund { refund_ratio }
         };
 
-        let fee_handling = if rng.gen() {
-            FeeHandling::PayToProposer
-        } else {
-            FeeHandling::Accumulate
+        let fee_handling = match rng.gen_range(0..3) {
+            0 => FeeHandling::PayToProposer,
+            1 => FeeHandling::Accumulate,
+            _ => {
+                let proposer = rng.gen_range(0..=100);
+                let accumulate = rng.gen_range(0..=(100 - proposer));
+                FeeHandling::Split {
+                    proposer_ratio: Ratio::new(proposer, 100),
+                    accumulate_ratio: Ratio::new(accumulate, 100),
+                    burn_ratio: Ratio::new(100 - proposer - accumulate, 100),
+                }
+            }
         };
 
         CoreConfig {
@@ -235,6 +257,7 @@ impl CoreConfig {
             administrators,
             allow_unrestricted_transfers,
             compute_rewards,
+            allow_secp256r1_keys,
             refund_handling,
             fee_handling,
         }
@@ -270,6 +293,7 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.allow_auction_bids.to_bytes()?);
         buffer.extend(self.allow_unrestricted_transfers.to_bytes()?);
         buffer.extend(self.compute_rewards.to_bytes()?);
+        buffer.extend(self.allow_secp256r1_keys.to_bytes()?);
         buffer.extend(self.administrators.to_bytes()?);
         buffer.extend(self.refund_handling.to_bytes()?);
         buffer.extend(self.fee_handling.to_bytes()?);
@@ -302,6 +326,7 @@ impl ToBytes for CoreConfig {
             + self.allow_auction_bids.serialized_length()
             + self.allow_unrestricted_transfers.serialized_length()
             + self.compute_rewards.serialized_length()
+            + self.allow_secp256r1_keys.serialized_length()
             + self.administrators.serialized_length()
             + self.refund_handling.serialized_length()
             + self.fee_handling.serialized_length()
@@ -334,6 +359,7 @@ impl FromBytes for CoreConfig {
         let (allow_auction_bids, remainder) = FromBytes::from_bytes(remainder)?;
         let (allow_unrestricted_transfers, remainder) = FromBytes::from_bytes(remainder)?;
         let (compute_rewards, remainder) = bool::from_bytes(remainder)?;
+        let (allow_secp256r1_keys, remainder) = bool::from_bytes(remainder)?;
         let (administrative_accounts, remainder) = FromBytes::from_bytes(remainder)?;
         let (refund_handling, remainder) = FromBytes::from_bytes(remainder)?;
         let (fee_handling, remainder) = FromBytes::from_bytes(remainder)?;
@@ -361,6 +387,7 @@ impl FromBytes for CoreConfig {
             allow_auction_bids,
             allow_unrestricted_transfers,
             compute_rewards,
+            allow_secp256r1_keys,
             administrators: administrative_accounts,
             refund_handling,
             fee_handling,
@@ -559,4 +586,37 @@ mod tests {
         config.finality_threshold_fraction = Ratio::new(u64::MAX, u64::MAX - 1);
         assert!(!config.is_valid());
     }
+
+    #[test]
+    fn should_validate_for_fee_handling_split() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+        config.finality_threshold_fraction = Ratio::new(1, 3);
+        // Should be valid if the ratios add up to one.
+        config.fee_handling = FeeHandling::Split {
+            proposer_ratio: Ratio::new(3, 10),
+            accumulate_ratio: Ratio::new(1, 5),
+            burn_ratio: Ratio::new(1, 2),
+        };
+        assert!(config.is_valid());
+        config.fee_handling = FeeHandling::Split {
+            proposer_ratio: Ratio::new(0, 1),
+            accumulate_ratio: Ratio::new(u64::MAX - 1, u64::MAX),
+            burn_ratio: Ratio::new(1, u64::MAX),
+        };
+        assert!(config.is_valid());
+        // Should be invalid otherwise.
+        config.fee_handling = FeeHandling::Split {
+            proposer_ratio: Ratio::new(1, 2),
+            accumulate_ratio: Ratio::new(1, 2),
+            burn_ratio: Ratio::new(1, 10),
+        };
+        assert!(!config.is_valid());
+        config.fee_handling = FeeHandling::Split {
+            proposer_ratio: Ratio::new(1, 2),
+            accumulate_ratio: Ratio::new(0, 1),
+            burn_ratio: Ratio::new(1, 3),
+        };
+        assert!(!config.is_valid());
+    }
 }
@@ -14,12 +14,13 @@ mod footprint;
 mod id;
 mod legacy_deploy;
 mod metadata;
+mod validation_metadata;
 
 use std::{
     cmp,
     collections::BTreeSet,
     fmt::{self, Debug, Display, Formatter},
-    hash,
+    hash, iter,
 };
 
 use datasize::DataSize;
@@ -50,7 +51,7 @@ use casper_types::{
 
 use crate::{
     components::{
-        fetcher::{EmptyValidationMetadata, FetchItem, Tag},
+        fetcher::{FetchItem, Tag},
         gossiper::{GossipItem, LargeGossipItem},
     },
     effect::GossipTarget,
@@ -71,6 +72,7 @@ pub(crate) use footprint::Footprint as DeployFootprint;
 pub use id::Id as DeployId;
 pub(crate) use legacy_deploy::LegacyDeploy;
 pub(crate) use metadata::{Metadata as DeployMetadata, MetadataExt as DeployMetadataExt};
+pub(crate) use validation_metadata::DeployValidationMetadata;
 
 static DEPLOY: Lazy<Deploy> = Lazy::new(|| {
     let payment_args = runtime_args! {
@@ -294,6 +296,22 @@ impl Deploy {
         self.is_valid.get_or_init(|| validate_deploy(self)).clone()
     }
 
+    /// Returns `Ok` if and only if the deploy's account and the signers of its approvals only use
+    /// public key types which are enabled in the chainspec.
+    pub(crate) fn has_allowed_key_types(
+        &self,
+        allow_secp256r1_keys: bool,
+    ) -> Result<(), DeployConfigurationFailure> {
+        let uses_secp256r1_keys = iter::once(self.header().account())
+            .chain(self.approvals.iter().map(|approval| approval.signer()))
+            .any(|public_key| matches!(public_key, PublicKey::Secp256r1(_)));
+        if !allow_secp256r1_keys && uses_secp256r1_keys {
+            debug!(deploy_hash = %self.hash(), "deploy uses secp256r1 keys, which are not enabled");
+            return Err(DeployConfigurationFailure::Secp256r1KeysNotAllowed);
+        }
+        Ok(())
+    }
+
     /// Returns Ok if and only if:
     ///   * the chain_name is correct,
     ///   * the configured parameters are complied with at the given timestamp
@@ -530,7 +548,7 @@ impl FromBytes for Deploy {
 impl FetchItem for Deploy {
     type Id = DeployId;
     type ValidationError = DeployConfigurationFailure;
-    type ValidationMetadata = EmptyValidationMetadata;
+    type ValidationMetadata = DeployValidationMetadata;
 
     const TAG: Tag = Tag::Deploy;
 
@@ -543,7 +561,8 @@ impl FetchItem for Deploy {
         DeployId::new(deploy_hash, approvals_hash)
     }
 
-    fn validate(&self, _metadata: &EmptyValidationMetadata) -> Result<(), Self::ValidationError> {
+    fn validate(&self, metadata: &DeployValidationMetadata) -> Result<(), Self::ValidationError> {
+        self.has_allowed_key_types(metadata.allow_secp256r1_keys)?;
         self.is_valid()
     }
 }
@@ -1184,6 +1203,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fetched_deploy_not_valid_due_to_disallowed_key_types() {
+        let mut rng = crate::new_rng();
+        let allowed = DeployValidationMetadata {
+            allow_secp256r1_keys: true,
+        };
+        let disallowed = DeployValidationMetadata {
+            allow_secp256r1_keys: false,
+        };
+
+        let deploy = create_deploy(&mut rng, DeployConfig::default().max_ttl, 0, "net-1");
+        deploy.validate(&disallowed).expect("should be valid");
+
+        let secret_key = SecretKey::random_secp256r1(&mut rng);
+        let secp256r1_deploy = Deploy::new(
+            deploy.header().timestamp(),
+            deploy.header().ttl(),
+            deploy.header().gas_price(),
+            vec![],
+            deploy.header().chain_name().to_string(),
+            deploy.payment().clone(),
+            deploy.session().clone(),
+            &secret_key,
+            None,
+        );
+        secp256r1_deploy
+            .validate(&allowed)
+            .expect("should be valid");
+        assert_eq!(
+            secp256r1_deploy.validate(&disallowed),
+            Err(DeployConfigurationFailure::Secp256r1KeysNotAllowed)
+        );
+    }
+
     #[test]
     fn not_valid_due_to_invalid_body_hash() {
         let mut rng = crate::new_rng();
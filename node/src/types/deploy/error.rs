@@ -135,6 +135,10 @@ pub enum DeployConfigurationFailure {
         /// The chainspec limit for max_associated_keys.
         max_associated_keys: u32,
     },
+
+    /// The deploy uses a secp256r1 key, but these are not enabled by the chainspec.
+    #[error("secp256r1 keys are not enabled on this network")]
+    Secp256r1KeysNotAllowed,
 }
 
 /// Error returned when a Deploy is too large.
@@ -0,0 +1,18 @@
+use datasize::DataSize;
+use serde::Serialize;
+
+use crate::types::Chainspec;
+
+/// The chainspec settings against which a fetched [`Deploy`](super::Deploy) is validated.
+#[derive(Clone, Copy, DataSize, Debug, Eq, PartialEq, Serialize)]
+pub(crate) struct DeployValidationMetadata {
+    pub(crate) allow_secp256r1_keys: bool,
+}
+
+impl DeployValidationMetadata {
+    pub(crate) fn from_chainspec(chainspec: &Chainspec) -> Self {
+        DeployValidationMetadata {
+            allow_secp256r1_keys: chainspec.core_config.allow_secp256r1_keys,
+        }
+    }
+}
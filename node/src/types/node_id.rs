@@ -1,4 +1,7 @@
-use std::fmt::{self, Debug, Display, Formatter};
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
 
 use datasize::DataSize;
 use hex_fmt::HexFmt;
@@ -6,6 +9,7 @@ use once_cell::sync::Lazy;
 #[cfg(test)]
 use rand::Rng;
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
@@ -101,6 +105,33 @@ impl From<KeyFingerprint> for NodeId {
     }
 }
 
+/// Error parsing a [`NodeId`] from a string.
+#[derive(Debug, Error)]
+pub enum ParseNodeIdError {
+    /// The string is not valid hex.
+    #[error("invalid hex: {0}")]
+    Hex(#[from] base16::DecodeError),
+    /// The decoded bytes have the wrong length.
+    #[error("expected {} bytes, got {0}", KeyFingerprint::LENGTH)]
+    Length(usize),
+}
+
+/// Parses the full, hex-encoded fingerprint of a node ID, optionally prefixed with `tls:`.
+impl FromStr for NodeId {
+    type Err = ParseNodeIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex_value = s.strip_prefix("tls:").unwrap_or(s);
+        let bytes = base16::decode(hex_value.as_bytes())?;
+        if bytes.len() != KeyFingerprint::LENGTH {
+            return Err(ParseNodeIdError::Length(bytes.len()));
+        }
+        let mut array = [0_u8; KeyFingerprint::LENGTH];
+        array.copy_from_slice(bytes.as_slice());
+        Ok(NodeId(KeyFingerprint::from(array)))
+    }
+}
+
 #[cfg(test)]
 impl From<[u8; KeyFingerprint::LENGTH]> for NodeId {
     fn from(raw_bytes: [u8; KeyFingerprint::LENGTH]) -> Self {
@@ -155,6 +186,20 @@ mod tests {
         assert_eq!(expected, json_string.as_str());
     }
 
+    #[test]
+    fn parses_full_hex_with_or_without_prefix() {
+        let node_id = NodeId::from(EXAMPLE_HASH_RAW);
+        let hex_value = base16::encode_lower(&EXAMPLE_HASH_RAW);
+
+        assert_eq!(hex_value.parse::<NodeId>().unwrap(), node_id);
+        assert_eq!(
+            format!("tls:{}", hex_value).parse::<NodeId>().unwrap(),
+            node_id
+        );
+        assert!(hex_value[..20].parse::<NodeId>().is_err());
+        assert!(node_id.to_string().parse::<NodeId>().is_err());
+    }
+
     #[test]
     fn msgpack_default_settings_known_specimen() {
         let node_id = NodeId::from(EXAMPLE_HASH_RAW);
@@ -412,13 +412,14 @@ where
                 System,
                 Ed25519,
                 Secp256k1,
+                Secp256r1,
             }
             largest_variant::<PublicKey, PublicKeyDiscriminants, _, _>(estimator, |variant| {
                 // We take advantage of two things here:
                 //
-                // 1. The required seed bytes for Ed25519 and Secp256k1 are both the same length of
-                //    32 bytes.
-                // 2. While Secp256k1 does not allow the most trivial seed bytes of 0x00..0001, a
+                // 1. The required seed bytes for Ed25519, Secp256k1 and Secp256r1 are all the same
+                //    length of 32 bytes.
+                // 2. While the ECDSA curves do not allow the most trivial seed bytes of 0x00..0001, a
                 //    a hash function output seems to satisfy it, and our current hashing scheme
                 //    also output 32 bytes.
                 let seed_bytes = Digest::hash(seed.to_be_bytes()).value();
@@ -435,6 +436,11 @@ where
                             .expect("unable to create secp256k1 key from seed bytes");
                         PublicKey::from(&secp256k1_sec)
                     }
+                    PublicKeyDiscriminants::Secp256r1 => {
+                        let secp256r1_sec = SecretKey::secp256r1_from_bytes(seed_bytes)
+                            .expect("unable to create secp256r1 key from seed bytes");
+                        PublicKey::from(&secp256r1_sec)
+                    }
                 }
             })
         }
@@ -479,6 +485,7 @@ impl LargestSpecimen for Signature {
             System,
             Ed25519,
             Secp256k1,
+            Secp256r1,
         }
 
         *cache.set(largest_variant::<Self, SignatureDiscriminants, _, _>(
@@ -495,6 +502,11 @@ impl LargestSpecimen for Signature {
 
                     sign([0_u8], secp256k1_sec, &secp256k1_sec.into())
                 }
+                SignatureDiscriminants::Secp256r1 => {
+                    let secp256r1_sec = &SecretKey::generate_secp256r1().expect("a correct secret");
+
+                    sign([0_u8], secp256r1_sec, &secp256r1_sec.into())
+                }
             },
         ))
     }
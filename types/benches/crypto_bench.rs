@@ -0,0 +1,80 @@
+use criterion::{black_box, criterion_group, criterion_main, Bencher, Criterion};
+
+use casper_types::{crypto, PublicKey, SecretKey, Signature};
+
+const MESSAGE: &[u8] = b"a deploy hash or a block hash and era id";
+
+fn public_key(index: usize) -> PublicKey {
+    PublicKey::from(&secret_key(index))
+}
+
+fn secret_key(index: usize) -> SecretKey {
+    let mut secret_key_bytes = [0u8; SecretKey::ED25519_LENGTH];
+    secret_key_bytes[..8].copy_from_slice(&(index as u64).to_le_bytes());
+    SecretKey::ed25519_from_bytes(secret_key_bytes).unwrap()
+}
+
+fn signed_messages(count: usize) -> Vec<(Vec<u8>, Signature, PublicKey)> {
+    (0..count)
+        .map(|index| {
+            let secret_key = secret_key(index);
+            let public_key = PublicKey::from(&secret_key);
+            let mut message = MESSAGE.to_vec();
+            message.extend_from_slice(&index.to_le_bytes());
+            let signature = crypto::sign(&message, &secret_key, &public_key);
+            (message, signature, public_key)
+        })
+        .collect()
+}
+
+fn verify_individually(count: usize, b: &mut Bencher) {
+    let items = signed_messages(count);
+    b.iter(|| {
+        for (message, signature, public_key) in &items {
+            crypto::verify(black_box(message), signature, public_key).unwrap();
+        }
+    })
+}
+
+fn verify_batch(count: usize, b: &mut Bencher) {
+    let items = signed_messages(count);
+    b.iter(|| {
+        crypto::verify_batch(
+            items.iter().map(|(message, signature, public_key)| {
+                (black_box(message), signature, public_key)
+            }),
+        )
+        .unwrap()
+    })
+}
+
+fn verify_batch_with_invalid_signature(count: usize, b: &mut Bencher) {
+    let mut items = signed_messages(count);
+    items[count / 2].2 = public_key(count);
+    b.iter(|| {
+        crypto::verify_batch(
+            items.iter().map(|(message, signature, public_key)| {
+                (black_box(message), signature, public_key)
+            }),
+        )
+        .unwrap_err()
+    })
+}
+
+fn crypto_bench(c: &mut Criterion) {
+    for count in [10, 100] {
+        c.bench_function(&format!("crypto::verify_individually_{}", count), |b| {
+            verify_individually(count, b)
+        });
+        c.bench_function(&format!("crypto::verify_batch_{}", count), |b| {
+            verify_batch(count, b)
+        });
+        c.bench_function(
+            &format!("crypto::verify_batch_with_invalid_signature_{}", count),
+            |b| verify_batch_with_invalid_signature(count, b),
+        );
+    }
+}
+
+criterion_group!(benches, crypto_bench);
+criterion_main!(benches);
@@ -16,6 +16,7 @@ use core::{
 #[cfg(any(feature = "std", test))]
 use std::path::Path;
 
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
 #[cfg(feature = "datasize")]
 use datasize::DataSize;
 #[cfg(any(feature = "std", test))]
@@ -33,6 +34,10 @@ use k256::ecdsa::{
 };
 #[cfg(any(feature = "std", test))]
 use once_cell::sync::Lazy;
+use p256::ecdsa::{
+    Signature as Secp256r1Signature, SigningKey as Secp256r1SecretKey,
+    VerifyingKey as Secp256r1PublicKey,
+};
 #[cfg(any(feature = "std", test))]
 use pem::Pem;
 #[cfg(any(all(feature = "std", feature = "testing"), test))]
@@ -83,6 +88,14 @@ const SECP256K1_SECRET_KEY_LENGTH: usize = 32;
 const SECP256K1_COMPRESSED_PUBLIC_KEY_LENGTH: usize = 33;
 const SECP256K1_SIGNATURE_LENGTH: usize = 64;
 
+/// Tag for secp256r1 variant.
+pub const SECP256R1_TAG: u8 = 3;
+const SECP256R1: &str = "Secp256r1";
+
+const SECP256R1_SECRET_KEY_LENGTH: usize = 32;
+const SECP256R1_COMPRESSED_PUBLIC_KEY_LENGTH: usize = 33;
+const SECP256R1_SIGNATURE_LENGTH: usize = 64;
+
 /// Public key for system account.
 pub const SYSTEM_ACCOUNT: PublicKey = PublicKey::System;
 
@@ -106,6 +119,14 @@ const SECP256K1_PEM_SECRET_KEY_TAG: &str = "EC PRIVATE KEY";
 #[cfg(any(feature = "std", test))]
 const SECP256K1_PEM_PUBLIC_KEY_TAG: &str = "PUBLIC KEY";
 
+// See https://www.rfc-editor.org/rfc/rfc5480#section-2.1.1.1
+#[cfg(any(feature = "std", test))]
+const SECP256R1_OBJECT_IDENTIFIER: [u8; 8] = [42, 134, 72, 206, 61, 3, 1, 7];
+#[cfg(any(feature = "std", test))]
+const SECP256R1_PEM_SECRET_KEY_TAG: &str = "EC PRIVATE KEY";
+#[cfg(any(feature = "std", test))]
+const SECP256R1_PEM_PUBLIC_KEY_TAG: &str = "PUBLIC KEY";
+
 #[cfg(any(feature = "std", test))]
 static ED25519_SECRET_KEY: Lazy<SecretKey> = Lazy::new(|| {
     let bytes = [15u8; SecretKey::ED25519_LENGTH];
@@ -159,9 +180,10 @@ where
             }
             ED25519_TAG => Self::ed25519_from_bytes(&key_bytes),
             SECP256K1_TAG => Self::secp256k1_from_bytes(&key_bytes),
+            SECP256R1_TAG => Self::secp256r1_from_bytes(&key_bytes),
             _ => Err(Error::AsymmetricKey(format!(
-                "failed to decode from hex: invalid tag.  Expected {}, {} or {}, got {}",
-                SYSTEM_TAG, ED25519_TAG, SECP256K1_TAG, tag[0]
+                "failed to decode from hex: invalid tag.  Expected {}, {}, {} or {}, got {}",
+                SYSTEM_TAG, ED25519_TAG, SECP256K1_TAG, SECP256R1_TAG, tag[0]
             ))),
         }
     }
@@ -174,6 +196,9 @@ where
 
     /// Constructs a new secp256k1 variant from a byte slice.
     fn secp256k1_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error>;
+
+    /// Constructs a new secp256r1 variant from a byte slice.
+    fn secp256r1_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error>;
 }
 
 /// A secret or private asymmetric key.
@@ -189,6 +214,9 @@ pub enum SecretKey {
     /// secp256k1 secret key.
     #[cfg_attr(feature = "datasize", data_size(skip))]
     Secp256k1(Secp256k1SecretKey),
+    /// secp256r1 (NIST P-256) secret key.
+    #[cfg_attr(feature = "datasize", data_size(skip))]
+    Secp256r1(Secp256r1SecretKey),
 }
 
 impl SecretKey {
@@ -201,6 +229,9 @@ impl SecretKey {
     /// The length in bytes of a secp256k1 secret key.
     pub const SECP256K1_LENGTH: usize = SECP256K1_SECRET_KEY_LENGTH;
 
+    /// The length in bytes of a secp256r1 secret key.
+    pub const SECP256R1_LENGTH: usize = SECP256R1_SECRET_KEY_LENGTH;
+
     /// Constructs a new system variant.
     pub fn system() -> Self {
         SecretKey::System
@@ -220,11 +251,19 @@ impl SecretKey {
         ))
     }
 
+    /// Constructs a new secp256r1 variant from a byte slice.
+    pub fn secp256r1_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error> {
+        Ok(SecretKey::Secp256r1(
+            Secp256r1SecretKey::from_slice(bytes.as_ref()).map_err(|_| Error::SignatureError)?,
+        ))
+    }
+
     fn variant_name(&self) -> &str {
         match self {
             SecretKey::System => SYSTEM,
             SecretKey::Ed25519(_) => ED25519,
             SecretKey::Secp256k1(_) => SECP256K1,
+            SecretKey::Secp256r1(_) => SECP256R1,
         }
     }
 }
@@ -245,6 +284,13 @@ impl SecretKey {
         SecretKey::secp256k1_from_bytes(bytes).map_err(Into::into)
     }
 
+    /// Generates a new secp256r1 variant using the system's secure random number generator.
+    pub fn generate_secp256r1() -> Result<Self, ErrorExt> {
+        let mut bytes = [0u8; Self::SECP256R1_LENGTH];
+        getrandom::getrandom(&mut bytes[..])?;
+        SecretKey::secp256r1_from_bytes(bytes).map_err(Into::into)
+    }
+
     /// Attempts to write the key bytes to the configured file path.
     #[cfg(any(feature = "std-fs-io", test))]
     pub fn to_file<P: AsRef<Path>>(&self, file: P) -> Result<(), ErrorExt> {
@@ -283,6 +329,21 @@ impl SecretKey {
                 let mut der = Der::new(&mut oid_bytes);
                 der.oid(&SECP256K1_OBJECT_IDENTIFIER)?;
 
+                let mut encoded = vec![];
+                der = Der::new(&mut encoded);
+                der.sequence(|der| {
+                    der.integer(&[1])?;
+                    der.octet_string(secret_key.to_bytes().as_slice())?;
+                    der.element(Tag::ContextSpecificConstructed0, &oid_bytes)
+                })?;
+                Ok(encoded)
+            }
+            SecretKey::Secp256r1(secret_key) => {
+                // See https://www.rfc-editor.org/rfc/rfc5915#section-3
+                let mut oid_bytes = vec![];
+                let mut der = Der::new(&mut oid_bytes);
+                der.oid(&SECP256R1_OBJECT_IDENTIFIER)?;
+
                 let mut encoded = vec![];
                 der = Der::new(&mut encoded);
                 der.sequence(|der| {
@@ -334,7 +395,7 @@ impl SecretKey {
 
                     return Ok((ED25519_TAG, raw_bytes));
                 } else if tag == Tag::OctetString as u8 {
-                    // Expecting a secp256k1 key.
+                    // Expecting a secp256k1 or secp256r1 key.
                     if version != 1 {
                         return Err(derp::Error::WrongValue);
                     }
@@ -348,16 +409,18 @@ impl SecretKey {
                     let object_identifier = parameter0.read_all(derp::Error::Read, |input| {
                         derp::expect_tag_and_get_value(input, Tag::Oid)
                     })?;
-                    if object_identifier.as_slice_less_safe() != SECP256K1_OBJECT_IDENTIFIER {
-                        return Err(derp::Error::WrongValue);
-                    }
+                    let key_type_tag = match object_identifier.as_slice_less_safe() {
+                        oid if oid == SECP256K1_OBJECT_IDENTIFIER => SECP256K1_TAG,
+                        oid if oid == SECP256R1_OBJECT_IDENTIFIER => SECP256R1_TAG,
+                        _ => return Err(derp::Error::WrongValue),
+                    };
 
                     // There might be an optional public key as the final value, but we're not
                     // interested in parsing that.  Read it to ensure `input.read_all` doesn't fail
                     // with unused bytes error.
                     let _ = derp::read_tag_and_get_value(input);
 
-                    return Ok((SECP256K1_TAG, raw_bytes));
+                    return Ok((key_type_tag, raw_bytes));
                 }
 
                 Err(derp::Error::WrongValue)
@@ -368,6 +431,7 @@ impl SecretKey {
             SYSTEM_TAG => Err(Error::AsymmetricKey("cannot construct variant".to_string()).into()),
             ED25519_TAG => SecretKey::ed25519_from_bytes(raw_bytes).map_err(Into::into),
             SECP256K1_TAG => SecretKey::secp256k1_from_bytes(raw_bytes).map_err(Into::into),
+            SECP256R1_TAG => SecretKey::secp256r1_from_bytes(raw_bytes).map_err(Into::into),
             _ => Err(Error::AsymmetricKey("unknown type tag".to_string()).into()),
         }
     }
@@ -378,6 +442,7 @@ impl SecretKey {
             SecretKey::System => return Err(Error::System(String::from("to_pem")).into()),
             SecretKey::Ed25519(_) => ED25519_PEM_SECRET_KEY_TAG.to_string(),
             SecretKey::Secp256k1(_) => SECP256K1_PEM_SECRET_KEY_TAG.to_string(),
+            SecretKey::Secp256r1(_) => SECP256R1_PEM_SECRET_KEY_TAG.to_string(),
         };
         let contents = self.to_der()?;
         let pem = Pem { tag, contents };
@@ -409,6 +474,11 @@ impl SecretKey {
                     return Err(bad_tag(SECP256K1_PEM_SECRET_KEY_TAG));
                 }
             }
+            SecretKey::Secp256r1(_) => {
+                if pem.tag != SECP256R1_PEM_SECRET_KEY_TAG {
+                    return Err(bad_tag(SECP256R1_PEM_SECRET_KEY_TAG));
+                }
+            }
         }
 
         Ok(secret_key)
@@ -440,6 +510,14 @@ impl SecretKey {
         SecretKey::secp256k1_from_bytes(bytes).unwrap()
     }
 
+    /// Generates a random secp256r1 instance using a `TestRng`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random_secp256r1(rng: &mut TestRng) -> Self {
+        let mut bytes = [0u8; Self::SECP256R1_LENGTH];
+        rng.fill_bytes(&mut bytes[..]);
+        SecretKey::secp256r1_from_bytes(bytes).unwrap()
+    }
+
     /// Returns an example value for documentation purposes.
     pub fn doc_example() -> &'static Self {
         &ED25519_SECRET_KEY
@@ -464,6 +542,7 @@ impl Tagged<u8> for SecretKey {
             SecretKey::System => SYSTEM_TAG,
             SecretKey::Ed25519(_) => ED25519_TAG,
             SecretKey::Secp256k1(_) => SECP256K1_TAG,
+            SecretKey::Secp256r1(_) => SECP256R1_TAG,
         }
     }
 }
@@ -481,6 +560,9 @@ pub enum PublicKey {
     /// secp256k1 public key.
     #[cfg_attr(feature = "datasize", data_size(skip))]
     Secp256k1(Secp256k1PublicKey),
+    /// secp256r1 (NIST P-256) public key.
+    #[cfg_attr(feature = "datasize", data_size(skip))]
+    Secp256r1(Secp256r1PublicKey),
 }
 
 impl PublicKey {
@@ -493,6 +575,9 @@ impl PublicKey {
     /// The length in bytes of a secp256k1 public key.
     pub const SECP256K1_LENGTH: usize = SECP256K1_COMPRESSED_PUBLIC_KEY_LENGTH;
 
+    /// The length in bytes of a secp256r1 public key.
+    pub const SECP256R1_LENGTH: usize = SECP256R1_COMPRESSED_PUBLIC_KEY_LENGTH;
+
     /// Creates an `AccountHash` from a given `PublicKey` instance.
     pub fn to_account_hash(&self) -> AccountHash {
         AccountHash::from(self)
@@ -508,6 +593,7 @@ impl PublicKey {
             PublicKey::System => SYSTEM,
             PublicKey::Ed25519(_) => ED25519,
             PublicKey::Secp256k1(_) => SECP256K1,
+            PublicKey::Secp256r1(_) => SECP256R1,
         }
     }
 }
@@ -568,6 +654,19 @@ impl PublicKey {
                 })?;
                 Ok(encoded)
             }
+            PublicKey::Secp256r1(public_key) => {
+                // See https://www.rfc-editor.org/rfc/rfc5480#section-2
+                let mut encoded = vec![];
+                let mut der = Der::new(&mut encoded);
+                der.sequence(|der| {
+                    der.sequence(|der| {
+                        der.oid(&EC_PUBLIC_KEY_OBJECT_IDENTIFIER)?;
+                        der.oid(&SECP256R1_OBJECT_IDENTIFIER)
+                    })?;
+                    der.bit_string(0, public_key.to_encoded_point(true).as_ref())
+                })?;
+                Ok(encoded)
+            }
         }
     }
 
@@ -586,14 +685,16 @@ impl PublicKey {
                         key_type_tag = ED25519_TAG;
                         Ok(())
                     } else if object_identifier == EC_PUBLIC_KEY_OBJECT_IDENTIFIER {
-                        // Assert the next object identifier is the secp256k1 ID.
+                        // Assert the next object identifier is the secp256k1 or secp256r1 ID.
                         let next_object_identifier =
                             derp::expect_tag_and_get_value(input, Tag::Oid)?.as_slice_less_safe();
-                        if next_object_identifier != SECP256K1_OBJECT_IDENTIFIER {
+                        if next_object_identifier == SECP256K1_OBJECT_IDENTIFIER {
+                            key_type_tag = SECP256K1_TAG;
+                        } else if next_object_identifier == SECP256R1_OBJECT_IDENTIFIER {
+                            key_type_tag = SECP256R1_TAG;
+                        } else {
                             return Err(derp::Error::WrongValue);
                         }
-
-                        key_type_tag = SECP256K1_TAG;
                         Ok(())
                     } else {
                         Err(derp::Error::WrongValue)
@@ -606,6 +707,7 @@ impl PublicKey {
         match key_type_tag {
             ED25519_TAG => PublicKey::ed25519_from_bytes(raw_bytes).map_err(Into::into),
             SECP256K1_TAG => PublicKey::secp256k1_from_bytes(raw_bytes).map_err(Into::into),
+            SECP256R1_TAG => PublicKey::secp256r1_from_bytes(raw_bytes).map_err(Into::into),
             _ => unreachable!(),
         }
     }
@@ -616,6 +718,7 @@ impl PublicKey {
             PublicKey::System => return Err(Error::System(String::from("to_pem")).into()),
             PublicKey::Ed25519(_) => ED25519_PEM_PUBLIC_KEY_TAG.to_string(),
             PublicKey::Secp256k1(_) => SECP256K1_PEM_PUBLIC_KEY_TAG.to_string(),
+            PublicKey::Secp256r1(_) => SECP256R1_PEM_PUBLIC_KEY_TAG.to_string(),
         };
         let contents = self.to_der()?;
         let pem = Pem { tag, contents };
@@ -644,6 +747,11 @@ impl PublicKey {
                     return Err(bad_tag(SECP256K1_PEM_PUBLIC_KEY_TAG));
                 }
             }
+            PublicKey::Secp256r1(_) => {
+                if pem.tag != SECP256R1_PEM_PUBLIC_KEY_TAG {
+                    return Err(bad_tag(SECP256R1_PEM_PUBLIC_KEY_TAG));
+                }
+            }
         }
         Ok(public_key)
     }
@@ -669,6 +777,13 @@ impl PublicKey {
         PublicKey::from(&secret_key)
     }
 
+    /// Generates a random secp256r1 instance using a `TestRng`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random_secp256r1(rng: &mut TestRng) -> Self {
+        let secret_key = SecretKey::random_secp256r1(rng);
+        PublicKey::from(&secret_key)
+    }
+
     /// Returns an example value for documentation purposes.
     pub fn doc_example() -> &'static Self {
         &ED25519_PUBLIC_KEY
@@ -692,6 +807,13 @@ impl AsymmetricType<'_> for PublicKey {
                 .map_err(|_| Error::SignatureError)?,
         ))
     }
+
+    fn secp256r1_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error> {
+        Ok(PublicKey::Secp256r1(
+            Secp256r1PublicKey::from_sec1_bytes(bytes.as_ref())
+                .map_err(|_| Error::SignatureError)?,
+        ))
+    }
 }
 
 impl From<&SecretKey> for PublicKey {
@@ -700,6 +822,7 @@ impl From<&SecretKey> for PublicKey {
             SecretKey::System => PublicKey::System,
             SecretKey::Ed25519(secret_key) => PublicKey::Ed25519(secret_key.into()),
             SecretKey::Secp256k1(secret_key) => PublicKey::Secp256k1(secret_key.into()),
+            SecretKey::Secp256r1(secret_key) => PublicKey::Secp256r1(secret_key.into()),
         }
     }
 }
@@ -710,6 +833,7 @@ impl From<&PublicKey> for Vec<u8> {
             PublicKey::System => Vec::new(),
             PublicKey::Ed25519(key) => key.to_bytes().into(),
             PublicKey::Secp256k1(key) => key.to_encoded_point(true).as_ref().into(),
+            PublicKey::Secp256r1(key) => key.to_encoded_point(true).as_ref().into(),
         }
     }
 }
@@ -776,6 +900,7 @@ impl Tagged<u8> for PublicKey {
             PublicKey::System => SYSTEM_TAG,
             PublicKey::Ed25519(_) => ED25519_TAG,
             PublicKey::Secp256k1(_) => SECP256K1_TAG,
+            PublicKey::Secp256r1(_) => SECP256R1_TAG,
         }
     }
 }
@@ -793,6 +918,7 @@ impl ToBytes for PublicKey {
                 PublicKey::System => Self::SYSTEM_LENGTH,
                 PublicKey::Ed25519(_) => Self::ED25519_LENGTH,
                 PublicKey::Secp256k1(_) => Self::SECP256K1_LENGTH,
+                PublicKey::Secp256r1(_) => Self::SECP256R1_LENGTH,
             }
     }
 
@@ -807,6 +933,10 @@ impl ToBytes for PublicKey {
                 writer.push(SECP256K1_TAG);
                 writer.extend_from_slice(public_key.to_encoded_point(true).as_ref());
             }
+            PublicKey::Secp256r1(public_key) => {
+                writer.push(SECP256R1_TAG);
+                writer.extend_from_slice(public_key.to_encoded_point(true).as_ref());
+            }
         }
         Ok(())
     }
@@ -831,6 +961,13 @@ impl FromBytes for PublicKey {
                     .map_err(|_error| bytesrepr::Error::Formatting)?;
                 Ok((public_key, remainder))
             }
+            SECP256R1_TAG => {
+                let (raw_bytes, remainder): ([u8; Self::SECP256R1_LENGTH], _) =
+                    FromBytes::from_bytes(remainder)?;
+                let public_key = Self::secp256r1_from_bytes(raw_bytes)
+                    .map_err(|_error| bytesrepr::Error::Formatting)?;
+                Ok((public_key, remainder))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -880,6 +1017,12 @@ impl JsonSchema for PublicKey {
                 followed by 66 characters",
                 "value": "0203408e9526316fd1f8def480dd45b2cc72ffd732771c9ceb5d92ffa4051e6ee084"
             }),
+            json!({
+                "name": "Secp256r1PublicKey",
+                "description": "A secp256r1 (NIST P-256) public key. Its hex-encoded value begins \
+                '03' and is followed by 66 characters",
+                "value": "030384c5f074fc0b51db1a4e39c34eb5d943a6dc547ebd8a2df6ac940a50436a674f"
+            }),
         ];
         schema_object.into()
     }
@@ -904,6 +1047,9 @@ pub enum Signature {
     /// Secp256k1 signature.
     #[cfg_attr(feature = "datasize", data_size(skip))]
     Secp256k1(Secp256k1Signature),
+    /// Secp256r1 signature.
+    #[cfg_attr(feature = "datasize", data_size(skip))]
+    Secp256r1(Secp256r1Signature),
 }
 
 impl Signature {
@@ -916,6 +1062,9 @@ impl Signature {
     /// The length in bytes of a secp256k1 signature
     pub const SECP256K1_LENGTH: usize = SECP256K1_SIGNATURE_LENGTH;
 
+    /// The length in bytes of a secp256r1 signature
+    pub const SECP256R1_LENGTH: usize = SECP256R1_SIGNATURE_LENGTH;
+
     /// Constructs a new Ed25519 variant from a byte array.
     pub fn ed25519(bytes: [u8; Self::ED25519_LENGTH]) -> Result<Self, Error> {
         let signature = Ed25519Signature::from_bytes(&bytes);
@@ -934,11 +1083,24 @@ impl Signature {
         Ok(Signature::Secp256k1(signature))
     }
 
+    /// Constructs a new secp256r1 variant from a byte array.
+    pub fn secp256r1(bytes: [u8; Self::SECP256R1_LENGTH]) -> Result<Self, Error> {
+        let signature = Secp256r1Signature::try_from(&bytes[..]).map_err(|_| {
+            Error::AsymmetricKey(format!(
+                "failed to construct secp256r1 signature from {:?}",
+                &bytes[..]
+            ))
+        })?;
+
+        Ok(Signature::Secp256r1(signature))
+    }
+
     fn variant_name(&self) -> &str {
         match self {
             Signature::System => SYSTEM,
             Signature::Ed25519(_) => ED25519,
             Signature::Secp256k1(_) => SECP256K1,
+            Signature::Secp256r1(_) => SECP256R1,
         }
     }
 }
@@ -967,6 +1129,16 @@ impl AsymmetricType<'_> for Signature {
         })?;
         Ok(Signature::Secp256k1(signature))
     }
+
+    fn secp256r1_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error> {
+        let signature = Secp256r1Signature::try_from(bytes.as_ref()).map_err(|_| {
+            Error::AsymmetricKey(format!(
+                "failed to construct secp256r1 signature from {:?}",
+                bytes.as_ref()
+            ))
+        })?;
+        Ok(Signature::Secp256r1(signature))
+    }
 }
 
 impl Debug for Signature {
@@ -1030,6 +1202,7 @@ impl Tagged<u8> for Signature {
             Signature::System => SYSTEM_TAG,
             Signature::Ed25519(_) => ED25519_TAG,
             Signature::Secp256k1(_) => SECP256K1_TAG,
+            Signature::Secp256r1(_) => SECP256R1_TAG,
         }
     }
 }
@@ -1047,6 +1220,7 @@ impl ToBytes for Signature {
                 Signature::System => Self::SYSTEM_LENGTH,
                 Signature::Ed25519(_) => Self::ED25519_LENGTH,
                 Signature::Secp256k1(_) => Self::SECP256K1_LENGTH,
+                Signature::Secp256r1(_) => Self::SECP256R1_LENGTH,
             }
     }
 
@@ -1063,6 +1237,10 @@ impl ToBytes for Signature {
                 writer.push(SECP256K1_TAG);
                 writer.extend_from_slice(&signature.to_bytes());
             }
+            Signature::Secp256r1(signature) => {
+                writer.push(SECP256R1_TAG);
+                writer.extend_from_slice(&signature.to_bytes());
+            }
         }
         Ok(())
     }
@@ -1087,6 +1265,13 @@ impl FromBytes for Signature {
                     Self::secp256k1(raw_bytes).map_err(|_error| bytesrepr::Error::Formatting)?;
                 Ok((public_key, remainder))
             }
+            SECP256R1_TAG => {
+                let (raw_bytes, remainder): ([u8; Self::SECP256R1_LENGTH], _) =
+                    FromBytes::from_bytes(remainder)?;
+                let public_key =
+                    Self::secp256r1(raw_bytes).map_err(|_error| bytesrepr::Error::Formatting)?;
+                Ok((public_key, remainder))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -1110,6 +1295,7 @@ impl From<&Signature> for Vec<u8> {
             Signature::System => Vec::new(),
             Signature::Ed25519(signature) => signature.to_bytes().into(),
             Signature::Secp256k1(signature) => (*signature.to_bytes()).into(),
+            Signature::Secp256r1(signature) => (*signature.to_bytes()).into(),
         }
     }
 }
@@ -1157,6 +1343,13 @@ pub fn sign<T: AsRef<[u8]>>(
                 .expect("should create signature");
             Signature::Secp256k1(signature)
         }
+        (SecretKey::Secp256r1(secret_key), PublicKey::Secp256r1(_public_key)) => {
+            let signature: Secp256r1Signature = secret_key
+                .try_sign(message.as_ref())
+                .expect("should create signature");
+            // Only low-S signatures are valid; see `verify`.
+            Signature::Secp256r1(signature.normalize_s().unwrap_or(signature))
+        }
         _ => panic!("secret and public key types must match"),
     }
 }
@@ -1182,6 +1375,20 @@ pub fn verify<T: AsRef<[u8]>>(
                     Error::AsymmetricKey(format!("failed to verify secp256k1 signature: {}", error))
                 })
         }
+        (Signature::Secp256r1(signature), PublicKey::Secp256r1(public_key)) => {
+            // Reject high-S signatures, as `(r, n - s)` is also a valid signature for any valid
+            // `(r, s)` and would otherwise make signatures malleable.
+            if signature.normalize_s().is_some() {
+                return Err(Error::AsymmetricKey(String::from(
+                    "failed to verify secp256r1 signature: signature is not normalized",
+                )));
+            }
+            public_key
+                .verify(message.as_ref(), signature)
+                .map_err(|error| {
+                    Error::AsymmetricKey(format!("failed to verify secp256r1 signature: {}", error))
+                })
+        }
         _ => Err(Error::AsymmetricKey(format!(
             "type mismatch between {} and {}",
             signature, public_key
@@ -1189,6 +1396,107 @@ pub fn verify<T: AsRef<[u8]>>(
     }
 }
 
+/// Verifies a batch of signatures, each against its own message and public key.
+///
+/// Ed25519 signatures are checked together using batch verification, which is considerably
+/// cheaper than checking them one at a time.  Signatures of other types are checked individually
+/// via [`verify`].  If the batch check fails, the Ed25519 signatures are re-checked individually to
+/// identify the invalid one.
+///
+/// On failure, returns the index of the first invalid signature along with the error [`verify`]
+/// would return for it.
+///
+/// The result is the same as calling [`verify`] on every item: only Ed25519 signatures whose `R`
+/// and public key are canonically encoded points of prime order are batched, as for those the
+/// randomized batch equation agrees with the strict check of [`verify`].  All other Ed25519
+/// signatures are checked individually.
+pub fn verify_batch<'a, T, I>(items: I) -> Result<(), (usize, Error)>
+where
+    T: AsRef<[u8]>,
+    I: IntoIterator<Item = (T, &'a Signature, &'a PublicKey)>,
+{
+    let mut first_failure: Option<(usize, Error)> = None;
+    let mut ed25519_indices = Vec::new();
+    let mut ed25519_messages = Vec::new();
+    let mut ed25519_signatures = Vec::new();
+    let mut ed25519_public_keys = Vec::new();
+
+    for (index, (message, signature, public_key)) in items.into_iter().enumerate() {
+        match (signature, public_key) {
+            (Signature::Ed25519(ed25519_signature), PublicKey::Ed25519(ed25519_public_key))
+                if is_batchable(ed25519_signature, ed25519_public_key) =>
+            {
+                ed25519_indices.push(index);
+                ed25519_messages.push(message);
+                ed25519_signatures.push(*ed25519_signature);
+                ed25519_public_keys.push(*ed25519_public_key);
+            }
+            _ => {
+                if first_failure.is_none() {
+                    if let Err(error) = verify(message, signature, public_key) {
+                        first_failure = Some((index, error));
+                    }
+                }
+            }
+        }
+    }
+
+    let messages: Vec<&[u8]> = ed25519_messages.iter().map(AsRef::as_ref).collect();
+    let batch_is_valid = messages.is_empty()
+        || ed25519_dalek::verify_batch(&messages, &ed25519_signatures, &ed25519_public_keys)
+            .is_ok();
+    if !batch_is_valid {
+        let ed25519_failure = ed25519_indices
+            .iter()
+            .zip(messages)
+            .zip(ed25519_signatures.into_iter().zip(ed25519_public_keys))
+            .find_map(|((index, message), (signature, public_key))| {
+                verify(
+                    message,
+                    &Signature::Ed25519(signature),
+                    &PublicKey::Ed25519(public_key),
+                )
+                .err()
+                .map(|error| (*index, error))
+            });
+        if let Some((index, error)) = ed25519_failure {
+            if first_failure
+                .as_ref()
+                .map_or(true, |(first_index, _)| index < *first_index)
+            {
+                first_failure = Some((index, error));
+            }
+        }
+    }
+
+    match first_failure {
+        Some(failure) => Err(failure),
+        None => Ok(()),
+    }
+}
+
+/// Returns `true` if the given Ed25519 signature and public key can be batch verified without
+/// changing the outcome of verifying them individually via [`verify`].
+///
+/// The batch equation weighs each signature by a scalar derived from the batch's inputs, which
+/// can cancel out small-order components of `R` and the public key that the strict check would
+/// reject, and an attacker can grind the inputs until it does.  Requiring both points to be
+/// torsion-free and not of small order, and `R` to be canonically encoded (as the strict check
+/// compares encodings), makes the two checks equivalent.
+fn is_batchable(signature: &Ed25519Signature, public_key: &Ed25519PublicKey) -> bool {
+    let is_prime_order = |point: &EdwardsPoint| !point.is_small_order() && point.is_torsion_free();
+    let r_bytes = signature.r_bytes();
+    let signature_r = match CompressedEdwardsY(*r_bytes).decompress() {
+        Some(point) if point.compress().as_bytes() == r_bytes => point,
+        _ => return false,
+    };
+    let public_key_point = match CompressedEdwardsY(public_key.to_bytes()).decompress() {
+        Some(point) => point,
+        None => return false,
+    };
+    is_prime_order(&signature_r) && is_prime_order(&public_key_point)
+}
+
 /// Generates an Ed25519 keypair using the operating system's cryptographically secure random number
 /// generator.
 #[cfg(any(feature = "std", test))]
@@ -1215,6 +1523,7 @@ mod detail {
         System,
         Ed25519(Vec<u8>),
         Secp256k1(Vec<u8>),
+        Secp256r1(Vec<u8>),
     }
 
     impl From<&PublicKey> for AsymmetricTypeAsBytes {
@@ -1223,6 +1532,7 @@ mod detail {
                 PublicKey::System => AsymmetricTypeAsBytes::System,
                 key @ PublicKey::Ed25519(_) => AsymmetricTypeAsBytes::Ed25519(key.into()),
                 key @ PublicKey::Secp256k1(_) => AsymmetricTypeAsBytes::Secp256k1(key.into()),
+                key @ PublicKey::Secp256r1(_) => AsymmetricTypeAsBytes::Secp256r1(key.into()),
             }
         }
     }
@@ -1233,6 +1543,7 @@ mod detail {
                 Signature::System => AsymmetricTypeAsBytes::System,
                 key @ Signature::Ed25519(_) => AsymmetricTypeAsBytes::Ed25519(key.into()),
                 key @ Signature::Secp256k1(_) => AsymmetricTypeAsBytes::Secp256k1(key.into()),
+                key @ Signature::Secp256r1(_) => AsymmetricTypeAsBytes::Secp256r1(key.into()),
             }
         }
     }
@@ -1272,6 +1583,9 @@ mod detail {
             AsymmetricTypeAsBytes::Secp256k1(raw_bytes) => {
                 T::secp256k1_from_bytes(raw_bytes).map_err(D::Error::custom)
             }
+            AsymmetricTypeAsBytes::Secp256r1(raw_bytes) => {
+                T::secp256r1_from_bytes(raw_bytes).map_err(D::Error::custom)
+            }
         }
     }
 }
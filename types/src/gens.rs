@@ -19,8 +19,8 @@ use crate::{
     },
     crypto::gens::public_key_arb_no_system,
     system::auction::{
-        gens::era_info_arb, Bid, DelegationRate, Delegator, UnbondingPurse, WithdrawPurse,
-        DELEGATION_RATE_DENOMINATOR,
+        gens::era_info_arb, Bid, DelegationRate, Delegator, RewardPolicy, UnbondingPurse,
+        WithdrawPurse, DELEGATION_RATE_DENOMINATOR,
     },
     transfer::TransferAddr,
     AccessRights, CLType, CLValue, Contract, ContractHash, ContractPackage, ContractVersionKey,
@@ -384,16 +384,24 @@ pub fn contract_package_arb() -> impl Strategy<Value = ContractPackage> {
         })
 }
 
+fn reward_policy_arb() -> impl Strategy<Value = RewardPolicy> {
+    prop_oneof![Just(RewardPolicy::Compound), Just(RewardPolicy::Liquid)]
+}
+
 fn delegator_arb() -> impl Strategy<Value = Delegator> {
     (
         public_key_arb_no_system(),
         u512_arb(),
         uref_arb(),
         public_key_arb_no_system(),
+        reward_policy_arb(),
     )
         .prop_map(
-            |(delegator_pk, staked_amount, bonding_purse, validator_pk)| {
-                Delegator::unlocked(delegator_pk, staked_amount, bonding_purse, validator_pk)
+            |(delegator_pk, staked_amount, bonding_purse, validator_pk, reward_policy)| {
+                let mut delegator =
+                    Delegator::unlocked(delegator_pk, staked_amount, bonding_purse, validator_pk);
+                delegator.with_reward_policy(reward_policy);
+                delegator
             },
         )
 }
@@ -410,6 +418,8 @@ pub(crate) fn bid_arb(delegations_len: impl Into<SizeRange>) -> impl Strategy<Va
         delegation_rate_arb(),
         bool::ANY,
         collection::vec(delegator_arb(), delegations_len),
+        reward_policy_arb(),
+        option::of(u512_arb()),
     )
         .prop_map(
             |(
@@ -419,6 +429,8 @@ pub(crate) fn bid_arb(delegations_len: impl Into<SizeRange>) -> impl Strategy<Va
                 delegation_rate,
                 is_locked,
                 new_delegators,
+                reward_policy,
+                maximum_delegated_stake,
             )| {
                 let mut bid = if is_locked {
                     Bid::locked(
@@ -436,6 +448,9 @@ pub(crate) fn bid_arb(delegations_len: impl Into<SizeRange>) -> impl Strategy<Va
                         delegation_rate,
                     )
                 };
+                bid.with_reward_policy(reward_policy);
+                bid.with_delegation_limits(None, maximum_delegated_stake)
+                    .expect("should set delegation limits without a minimum");
                 let delegators = bid.delegators_mut();
                 new_delegators.into_iter().for_each(|delegator| {
                     assert!(delegators
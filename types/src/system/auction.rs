@@ -5,6 +5,7 @@ mod delegator;
 mod entry_points;
 mod era_info;
 mod error;
+mod reward_policy;
 mod seigniorage_recipient;
 mod unbonding_purse;
 mod withdraw_purse;
@@ -17,6 +18,7 @@ pub use delegator::Delegator;
 pub use entry_points::auction_entry_points;
 pub use era_info::{EraInfo, SeigniorageAllocation};
 pub use error::Error;
+pub use reward_policy::RewardPolicy;
 pub use seigniorage_recipient::SeigniorageRecipient;
 pub use unbonding_purse::UnbondingPurse;
 pub use withdraw_purse::WithdrawPurse;
@@ -28,6 +30,11 @@ pub(crate) mod gens {
 
 use crate::{account::AccountHash, EraId, PublicKey, U512};
 
+/// Leading byte of the versioned serialized forms of [`Bid`] and [`Delegator`].
+///
+/// The legacy serialized forms start with the tag of a [`PublicKey`], which never takes this value.
+const VERSIONED_ENCODING_TAG: u8 = u8::MAX;
+
 /// Representation of delegation rate of tokens. Range from 0..=100.
 pub type DelegationRate = u8;
 
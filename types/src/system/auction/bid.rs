@@ -13,12 +13,18 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
-    system::auction::{DelegationRate, Delegator, Error},
+    system::auction::{DelegationRate, Delegator, Error, RewardPolicy, VERSIONED_ENCODING_TAG},
     CLType, CLTyped, PublicKey, URef, U512,
 };
 
 pub use vesting::{VestingSchedule, VESTING_SCHEDULE_LENGTH_MILLIS};
 
+/// Version of the serialized form of a [`Bid`] which is used if the bid cannot be represented in
+/// the legacy serialized form.
+///
+/// Version 1 appends the reward policy, version 2 additionally appends the delegation limits.
+const BID_VERSION: u8 = 2;
+
 /// An entry in the validator map.
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -39,6 +45,16 @@ pub struct Bid {
     delegators: BTreeMap<PublicKey, Delegator>,
     /// `true` if validator has been "evicted"
     inactive: bool,
+    /// How the validator's rewards are paid out.
+    #[serde(default)]
+    reward_policy: RewardPolicy,
+    /// Minimum amount which can be delegated to this validator at once, on top of the global
+    /// minimum.
+    #[serde(default)]
+    minimum_delegation_amount: Option<U512>,
+    /// Maximum total amount which can be delegated to this validator.
+    #[serde(default)]
+    maximum_delegated_stake: Option<U512>,
 }
 
 impl Bid {
@@ -53,6 +69,7 @@ impl Bid {
         let vesting_schedule = Some(VestingSchedule::new(release_timestamp_millis));
         let delegators = BTreeMap::new();
         let inactive = false;
+        let reward_policy = RewardPolicy::default();
         Self {
             validator_public_key,
             bonding_purse,
@@ -61,6 +78,9 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            reward_policy,
+            minimum_delegation_amount: None,
+            maximum_delegated_stake: None,
         }
     }
 
@@ -74,6 +94,7 @@ impl Bid {
         let vesting_schedule = None;
         let delegators = BTreeMap::new();
         let inactive = false;
+        let reward_policy = RewardPolicy::default();
         Self {
             validator_public_key,
             bonding_purse,
@@ -82,6 +103,9 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            reward_policy,
+            minimum_delegation_amount: None,
+            maximum_delegated_stake: None,
         }
     }
 
@@ -92,6 +116,7 @@ impl Bid {
         let inactive = true;
         let staked_amount = 0.into();
         let delegation_rate = Default::default();
+        let reward_policy = RewardPolicy::default();
         Self {
             validator_public_key,
             bonding_purse,
@@ -100,6 +125,9 @@ impl Bid {
             vesting_schedule,
             delegators,
             inactive,
+            reward_policy,
+            minimum_delegation_amount: None,
+            maximum_delegated_stake: None,
         }
     }
 
@@ -180,6 +208,68 @@ impl Bid {
         self.inactive
     }
 
+    /// Returns the policy applied to the validator's rewards.
+    pub fn reward_policy(&self) -> RewardPolicy {
+        self.reward_policy
+    }
+
+    /// Updates the policy applied to the validator's rewards.
+    pub fn with_reward_policy(&mut self, reward_policy: RewardPolicy) -> &mut Self {
+        self.reward_policy = reward_policy;
+        self
+    }
+
+    /// Returns the minimum amount which can be delegated to the validator at once.
+    pub fn minimum_delegation_amount(&self) -> Option<U512> {
+        self.minimum_delegation_amount
+    }
+
+    /// Returns the maximum total amount which can be delegated to the validator.
+    pub fn maximum_delegated_stake(&self) -> Option<U512> {
+        self.maximum_delegated_stake
+    }
+
+    /// Updates the delegation limits of the provided bid.
+    ///
+    /// Returns an error if the minimum delegation amount exceeds the maximum delegated stake.
+    pub fn with_delegation_limits(
+        &mut self,
+        minimum_delegation_amount: Option<U512>,
+        maximum_delegated_stake: Option<U512>,
+    ) -> Result<&mut Self, Error> {
+        if let (Some(minimum), Some(maximum)) = (minimum_delegation_amount, maximum_delegated_stake)
+        {
+            if minimum > maximum {
+                return Err(Error::InvalidDelegationLimits);
+            }
+        }
+        self.minimum_delegation_amount = minimum_delegation_amount;
+        self.maximum_delegated_stake = maximum_delegated_stake;
+        Ok(self)
+    }
+
+    /// Checks that delegating `amount` to the validator respects its delegation limits.
+    pub fn check_delegation_limits(&self, amount: U512) -> Result<(), Error> {
+        if let Some(minimum_delegation_amount) = self.minimum_delegation_amount {
+            if amount < minimum_delegation_amount {
+                return Err(Error::DelegationAmountBelowValidatorMinimum);
+            }
+        }
+        if let Some(maximum_delegated_stake) = self.maximum_delegated_stake {
+            let delegated_stake = self
+                .delegators
+                .values()
+                .try_fold(amount, |sum, delegator| {
+                    sum.checked_add(*delegator.staked_amount())
+                })
+                .ok_or(Error::InvalidAmount)?;
+            if delegated_stake > maximum_delegated_stake {
+                return Err(Error::ExceededValidatorDelegationCap);
+            }
+        }
+        Ok(())
+    }
+
     /// Decreases the stake of the provided bid
     pub fn decrease_stake(
         &mut self,
@@ -304,6 +394,13 @@ impl Bid {
             .and_then(|delegators_sum| delegators_sum.checked_add(*self.staked_amount()))
             .ok_or(Error::InvalidAmount)
     }
+
+    /// Returns `true` if the bid can be represented in the legacy serialized form.
+    fn is_legacy(&self) -> bool {
+        self.reward_policy == RewardPolicy::default()
+            && self.minimum_delegation_amount.is_none()
+            && self.maximum_delegated_stake.is_none()
+    }
 }
 
 impl CLTyped for Bid {
@@ -315,27 +412,35 @@ impl CLTyped for Bid {
 impl ToBytes for Bid {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
-        self.validator_public_key.write_bytes(&mut result)?;
-        self.bonding_purse.write_bytes(&mut result)?;
-        self.staked_amount.write_bytes(&mut result)?;
-        self.delegation_rate.write_bytes(&mut result)?;
-        self.vesting_schedule.write_bytes(&mut result)?;
-        self.delegators().write_bytes(&mut result)?;
-        self.inactive.write_bytes(&mut result)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
-        self.validator_public_key.serialized_length()
+        let legacy_length = self.validator_public_key.serialized_length()
             + self.bonding_purse.serialized_length()
             + self.staked_amount.serialized_length()
             + self.delegation_rate.serialized_length()
             + self.vesting_schedule.serialized_length()
             + self.delegators.serialized_length()
-            + self.inactive.serialized_length()
+            + self.inactive.serialized_length();
+        if self.is_legacy() {
+            return legacy_length;
+        }
+        VERSIONED_ENCODING_TAG.serialized_length()
+            + BID_VERSION.serialized_length()
+            + legacy_length
+            + self.reward_policy.serialized_length()
+            + self.minimum_delegation_amount.serialized_length()
+            + self.maximum_delegated_stake.serialized_length()
     }
 
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        let is_legacy = self.is_legacy();
+        if !is_legacy {
+            VERSIONED_ENCODING_TAG.write_bytes(writer)?;
+            BID_VERSION.write_bytes(writer)?;
+        }
         self.validator_public_key.write_bytes(writer)?;
         self.bonding_purse.write_bytes(writer)?;
         self.staked_amount.write_bytes(writer)?;
@@ -343,12 +448,27 @@ impl ToBytes for Bid {
         self.vesting_schedule.write_bytes(writer)?;
         self.delegators().write_bytes(writer)?;
         self.inactive.write_bytes(writer)?;
+        if !is_legacy {
+            self.reward_policy.write_bytes(writer)?;
+            self.minimum_delegation_amount.write_bytes(writer)?;
+            self.maximum_delegated_stake.write_bytes(writer)?;
+        }
         Ok(())
     }
 }
 
 impl FromBytes for Bid {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, bytes) = match bytes.split_first() {
+            Some((&VERSIONED_ENCODING_TAG, remainder)) => {
+                let (version, remainder) = u8::from_bytes(remainder)?;
+                if version == 0 || version > BID_VERSION {
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                (version, remainder)
+            }
+            _ => (0, bytes),
+        };
         let (validator_public_key, bytes) = FromBytes::from_bytes(bytes)?;
         let (bonding_purse, bytes) = FromBytes::from_bytes(bytes)?;
         let (staked_amount, bytes) = FromBytes::from_bytes(bytes)?;
@@ -356,6 +476,18 @@ impl FromBytes for Bid {
         let (vesting_schedule, bytes) = FromBytes::from_bytes(bytes)?;
         let (delegators, bytes) = FromBytes::from_bytes(bytes)?;
         let (inactive, bytes) = FromBytes::from_bytes(bytes)?;
+        let (reward_policy, bytes) = if version >= 1 {
+            RewardPolicy::from_bytes(bytes)?
+        } else {
+            (RewardPolicy::default(), bytes)
+        };
+        let (minimum_delegation_amount, maximum_delegated_stake, bytes) = if version >= 2 {
+            let (minimum_delegation_amount, bytes) = FromBytes::from_bytes(bytes)?;
+            let (maximum_delegated_stake, bytes) = FromBytes::from_bytes(bytes)?;
+            (minimum_delegation_amount, maximum_delegated_stake, bytes)
+        } else {
+            (None, None, bytes)
+        };
         Ok((
             Bid {
                 validator_public_key,
@@ -365,6 +497,9 @@ impl FromBytes for Bid {
                 vesting_schedule,
                 delegators,
                 inactive,
+                reward_policy,
+                minimum_delegation_amount,
+                maximum_delegated_stake,
             },
             bytes,
         ))
@@ -376,8 +511,11 @@ mod tests {
     use alloc::collections::BTreeMap;
 
     use crate::{
-        bytesrepr,
-        system::auction::{bid::VestingSchedule, Bid, DelegationRate, Delegator},
+        bytesrepr::{self, ToBytes},
+        system::auction::{
+            bid::VestingSchedule, Bid, DelegationRate, Delegator, Error, RewardPolicy,
+            VERSIONED_ENCODING_TAG,
+        },
         AccessRights, PublicKey, SecretKey, URef, U512,
     };
 
@@ -396,10 +534,109 @@ mod tests {
             vesting_schedule: Some(VestingSchedule::default()),
             delegators: BTreeMap::default(),
             inactive: true,
+            reward_policy: RewardPolicy::default(),
+            minimum_delegation_amount: None,
+            maximum_delegated_stake: None,
         };
         bytesrepr::test_serialization_roundtrip(&founding_validator);
     }
 
+    #[test]
+    fn should_only_version_non_default_reward_policy() {
+        let validator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([42; 32]).unwrap()).into();
+        let delegator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let mut delegator = Delegator::unlocked(
+            delegator_public_key.clone(),
+            U512::from(2000),
+            URef::new([52; 32], AccessRights::ADD),
+            validator_public_key.clone(),
+        );
+        delegator.with_reward_policy(RewardPolicy::Liquid);
+
+        let mut bid = Bid::unlocked(
+            validator_public_key.clone(),
+            URef::new([42; 32], AccessRights::ADD),
+            U512::from(1000),
+            0,
+        );
+        bid.delegators_mut().insert(delegator_public_key, delegator);
+        let legacy_bytes = bid.to_bytes().unwrap();
+        assert!(legacy_bytes.starts_with(&validator_public_key.to_bytes().unwrap()));
+        bytesrepr::test_serialization_roundtrip(&bid);
+
+        bid.with_reward_policy(RewardPolicy::Liquid);
+        let versioned_bytes = bid.to_bytes().unwrap();
+        assert_eq!(versioned_bytes.len(), legacy_bytes.len() + 5);
+        bytesrepr::test_serialization_roundtrip(&bid);
+
+        bid.with_delegation_limits(Some(U512::from(10)), Some(U512::from(5000)))
+            .unwrap();
+        bytesrepr::test_serialization_roundtrip(&bid);
+    }
+
+    #[test]
+    fn should_read_version_1_bids() {
+        let validator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([42; 32]).unwrap()).into();
+        let bid = Bid::unlocked(
+            validator_public_key,
+            URef::new([42; 32], AccessRights::ADD),
+            U512::from(1000),
+            0,
+        );
+        let mut version_1_bytes = vec![VERSIONED_ENCODING_TAG, 1];
+        version_1_bytes.extend(bid.to_bytes().unwrap());
+        version_1_bytes.extend(RewardPolicy::Liquid.to_bytes().unwrap());
+
+        let version_1_bid: Bid = bytesrepr::deserialize(version_1_bytes).unwrap();
+        assert_eq!(version_1_bid.reward_policy(), RewardPolicy::Liquid);
+        assert_eq!(version_1_bid.minimum_delegation_amount(), None);
+        assert_eq!(version_1_bid.maximum_delegated_stake(), None);
+    }
+
+    #[test]
+    fn should_check_delegation_limits() {
+        let validator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([42; 32]).unwrap()).into();
+        let delegator_public_key: PublicKey =
+            (&SecretKey::ed25519_from_bytes([43; 32]).unwrap()).into();
+        let mut bid = Bid::unlocked(
+            validator_public_key.clone(),
+            URef::new([42; 32], AccessRights::ADD),
+            U512::from(1000),
+            0,
+        );
+        let delegator = Delegator::unlocked(
+            delegator_public_key.clone(),
+            U512::from(300),
+            URef::new([52; 32], AccessRights::ADD),
+            validator_public_key,
+        );
+        bid.delegators_mut().insert(delegator_public_key, delegator);
+        assert_eq!(bid.check_delegation_limits(U512::one()), Ok(()));
+
+        assert_eq!(
+            bid.with_delegation_limits(Some(U512::from(501)), Some(U512::from(500)))
+                .map(|_| ()),
+            Err(Error::InvalidDelegationLimits)
+        );
+
+        bid.with_delegation_limits(Some(U512::from(100)), Some(U512::from(500)))
+            .unwrap();
+        assert_eq!(
+            bid.check_delegation_limits(U512::from(99)),
+            Err(Error::DelegationAmountBelowValidatorMinimum)
+        );
+        assert_eq!(bid.check_delegation_limits(U512::from(100)), Ok(()));
+        assert_eq!(bid.check_delegation_limits(U512::from(200)), Ok(()));
+        assert_eq!(
+            bid.check_delegation_limits(U512::from(201)),
+            Err(Error::ExceededValidatorDelegationCap)
+        );
+    }
+
     #[test]
     fn should_immediately_initialize_unlock_amounts() {
         const TIMESTAMP_MILLIS: u64 = 0;
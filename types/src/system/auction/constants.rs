@@ -58,6 +58,12 @@ pub const ARG_UNBONDING_DELAY: &str = "unbonding_delay";
 pub const ARG_ERA_END_TIMESTAMP_MILLIS: &str = "era_end_timestamp_millis";
 /// Named constant for `evicted_validators`;
 pub const ARG_EVICTED_VALIDATORS: &str = "evicted_validators";
+/// Named constant for `reward_policy`.
+pub const ARG_REWARD_POLICY: &str = "reward_policy";
+/// Named constant for `minimum_delegation_amount`.
+pub const ARG_MINIMUM_DELEGATION_AMOUNT: &str = "minimum_delegation_amount";
+/// Named constant for `maximum_delegated_stake`.
+pub const ARG_MAXIMUM_DELEGATED_STAKE: &str = "maximum_delegated_stake";
 
 /// Named constant for method `get_era_validators`.
 pub const METHOD_GET_ERA_VALIDATORS: &str = "get_era_validators";
@@ -81,6 +87,12 @@ pub const METHOD_DISTRIBUTE: &str = "distribute";
 pub const METHOD_READ_ERA_ID: &str = "read_era_id";
 /// Named constant for method `activate_bid`.
 pub const METHOD_ACTIVATE_BID: &str = "activate_bid";
+/// Named constant for method `set_reward_policy`.
+pub const METHOD_SET_REWARD_POLICY: &str = "set_reward_policy";
+/// Named constant for method `set_delegator_reward_policy`.
+pub const METHOD_SET_DELEGATOR_REWARD_POLICY: &str = "set_delegator_reward_policy";
+/// Named constant for method `set_delegation_limits`.
+pub const METHOD_SET_DELEGATION_LIMITS: &str = "set_delegation_limits";
 
 /// Storage for `EraId`.
 pub const ERA_ID_KEY: &str = "era_id";
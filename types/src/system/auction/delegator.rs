@@ -11,10 +11,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes},
-    system::auction::{bid::VestingSchedule, Error},
+    system::auction::{bid::VestingSchedule, Error, RewardPolicy, VERSIONED_ENCODING_TAG},
     CLType, CLTyped, PublicKey, URef, U512,
 };
 
+/// Version of the serialized form of a [`Delegator`] which is used if the delegator cannot be
+/// represented in the legacy serialized form.
+const DELEGATOR_VERSION: u8 = 1;
+
 /// Represents a party delegating their stake to a validator (or "delegatee")
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -26,6 +30,8 @@ pub struct Delegator {
     bonding_purse: URef,
     validator_public_key: PublicKey,
     vesting_schedule: Option<VestingSchedule>,
+    #[serde(default)]
+    reward_policy: RewardPolicy,
 }
 
 impl Delegator {
@@ -37,12 +43,14 @@ impl Delegator {
         validator_public_key: PublicKey,
     ) -> Self {
         let vesting_schedule = None;
+        let reward_policy = RewardPolicy::default();
         Delegator {
             delegator_public_key,
             staked_amount,
             bonding_purse,
             validator_public_key,
             vesting_schedule,
+            reward_policy,
         }
     }
 
@@ -55,12 +63,14 @@ impl Delegator {
         release_timestamp_millis: u64,
     ) -> Self {
         let vesting_schedule = Some(VestingSchedule::new(release_timestamp_millis));
+        let reward_policy = RewardPolicy::default();
         Delegator {
             delegator_public_key,
             staked_amount,
             bonding_purse,
             validator_public_key,
             vesting_schedule,
+            reward_policy,
         }
     }
 
@@ -147,6 +157,22 @@ impl Delegator {
     pub fn vesting_schedule_mut(&mut self) -> Option<&mut VestingSchedule> {
         self.vesting_schedule.as_mut()
     }
+
+    /// Returns the policy applied to the rewards of the delegator.
+    pub fn reward_policy(&self) -> RewardPolicy {
+        self.reward_policy
+    }
+
+    /// Updates the policy applied to the rewards of the delegator.
+    pub fn with_reward_policy(&mut self, reward_policy: RewardPolicy) -> &mut Self {
+        self.reward_policy = reward_policy;
+        self
+    }
+
+    /// Returns `true` if the delegator can be represented in the legacy serialized form.
+    fn is_legacy(&self) -> bool {
+        self.reward_policy == RewardPolicy::default()
+    }
 }
 
 impl CLTyped for Delegator {
@@ -158,39 +184,65 @@ impl CLTyped for Delegator {
 impl ToBytes for Delegator {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
-        buffer.extend(self.delegator_public_key.to_bytes()?);
-        buffer.extend(self.staked_amount.to_bytes()?);
-        buffer.extend(self.bonding_purse.to_bytes()?);
-        buffer.extend(self.validator_public_key.to_bytes()?);
-        buffer.extend(self.vesting_schedule.to_bytes()?);
+        self.write_bytes(&mut buffer)?;
         Ok(buffer)
     }
 
     fn serialized_length(&self) -> usize {
-        self.delegator_public_key.serialized_length()
+        let legacy_length = self.delegator_public_key.serialized_length()
             + self.staked_amount.serialized_length()
             + self.bonding_purse.serialized_length()
             + self.validator_public_key.serialized_length()
-            + self.vesting_schedule.serialized_length()
+            + self.vesting_schedule.serialized_length();
+        if self.is_legacy() {
+            return legacy_length;
+        }
+        VERSIONED_ENCODING_TAG.serialized_length()
+            + DELEGATOR_VERSION.serialized_length()
+            + legacy_length
+            + self.reward_policy.serialized_length()
     }
 
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        let is_legacy = self.is_legacy();
+        if !is_legacy {
+            VERSIONED_ENCODING_TAG.write_bytes(writer)?;
+            DELEGATOR_VERSION.write_bytes(writer)?;
+        }
         self.delegator_public_key.write_bytes(writer)?;
         self.staked_amount.write_bytes(writer)?;
         self.bonding_purse.write_bytes(writer)?;
         self.validator_public_key.write_bytes(writer)?;
         self.vesting_schedule.write_bytes(writer)?;
+        if !is_legacy {
+            self.reward_policy.write_bytes(writer)?;
+        }
         Ok(())
     }
 }
 
 impl FromBytes for Delegator {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, bytes) = match bytes.split_first() {
+            Some((&VERSIONED_ENCODING_TAG, remainder)) => {
+                let (version, remainder) = u8::from_bytes(remainder)?;
+                if version == 0 || version > DELEGATOR_VERSION {
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                (version, remainder)
+            }
+            _ => (0, bytes),
+        };
         let (delegator_public_key, bytes) = PublicKey::from_bytes(bytes)?;
         let (staked_amount, bytes) = U512::from_bytes(bytes)?;
         let (bonding_purse, bytes) = URef::from_bytes(bytes)?;
         let (validator_public_key, bytes) = PublicKey::from_bytes(bytes)?;
         let (vesting_schedule, bytes) = FromBytes::from_bytes(bytes)?;
+        let (reward_policy, bytes) = if version >= 1 {
+            RewardPolicy::from_bytes(bytes)?
+        } else {
+            (RewardPolicy::default(), bytes)
+        };
         Ok((
             Delegator {
                 delegator_public_key,
@@ -198,6 +250,7 @@ impl FromBytes for Delegator {
                 bonding_purse,
                 validator_public_key,
                 vesting_schedule,
+                reward_policy,
             },
             bytes,
         ))
@@ -207,7 +260,9 @@ impl FromBytes for Delegator {
 #[cfg(test)]
 mod tests {
     use crate::{
-        bytesrepr, system::auction::Delegator, AccessRights, PublicKey, SecretKey, URef, U512,
+        bytesrepr::{self, ToBytes},
+        system::auction::{Delegator, RewardPolicy},
+        AccessRights, PublicKey, SecretKey, URef, U512,
     };
 
     #[test]
@@ -239,4 +294,32 @@ mod tests {
         );
         bytesrepr::test_serialization_roundtrip(&locked_delegator);
     }
+
+    #[test]
+    fn should_only_version_non_default_reward_policy() {
+        let delegator_public_key: PublicKey = PublicKey::from(
+            &SecretKey::ed25519_from_bytes([42; SecretKey::ED25519_LENGTH]).unwrap(),
+        );
+        let validator_public_key: PublicKey = PublicKey::from(
+            &SecretKey::ed25519_from_bytes([43; SecretKey::ED25519_LENGTH]).unwrap(),
+        );
+        let mut delegator = Delegator::unlocked(
+            delegator_public_key.clone(),
+            U512::one(),
+            URef::new([42; 32], AccessRights::READ_ADD_WRITE),
+            validator_public_key,
+        );
+        let legacy_bytes = delegator.to_bytes().unwrap();
+        assert!(legacy_bytes.starts_with(&delegator_public_key.to_bytes().unwrap()));
+
+        delegator.with_reward_policy(RewardPolicy::Liquid);
+        let versioned_bytes = delegator.to_bytes().unwrap();
+        assert_eq!(versioned_bytes.len(), legacy_bytes.len() + 3);
+        bytesrepr::test_serialization_roundtrip(&delegator);
+
+        // Unknown versions are rejected.
+        let mut future_bytes = versioned_bytes;
+        future_bytes[1] = u8::MAX;
+        assert!(bytesrepr::deserialize::<Delegator>(future_bytes).is_err());
+    }
 }
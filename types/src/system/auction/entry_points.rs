@@ -2,12 +2,14 @@ use alloc::boxed::Box;
 
 use crate::{
     system::auction::{
-        DelegationRate, ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
-        ARG_ERA_END_TIMESTAMP_MILLIS, ARG_NEW_VALIDATOR, ARG_PUBLIC_KEY, ARG_REWARD_FACTORS,
-        ARG_VALIDATOR, ARG_VALIDATOR_PUBLIC_KEY, METHOD_ACTIVATE_BID, METHOD_ADD_BID,
-        METHOD_DELEGATE, METHOD_DISTRIBUTE, METHOD_GET_ERA_VALIDATORS, METHOD_READ_ERA_ID,
-        METHOD_REDELEGATE, METHOD_RUN_AUCTION, METHOD_SLASH, METHOD_UNDELEGATE,
-        METHOD_WITHDRAW_BID,
+        DelegationRate, RewardPolicy, ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE,
+        ARG_DELEGATOR, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_MAXIMUM_DELEGATED_STAKE,
+        ARG_MINIMUM_DELEGATION_AMOUNT, ARG_NEW_VALIDATOR, ARG_PUBLIC_KEY, ARG_REWARD_FACTORS,
+        ARG_REWARD_POLICY, ARG_VALIDATOR, ARG_VALIDATOR_PUBLIC_KEY, METHOD_ACTIVATE_BID,
+        METHOD_ADD_BID, METHOD_DELEGATE, METHOD_DISTRIBUTE, METHOD_GET_ERA_VALIDATORS,
+        METHOD_READ_ERA_ID, METHOD_REDELEGATE, METHOD_RUN_AUCTION, METHOD_SET_DELEGATION_LIMITS,
+        METHOD_SET_DELEGATOR_REWARD_POLICY, METHOD_SET_REWARD_POLICY, METHOD_SLASH,
+        METHOD_UNDELEGATE, METHOD_WITHDRAW_BID,
     },
     CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
     PublicKey, U512,
@@ -142,5 +144,43 @@ pub fn auction_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_SET_REWARD_POLICY,
+        vec![
+            Parameter::new(ARG_PUBLIC_KEY, PublicKey::cl_type()),
+            Parameter::new(ARG_REWARD_POLICY, RewardPolicy::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_SET_DELEGATOR_REWARD_POLICY,
+        vec![
+            Parameter::new(ARG_DELEGATOR, PublicKey::cl_type()),
+            Parameter::new(ARG_VALIDATOR, PublicKey::cl_type()),
+            Parameter::new(ARG_REWARD_POLICY, RewardPolicy::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_SET_DELEGATION_LIMITS,
+        vec![
+            Parameter::new(ARG_PUBLIC_KEY, PublicKey::cl_type()),
+            Parameter::new(ARG_MINIMUM_DELEGATION_AMOUNT, Option::<U512>::cl_type()),
+            Parameter::new(ARG_MAXIMUM_DELEGATED_STAKE, Option::<U512>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     entry_points
 }
@@ -327,6 +327,31 @@ pub enum Error {
     /// assert_eq!(49, Error::TransferToAdministrator as u8);
     /// ```
     TransferToAdministrator = 49,
+    /// The delegated amount is below the minimum delegation amount set by the validator.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(50, Error::DelegationAmountBelowValidatorMinimum as u8);
+    /// ```
+    DelegationAmountBelowValidatorMinimum = 50,
+    /// The delegation would raise the total delegated stake above the maximum set by the
+    /// validator.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(51, Error::ExceededValidatorDelegationCap as u8);
+    /// ```
+    ExceededValidatorDelegationCap = 51,
+    /// The minimum delegation amount set by a validator exceeds the maximum delegated stake.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(52, Error::InvalidDelegationLimits as u8);
+    /// ```
+    InvalidDelegationLimits = 52,
+    /// A public key passed to the auction uses a key type which is not enabled.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(53, Error::PublicKeyTypeNotAllowed as u8);
+    /// ```
+    PublicKeyTypeNotAllowed = 53,
 }
 
 impl Display for Error {
@@ -382,6 +407,10 @@ impl Display for Error {
             Error::AuctionBidsDisabled => formatter.write_str("Auction bids are disabled"),
             Error::GetAccumulationPurse => formatter.write_str("Get accumulation purse error"),
             Error::TransferToAdministrator => formatter.write_str("Transfer to administrator error"),
+            Error::DelegationAmountBelowValidatorMinimum => formatter.write_str("The delegated amount is below the minimum set by the validator"),
+            Error::ExceededValidatorDelegationCap => formatter.write_str("The total delegated stake would exceed the maximum set by the validator"),
+            Error::InvalidDelegationLimits => formatter.write_str("The minimum delegation amount exceeds the maximum delegated stake"),
+            Error::PublicKeyTypeNotAllowed => formatter.write_str("Public key type not allowed"),
         }
     }
 }
@@ -463,6 +492,14 @@ impl TryFrom<u8> for Error {
             d if d == Error::AuctionBidsDisabled as u8 => Ok(Error::AuctionBidsDisabled),
             d if d == Error::GetAccumulationPurse as u8 => Ok(Error::GetAccumulationPurse),
             d if d == Error::TransferToAdministrator as u8 => Ok(Error::TransferToAdministrator),
+            d if d == Error::DelegationAmountBelowValidatorMinimum as u8 => {
+                Ok(Error::DelegationAmountBelowValidatorMinimum)
+            }
+            d if d == Error::ExceededValidatorDelegationCap as u8 => {
+                Ok(Error::ExceededValidatorDelegationCap)
+            }
+            d if d == Error::InvalidDelegationLimits as u8 => Ok(Error::InvalidDelegationLimits),
+            d if d == Error::PublicKeyTypeNotAllowed as u8 => Ok(Error::PublicKeyTypeNotAllowed),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::{FromPrimitive, ToPrimitive};
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// How the seigniorage rewards of a validator or delegator are paid out by `distribute`.
+#[derive(
+    Debug, Default, PartialEq, Eq, Clone, Copy, FromPrimitive, ToPrimitive, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[repr(u8)]
+pub enum RewardPolicy {
+    /// Rewards are added to the stake.
+    #[default]
+    Compound = 0,
+    /// Rewards are paid into the main purse of the account and the stake is left unchanged.
+    Liquid = 1,
+}
+
+impl CLTyped for RewardPolicy {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for RewardPolicy {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        // NOTE: Assumed safe as [`RewardPolicy`] is represented as u8.
+        let id = self.to_u8().expect("RewardPolicy is represented as a u8");
+        id.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for RewardPolicy {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (id, rest) = u8::from_bytes(bytes)?;
+        let reward_policy = FromPrimitive::from_u8(id).ok_or(bytesrepr::Error::Formatting)?;
+        Ok((reward_policy, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bytesrepr, system::auction::RewardPolicy};
+
+    #[test]
+    fn serialization_roundtrip() {
+        bytesrepr::test_serialization_roundtrip(&RewardPolicy::Compound);
+        bytesrepr::test_serialization_roundtrip(&RewardPolicy::Liquid);
+    }
+
+    #[test]
+    fn should_reject_unknown_tag() {
+        assert_eq!(
+            bytesrepr::deserialize::<RewardPolicy>(vec![2]),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
+}
@@ -2,6 +2,9 @@ use alloc::vec::Vec;
 
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     account::AccountHash,
@@ -22,7 +25,8 @@ pub enum CallStackElementTag {
 }
 
 /// Represents the origin of a sub-call.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 pub enum CallStackElement {
     /// Session
     Session {
@@ -0,0 +1,312 @@
+//! Static export of the unit DAG to an SVG or PNG image, without opening a window.
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Write},
+    fs,
+    ops::RangeInclusive,
+    path::Path,
+};
+
+use resvg::{
+    tiny_skia,
+    usvg::{self, fontdb, TreeParsing, TreeTextToPath},
+};
+
+use crate::{
+    renderer::{CORNER_RADIUS, FONT_FILE, UNIT_HEIGHT, UNIT_WIDTH},
+    Graph, GraphUnit, UnitId,
+};
+
+/// The number of pixels per unit of scene coordinates, i.e. the distance between neighboring
+/// swimlanes.
+const SCALE: f32 = 300.0;
+/// The space around the graph, in pixels.
+const MARGIN: f32 = 40.0;
+/// The height of the validator labels above the swimlanes, in pixels.
+const HEADER_HEIGHT: f32 = 60.0;
+/// The font size of the unit labels, in pixels.
+const FONT_SIZE: f32 = 11.0;
+
+/// Renders the units of the rounds in `rounds` and writes the image to `path`: as a PNG if the
+/// file name ends with `.png`, otherwise as an SVG.
+pub(crate) fn export(
+    graph: &Graph,
+    rounds: RangeInclusive<u64>,
+    path: &Path,
+) -> Result<(), String> {
+    let svg = render_svg(graph, rounds).map_err(|err| err.to_string())?;
+    let is_png = path
+        .extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("png"));
+    if is_png {
+        let pixmap = rasterize(&svg)?;
+        pixmap
+            .save_png(path)
+            .map_err(|err| format!("failed to write {}: {}", path.display(), err))
+    } else {
+        fs::write(path, svg).map_err(|err| format!("failed to write {}: {}", path.display(), err))
+    }
+}
+
+/// Renders the swimlanes, the units of the rounds in `rounds` and the edges between them to an SVG
+/// document.
+fn render_svg(graph: &Graph, rounds: RangeInclusive<u64>) -> Result<String, fmt::Error> {
+    let units: Vec<&GraphUnit> = graph
+        .units
+        .iter()
+        .flatten()
+        .filter(|unit| rounds.contains(&unit.round_num))
+        .collect();
+    let unit_ids: HashSet<UnitId> = units.iter().map(|unit| unit.id).collect();
+    let min_graph_height = units
+        .iter()
+        .map(|unit| unit.graph_height)
+        .min()
+        .unwrap_or(0);
+    let max_graph_height = units
+        .iter()
+        .map(|unit| unit.graph_height)
+        .max()
+        .unwrap_or(0);
+    let layout = Layout { max_graph_height };
+
+    let weights = graph.validator_weights();
+    let width = 2.0 * MARGIN + weights.len() as f32 * SCALE;
+    let height =
+        2.0 * MARGIN + HEADER_HEIGHT + (max_graph_height - min_graph_height + 1) as f32 * SCALE;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="DejaVu Sans">"#
+    )?;
+    writeln!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="{}"/>"#,
+        rgb([0.0, 0.0, 0.2])
+    )?;
+
+    // validator lanes
+    for (index, weight) in weights.iter().enumerate() {
+        let x = MARGIN + (index as f32 + 0.5) * SCALE;
+        writeln!(
+            svg,
+            r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="white" stroke-opacity="0.2"/>"#,
+            MARGIN + HEADER_HEIGHT,
+            height - MARGIN
+        )?;
+        writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" fill="white" font-size="16" text-anchor="middle">V{index}</text>"#,
+            MARGIN + 20.0
+        )?;
+        writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" fill="white" font-size="{FONT_SIZE}" text-anchor="middle">weight: {weight:3.1}%</text>"#,
+            MARGIN + 40.0
+        )?;
+    }
+
+    // edges between the exported units
+    writeln!(svg, r#"<g stroke="yellow" stroke-opacity="0.5">"#)?;
+    for unit in &units {
+        let (x1, y1) = layout.unit_pos(unit);
+        for cited in unit.cited_units.iter().filter(|id| unit_ids.contains(id)) {
+            let (x2, y2) = layout.unit_pos(graph.get(cited).unwrap());
+            writeln!(svg, r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}"/>"#)?;
+        }
+    }
+    writeln!(svg, "</g>")?;
+
+    for unit in &units {
+        write_unit(&mut svg, &layout, unit)?;
+    }
+
+    writeln!(svg, "</svg>")?;
+    Ok(svg)
+}
+
+/// Writes a unit as a rounded rectangle with its labels. Proposals of finalized blocks get a
+/// white frame and a marker in the top right corner.
+fn write_unit(svg: &mut String, layout: &Layout, unit: &GraphUnit) -> fmt::Result {
+    let (x, y) = layout.unit_pos(unit);
+    let (width, height) = (UNIT_WIDTH * SCALE, UNIT_HEIGHT * SCALE);
+    let (left, top) = (x - width / 2.0, y - height / 2.0);
+    let (frame_color, frame_width) = if unit.is_finalized {
+        ("white", 3.0)
+    } else {
+        ("yellow", 1.0)
+    };
+    writeln!(
+        svg,
+        r#"<rect x="{left}" y="{top}" width="{width}" height="{height}" rx="{radius}" fill="{}" stroke="{frame_color}" stroke-width="{frame_width}"/>"#,
+        rgb(unit.color()),
+        radius = CORNER_RADIUS * SCALE,
+    )?;
+    if unit.is_finalized {
+        writeln!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="6" fill="white"/>"#,
+            left + width - 12.0,
+            top + 12.0
+        )?;
+    }
+
+    let mut lines = vec![
+        format!("Vote: {:?}", unit.vote),
+        format!("round_exp: {}", unit.round_exp),
+        format!("round_id: {}", unit.round_id),
+        format!("timestamp: {} (round {})", unit.timestamp, unit.round_num),
+    ];
+    if let Some(quorum) = unit.max_quorum.as_ref() {
+        lines.push(format!("max quorum: {:3.1}%", quorum.weight_percent));
+    }
+    let text_x = left + 8.0;
+    writeln!(
+        svg,
+        r#"<text x="{text_x}" y="{}" fill="white" font-size="{}">{:?}</text>"#,
+        top + 18.0,
+        FONT_SIZE * 1.3,
+        unit.id
+    )?;
+    for (index, line) in lines.iter().enumerate() {
+        writeln!(
+            svg,
+            r#"<text x="{text_x}" y="{}" fill="white" font-size="{FONT_SIZE}">{line}</text>"#,
+            top + 36.0 + index as f32 * (FONT_SIZE + 3.0)
+        )?;
+    }
+    Ok(())
+}
+
+/// Maps units to pixel coordinates. Like in the interactive view, swimlanes are columns and units
+/// with a greater graph height are drawn higher up.
+struct Layout {
+    max_graph_height: usize,
+}
+
+impl Layout {
+    /// Returns the position of the center of the unit, in pixels.
+    fn unit_pos(&self, unit: &GraphUnit) -> (f32, f32) {
+        let x = MARGIN + (unit.creator.0 as f32 + 0.5) * SCALE;
+        let y = MARGIN
+            + HEADER_HEIGHT
+            + ((self.max_graph_height - unit.graph_height) as f32 + 0.5) * SCALE;
+        (x, y)
+    }
+}
+
+/// Converts a color with components between 0 and 1 into an SVG color.
+fn rgb(color: [f32; 3]) -> String {
+    let [r, g, b] = color.map(|component| (component * 255.0).round() as u8);
+    format!("rgb({r},{g},{b})")
+}
+
+/// Renders the SVG document to a pixmap, using the bundled font for the labels.
+fn rasterize(svg: &str) -> Result<tiny_skia::Pixmap, String> {
+    let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default())
+        .map_err(|err| format!("failed to parse the generated SVG: {}", err))?;
+    let mut fontdb = fontdb::Database::new();
+    fontdb.load_font_data(FONT_FILE.to_vec());
+    tree.convert_text(&fontdb);
+
+    let size = tree.size.to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).ok_or_else(|| {
+        format!(
+            "a {}x{} image is too large; try a smaller round range",
+            size.width(),
+            size.height()
+        )
+    })?;
+    resvg::Tree::from_usvg(&tree).render(tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    Ok(pixmap)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use casper_node::consensus::utils::{ValidatorIndex, ValidatorMap};
+    use casper_types::Timestamp;
+
+    use super::*;
+    use crate::{BlockId, BlockMapper};
+
+    fn unit(creator: u32, graph_height: usize, cited_units: Vec<UnitId>) -> GraphUnit {
+        let creator = ValidatorIndex::from(creator);
+        GraphUnit {
+            id: UnitId(creator, 0),
+            creator,
+            vote: BlockId(1, 0),
+            is_proposal: cited_units.is_empty(),
+            cited_units,
+            height: 0,
+            graph_height,
+            timestamp: graph_height as u64,
+            round_num: graph_height as u64,
+            round_id: Timestamp::from(graph_height as u64),
+            round_exp: 0,
+            max_quorum: None,
+            is_finalized: false,
+        }
+    }
+
+    /// Returns a graph where V1 cites the finalized proposal by V0 in the following round.
+    fn graph() -> Graph {
+        let mut proposal = unit(0, 0, vec![]);
+        proposal.is_finalized = true;
+        let vote = unit(1, 1, vec![proposal.id]);
+        Graph {
+            units: ValidatorMap::from(vec![vec![proposal], vec![vote]]),
+            reverse_edges: HashMap::new(),
+            blocks: BlockMapper::new(),
+            weight_percentages: ValidatorMap::from(vec![60.0, 40.0]),
+        }
+    }
+
+    #[test]
+    fn should_render_swimlanes_units_and_edges() {
+        let svg = render_svg(&graph(), 0..=1).unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r#"width="680" height="740" viewBox="0 0 680 740""#));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(">V0</text>"));
+        assert!(svg.contains(">weight: 40.0%</text>"));
+        assert!(svg.contains(">V0_0</text>"));
+        assert!(svg.contains(">V1_0</text>"));
+        assert!(svg.contains(">Vote: B1</text>"));
+        // The edge from V1's unit at the top to the proposal at the bottom.
+        assert!(svg.contains(r#"<line x1="490" y1="250" x2="190" y2="550"/>"#));
+    }
+
+    #[test]
+    fn should_frame_only_finalized_proposals() {
+        let svg = render_svg(&graph(), 0..=1).unwrap();
+        let frames: Vec<_> = svg
+            .lines()
+            .filter(|line| line.starts_with("<rect x="))
+            .collect();
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].ends_with(r#"stroke="white" stroke-width="3"/>"#));
+        assert!(frames[1].ends_with(r#"stroke="yellow" stroke-width="1"/>"#));
+        assert_eq!(svg.matches("<circle ").count(), 1);
+    }
+
+    #[test]
+    fn should_only_render_units_in_round_range() {
+        let svg = render_svg(&graph(), 1..=1).unwrap();
+        assert!(!svg.contains(">V0_0</text>"));
+        assert!(svg.contains(">V1_0</text>"));
+        assert!(!svg.contains("<circle "));
+        // Edges to units outside the range are omitted, and all swimlanes are still drawn.
+        assert!(svg.contains("<g stroke=\"yellow\" stroke-opacity=\"0.5\">\n</g>"));
+        assert!(svg.contains(">V0</text>"));
+        assert!(svg.contains(r#"height="440""#));
+    }
+
+    #[test]
+    fn should_format_colors() {
+        assert_eq!(rgb([0.0, 0.5, 1.0]), "rgb(0,128,255)");
+    }
+}
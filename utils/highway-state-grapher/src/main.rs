@@ -1,4 +1,6 @@
+mod export;
 mod renderer;
+mod timeline;
 
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
@@ -6,6 +8,8 @@ use std::{
     fs::File,
     io::Read,
     ops::RangeBounds,
+    path::PathBuf,
+    process,
 };
 
 use casper_hashing::Digest;
@@ -14,12 +18,12 @@ use casper_node::consensus::{
         finality_detector::{assigned_weight_and_latest_unit, find_max_quora},
         Panorama, State,
     },
-    utils::{ValidatorIndex, ValidatorMap},
+    utils::{ValidatorIndex, ValidatorMap, Weight},
     ClContext,
 };
 use casper_types::{EraId, PublicKey, Timestamp, U512};
 
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use flate2::read::GzDecoder;
 use glium::{
     glutin::{
@@ -32,12 +36,48 @@ use glium::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::renderer::Renderer;
+use crate::{
+    renderer::Renderer,
+    timeline::{fork_choice_finality, FinalityThreshold, Timeline},
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     filename: String,
+    /// Render the unit DAG to a static image instead of opening a window: a PNG if the file name
+    /// ends with `.png`, otherwise an SVG.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// The first round to render with `--output`, as shown in the unit labels.
+    #[arg(long, requires = "output", default_value_t = 0)]
+    from_round: u64,
+    /// The last round to render with `--output`, as shown in the unit labels.
+    #[arg(long, requires = "output", default_value_t = u64::MAX)]
+    to_round: u64,
+    /// The chain's finality threshold fraction, as in the chainspec: a block counts as finalized
+    /// once it has a summit that tolerates this fraction of the total weight being faulty.
+    #[arg(long, default_value = "1/3")]
+    finality_threshold: FinalityThreshold,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a per-round timeline of proposals, citations, equivocations and finalized blocks
+    /// instead of opening a window.
+    Timeline {
+        /// The output format.
+        #[arg(long, value_enum, default_value_t = TimelineFormat::Text)]
+        format: TimelineFormat,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum TimelineFormat {
+    Text,
+    Json,
 }
 
 /// Debug dump of era used for serialization.
@@ -186,6 +226,34 @@ pub struct GraphUnit {
     pub round_id: Timestamp,
     pub round_exp: u8,
     pub max_quorum: Option<Quorum>,
+    /// `true` if this is the proposal of a block in the fork choice of the complete dumped state
+    /// that has, or has a descendant with, a level-1 summit exceeding the FTT.
+    pub is_finalized: bool,
+}
+
+impl GraphUnit {
+    /// Returns the fill color of the unit: proposals and units with a max quorum are highlighted.
+    pub fn color(&self) -> [f32; 3] {
+        match (self.is_proposal, self.max_quorum.as_ref()) {
+            (false, Some(quorum)) => {
+                if quorum.max_rank <= 1 {
+                    quorum_color_spectrum(0.0)
+                } else {
+                    let frac = quorum.rank as f32 / (quorum.max_rank - 1) as f32;
+                    quorum_color_spectrum(frac)
+                }
+            }
+            (true, _) => [0.0_f32, 0.5, 0.5],
+            _ => [0.0_f32, 0.0, 0.2],
+        }
+    }
+}
+
+/// Returns a color for the max quorum based on its rank.
+fn quorum_color_spectrum(frac: f32) -> [f32; 3] {
+    let r = if frac < 0.5 { frac } else { 1.0 };
+    let g = if frac < 0.5 { 1.0 } else { 1.0 - frac };
+    [r * 0.5, g * 0.5, 0.0]
 }
 
 impl Debug for GraphUnit {
@@ -202,6 +270,7 @@ impl Debug for GraphUnit {
             .field("round_id", &self.round_id)
             .field("round_exp", &self.round_exp)
             .field("max_quorum", &self.max_quorum)
+            .field("is_finalized", &self.is_finalized)
             .field("cited_units", &self.cited_units)
             .finish()
     }
@@ -268,8 +337,8 @@ pub struct Graph {
 }
 
 impl Graph {
-    /// Creates a `Graph` based on the `state`.
-    fn new(state: &State<ClContext>, start_time: Timestamp) -> Self {
+    /// Creates a `Graph` based on the `state`, with the given fault tolerance threshold.
+    fn new(state: &State<ClContext>, start_time: Timestamp, ftt: Weight) -> Self {
         let mut units: BTreeMap<ValidatorIndex, Vec<GraphUnit>> = state
             .weights()
             .iter()
@@ -353,6 +422,7 @@ impl Graph {
                 round_exp: (unit.round_len().millis() / state.params().min_round_length().millis())
                     .trailing_zeros() as u8,
                 max_quorum: None,
+                is_finalized: false,
             };
             unit_ids_by_hash.insert(*unit_hash, unit_id);
             units.get_mut(&unit.creator).unwrap().push(graph_unit);
@@ -393,6 +463,14 @@ impl Graph {
             }
         }
 
+        // mark the proposals of the finalized blocks
+        let finality = fork_choice_finality(state, ftt);
+        for (bhash, _) in finality.iter().filter(|(_, finality)| finality.finalized) {
+            if let Some(gunit_id) = unit_ids_by_hash.get(bhash) {
+                units.get_mut(&gunit_id.0).unwrap()[gunit_id.1].is_finalized = true;
+            }
+        }
+
         let weight_percentages: ValidatorMap<f32> = state
             .weights()
             .iter()
@@ -462,9 +540,32 @@ fn main() {
 
     let dump: EraDump = bincode::deserialize(&data).unwrap();
 
+    let ftt = args
+        .finality_threshold
+        .ftt(dump.highway_state.total_weight());
+
+    if let Some(Command::Timeline { format }) = args.command {
+        let timeline = Timeline::new(&dump, ftt);
+        match format {
+            TimelineFormat::Text => print!("{}", timeline),
+            TimelineFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&timeline).unwrap())
+            }
+        }
+        return;
+    }
+
     eprintln!("{}", dump.id);
 
-    let graph = Graph::new(&dump.highway_state, dump.start_time);
+    let graph = Graph::new(&dump.highway_state, dump.start_time, ftt);
+
+    if let Some(output) = args.output {
+        if let Err(err) = export::export(&graph, args.from_round..=args.to_round, &output) {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+        return;
+    }
 
     for (index, (pub_key, _)) in dump.validators.iter().enumerate() {
         eprintln!("{}: {}", index, pub_key);
@@ -38,7 +38,7 @@ const FRAGMENT_SHADER_SRC: &str = r#"
     }
 "#;
 
-const FONT_FILE: &[u8] = include_bytes!("../DejaVuSans.ttf");
+pub(crate) const FONT_FILE: &[u8] = include_bytes!("../DejaVuSans.ttf");
 
 #[derive(Debug, Clone, Copy)]
 struct Vertex {
@@ -70,9 +70,9 @@ pub struct Renderer {
     edges_enabled: bool,
 }
 
-const UNIT_WIDTH: f32 = 0.5;
-const UNIT_HEIGHT: f32 = 0.4;
-const CORNER_RADIUS: f32 = 0.05;
+pub(crate) const UNIT_WIDTH: f32 = 0.5;
+pub(crate) const UNIT_HEIGHT: f32 = 0.4;
+pub(crate) const CORNER_RADIUS: f32 = 0.05;
 const LINE_WIDTH: f32 = 0.015;
 
 impl Renderer {
@@ -258,18 +258,7 @@ impl Renderer {
 
         let matrix2 = Matrix::translation(x, y) * *view;
 
-        let color = match (unit.is_proposal, unit.max_quorum.as_ref()) {
-            (false, Some(quorum)) => {
-                if quorum.max_rank <= 1 {
-                    Self::quorum_color_spectrum(0.0)
-                } else {
-                    let frac = quorum.rank as f32 / (quorum.max_rank - 1) as f32;
-                    Self::quorum_color_spectrum(frac)
-                }
-            }
-            (true, _) => [0.0_f32, 0.5, 0.5],
-            _ => [0.0_f32, 0.0, 0.2],
-        };
+        let color = unit.color();
 
         let uniforms = uniform! {
             matrix: matrix2.inner(),
@@ -440,11 +429,4 @@ impl Renderer {
     pub fn toggle_edges(&mut self) {
         self.edges_enabled = !self.edges_enabled;
     }
-
-    /// Returns a color for the max quorum based on its rank.
-    fn quorum_color_spectrum(frac: f32) -> [f32; 3] {
-        let r = if frac < 0.5 { frac } else { 1.0 };
-        let g = if frac < 0.5 { 1.0 } else { 1.0 - frac };
-        [r * 0.5, g * 0.5, 0.0]
-    }
 }
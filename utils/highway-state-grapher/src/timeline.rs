@@ -0,0 +1,646 @@
+//! A per-round textual or JSON timeline of an era, for analysis without a display.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{self, Display},
+    iter,
+    str::FromStr,
+};
+
+use casper_hashing::Digest;
+use casper_node::consensus::{
+    highway_core::{
+        finality_detector::{assigned_weight_and_latest_unit, find_max_quora},
+        Evidence, Fault, State,
+    },
+    protocols::common::validators,
+    utils::{ValidatorIndex, ValidatorMap, Weight},
+    ClContext,
+};
+use casper_types::{EraId, PublicKey, Timestamp};
+use serde::Serialize;
+
+use crate::{BlockMapper, EraDump, UnitId, Units};
+
+/// The timeline of an era.
+#[derive(Debug, Serialize)]
+pub struct Timeline {
+    pub era_id: EraId,
+    pub start_time: Timestamp,
+    pub start_height: u64,
+    pub validators: Vec<ValidatorInfo>,
+    pub rounds: Vec<RoundInfo>,
+    pub faults: Vec<FaultInfo>,
+}
+
+/// A validator of the era.
+#[derive(Debug, Serialize)]
+pub struct ValidatorInfo {
+    pub index: u32,
+    pub public_key: PublicKey,
+    pub weight_percent: f32,
+    pub faulty: bool,
+}
+
+/// All units created in a round, by correct validators.
+#[derive(Debug, Serialize)]
+pub struct RoundInfo {
+    pub round_id: Timestamp,
+    /// Milliseconds since the start of the era.
+    pub offset_ms: u64,
+    pub leader: u32,
+    pub proposals: Vec<ProposalInfo>,
+    pub units: Vec<UnitInfo>,
+}
+
+/// A unit and the units it newly cites, compared to its creator's previous unit.
+#[derive(Debug, Serialize)]
+pub struct UnitInfo {
+    pub unit: String,
+    pub creator: u32,
+    pub timestamp: Timestamp,
+    /// Milliseconds since the start of the round.
+    pub offset_ms: u64,
+    pub vote: String,
+    pub cites: Vec<String>,
+}
+
+/// A unit that introduced a new block.
+#[derive(Debug, Serialize)]
+pub struct ProposalInfo {
+    pub unit: String,
+    pub block: String,
+    pub proposer: u32,
+    pub timestamp: Timestamp,
+    /// For every other correct validator that saw the proposal, their first unit citing it.
+    pub cited_by: Vec<Citation>,
+    /// Milliseconds until validators with more than half of the total weight cited the proposal.
+    pub quorum_latency_ms: Option<u64>,
+    /// Whether the block or one of its descendants has a level-1 summit whose quorum exceeds the
+    /// fault tolerance threshold.
+    pub finalized: bool,
+    /// The highest quorum of a level-1 summit for the block, in percent of the weight assigned to
+    /// its round, if the block is part of the fork choice of the complete dumped state.
+    pub summit_quorum_percent: Option<f32>,
+}
+
+/// A validator's first unit citing a proposal, directly or indirectly.
+#[derive(Debug, Serialize)]
+pub struct Citation {
+    pub validator: u32,
+    pub unit: String,
+    /// Milliseconds since the proposal.
+    pub latency_ms: u64,
+}
+
+/// A faulty validator.
+#[derive(Debug, Serialize)]
+pub struct FaultInfo {
+    pub validator: u32,
+    pub kind: FaultKind,
+}
+
+/// The kind of a validator's fault.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultKind {
+    /// The validator created two units with the same sequence number.
+    Equivocation {
+        seq_number: u64,
+        timestamps: [Timestamp; 2],
+    },
+    /// The validator endorsed two conflicting units.
+    ConflictingEndorsements { seq_numbers: [u64; 2] },
+    /// The validator was faulty in a previous era.
+    Banned,
+    /// The validator is known to be faulty, without evidence in this era.
+    Indirect,
+}
+
+/// The fraction of the total weight that can be faulty without a finalized block being reverted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct FinalityThreshold {
+    numer: u64,
+    denom: u64,
+}
+
+impl FinalityThreshold {
+    /// Returns the fault tolerance threshold for the given total weight.
+    pub(crate) fn ftt(&self, total_weight: Weight) -> Weight {
+        let ftt = u128::from(total_weight.0) * u128::from(self.numer) / u128::from(self.denom);
+        Weight(ftt as u64)
+    }
+}
+
+impl FromStr for FinalityThreshold {
+    type Err = String;
+
+    /// Parses a fraction like `1/3`, as in the chainspec's `finality_threshold_fraction`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numer, denom) = s
+            .split_once('/')
+            .ok_or_else(|| format!("expected a fraction like 1/3, got {}", s))?;
+        let numer: u64 = numer.trim().parse().map_err(|err| format!("{}", err))?;
+        let denom: u64 = denom.trim().parse().map_err(|err| format!("{}", err))?;
+        if numer >= denom {
+            return Err("finality threshold must be less than 100%".to_string());
+        }
+        Ok(FinalityThreshold { numer, denom })
+    }
+}
+
+/// The finality of a block in the fork choice of the complete dumped state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct BlockFinality {
+    /// Whether the block or one of its descendants has a level-1 summit whose quorum exceeds the
+    /// fault tolerance threshold.
+    pub finalized: bool,
+    /// The highest quorum of a level-1 summit for the block, in percent of the weight assigned to
+    /// its round.
+    pub summit_quorum_percent: f32,
+}
+
+impl Timeline {
+    /// Creates the timeline of the dumped era, with the given fault tolerance threshold.
+    pub(crate) fn new(dump: &EraDump, ftt: Weight) -> Self {
+        let state = &dump.highway_state;
+        let validators =
+            validators::<ClContext>(&dump.faulty, &dump.cannot_propose, dump.validators.clone());
+
+        let mut units_set = Units {
+            set: HashSet::new(),
+            order: vec![],
+        };
+        units_set.collect_ancestor_units(state);
+
+        // Every correct validator's units, by sequence number.
+        let mut swimlanes: ValidatorMap<Vec<Digest>> =
+            ValidatorMap::from(vec![vec![]; state.validator_count()]);
+        let mut blocks = BlockMapper::new();
+        for unit_hash in &units_set.order {
+            let unit = state.unit(unit_hash);
+            swimlanes[unit.creator].push(*unit_hash);
+            if blocks.get(&unit.block).is_none() {
+                let block_id = blocks.next_id_for_height(state.block(&unit.block).height);
+                blocks.insert(unit.block, block_id);
+            }
+        }
+
+        let finality = fork_choice_finality(state, ftt);
+        let mut rounds: BTreeMap<Timestamp, RoundInfo> = BTreeMap::new();
+        for unit_hash in &units_set.order {
+            let unit = state.unit(unit_hash);
+            let round_id = unit.round_id();
+            let round = rounds.entry(round_id).or_insert_with(|| RoundInfo {
+                round_id,
+                offset_ms: round_id.saturating_diff(dump.start_time).millis(),
+                leader: state.leader(round_id).0,
+                proposals: vec![],
+                units: vec![],
+            });
+
+            let previous_panorama = unit.previous().map(|prev| &state.unit(prev).panorama);
+            let cites = unit
+                .panorama
+                .enumerate()
+                .filter(|(idx, obs)| {
+                    previous_panorama.map_or(true, |prev_pan| prev_pan[*idx] != **obs)
+                })
+                .filter_map(|(_, obs)| obs.correct())
+                .map(|hash| unit_id(state, hash))
+                .collect();
+            round.units.push(UnitInfo {
+                unit: unit_id(state, unit_hash),
+                creator: unit.creator.0,
+                timestamp: unit.timestamp,
+                offset_ms: unit.timestamp.saturating_diff(round_id).millis(),
+                vote: format!("{:?}", blocks.get(&unit.block).unwrap()),
+                cites,
+            });
+
+            if unit.block == *unit_hash {
+                round.proposals.push(ProposalInfo::new(
+                    state,
+                    &swimlanes,
+                    unit_hash,
+                    format!("{:?}", blocks.get(unit_hash).unwrap()),
+                    finality.get(unit_hash).copied(),
+                ));
+            }
+        }
+
+        let mut rounds: Vec<RoundInfo> = rounds.into_values().collect();
+        for round in &mut rounds {
+            round
+                .units
+                .sort_by_key(|unit| (unit.timestamp, unit.creator));
+        }
+
+        let validator_infos = validators
+            .enumerate_ids()
+            .map(|(idx, public_key)| ValidatorInfo {
+                index: idx.0,
+                public_key: public_key.clone(),
+                weight_percent: percent(state.weight(idx), state.total_weight()),
+                faulty: state.is_faulty(idx),
+            })
+            .collect();
+        let faults = state
+            .faulty_validators()
+            .filter_map(|idx| {
+                let kind = match state.maybe_fault(idx)? {
+                    Fault::Banned => FaultKind::Banned,
+                    Fault::Indirect => FaultKind::Indirect,
+                    Fault::Direct(Evidence::Equivocation(unit1, unit2)) => {
+                        FaultKind::Equivocation {
+                            seq_number: unit1.wire_unit().seq_number,
+                            timestamps: [unit1.wire_unit().timestamp, unit2.wire_unit().timestamp],
+                        }
+                    }
+                    Fault::Direct(Evidence::Endorsements { unit1, unit2, .. }) => {
+                        FaultKind::ConflictingEndorsements {
+                            seq_numbers: [
+                                unit1.wire_unit().seq_number,
+                                unit2.wire_unit().seq_number,
+                            ],
+                        }
+                    }
+                };
+                Some(FaultInfo {
+                    validator: idx.0,
+                    kind,
+                })
+            })
+            .collect();
+
+        Timeline {
+            era_id: dump.id,
+            start_time: dump.start_time,
+            start_height: dump.start_height,
+            validators: validator_infos,
+            rounds,
+            faults,
+        }
+    }
+}
+
+impl ProposalInfo {
+    /// Collects the citations of the proposal with the given hash.
+    fn new(
+        state: &State<ClContext>,
+        swimlanes: &ValidatorMap<Vec<Digest>>,
+        proposal_hash: &Digest,
+        block: String,
+        finality: Option<BlockFinality>,
+    ) -> Self {
+        let proposal = state.unit(proposal_hash);
+        let mut cited_by = vec![];
+        for (idx, swimlane) in swimlanes.enumerate() {
+            if idx == proposal.creator {
+                continue;
+            }
+            // Later units of a swimlane see everything earlier ones do.
+            let first = swimlane.partition_point(|hash| !state.sees(hash, proposal_hash));
+            if let Some(hash) = swimlane.get(first) {
+                cited_by.push(Citation {
+                    validator: idx.0,
+                    unit: unit_id(state, hash),
+                    latency_ms: state
+                        .unit(hash)
+                        .timestamp
+                        .saturating_diff(proposal.timestamp)
+                        .millis(),
+                });
+            }
+        }
+        cited_by.sort_by_key(|citation| citation.latency_ms);
+
+        let is_quorum =
+            |weight: Weight| u128::from(weight.0) * 2 > u128::from(state.total_weight().0);
+        let mut cited_weight = state.weight(proposal.creator);
+        let quorum_latency_ms = if is_quorum(cited_weight) {
+            Some(0)
+        } else {
+            cited_by.iter().find_map(|citation| {
+                cited_weight = cited_weight
+                    .saturating_add(state.weight(ValidatorIndex::from(citation.validator)));
+                is_quorum(cited_weight).then_some(citation.latency_ms)
+            })
+        };
+
+        ProposalInfo {
+            unit: unit_id(state, proposal_hash),
+            block,
+            proposer: proposal.creator.0,
+            timestamp: proposal.timestamp,
+            cited_by,
+            quorum_latency_ms,
+            finalized: finality.map_or(false, |finality| finality.finalized),
+            summit_quorum_percent: finality.map(|finality| finality.summit_quorum_percent),
+        }
+    }
+}
+
+/// Returns the finality of the blocks in the fork choice of the complete state.
+pub(crate) fn fork_choice_finality(
+    state: &State<ClContext>,
+    ftt: Weight,
+) -> HashMap<Digest, BlockFinality> {
+    let fork_choice = match state.fork_choice(state.panorama()) {
+        Some(fork_choice) => fork_choice,
+        None => return HashMap::new(),
+    };
+    let chain = iter::once(fork_choice)
+        .chain(state.ancestor_hashes(fork_choice))
+        .map(|bhash| {
+            let round_id = state.unit(bhash).round_id();
+            let (assigned_weight, latest) =
+                assigned_weight_and_latest_unit(state, state.panorama(), round_id);
+            let max_quorum = find_max_quora(state, bhash, &latest)
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or(Weight(0));
+            (*bhash, max_quorum, assigned_weight)
+        });
+    chain_finality(chain, state.total_weight(), ftt)
+}
+
+/// Returns the finality of the blocks of a chain, given from the newest to the oldest one with
+/// the highest quorum of their level-1 summits and the weight assigned to their rounds.
+///
+/// Like the finality detector, this finalizes a block together with all its ancestors once its
+/// summit exceeds the FTT.
+fn chain_finality<I>(chain: I, total_weight: Weight, ftt: Weight) -> HashMap<Digest, BlockFinality>
+where
+    I: IntoIterator<Item = (Digest, Weight, Weight)>,
+{
+    // The chain starts with the newest block, so once a block is finalized, so are all the
+    // following ones.
+    let mut finalized = false;
+    chain
+        .into_iter()
+        .map(|(bhash, max_quorum, assigned_weight)| {
+            finalized = finalized || is_summit_finalizing(max_quorum, total_weight, ftt);
+            let finality = BlockFinality {
+                finalized,
+                summit_quorum_percent: percent(max_quorum, assigned_weight),
+            };
+            (bhash, finality)
+        })
+        .collect()
+}
+
+/// Returns whether a level-1 summit with the given quorum exceeds the fault tolerance threshold.
+///
+/// A level-1 summit with quorum  `total_weight / 2 + t`  has FTT  `t`, so the quorum must be at
+/// least  `(total_weight + 2 * ftt) / 2`, as in the finality detector.
+fn is_summit_finalizing(quorum: Weight, total_weight: Weight, ftt: Weight) -> bool {
+    2 * u128::from(quorum.0) >= u128::from(total_weight.0) + 2 * u128::from(ftt.0)
+}
+
+/// Returns the readable ID of the unit with the given hash.
+fn unit_id(state: &State<ClContext>, hash: &Digest) -> String {
+    let unit = state.unit(hash);
+    format!("{:?}", UnitId(unit.creator, unit.seq_number as usize))
+}
+
+/// Returns `part` in percent of `total`.
+fn percent(part: Weight, total: Weight) -> f32 {
+    if total.0 == 0 {
+        return 0.0;
+    }
+    part.0 as f32 / total.0 as f32 * 100.0
+}
+
+impl Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}, starting at {} with block height {}",
+            self.era_id, self.start_time, self.start_height
+        )?;
+        writeln!(f, "validators:")?;
+        for validator in &self.validators {
+            write!(
+                f,
+                "  V{}: {} ({:.1}%)",
+                validator.index, validator.public_key, validator.weight_percent
+            )?;
+            if validator.faulty {
+                write!(f, " faulty")?;
+            }
+            writeln!(f)?;
+        }
+
+        for round in &self.rounds {
+            writeln!(
+                f,
+                "round {} (+{} ms), leader V{}",
+                round.round_id, round.offset_ms, round.leader
+            )?;
+            for proposal in &round.proposals {
+                write!(
+                    f,
+                    "  proposal {} of block {} by V{}",
+                    proposal.unit, proposal.block, proposal.proposer
+                )?;
+                match proposal.quorum_latency_ms {
+                    Some(latency_ms) => write!(f, ", cited by a quorum after {} ms", latency_ms)?,
+                    None => write!(f, ", not cited by a quorum")?,
+                }
+                match (proposal.finalized, proposal.summit_quorum_percent) {
+                    (true, Some(quorum)) => {
+                        writeln!(f, ", finalized (summit quorum {:.1}%)", quorum)?
+                    }
+                    (false, Some(quorum)) => {
+                        writeln!(f, ", not finalized (summit quorum {:.1}%)", quorum)?
+                    }
+                    (_, None) => writeln!(f, ", not finalized")?,
+                }
+                let citations: Vec<_> = proposal
+                    .cited_by
+                    .iter()
+                    .map(|citation| {
+                        format!(
+                            "V{} in {} (+{} ms)",
+                            citation.validator, citation.unit, citation.latency_ms
+                        )
+                    })
+                    .collect();
+                writeln!(f, "    cited by: {}", citations.join(", "))?;
+            }
+            for unit in &round.units {
+                writeln!(
+                    f,
+                    "  unit {} at +{} ms votes {}, cites {}",
+                    unit.unit,
+                    unit.offset_ms,
+                    unit.vote,
+                    unit.cites.join(", ")
+                )?;
+            }
+        }
+
+        if !self.faults.is_empty() {
+            writeln!(f, "faults:")?;
+        }
+        for fault in &self.faults {
+            write!(f, "  V{}: ", fault.validator)?;
+            match &fault.kind {
+                FaultKind::Equivocation {
+                    seq_number,
+                    timestamps,
+                } => writeln!(
+                    f,
+                    "equivocation at sequence number {}, units at {} and {}",
+                    seq_number, timestamps[0], timestamps[1]
+                )?,
+                FaultKind::ConflictingEndorsements { seq_numbers } => writeln!(
+                    f,
+                    "conflicting endorsements of units {} and {}",
+                    seq_numbers[0], seq_numbers[1]
+                )?,
+                FaultKind::Banned => writeln!(f, "banned in a previous era")?,
+                FaultKind::Indirect => writeln!(f, "faulty without evidence in this era")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_finality_threshold() {
+        let threshold: FinalityThreshold = "1/3".parse().unwrap();
+        assert_eq!(threshold.ftt(Weight(90)), Weight(30));
+        assert_eq!(threshold.ftt(Weight(100)), Weight(33));
+        assert_eq!(threshold.ftt(Weight(u64::MAX)), Weight(u64::MAX / 3));
+
+        assert!("1".parse::<FinalityThreshold>().is_err());
+        assert!("a/3".parse::<FinalityThreshold>().is_err());
+        assert!("3/3".parse::<FinalityThreshold>().is_err());
+    }
+
+    #[test]
+    fn summit_should_be_finalizing_only_above_ftt() {
+        // With total weight 90 and FTT 30, the quorum must be at least 45 + 30.
+        assert!(is_summit_finalizing(Weight(75), Weight(90), Weight(30)));
+        assert!(!is_summit_finalizing(Weight(74), Weight(90), Weight(30)));
+        // A majority is enough without an FTT, but the fork choice alone is not.
+        assert!(is_summit_finalizing(Weight(45), Weight(90), Weight(0)));
+        assert!(!is_summit_finalizing(Weight(0), Weight(90), Weight(0)));
+    }
+
+    #[test]
+    fn should_finalize_ancestors_of_finalized_blocks() {
+        let [b0, b1, b2, b3] = [0u8, 1, 2, 3].map(|i| Digest::hash([i]));
+        let total = Weight(90);
+        // From the newest to the oldest: b3 has no summit yet, b2 is finalized with a summit
+        // exceeding the FTT, b1 only has a majority and b0 has no summit.
+        let chain = vec![
+            (b3, Weight(0), total),
+            (b2, Weight(81), total),
+            (b1, Weight(50), Weight(100)),
+            (b0, Weight(0), total),
+        ];
+        let finality = chain_finality(chain, total, Weight(30));
+
+        assert_eq!(
+            finality[&b3],
+            BlockFinality {
+                finalized: false,
+                summit_quorum_percent: 0.0,
+            }
+        );
+        assert_eq!(
+            finality[&b2],
+            BlockFinality {
+                finalized: true,
+                summit_quorum_percent: 90.0,
+            }
+        );
+        assert_eq!(
+            finality[&b1],
+            BlockFinality {
+                finalized: true,
+                summit_quorum_percent: 50.0,
+            }
+        );
+        assert!(finality[&b0].finalized);
+    }
+
+    #[test]
+    fn should_not_finalize_blocks_below_ftt() {
+        let [b0, b1] = [0u8, 1].map(|i| Digest::hash([i]));
+        let total = Weight(90);
+        let chain = vec![(b1, Weight(74), total), (b0, Weight(60), total)];
+        let finality = chain_finality(chain, total, Weight(30));
+        assert!(!finality[&b1].finalized);
+        assert!(!finality[&b0].finalized);
+        assert!(chain_finality(vec![], total, Weight(30)).is_empty());
+    }
+
+    fn proposal(unit: &str, finalized: bool, summit_quorum_percent: Option<f32>) -> ProposalInfo {
+        ProposalInfo {
+            unit: unit.to_string(),
+            block: "B1".to_string(),
+            proposer: 0,
+            timestamp: Timestamp::from(1_000),
+            cited_by: vec![Citation {
+                validator: 1,
+                unit: "V1_0".to_string(),
+                latency_ms: 12,
+            }],
+            quorum_latency_ms: Some(12),
+            finalized,
+            summit_quorum_percent,
+        }
+    }
+
+    #[test]
+    fn should_display_finality_of_proposals() {
+        let secret_key = SecretKey::ed25519_from_bytes([1; 32]).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+        let timeline = Timeline {
+            era_id: EraId::new(3),
+            start_time: Timestamp::from(1_000),
+            start_height: 10,
+            validators: vec![ValidatorInfo {
+                index: 0,
+                public_key: public_key.clone(),
+                weight_percent: 100.0,
+                faulty: false,
+            }],
+            rounds: vec![RoundInfo {
+                round_id: Timestamp::from(1_000),
+                offset_ms: 0,
+                leader: 0,
+                proposals: vec![
+                    proposal("V0_0", true, Some(90.0)),
+                    proposal("V0_1", false, Some(60.0)),
+                    proposal("V0_2", false, None),
+                ],
+                units: vec![],
+            }],
+            faults: vec![],
+        };
+
+        let text = timeline.to_string();
+        assert!(text.contains(&format!("V0: {} (100.0%)", public_key)));
+        let proposal_line = |unit: &str| {
+            text.lines()
+                .find(|line| line.starts_with(&format!("  proposal {} of block B1 by V0", unit)))
+                .unwrap()
+        };
+        assert!(proposal_line("V0_0").ends_with(", finalized (summit quorum 90.0%)"));
+        assert!(proposal_line("V0_1").ends_with(", not finalized (summit quorum 60.0%)"));
+        assert!(proposal_line("V0_2").ends_with("after 12 ms, not finalized"));
+        assert!(text.contains("    cited by: V1 in V1_0 (+12 ms)"));
+    }
+}